@@ -5,15 +5,28 @@ use syn::{Lit, Meta, MetaNameValue, NestedMeta};
 
 #[cfg(feature = "std")]
 use crate::constants::{
-    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_TRACE_LENGTH, DEFAULT_MEMORY_SIZE,
-    DEFAULT_STACK_SIZE,
+    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_PRIVATE_INPUT_SIZE,
+    DEFAULT_MAX_TRACE_LENGTH, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
 };
 
+/// The global allocator of a no-std guest, selected with
+/// `#[jolt::provable(allocator = "...")]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GuestAllocator {
+    /// `jolt::BumpAllocator` (`"bump"`), which never frees memory.
+    #[default]
+    Bump,
+    /// `jolt::FreeListAllocator` (`"free_list"`), which reuses freed memory.
+    FreeList,
+}
+
 pub struct Attributes {
     pub wasm: bool,
+    pub allocator: GuestAllocator,
     pub memory_size: u64,
     pub stack_size: u64,
     pub max_input_size: u64,
+    pub max_private_input_size: u64,
     pub max_output_size: u64,
     pub max_trace_length: u64,
     pub guest_only: bool,
@@ -24,9 +37,22 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
     let mut attributes = HashMap::<_, u64>::new();
     let mut wasm = false;
     let mut guest_only = false;
+    let mut allocator = GuestAllocator::default();
 
     for attr in attr {
         match attr {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                if path.is_ident("allocator") =>
+            {
+                allocator = match lit {
+                    Lit::Str(lit) => match lit.value().as_str() {
+                        "bump" => GuestAllocator::Bump,
+                        "free_list" => GuestAllocator::FreeList,
+                        _ => panic!("invalid allocator, expected \"bump\" or \"free_list\""),
+                    },
+                    _ => panic!("expected string literal"),
+                };
+            }
             NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
                 let value: u64 = match lit {
                     Lit::Int(lit) => lit.base10_parse().unwrap(),
@@ -37,6 +63,7 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
                     "memory_size" => attributes.insert("memory_size", value),
                     "stack_size" => attributes.insert("stack_size", value),
                     "max_input_size" => attributes.insert("max_input_size", value),
+                    "max_private_input_size" => attributes.insert("max_private_input_size", value),
                     "max_output_size" => attributes.insert("max_output_size", value),
                     "max_trace_length" => attributes.insert("max_trace_length", value),
                     _ => panic!("invalid attribute"),
@@ -59,6 +86,9 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
     let max_input_size = *attributes
         .get("max_input_size")
         .unwrap_or(&DEFAULT_MAX_INPUT_SIZE);
+    let max_private_input_size = *attributes
+        .get("max_private_input_size")
+        .unwrap_or(&DEFAULT_MAX_PRIVATE_INPUT_SIZE);
     let max_output_size = *attributes
         .get("max_output_size")
         .unwrap_or(&DEFAULT_MAX_OUTPUT_SIZE);
@@ -68,9 +98,11 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
 
     Attributes {
         wasm,
+        allocator,
         memory_size,
         stack_size,
         max_input_size,
+        max_private_input_size,
         max_output_size,
         max_trace_length,
         guest_only,
@@ -1,10 +1,27 @@
 pub const XLEN: usize = 32;
 const RISCV_REGISTER_COUNT: u8 = 32;
 const VIRTUAL_REGISTER_COUNT: u8 = 32; //  see Section 6.1 of Jolt paper
-pub const REGISTER_COUNT: u8 = RISCV_REGISTER_COUNT + VIRTUAL_REGISTER_COUNT;
+/// Floating-point registers `f0`-`f31` are virtual registers placed after the
+/// general-purpose virtual registers, so F instructions can be expanded into
+/// virtual sequences of integer instructions. They are only allocated when the
+/// `float` feature is enabled, since they double the size of the register file.
+#[cfg(feature = "float")]
+const FLOAT_REGISTER_COUNT: u8 = 32;
+#[cfg(not(feature = "float"))]
+const FLOAT_REGISTER_COUNT: u8 = 0;
+/// Padded to a power of two, since registers are addressed by Twist.
+pub const REGISTER_COUNT: u8 =
+    (RISCV_REGISTER_COUNT + VIRTUAL_REGISTER_COUNT + FLOAT_REGISTER_COUNT).next_power_of_two();
 pub const BYTES_PER_INSTRUCTION: usize = 4;
+/// Size of a compressed ("C" extension) instruction, and the alignment of all instructions
+pub const BYTES_PER_COMPRESSED_INSTRUCTION: usize = 2;
 
 pub const RAM_START_ADDRESS: u64 = 0x80000000;
+/// The word just below `RAM_START_ADDRESS` holds the address reserved by the
+/// last LR.W, or 0 if there is no reservation. The LR/SC virtual sequences
+/// read and write it like any other RAM word; guest code cannot access it, and
+/// it is not part of the program's I/O.
+pub const RESERVATION_ADDRESS: u64 = RAM_START_ADDRESS - 4;
 
 // big enough to run Linux and xv6
 pub const EMULATOR_MEMORY_CAPACITY: u64 = 1024 * 1024 * 128;
@@ -16,12 +33,19 @@ pub const DEFAULT_STACK_SIZE: u64 = 4096;
 pub const STACK_CANARY_SIZE: u64 = 128;
 pub const DEFAULT_MAX_INPUT_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_OUTPUT_SIZE: u64 = 4096;
+pub const DEFAULT_MAX_PRIVATE_INPUT_SIZE: u64 = 0;
 pub const DEFAULT_MAX_TRACE_LENGTH: u64 = 1 << 24;
 
 pub const fn virtual_register_index(index: u8) -> u8 {
     index + VIRTUAL_REGISTER_COUNT
 }
 
+/// Index of the floating-point register `f{index}` in the register file.
+/// Only valid with the `float` feature enabled.
+pub const fn float_register_index(index: u8) -> u8 {
+    index + RISCV_REGISTER_COUNT + VIRTUAL_REGISTER_COUNT
+}
+
 // Layout of the witness (where || denotes concatenation):
 //     inputs || outputs || panic || termination || padding || RAM
 // Layout of VM memory:
@@ -1,9 +1,13 @@
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::constants::{
-    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
-    RAM_START_ADDRESS,
+    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_PRIVATE_INPUT_SIZE,
+    DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE, RAM_START_ADDRESS, RESERVATION_ADDRESS,
+    STACK_CANARY_SIZE,
 };
 
 #[allow(clippy::too_long_first_doc_paragraph)]
@@ -11,6 +15,8 @@ use crate::constants::{
 /// all reads from the reserved memory address space for program inputs and all writes
 /// to the reserved memory address space for program outputs.
 /// The inputs and outputs are part of the public inputs to the proof.
+/// The private inputs are not part of the program I/O: they must be cleared before
+/// the device is handed to the verifier. Note that the proof does not hide them.
 #[derive(
     Default,
     Debug,
@@ -23,17 +29,87 @@ use crate::constants::{
 )]
 pub struct JoltDevice {
     pub inputs: Vec<u8>,
+    pub private_inputs: Vec<u8>,
     pub outputs: Vec<u8>,
     pub panic: bool,
+    /// Set if the emulator stopped the guest because it ran out of stack or heap.
+    /// Unlike a panic, no guest code runs: the trace ends with the faulting access,
+    /// which is not performed (see `MemoryLayout::fault`).
+    pub fault: Option<GuestFault>,
     pub memory_layout: MemoryLayout,
 }
 
+/// A memory access by the guest outside of the stack and heap it was given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuestFault {
+    /// A write to the stack canary, just below the stack.
+    StackOverflow,
+    /// An access past `MemoryLayout::memory_end`, the end of the heap.
+    HeapOverflow,
+}
+
+impl GuestFault {
+    /// The nonzero code absorbed into the Fiat-Shamir transcript; 0 means no fault.
+    pub fn code(fault: Option<Self>) -> u8 {
+        match fault {
+            None => 0,
+            Some(GuestFault::StackOverflow) => 1,
+            Some(GuestFault::HeapOverflow) => 2,
+        }
+    }
+}
+
+impl core::fmt::Display for GuestFault {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GuestFault::StackOverflow => write!(f, "guest ran out of stack"),
+            GuestFault::HeapOverflow => write!(f, "guest ran out of heap"),
+        }
+    }
+}
+
+impl CanonicalSerialize for GuestFault {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        GuestFault::code(Some(*self)).serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        GuestFault::code(Some(*self)).serialized_size(compress)
+    }
+}
+
+impl Valid for GuestFault {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for GuestFault {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match u8::deserialize_with_mode(&mut reader, compress, validate)? {
+            1 => Ok(GuestFault::StackOverflow),
+            2 => Ok(GuestFault::HeapOverflow),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
 impl JoltDevice {
     pub fn new(memory_config: &MemoryConfig) -> Self {
         Self {
             inputs: Vec::new(),
+            private_inputs: Vec::new(),
             outputs: Vec::new(),
             panic: false,
+            fault: None,
             memory_layout: MemoryLayout::new(memory_config),
         }
     }
@@ -50,6 +126,13 @@ impl JoltDevice {
             } else {
                 self.inputs[internal_address]
             }
+        } else if self.is_private_input(address) {
+            let internal_address = (address - self.memory_layout.private_input_start) as usize;
+            if self.private_inputs.len() <= internal_address {
+                0
+            } else {
+                self.private_inputs[internal_address]
+            }
         } else if self.is_output(address) {
             let internal_address = self.convert_write_address(address);
             if self.outputs.len() <= internal_address {
@@ -87,6 +170,11 @@ impl JoltDevice {
         address >= self.memory_layout.input_start && address < self.memory_layout.input_end
     }
 
+    pub fn is_private_input(&self, address: u64) -> bool {
+        address >= self.memory_layout.private_input_start
+            && address < self.memory_layout.private_input_end
+    }
+
     pub fn is_output(&self, address: u64) -> bool {
         address >= self.memory_layout.output_start && address < self.memory_layout.termination
     }
@@ -99,6 +187,10 @@ impl JoltDevice {
         address >= self.memory_layout.termination && address < self.memory_layout.io_end
     }
 
+    pub fn is_reservation(&self, address: u64) -> bool {
+        (RESERVATION_ADDRESS..RAM_START_ADDRESS).contains(&address)
+    }
+
     fn convert_read_address(&self, address: u64) -> usize {
         (address - self.memory_layout.input_start) as usize
     }
@@ -111,6 +203,7 @@ impl JoltDevice {
 #[derive(Debug, Copy, Clone)]
 pub struct MemoryConfig {
     pub max_input_size: u64,
+    pub max_private_input_size: u64,
     pub max_output_size: u64,
     pub stack_size: u64,
     pub memory_size: u64,
@@ -121,6 +214,7 @@ impl Default for MemoryConfig {
     fn default() -> Self {
         Self {
             max_input_size: DEFAULT_MAX_INPUT_SIZE,
+            max_private_input_size: DEFAULT_MAX_PRIVATE_INPUT_SIZE,
             max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
             stack_size: DEFAULT_STACK_SIZE,
             memory_size: DEFAULT_MEMORY_SIZE,
@@ -136,11 +230,18 @@ pub struct MemoryLayout {
     /// The total size of the elf's sections, including the .text, .data, .rodata, and .bss sections.
     pub program_size: u64,
     pub max_input_size: u64,
+    pub max_private_input_size: u64,
     pub max_output_size: u64,
     pub input_start: u64,
     pub input_end: u64,
     pub output_start: u64,
     pub output_end: u64,
+    /// Private inputs occupy the top of the I/O region, ending one word below `RAM_START_ADDRESS`.
+    /// They are part of the initial RAM state but, unlike the rest of the I/O region,
+    /// are not part of the program I/O given to the verifier. The region is empty
+    /// (`private_input_start == private_input_end`) if `max_private_input_size` is 0.
+    pub private_input_start: u64,
+    pub private_input_end: u64,
     pub stack_size: u64,
     /// Stack starts from (RAM_START_ADDRESS + `program_size` + `stack_size`) and grows in descending addresses by `stack_size` bytes.
     pub stack_end: u64,
@@ -149,7 +250,7 @@ pub struct MemoryLayout {
     pub memory_end: u64,
     pub panic: u64,
     pub termination: u64,
-    /// End of the memory region containing inputs, outputs, the panic bit,
+    /// End of the memory region containing (public) inputs, outputs, the panic bit,
     /// and the termination bit
     pub io_end: u64,
 }
@@ -159,11 +260,20 @@ impl core::fmt::Debug for MemoryLayout {
         f.debug_struct("MemoryLayout")
             .field("program_size", &self.program_size)
             .field("max_input_size", &self.max_input_size)
+            .field("max_private_input_size", &self.max_private_input_size)
             .field("max_output_size", &self.max_output_size)
             .field("input_start", &format_args!("{:#X}", self.input_start))
             .field("input_end", &format_args!("{:#X}", self.input_end))
             .field("output_start", &format_args!("{:#X}", self.output_start))
             .field("output_end", &format_args!("{:#X}", self.output_end))
+            .field(
+                "private_input_start",
+                &format_args!("{:#X}", self.private_input_start),
+            )
+            .field(
+                "private_input_end",
+                &format_args!("{:#X}", self.private_input_end),
+            )
             .field("stack_size", &format_args!("{:#X}", self.stack_size))
             .field("stack_end", &format_args!("{:#X}", self.stack_end))
             .field("memory_size", &format_args!("{:#X}", self.memory_size))
@@ -197,15 +307,25 @@ impl MemoryLayout {
         } // Must be word-aligned
 
         let max_input_size = align_up(config.max_input_size, 4);
+        // The private input region spans a power-of-two number of words, so that
+        // it can be opened as a block of the initial RAM state. It is empty unless
+        // requested.
+        let max_private_input_size = if config.max_private_input_size == 0 {
+            0
+        } else {
+            4 * (align_up(config.max_private_input_size, 4) / 4).next_power_of_two()
+        };
         let max_output_size = align_up(config.max_output_size, 4);
         let stack_size = align_up(config.stack_size, 4);
         let memory_size = align_up(config.memory_size, 4);
 
         // Adds 8 to account for panic bit and termination bit
-        // (they each occupy one full 4-byte word)
+        // (they each occupy one full 4-byte word), and 4 for the LR/SC
+        // reservation word separating the private inputs from `RAM_START_ADDRESS`
         let io_region_bytes = max_input_size
             .checked_add(max_output_size)
-            .and_then(|s| s.checked_add(8))
+            .and_then(|s| s.checked_add(max_private_input_size))
+            .and_then(|s| s.checked_add(8 + 4))
             .expect("I/O region size overflow");
 
         // Padded so that the witness index corresponding to `input_start`
@@ -229,6 +349,12 @@ impl MemoryLayout {
         let panic = output_end;
         let termination = panic.checked_add(4).expect("termination overflow");
         let io_end = termination.checked_add(4).expect("io_end overflow");
+        // Private inputs sit at the top of the I/O region, one word below
+        // `RAM_START_ADDRESS`. Since `input_start` has witness index 1, the witness
+        // index of `private_input_start` is then io_region_words - max_private_input_size / 4,
+        // which is a multiple of the (power-of-two) size of the private region.
+        let private_input_end = RESERVATION_ADDRESS;
+        let private_input_start = private_input_end - max_private_input_size;
 
         let program_size = config.program_size.unwrap();
         // stack grows downwards (decreasing addresses) from the bytecode_end + stack_size up to bytecode_end
@@ -247,11 +373,14 @@ impl MemoryLayout {
         Self {
             program_size,
             max_input_size,
+            max_private_input_size,
             max_output_size,
             input_start,
             input_end,
             output_start,
             output_end,
+            private_input_start,
+            private_input_end,
             stack_size,
             stack_end,
             memory_size,
@@ -261,4 +390,17 @@ impl MemoryLayout {
             io_end,
         }
     }
+
+    /// The fault caused by a guest access to `address`, if any: a write to the stack
+    /// canary (the lowest `STACK_CANARY_SIZE` bytes of the stack) is a stack overflow,
+    /// and any access past `memory_end` is a heap overflow.
+    pub fn fault(&self, address: u64, is_write: bool) -> Option<GuestFault> {
+        if is_write && address > self.stack_end && address <= self.stack_end + STACK_CANARY_SIZE {
+            Some(GuestFault::StackOverflow)
+        } else if address >= self.memory_end {
+            Some(GuestFault::HeapOverflow)
+        } else {
+            None
+        }
+    }
 }
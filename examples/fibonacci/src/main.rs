@@ -24,6 +24,12 @@ pub fn main() {
     let verify_fib = guest::build_verifier_fib(verifier_preprocessing);
 
     let program_summary = guest::analyze_fib(10);
+    for span in program_summary.cycle_tracking.summary() {
+        println!(
+            "\"{}\": {} RV32IM cycles, {} virtual cycles",
+            span.label, span.rv32im_cycles, span.virtual_cycles
+        );
+    }
     program_summary
         .write_to_file("fib_10.txt".into())
         .expect("should write");
@@ -33,7 +39,7 @@ pub fn main() {
     println!("Trace file written to: {trace_file}.");
 
     let now = Instant::now();
-    let (output, proof, io_device) = prove_fib(50);
+    let (output, proof, io_device) = prove_fib(50).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
 
     if save_to_disk {
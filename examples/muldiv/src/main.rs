@@ -11,8 +11,16 @@ pub fn main() {
     let prove = guest::build_prover_muldiv(program, prover_preprocessing);
     let verify = guest::build_verifier_muldiv(verifier_preprocessing);
 
+    let program_summary = guest::analyze_muldiv(12031293, 17, 92);
+    for span in program_summary.cycle_tracking.summary() {
+        println!(
+            "\"{}\": {} RV32IM cycles, {} virtual cycles",
+            span.label, span.rv32im_cycles, span.virtual_cycles
+        );
+    }
+
     let now = Instant::now();
-    let (output, proof, program_io) = prove(12031293, 17, 92);
+    let (output, proof, program_io) = prove(12031293, 17, 92).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify(12031293, 17, 92, output, program_io.panic, proof);
 
@@ -23,14 +23,14 @@ pub fn main() {
     let verify_mul = guest::build_verifier_mul(verifier_preprocessing);
 
     let now = Instant::now();
-    let (output, proof, program_io) = prove_add(5, 10);
+    let (output, proof, program_io) = prove_add(5, 10).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_add(5, 10, output, program_io.panic, proof);
 
     println!("add output: {output}");
     println!("add valid: {is_valid}");
 
-    let (output, proof, program_io) = prove_mul(5, 10);
+    let (output, proof, program_io) = prove_mul(5, 10).unwrap();
     let is_valid = verify_mul(5, 10, output, program_io.panic, proof);
 
     println!("mul output: {output}");
@@ -24,3 +24,17 @@ fn overflow_heap() -> u32 {
         vectors.extend(v);
     }
 }
+
+// The allocator never hands out memory past the end of the heap (exhausting it makes
+// the guest panic, as in `overflow_heap`), so this writes there directly.
+#[jolt::provable(memory_size = 10240, max_trace_length = 65536)]
+fn write_past_heap() -> u32 {
+    extern "C" {
+        static _HEAP_END: u8;
+    }
+    unsafe {
+        let heap_end = core::ptr::addr_of!(_HEAP_END) as *mut u32;
+        heap_end.byte_add(4096).write_volatile(1);
+    }
+    0
+}
@@ -1,5 +1,3 @@
-use std::any::Any;
-use std::panic;
 use std::time::Instant;
 
 pub fn main() {
@@ -9,22 +7,24 @@ pub fn main() {
     let prover_preprocessing = guest::preprocess_prover_overflow_stack(&mut program);
     let prove_overflow_stack = guest::build_prover_overflow_stack(program, prover_preprocessing);
 
-    let res = panic::catch_unwind(|| {
-        // trying to allocate 1024 elems array and sum it up
-        // with stack_size=1024, should panic
-        let (_, _, _) = prove_overflow_stack();
-    });
-    handle_result(res);
+    // trying to allocate 1024 elems array and sum it up
+    // with stack_size=1024, should fail
+    handle_result(prove_overflow_stack());
 
-    // now lets try to overflow the heap, should also panic
+    // now lets try to overflow the heap, should also fail: the allocator
+    // runs out of memory and the guest panics
     let mut program = guest::compile_overflow_heap(target_dir);
     let prover_preprocessing = guest::preprocess_prover_overflow_heap(&mut program);
     let prove_overflow_heap = guest::build_prover_overflow_heap(program, prover_preprocessing);
 
-    let res = panic::catch_unwind(|| {
-        let (_, _, _) = prove_overflow_heap();
-    });
-    handle_result(res);
+    handle_result(prove_overflow_heap());
+
+    // writing past the end of the heap directly is reported as a heap overflow
+    let mut program = guest::compile_write_past_heap(target_dir);
+    let prover_preprocessing = guest::preprocess_prover_write_past_heap(&mut program);
+    let prove_write_past_heap = guest::build_prover_write_past_heap(program, prover_preprocessing);
+
+    handle_result(prove_write_past_heap());
 
     // valid case for stack allocation, calls overflow_stack() under the hood
     // but with stack_size=8192
@@ -43,7 +43,7 @@ pub fn main() {
         guest::build_verifier_allocate_stack_with_increased_size(verifier_preprocessing);
 
     let now = Instant::now();
-    let (output, proof, program_io) = prove_allocate_stack_with_increased_size();
+    let (output, proof, program_io) = prove_allocate_stack_with_increased_size().unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_allocate_stack_with_increased_size(output, program_io.panic, proof);
 
@@ -51,10 +51,8 @@ pub fn main() {
     println!("valid: {is_valid}");
 }
 
-fn handle_result(res: Result<(), Box<dyn Any + Send>>) {
+fn handle_result<T>(res: Result<T, jolt_sdk::ProveError>) {
     if let Err(e) = &res {
-        if let Some(msg) = e.downcast_ref::<String>() {
-            println!("--> Panic occurred with message: {msg}\n");
-        }
+        println!("--> Proving failed with error: {e}\n");
     }
 }
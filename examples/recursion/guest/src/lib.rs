@@ -0,0 +1,25 @@
+use jolt::{end_cycle_tracking, start_cycle_tracking};
+use jolt_verifier::common::jolt_device::JoltDevice;
+use jolt_verifier::zkvm::{
+    Jolt, JoltRV32IM, JoltVerifierPreprocessing, RV32IMJoltProof, Serializable,
+};
+
+/// Verifies a serialized Jolt proof inside the guest, so that proving this function
+/// proves "I verified a Jolt proof".
+///
+/// The verifier uses `std` (and rayon, which falls back to the current thread when
+/// threads are unavailable), so this guest is built for the Jolt `std` target.
+#[jolt::provable(max_input_size = 262144, stack_size = 1048576, memory_size = 33554432)]
+fn verify(preprocessing: Vec<u8>, proof: Vec<u8>, io_device: Vec<u8>) -> bool {
+    start_cycle_tracking("deserialize");
+    let preprocessing = JoltVerifierPreprocessing::deserialize_from_bytes(&preprocessing)
+        .expect("invalid verifier preprocessing");
+    let proof = RV32IMJoltProof::deserialize_from_bytes(&proof).expect("invalid proof");
+    let io_device = JoltDevice::deserialize_from_bytes(&io_device).expect("invalid io device");
+    end_cycle_tracking("deserialize");
+
+    start_cycle_tracking("verify");
+    let is_valid = JoltRV32IM::verify(&preprocessing, proof, io_device, None).is_ok();
+    end_cycle_tracking("verify");
+    is_valid
+}
@@ -0,0 +1,4 @@
+#![no_main]
+
+#[allow(unused_imports)]
+use recursion_guest::*;
@@ -0,0 +1,41 @@
+use std::fs;
+
+const FIXTURES_DIR: &str = "jolt-verifier/tests/fixtures";
+
+pub fn main() {
+    let build_only = std::env::args().any(|arg| arg == "--build-only");
+
+    let target_dir = "/tmp/jolt-guest-targets";
+    let program = guest::compile_verify(target_dir);
+    if build_only {
+        println!("Built {:?}", program.elf);
+        return;
+    }
+
+    let read_fixture = |name: &str| {
+        let path = format!("{}/../../{FIXTURES_DIR}/{name}", env!("CARGO_MANIFEST_DIR"));
+        fs::read(&path).unwrap_or_else(|e| panic!("could not read {path}: {e}"))
+    };
+    let preprocessing = read_fixture("jolt_verifier_preprocessing.dat");
+    let proof = read_fixture("fib_proof.bin");
+    let io_device = read_fixture("fib_io_device.bin");
+
+    let program_summary = guest::analyze_verify(preprocessing, proof, io_device);
+    let is_valid: bool = jolt_sdk::postcard::from_bytes(&program_summary.io_device.outputs)
+        .expect("could not decode the guest output");
+    println!("valid: {is_valid}");
+    assert!(is_valid, "the guest rejected the fibonacci proof");
+    println!(
+        "Verifying the fibonacci proof takes {} cycles",
+        program_summary.trace_len()
+    );
+    for span in program_summary.cycle_tracking.summary() {
+        println!(
+            "\"{}\": {} RV32IM cycles, {} virtual cycles",
+            span.label, span.rv32im_cycles, span.virtual_cycles
+        );
+    }
+    program_summary
+        .write_to_file("recursion.txt".into())
+        .expect("should write");
+}
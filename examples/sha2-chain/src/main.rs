@@ -15,7 +15,7 @@ pub fn main() {
     let iters = 1000;
     let native_output = guest::sha2_chain(input, iters);
     let now = Instant::now();
-    let (output, proof, program_io) = prove_sha2_chain(input, iters);
+    let (output, proof, program_io) = prove_sha2_chain(input, iters).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_sha2_chain(input, iters, output, program_io.panic, proof);
 
@@ -14,7 +14,7 @@ pub fn main() {
     let input = [5u8; 32];
     let iters = 100;
     let now = Instant::now();
-    let (output, proof, program_io) = prove_sha3_chain(input, iters);
+    let (output, proof, program_io) = prove_sha3_chain(input, iters).unwrap();
     println!("Prover runtime: {} s", now.elapsed().as_secs_f64());
     let is_valid = verify_sha3_chain(input, iters, output, program_io.panic, proof);
 
@@ -135,7 +135,7 @@ fn prove_example(
     let mut tasks = Vec::new();
     let mut program = host::Program::new(example_name);
     let (bytecode, init_memory_state, _) = program.decode();
-    let (_, _, program_io) = program.trace(&serialized_input);
+    let program_io = program.trace(&serialized_input).io_device;
 
     let task = move || {
         let preprocessing = JoltRV32IM::prover_preprocess(
@@ -146,7 +146,7 @@ fn prove_example(
         );
 
         let (jolt_proof, program_io, _) =
-            JoltRV32IM::prove(&preprocessing, &mut program, &serialized_input);
+            JoltRV32IM::prove(&preprocessing, &mut program, &serialized_input).unwrap();
 
         let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
         let verification_result =
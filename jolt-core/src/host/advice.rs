@@ -0,0 +1,69 @@
+//! Host-side encoding of the untrusted advice tape.
+//!
+//! The advice tape is a stream of hints that the guest reads with
+//! `jolt::advice::read`. Unlike program inputs, it is not part of the
+//! `JoltDevice`, so the verifier never sees it: anything the guest reads from
+//! the tape must be checked by the guest itself.
+
+use serde::Serialize;
+
+/// Values are written to the tape in whole 32-bit words, matching the word
+/// size of the ADVICE instruction on RV32.
+const WORD_SIZE: usize = 4;
+
+/// An append-only advice tape. Each value is encoded as a little-endian `u32`
+/// byte length followed by its postcard encoding, zero-padded to a multiple of
+/// [`WORD_SIZE`] bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdviceTape {
+    bytes: Vec<u8>,
+}
+
+impl AdviceTape {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `value` to the tape. Values are read back by the guest in the
+    /// order in which they were written.
+    pub fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> &mut Self {
+        let encoded = postcard::to_stdvec(value).expect("failed to serialize advice");
+        self.bytes
+            .extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+        self.bytes.extend_from_slice(&encoded);
+        let padded_len = self.bytes.len().next_multiple_of(WORD_SIZE);
+        self.bytes.resize(padded_len, 0);
+        self
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_length_prefixed_and_word_aligned() {
+        let mut tape = AdviceTape::new();
+        tape.write(&7u8).write(&[1u32, 2, 3]);
+
+        let bytes = tape.as_bytes();
+        assert_eq!(bytes.len() % WORD_SIZE, 0);
+        assert_eq!(&bytes[..4], &1u32.to_le_bytes());
+        assert_eq!(bytes[4], 7);
+        // Postcard varint-encodes each `u32`, so the array takes three bytes
+        assert_eq!(&bytes[8..12], &3u32.to_le_bytes());
+        assert_eq!(
+            postcard::from_bytes::<[u32; 3]>(&bytes[12..15]).unwrap(),
+            [1, 2, 3]
+        );
+        assert_eq!(bytes.len(), 16);
+    }
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, fs::File, io, path::PathBuf};
 use serde::{Deserialize, Serialize};
 use tracer::{
     instruction::{RV32IMCycle, RV32IMInstruction},
-    JoltDevice,
+    CycleTrackingReport, GuestOutput, JoltDevice,
 };
 
 use crate::field::JoltField;
@@ -14,6 +14,10 @@ pub struct ProgramSummary {
     pub bytecode: Vec<RV32IMInstruction>,
     pub memory_init: Vec<(u64, u8)>,
     pub io_device: JoltDevice,
+    /// Spans recorded by `jolt::start_cycle_tracking`/`jolt::end_cycle_tracking`.
+    pub cycle_tracking: CycleTrackingReport,
+    /// Text written by the guest through `jolt::print!`/`jolt::eprint!`.
+    pub guest_output: GuestOutput,
 }
 
 impl ProgramSummary {
@@ -39,6 +43,17 @@ impl ProgramSummary {
         counts
     }
 
+    /// Writes the cycle-tracking spans, along with per-label totals and call
+    /// counts, to `path` as JSON.
+    pub fn write_cycle_tracking_report(
+        &self,
+        path: PathBuf,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(path)?;
+        io::Write::write_all(&mut file, self.cycle_tracking.to_json()?.as_bytes())?;
+        Ok(())
+    }
+
     pub fn write_to_file(self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         let mut file = File::create(path)?;
         let data = bincode::serialize(&self)?;
@@ -2,6 +2,8 @@
 
 use std::path::PathBuf;
 
+#[cfg(feature = "host")]
+pub mod advice;
 #[cfg(feature = "host")]
 pub mod analyze;
 #[cfg(feature = "host")]
@@ -16,8 +18,10 @@ pub struct Program {
     memory_size: u64,
     stack_size: u64,
     max_input_size: u64,
+    max_private_input_size: u64,
     max_output_size: u64,
     std: bool,
+    target_dir: String,
     pub elf: Option<PathBuf>,
 }
 
@@ -53,5 +57,7 @@ SECTIONS {
 
   . = ALIGN(8);
   _HEAP_PTR = .;
+  /* MemoryLayout::memory_end, less the slack from aligning _STACK_END */
+  _HEAP_END = _STACK_END + {STACK_SIZE} + {MEMORY_SIZE} - 8;
 }
 "#;
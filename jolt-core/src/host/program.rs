@@ -3,9 +3,11 @@ use crate::host::analyze::ProgramSummary;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::host::toolchain::{install_no_std_toolchain, install_toolchain};
 use crate::host::{Program, DEFAULT_TARGET_DIR, LINKER_SCRIPT_TEMPLATE};
+use crate::utils::errors::JoltProverError;
 use common::constants::{
-    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
-    EMULATOR_MEMORY_CAPACITY, RAM_START_ADDRESS, STACK_CANARY_SIZE,
+    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_PRIVATE_INPUT_SIZE,
+    DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE, EMULATOR_MEMORY_CAPACITY, RAM_START_ADDRESS,
+    STACK_CANARY_SIZE,
 };
 use common::jolt_device::{JoltDevice, MemoryConfig};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -16,8 +18,9 @@ use std::process::Command;
 use std::str::FromStr;
 use std::{fs, io};
 use tracer::emulator::memory::Memory;
+use tracer::instruction::RV32IMInstruction;
 use tracer::instruction::VirtualInstructionSequence;
-use tracer::instruction::{RV32IMCycle, RV32IMInstruction};
+use tracer::{GuestInputs, LazyTraceIterator, Segment, TraceOutput};
 
 impl Program {
     pub fn new(guest: &str) -> Self {
@@ -27,8 +30,10 @@ impl Program {
             memory_size: DEFAULT_MEMORY_SIZE,
             stack_size: DEFAULT_STACK_SIZE,
             max_input_size: DEFAULT_MAX_INPUT_SIZE,
+            max_private_input_size: DEFAULT_MAX_PRIVATE_INPUT_SIZE,
             max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
             std: false,
+            target_dir: DEFAULT_TARGET_DIR.to_string(),
             elf: None,
         }
     }
@@ -53,33 +58,63 @@ impl Program {
         self.max_input_size = size;
     }
 
+    pub fn set_max_private_input_size(&mut self, size: u64) {
+        self.max_private_input_size = size;
+    }
+
     pub fn set_max_output_size(&mut self, size: u64) {
         self.max_output_size = size;
     }
 
     #[tracing::instrument(skip_all, name = "Program::build")]
     pub fn build(&mut self, target_dir: &str) {
+        self.try_build(target_dir)
+            .unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Fallible version of [`Program::build`], returning an error instead of
+    /// panicking if the toolchain cannot be installed or the guest fails to compile.
+    /// `target_dir` is remembered, so later implicit builds (e.g. from
+    /// [`Program::decode`]) reuse it.
+    pub fn try_build(&mut self, target_dir: &str) -> Result<(), JoltProverError> {
+        self.target_dir = target_dir.to_string();
         if self.elf.is_none() {
             #[cfg(not(target_arch = "wasm32"))]
-            install_toolchain().unwrap();
+            install_toolchain().map_err(|e| JoltProverError::GuestBuildError(e.to_string()))?;
             #[cfg(not(target_arch = "wasm32"))]
-            install_no_std_toolchain().unwrap();
+            install_no_std_toolchain()
+                .map_err(|e| JoltProverError::GuestBuildError(e.to_string()))?;
 
             self.save_linker();
 
-            let rust_flags = [
-                "-C",
-                &format!("link-arg=-T{}", self.linker_path()),
-                "-C",
-                "passes=lower-atomic",
-                "-C",
-                "panic=abort",
-                "-C",
-                "strip=symbols",
-                "-C",
-                "opt-level=z",
+            let mut rust_flags = vec![
+                "-C".to_string(),
+                format!("link-arg=-T{}", self.linker_path()),
+                "-C".to_string(),
+                "panic=abort".to_string(),
+                "-C".to_string(),
+                "strip=symbols".to_string(),
+                "-C".to_string(),
+                "opt-level=z".to_string(),
             ];
 
+            // RV32A instructions are expanded into virtual sequences, so 32-bit
+            // guests are built for riscv32ima rather than lowering atomics away.
+            // Compressed instructions are decoded into their 32-bit equivalents,
+            // so guests are also built with the "C" extension for smaller bytecode.
+            // With the `float` feature, single-precision float instructions ("F") are
+            // expanded into soft-float virtual sequences, which are much shorter than the
+            // equivalent libcalls; the ABI stays soft-float, so floats are still passed in
+            // integer registers. It is opt-in because the float registers double the size
+            // of the register file, and the fused multiply-add instructions are not supported.
+            let target_features = if cfg!(feature = "float") {
+                "target-feature=+a,+c,+f"
+            } else {
+                "target-feature=+a,+c"
+            };
+            rust_flags.push("-C".to_string());
+            rust_flags.push(target_features.to_string());
+
             let toolchain = if self.std {
                 "riscv32im-jolt-zkvm-elf"
             } else {
@@ -118,20 +153,32 @@ impl Program {
                     toolchain,
                 ])
                 .output()
-                .expect("failed to build guest");
+                .map_err(|e| JoltProverError::GuestBuildError(e.to_string()))?;
 
             if !output.status.success() {
                 io::stderr().write_all(&output.stderr).unwrap();
-                panic!("failed to compile guest");
+                return Err(JoltProverError::GuestBuildError(format!(
+                    "cargo build exited with {} for guest {}",
+                    output.status, self.guest
+                )));
             }
 
             let elf = format!("{}/{}/release/{}", target, toolchain, self.guest);
             self.elf = Some(PathBuf::from_str(&elf).unwrap());
         }
+        Ok(())
+    }
+
+    /// Builds the guest into the target directory it was last built with,
+    /// or [`DEFAULT_TARGET_DIR`] if it has not been built yet.
+    pub fn try_build_in_target_dir(&mut self) -> Result<(), JoltProverError> {
+        let target_dir = self.target_dir.clone();
+        self.try_build(&target_dir)
     }
 
     pub fn decode(&mut self) -> (Vec<RV32IMInstruction>, Vec<(u64, u8)>, u64) {
-        self.build(DEFAULT_TARGET_DIR);
+        self.try_build_in_target_dir()
+            .unwrap_or_else(|err| panic!("{err}"));
         let elf = self.elf.as_ref().unwrap();
         let mut elf_file =
             File::open(elf).unwrap_or_else(|_| panic!("could not open elf file: {elf:?}"));
@@ -163,6 +210,40 @@ impl Program {
                 RV32IMInstruction::SRL(srl) => srl.virtual_sequence(),
                 RV32IMInstruction::SRLI(srli) => srli.virtual_sequence(),
                 RV32IMInstruction::INLINE(inline) => inline.virtual_sequence(),
+                RV32IMInstruction::ADVICE(advice) => advice.virtual_sequence(),
+                RV32IMInstruction::LRW(lrw) => lrw.virtual_sequence(),
+                RV32IMInstruction::SCW(scw) => scw.virtual_sequence(),
+                RV32IMInstruction::AMOSWAPW(amoswapw) => amoswapw.virtual_sequence(),
+                RV32IMInstruction::AMOADDW(amoaddw) => amoaddw.virtual_sequence(),
+                RV32IMInstruction::AMOANDW(amoandw) => amoandw.virtual_sequence(),
+                RV32IMInstruction::AMOORW(amoorw) => amoorw.virtual_sequence(),
+                RV32IMInstruction::AMOXORW(amoxorw) => amoxorw.virtual_sequence(),
+                RV32IMInstruction::AMOMINW(amominw) => amominw.virtual_sequence(),
+                RV32IMInstruction::AMOMAXW(amomaxw) => amomaxw.virtual_sequence(),
+                RV32IMInstruction::AMOMINUW(amominuw) => amominuw.virtual_sequence(),
+                RV32IMInstruction::AMOMAXUW(amomaxuw) => amomaxuw.virtual_sequence(),
+                RV32IMInstruction::FLW(flw) => flw.virtual_sequence(),
+                RV32IMInstruction::FSW(fsw) => fsw.virtual_sequence(),
+                RV32IMInstruction::FADDS(fadds) => fadds.virtual_sequence(),
+                RV32IMInstruction::FSUBS(fsubs) => fsubs.virtual_sequence(),
+                RV32IMInstruction::FMULS(fmuls) => fmuls.virtual_sequence(),
+                RV32IMInstruction::FDIVS(fdivs) => fdivs.virtual_sequence(),
+                RV32IMInstruction::FSQRTS(fsqrts) => fsqrts.virtual_sequence(),
+                RV32IMInstruction::FSGNJS(fsgnjs) => fsgnjs.virtual_sequence(),
+                RV32IMInstruction::FSGNJNS(fsgnjns) => fsgnjns.virtual_sequence(),
+                RV32IMInstruction::FSGNJXS(fsgnjxs) => fsgnjxs.virtual_sequence(),
+                RV32IMInstruction::FMINS(fmins) => fmins.virtual_sequence(),
+                RV32IMInstruction::FMAXS(fmaxs) => fmaxs.virtual_sequence(),
+                RV32IMInstruction::FEQS(feqs) => feqs.virtual_sequence(),
+                RV32IMInstruction::FLTS(flts) => flts.virtual_sequence(),
+                RV32IMInstruction::FLES(fles) => fles.virtual_sequence(),
+                RV32IMInstruction::FCLASSS(fclasss) => fclasss.virtual_sequence(),
+                RV32IMInstruction::FCVTWS(fcvtws) => fcvtws.virtual_sequence(),
+                RV32IMInstruction::FCVTWUS(fcvtwus) => fcvtwus.virtual_sequence(),
+                RV32IMInstruction::FCVTSW(fcvtsw) => fcvtsw.virtual_sequence(),
+                RV32IMInstruction::FCVTSWU(fcvtswu) => fcvtswu.virtual_sequence(),
+                RV32IMInstruction::FMVXW(fmvxw) => fmvxw.virtual_sequence(),
+                RV32IMInstruction::FMVWX(fmvwx) => fmvwx.virtual_sequence(),
                 _ => vec![instr],
             })
             .collect();
@@ -172,8 +253,61 @@ impl Program {
 
     // TODO(moodlezoup): Make this generic over InstructionSet
     #[tracing::instrument(skip_all, name = "Program::trace")]
-    pub fn trace(&mut self, inputs: &[u8]) -> (Vec<RV32IMCycle>, Memory, JoltDevice) {
-        self.build(DEFAULT_TARGET_DIR);
+    pub fn trace(&mut self, inputs: &[u8]) -> TraceOutput {
+        self.trace_with_advice(inputs, &[])
+    }
+
+    /// Like [`Program::trace`], but also supplies an untrusted advice tape
+    /// (see [`AdviceTape`](super::advice::AdviceTape)) that the guest can
+    /// read from via `jolt::advice::read`. The advice tape is not part of the
+    /// returned `JoltDevice`, so it is never seen by the verifier.
+    #[tracing::instrument(skip_all, name = "Program::trace_with_advice")]
+    pub fn trace_with_advice(&mut self, inputs: &[u8], advice: &[u8]) -> TraceOutput {
+        self.trace_with_witness(GuestInputs {
+            inputs,
+            advice,
+            ..Default::default()
+        })
+    }
+
+    /// Like [`Program::trace_with_advice`], but also loads the private inputs into
+    /// the private input region of memory. The private inputs are part of the
+    /// returned `JoltDevice` so that they can be committed to by the prover; they
+    /// must be cleared before the device is handed to the verifier.
+    #[tracing::instrument(skip_all, name = "Program::trace_with_witness")]
+    pub fn trace_with_witness(&mut self, guest_inputs: GuestInputs) -> TraceOutput {
+        self.try_build_in_target_dir()
+            .unwrap_or_else(|err| panic!("{err}"));
+        let elf = self.elf.as_ref().unwrap();
+        let mut elf_file =
+            File::open(elf).unwrap_or_else(|_| panic!("could not open elf file: {elf:?}"));
+        let mut elf_contents = Vec::new();
+        elf_file.read_to_end(&mut elf_contents).unwrap();
+        let (_, _, program_end) = tracer::decode(&elf_contents);
+        let program_size = program_end - RAM_START_ADDRESS;
+
+        let memory_config = MemoryConfig {
+            memory_size: self.memory_size,
+            stack_size: self.stack_size,
+            max_input_size: self.max_input_size,
+            max_private_input_size: self.max_private_input_size,
+            max_output_size: self.max_output_size,
+            program_size: Some(program_size),
+        };
+        tracer::trace(elf_contents, guest_inputs, &memory_config)
+    }
+
+    /// Executes the guest, splitting execution into segments of (at least)
+    /// `segment_length` cycles for continuations. Returns the segments and the
+    /// emulator state after execution has completed.
+    #[tracing::instrument(skip_all, name = "Program::trace_segments")]
+    pub fn trace_segments(
+        &mut self,
+        inputs: &[u8],
+        segment_length: usize,
+    ) -> (Vec<Segment>, LazyTraceIterator) {
+        self.try_build_in_target_dir()
+            .unwrap_or_else(|err| panic!("{err}"));
         let elf = self.elf.as_ref().unwrap();
         let mut elf_file =
             File::open(elf).unwrap_or_else(|_| panic!("could not open elf file: {elf:?}"));
@@ -186,15 +320,26 @@ impl Program {
             memory_size: self.memory_size,
             stack_size: self.stack_size,
             max_input_size: self.max_input_size,
+            max_private_input_size: self.max_private_input_size,
             max_output_size: self.max_output_size,
             program_size: Some(program_size),
         };
-        tracer::trace(elf_contents, inputs, &memory_config)
+        tracer::trace_segments(elf_contents, inputs, &memory_config, segment_length)
     }
 
     #[tracing::instrument(skip_all, name = "Program::trace_to_file")]
     pub fn trace_to_file(&mut self, inputs: &[u8], trace_file: &PathBuf) -> (Memory, JoltDevice) {
-        self.build(DEFAULT_TARGET_DIR);
+        self.trace_to_file_with_private_inputs(inputs, &[], trace_file)
+    }
+
+    pub fn trace_to_file_with_private_inputs(
+        &mut self,
+        inputs: &[u8],
+        private_inputs: &[u8],
+        trace_file: &PathBuf,
+    ) -> (Memory, JoltDevice) {
+        self.try_build_in_target_dir()
+            .unwrap_or_else(|err| panic!("{err}"));
         let elf = self.elf.as_ref().unwrap();
         let mut elf_file =
             File::open(elf).unwrap_or_else(|_| panic!("could not open elf file: {elf:?}"));
@@ -206,21 +351,51 @@ impl Program {
             memory_size: self.memory_size,
             stack_size: self.stack_size,
             max_input_size: self.max_input_size,
+            max_private_input_size: self.max_private_input_size,
             max_output_size: self.max_output_size,
             program_size: Some(program_size),
         };
-        tracer::trace_to_file(elf_contents, inputs, &memory_config, trace_file)
+        tracer::trace_to_file(
+            elf_contents,
+            GuestInputs {
+                inputs,
+                private_inputs,
+                ..Default::default()
+            },
+            &memory_config,
+            trace_file,
+        )
     }
 
-    pub fn trace_analyze<F: JoltField>(mut self, inputs: &[u8]) -> ProgramSummary {
+    pub fn trace_analyze<F: JoltField>(self, inputs: &[u8]) -> ProgramSummary {
+        self.trace_analyze_with_private_inputs::<F>(inputs, &[])
+    }
+
+    pub fn trace_analyze_with_private_inputs<F: JoltField>(
+        mut self,
+        inputs: &[u8],
+        private_inputs: &[u8],
+    ) -> ProgramSummary {
         let (bytecode, init_memory_state, _) = self.decode();
-        let (trace, _, io_device) = self.trace(inputs);
+        let TraceOutput {
+            trace,
+            io_device,
+            cycle_tracking,
+            guest_output,
+            ..
+        } = self.trace_with_witness(GuestInputs {
+            inputs,
+            private_inputs,
+            ..Default::default()
+        });
 
         ProgramSummary {
             trace,
             bytecode,
             memory_init: init_memory_state,
             io_device,
+            cycle_tracking,
+            guest_output,
         }
     }
 
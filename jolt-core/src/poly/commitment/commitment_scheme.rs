@@ -1,10 +1,13 @@
+use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use std::borrow::Borrow;
 use std::fmt::Debug;
+use std::ops::Range;
 
 use crate::utils::transcript::Transcript;
 use crate::{
     field::JoltField,
+    msm::VariableBaseMSM,
     poly::multilinear_polynomial::MultilinearPolynomial,
     utils::{errors::ProofVerifyError, transcript::AppendToTranscript},
 };
@@ -62,24 +65,6 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
     where
         U: Borrow<MultilinearPolynomial<Self::Field>> + Sync;
 
-    /// Homomorphically combines multiple commitments into a single commitment, computed as a
-    /// linear combination with the given coefficients.
-    fn combine_commitments<C: Borrow<Self::Commitment>>(
-        _commitments: &[C],
-        _coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        todo!("`combine_commitments` should be on a separate `AdditivelyHomomorphic` trait")
-    }
-
-    /// Homomorphically combines multiple opening proof hints into a single hint, computed as a
-    /// linear combination with the given coefficients.
-    fn combine_hints(
-        _hints: Vec<Self::OpeningProofHint>,
-        _coeffs: &[Self::Field],
-    ) -> Self::OpeningProofHint {
-        unimplemented!()
-    }
-
     /// Generates a proof of evaluation for a polynomial at a specific point.
     ///
     /// # Arguments
@@ -123,10 +108,214 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
     fn protocol_name() -> &'static [u8];
 }
 
+/// A commitment scheme whose final opening checks can be combined across many independent
+/// openings, at unrelated points, e.g. via a random linear combination of pairing checks,
+/// so that verifying many openings costs little more than verifying one.
+///
+/// Dory does not implement this: its opening proofs end in an interactive reduction that
+/// is specific to the opening point, and combining its commitments (see
+/// [`AdditivelyHomomorphic::combine_commitments`]) only helps openings at the same point.
+pub trait BatchVerification: CommitmentScheme {
+    /// Verifies many independent opening proofs, e.g. the reduced openings of many Jolt
+    /// proofs, with a single combined check.
+    fn batch_verify<ProofTranscript: Transcript>(
+        openings: Vec<DeferredOpening<Self, ProofTranscript>>,
+        setup: &Self::VerifierSetup,
+    ) -> Result<(), ProofVerifyError>;
+}
+
+/// A commitment scheme whose commitments and opening proof hints can be combined linearly,
+/// i.e. the commitment to a linear combination of polynomials can be computed from the
+/// commitments to the polynomials. Such schemes get [`BatchOpening`] for free.
+pub trait AdditivelyHomomorphic: CommitmentScheme {
+    /// Homomorphically combines multiple commitments into a single commitment, computed as a
+    /// linear combination with the given coefficients.
+    fn combine_commitments<C: Borrow<Self::Commitment>>(
+        commitments: &[C],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment;
+
+    /// Homomorphically combines multiple opening proof hints into a single hint, computed as a
+    /// linear combination with the given coefficients.
+    fn combine_hints(
+        hints: Vec<Self::OpeningProofHint>,
+        coeffs: &[Self::Field],
+    ) -> Self::OpeningProofHint;
+}
+
+/// A commitment scheme that can prove an opening of a linear combination of committed
+/// polynomials. The batch opening reduction in `opening_proof.rs` reduces all the openings
+/// of a Jolt proof to a single such opening.
+///
+/// Additively homomorphic schemes implement this by combining the commitments and hints
+/// and opening the combined polynomial. Other schemes (e.g. [`Ligero`](super::ligero::Ligero))
+/// can instead open each of the committed polynomials in the same proof.
+pub trait BatchOpening: CommitmentScheme {
+    /// The commitment to `sum_i coeffs[i] * polys[i]` given the commitments to the `polys`,
+    /// against which the proof computed by `prove_linear_combination` is verified.
+    fn linear_combination_commitment(
+        commitments: Vec<Self::Commitment>,
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment;
+
+    /// Proves the evaluation of `sum_i coeffs[i] * polynomials[i]` at `opening_point`, where
+    /// `hints[i]` is the hint returned when committing to `polynomials[i]`.
+    fn prove_linear_combination<ProofTranscript: Transcript>(
+        setup: &Self::ProverSetup,
+        polynomials: &[&MultilinearPolynomial<Self::Field>],
+        coeffs: &[Self::Field],
+        hints: Vec<Self::OpeningProofHint>,
+        opening_point: &[Self::Field],
+        transcript: &mut ProofTranscript,
+    ) -> Self::Proof;
+
+    /// Commits to the linear combination computed by the prover directly, for tests to
+    /// check that it matches `linear_combination_commitment`. `None` if the two are not
+    /// expected to match.
+    #[cfg(test)]
+    fn commit_linear_combination(
+        _joint_poly: &MultilinearPolynomial<Self::Field>,
+        _setup: &Self::ProverSetup,
+    ) -> Option<Self::Commitment> {
+        None
+    }
+}
+
+impl<PCS: AdditivelyHomomorphic> BatchOpening for PCS {
+    fn linear_combination_commitment(
+        commitments: Vec<Self::Commitment>,
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        Self::combine_commitments(&commitments, coeffs)
+    }
+
+    fn prove_linear_combination<ProofTranscript: Transcript>(
+        setup: &Self::ProverSetup,
+        polynomials: &[&MultilinearPolynomial<Self::Field>],
+        coeffs: &[Self::Field],
+        hints: Vec<Self::OpeningProofHint>,
+        opening_point: &[Self::Field],
+        transcript: &mut ProofTranscript,
+    ) -> Self::Proof {
+        let joint_poly = MultilinearPolynomial::linear_combination(polynomials, coeffs);
+        let hint = Self::combine_hints(hints, coeffs);
+        Self::prove(setup, &joint_poly, opening_point, hint, transcript)
+    }
+
+    #[cfg(test)]
+    fn commit_linear_combination(
+        joint_poly: &MultilinearPolynomial<Self::Field>,
+        setup: &Self::ProverSetup,
+    ) -> Option<Self::Commitment> {
+        Some(Self::commit(joint_poly, setup).0)
+    }
+}
+
+/// An opening proof whose verification has been deferred, along with everything needed
+/// to verify it later: the transcript in the state it was in when verification was
+/// deferred, the opening point, the claimed opening, and the commitment.
+#[derive(Clone)]
+pub struct DeferredOpening<PCS: CommitmentScheme, ProofTranscript: Transcript> {
+    pub proof: PCS::Proof,
+    pub transcript: ProofTranscript,
+    pub opening_point: Vec<PCS::Field>,
+    pub opening: PCS::Field,
+    pub commitment: PCS::Commitment,
+}
+
+/// A chunk of the coefficients of a polynomial being committed to with a
+/// [`StreamingCommitmentScheme`], in the compact representation produced by witness
+/// generation. Unlike a [`MultilinearPolynomial`], a chunk may have any length.
+#[derive(Clone, Copy, Debug)]
+pub enum StreamingChunk<'a, F: JoltField> {
+    LargeScalars(&'a [F]),
+    U8Scalars(&'a [u8]),
+    U64Scalars(&'a [u64]),
+    I64Scalars(&'a [i64]),
+    /// The nonzero indices of a range of cycles of a one-hot polynomial with address
+    /// space size `K`
+    OneHot(&'a [Option<usize>], usize),
+}
+
+impl<F: JoltField> StreamingChunk<'_, F> {
+    /// The number of coefficients in the chunk, or the number of cycles if it is one-hot.
+    pub fn len(&self) -> usize {
+        match self {
+            StreamingChunk::LargeScalars(coeffs) => coeffs.len(),
+            StreamingChunk::U8Scalars(coeffs) => coeffs.len(),
+            StreamingChunk::U64Scalars(coeffs) => coeffs.len(),
+            StreamingChunk::I64Scalars(coeffs) => coeffs.len(),
+            StreamingChunk::OneHot(indices, _) => indices.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Coefficients `range` of a dense chunk.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        match self {
+            StreamingChunk::LargeScalars(coeffs) => StreamingChunk::LargeScalars(&coeffs[range]),
+            StreamingChunk::U8Scalars(coeffs) => StreamingChunk::U8Scalars(&coeffs[range]),
+            StreamingChunk::U64Scalars(coeffs) => StreamingChunk::U64Scalars(&coeffs[range]),
+            StreamingChunk::I64Scalars(coeffs) => StreamingChunk::I64Scalars(&coeffs[range]),
+            StreamingChunk::OneHot(..) => panic!("One-hot chunks have no dense coefficients"),
+        }
+    }
+
+    /// Computes `sum_i c_i * bases[i]` over the coefficients `c_i` of a dense chunk.
+    pub fn msm<G: CurveGroup<ScalarField = F> + VariableBaseMSM>(
+        &self,
+        bases: &[G::Affine],
+    ) -> Result<G, ProofVerifyError> {
+        match self {
+            StreamingChunk::LargeScalars(coeffs) => {
+                <G as VariableBaseMSM>::msm_field_elements(bases, coeffs, None)
+            }
+            StreamingChunk::U8Scalars(coeffs) => <G as VariableBaseMSM>::msm_u8(bases, coeffs),
+            StreamingChunk::U64Scalars(coeffs) => <G as VariableBaseMSM>::msm_u64(bases, coeffs),
+            StreamingChunk::I64Scalars(coeffs) => {
+                let scalars: Vec<F> = coeffs.iter().map(|&c| F::from_i64(c)).collect();
+                <G as VariableBaseMSM>::msm_field_elements(bases, &scalars, None)
+            }
+            StreamingChunk::OneHot(..) => panic!("One-hot chunks have no dense coefficients"),
+        }
+    }
+
+    /// Coefficient `index` of a dense chunk.
+    pub fn get_coeff(&self, index: usize) -> F {
+        match self {
+            StreamingChunk::LargeScalars(coeffs) => coeffs[index],
+            StreamingChunk::U8Scalars(coeffs) => F::from_u8(coeffs[index]),
+            StreamingChunk::U64Scalars(coeffs) => F::from_u64(coeffs[index]),
+            StreamingChunk::I64Scalars(coeffs) => F::from_i64(coeffs[index]),
+            StreamingChunk::OneHot(..) => panic!("One-hot chunks have no dense coefficients"),
+        }
+    }
+}
+
+/// A commitment scheme that can commit to a polynomial whose coefficients are produced
+/// incrementally, e.g. one chunk of the execution trace at a time, so that the polynomial
+/// never needs to be held in memory in full.
 pub trait StreamingCommitmentScheme: CommitmentScheme {
-    type State<'a>; // : Clone + Debug;
+    type State<'a>: Send;
 
+    /// Starts a commitment to a polynomial with `size` coefficients.
     fn initialize<'a>(size: usize, setup: &'a Self::ProverSetup) -> Self::State<'a>;
+    /// Appends a single coefficient to the polynomial.
     fn process<'a>(state: Self::State<'a>, eval: Self::Field) -> Self::State<'a>;
-    fn finalize<'a>(state: Self::State<'a>) -> Self::Commitment;
+    /// Appends a chunk of coefficients to the polynomial. If `chunk` is dense, its
+    /// coefficients are the next `chunk.len()` coefficients of the polynomial. If it is
+    /// one-hot, the polynomial is a one-hot polynomial with `T = size / K` cycles, and
+    /// `chunk` holds its next cycles, i.e. coefficient `(k, t)` of `chunk` is coefficient
+    /// `(k, offset + t)` of the polynomial, where `offset` is the number of cycles
+    /// appended so far. A polynomial is appended to either densely or one-hot, not both.
+    fn process_chunk<'a>(
+        state: Self::State<'a>,
+        chunk: StreamingChunk<Self::Field>,
+    ) -> Self::State<'a>;
+    /// Completes the commitment. Returns the same commitment and opening proof hint as
+    /// [`CommitmentScheme::commit`] would for the polynomial.
+    fn finalize<'a>(state: Self::State<'a>) -> (Self::Commitment, Self::OpeningProofHint);
 }
@@ -1,6 +1,8 @@
 #![allow(static_mut_refs)]
 
-use super::commitment_scheme::CommitmentScheme;
+use super::commitment_scheme::{
+    AdditivelyHomomorphic, CommitmentScheme, StreamingChunk, StreamingCommitmentScheme,
+};
 use crate::{
     field::JoltField,
     msm::VariableBaseMSM,
@@ -12,7 +14,7 @@ use crate::{
         transcript::{AppendToTranscript, Transcript},
     },
 };
-use ark_bn254::{Bn254, Fr, G1Projective, G2Projective};
+use ark_bn254::{Bn254, Fr, G1Affine, G1Projective, G2Projective};
 use ark_ec::{
     pairing::{MillerLoopOutput, Pairing as ArkPairing, PairingOutput},
     AffineRepr, CurveGroup,
@@ -32,10 +34,13 @@ use dory::{
     },
     commit,
     curve::G2Cache,
-    evaluate, setup_with_srs_file,
+    evaluate,
     transcript::Transcript as DoryTranscript,
     verify, DoryProof, DoryProofBuilder, Polynomial as DoryPolynomial, ProverSetup, VerifierSetup,
 };
+use srs::SrsCache;
+
+pub mod srs;
 
 /// The (padded) length of the execution trace currently being proven
 static mut GLOBAL_T: OnceCell<usize> = OnceCell::new();
@@ -1014,24 +1019,33 @@ pub struct DoryBatchedProof {
     proofs: Vec<DoryProofData>,
 }
 
-impl CommitmentScheme for DoryCommitmentScheme {
-    type Field = Fr;
-    type ProverSetup = ProverSetup<JoltBn254>;
-    type VerifierSetup = VerifierSetup<JoltBn254>;
-    type Commitment = DoryCommitment;
-    type Proof = DoryProofData;
-    type BatchedProof = DoryBatchedProof;
-    type OpeningProofHint = Vec<JoltG1Wrapper>; // row commitments
-
-    #[tracing::instrument(skip_all, name = "DoryCommitmentScheme::setup_prover")]
-    fn setup_prover(max_num_vars: usize) -> Self::ProverSetup {
-        let srs_file_name = format!("dory_srs_{max_num_vars}_variables.srs");
-        let (mut prover_setup, _) = setup_with_srs_file::<JoltBn254, _>(
-            &mut ark_std::rand::thread_rng(),
-            max_num_vars,
-            Some(&srs_file_name), // Will load if exists, generate and save if not
+impl DoryCommitmentScheme {
+    /// The deterministic setup for `max_num_vars` variables (see [`srs`]), read from
+    /// `cache` if it is there and written to it otherwise.
+    pub fn setup_prover_with_cache(
+        cache: &SrsCache,
+        max_num_vars: usize,
+    ) -> ProverSetup<JoltBn254> {
+        Self::with_g2_cache(cache.load_or_generate(max_num_vars))
+    }
+
+    /// A setup supporting up to `max_num_vars` variables, derived from the (cached) setup
+    /// for `larger_num_vars` variables. This avoids generating a setup for every size when
+    /// a larger one is at hand; the setup, and so the verifier preprocessing, is then the
+    /// one for `larger_num_vars`, whatever the cache holds.
+    pub fn setup_prover_from_larger(
+        cache: &SrsCache,
+        max_num_vars: usize,
+        larger_num_vars: usize,
+    ) -> ProverSetup<JoltBn254> {
+        assert!(
+            larger_num_vars >= max_num_vars,
+            "Setup for {larger_num_vars} variables does not cover {max_num_vars} variables"
         );
+        Self::with_g2_cache(cache.load_or_generate(larger_num_vars))
+    }
 
+    fn with_g2_cache(mut prover_setup: ProverSetup<JoltBn254>) -> ProverSetup<JoltBn254> {
         // Initialize cache for G2 Prepared elements for multi pairing
         // # Safety: ProverSetup<E> is always concretely ProverSetup<Bn254>.
         unsafe {
@@ -1062,6 +1076,21 @@ impl CommitmentScheme for DoryCommitmentScheme {
 
         prover_setup
     }
+}
+
+impl CommitmentScheme for DoryCommitmentScheme {
+    type Field = Fr;
+    type ProverSetup = ProverSetup<JoltBn254>;
+    type VerifierSetup = VerifierSetup<JoltBn254>;
+    type Commitment = DoryCommitment;
+    type Proof = DoryProofData;
+    type BatchedProof = DoryBatchedProof;
+    type OpeningProofHint = Vec<JoltG1Wrapper>; // row commitments
+
+    #[tracing::instrument(skip_all, name = "DoryCommitmentScheme::setup_prover")]
+    fn setup_prover(max_num_vars: usize) -> Self::ProverSetup {
+        Self::setup_prover_with_cache(&SrsCache::default(), max_num_vars)
+    }
 
     #[tracing::instrument(skip_all, name = "DoryCommitmentScheme::setup_verifier")]
     fn setup_verifier(prover_setup: &Self::ProverSetup) -> Self::VerifierSetup {
@@ -1172,6 +1201,12 @@ impl CommitmentScheme for DoryCommitmentScheme {
         }
     }
 
+    fn protocol_name() -> &'static [u8] {
+        b"dory_commitment_scheme"
+    }
+}
+
+impl AdditivelyHomomorphic for DoryCommitmentScheme {
     fn combine_commitments<C: Borrow<Self::Commitment>>(
         commitments: &[C],
         coeffs: &[Self::Field],
@@ -1227,9 +1262,173 @@ impl CommitmentScheme for DoryCommitmentScheme {
 
         rlc_hint
     }
+}
 
-    fn protocol_name() -> &'static [u8] {
-        b"dory_commitment_scheme"
+/// The state of a streaming Dory commitment. Dory commits to a polynomial by committing to
+/// the rows of its coefficient matrix (the opening proof hint) and then to the vector of
+/// row commitments, so we add the contribution of each chunk to the row commitments as it
+/// is appended, and only commit to the row commitments in [`StreamingCommitmentScheme::finalize`].
+pub struct DoryStreamingState<'a> {
+    setup: &'a ProverSetup<JoltBn254>,
+    /// The generators for a single row
+    bases: Vec<G1Affine>,
+    size: usize,
+    row_commitments: Vec<G1Projective>,
+    /// The number of coefficients appended so far, or the number of cycles if the
+    /// polynomial is one-hot
+    offset: usize,
+    /// Coefficients appended one at a time, not yet added to the row commitments
+    pending: Vec<Fr>,
+}
+
+impl DoryStreamingState<'_> {
+    fn add_dense(&mut self, chunk: StreamingChunk<Fr>) {
+        let row_len = self.bases.len();
+        // Split the chunk into (row, column, start, length) segments, one per row it spans
+        let mut segments = vec![];
+        let mut start = 0;
+        while start < chunk.len() {
+            let (row, col) = (
+                (self.offset + start) / row_len,
+                (self.offset + start) % row_len,
+            );
+            let len = (row_len - col).min(chunk.len() - start);
+            segments.push((row, col, start, len));
+            start += len;
+        }
+
+        let sums: Vec<G1Projective> = segments
+            .par_iter()
+            .map(|&(_, col, start, len)| {
+                chunk
+                    .slice(start..start + len)
+                    .msm(&self.bases[col..col + len])
+                    .unwrap()
+            })
+            .collect();
+        for ((row, ..), sum) in segments.into_iter().zip(sums) {
+            self.row_commitments[row] += sum;
+        }
+        self.offset += chunk.len();
+    }
+
+    fn add_one_hot(&mut self, indices: &[Option<usize>], K: usize) {
+        let row_len = self.bases.len();
+        let (T, offset) = (self.size / K, self.offset);
+        // Coefficient (k, t) of a one-hot polynomial is at index k * T + t
+        let mut entries: Vec<(usize, usize)> = indices
+            .par_iter()
+            .enumerate()
+            .filter_map(|(t, k)| {
+                k.map(|k| {
+                    let index = k * T + offset + t;
+                    (index / row_len, index % row_len)
+                })
+            })
+            .collect();
+        entries.par_sort_unstable();
+
+        let (rows, columns): (Vec<usize>, Vec<Vec<usize>>) = entries
+            .chunk_by(|a, b| a.0 == b.0)
+            .map(|entries| (entries[0].0, entries.iter().map(|(_, col)| *col).collect()))
+            .unzip();
+        let sums = jolt_optimizations::batch_g1_additions_multi(&self.bases, &columns);
+        for (row, sum) in rows.into_iter().zip(sums) {
+            self.row_commitments[row] += sum;
+        }
+        self.offset += indices.len();
+    }
+
+    fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.add_dense(StreamingChunk::LargeScalars(&pending));
+        }
+    }
+}
+
+/// Row commitments computed ahead of time, e.g. by a streaming commitment, so that
+/// committing to the "polynomial" only commits to the row commitments.
+struct PrecomputedRows(Vec<JoltG1Wrapper>);
+
+impl DoryPolynomial<JoltFieldWrapper<Fr>, JoltG1Wrapper> for PrecomputedRows {
+    fn len(&self) -> usize {
+        self.0.len() * DoryGlobals::get_num_columns()
+    }
+
+    fn commit_rows<M1: DoryMultiScalarMul<JoltG1Wrapper>>(
+        &self,
+        _g1_generators: &[JoltG1Wrapper],
+        _row_len: usize,
+    ) -> Vec<JoltG1Wrapper> {
+        self.0.clone()
+    }
+
+    fn vector_matrix_product(
+        &self,
+        _left_vec: &[JoltFieldWrapper<Fr>],
+        _sigma: usize,
+        _nu: usize,
+    ) -> Vec<JoltFieldWrapper<Fr>> {
+        unimplemented!("Precomputed rows can only be committed to")
+    }
+}
+
+impl StreamingCommitmentScheme for DoryCommitmentScheme {
+    type State<'a> = DoryStreamingState<'a>;
+
+    fn initialize(size: usize, setup: &Self::ProverSetup) -> Self::State<'_> {
+        let row_len = DoryGlobals::get_num_columns();
+        let bases = setup.core.g1_vec[..row_len]
+            .par_iter()
+            .map(|g| g.0.into_affine())
+            .collect();
+        DoryStreamingState {
+            setup,
+            bases,
+            size,
+            row_commitments: vec![G1Projective::zero(); size.div_ceil(row_len)],
+            offset: 0,
+            pending: vec![],
+        }
+    }
+
+    fn process<'a>(mut state: Self::State<'a>, eval: Self::Field) -> Self::State<'a> {
+        state.pending.push(eval);
+        if state.pending.len() == state.bases.len() {
+            state.flush();
+        }
+        state
+    }
+
+    fn process_chunk<'a>(
+        mut state: Self::State<'a>,
+        chunk: StreamingChunk<Self::Field>,
+    ) -> Self::State<'a> {
+        match chunk {
+            StreamingChunk::OneHot(indices, K) => state.add_one_hot(indices, K),
+            _ => {
+                state.flush();
+                state.add_dense(chunk);
+            }
+        }
+        state
+    }
+
+    #[tracing::instrument(skip_all, name = "DoryCommitmentScheme::finalize")]
+    fn finalize<'a>(mut state: Self::State<'a>) -> (Self::Commitment, Self::OpeningProofHint) {
+        state.flush();
+        let rows = PrecomputedRows(
+            state
+                .row_commitments
+                .into_iter()
+                .map(JoltGroupWrapper)
+                .collect(),
+        );
+        let sigma = DoryGlobals::get_num_columns().log_2();
+        let (commitment, row_commitments) =
+            commit::<JoltBn254, JoltMsmG1, _>(&rows, 0, sigma, state.setup);
+        (DoryCommitment(commitment), row_commitments)
     }
 }
 
@@ -1245,6 +1444,7 @@ mod tests {
     use crate::poly::compact_polynomial::CompactPolynomial;
     use crate::poly::dense_mlpoly::DensePolynomial;
     use crate::poly::multilinear_polynomial::PolynomialEvaluation;
+    use crate::poly::one_hot_polynomial::OneHotPolynomial;
     use crate::utils::transcript::KeccakTranscript;
     use ark_std::rand::thread_rng;
     use ark_std::UniformRand;
@@ -1329,6 +1529,44 @@ mod tests {
         (commit_time, prove_time, verify_time, total_time)
     }
 
+    #[test]
+    #[serial]
+    fn test_dory_streaming_commitment() {
+        let (K, T) = (4, 1 << 8);
+        let _guard = DoryGlobals::initialize(K, T);
+        let prover_setup = DoryCommitmentScheme::setup_prover((K * T).log_2());
+        let mut rng = thread_rng();
+
+        // Dense chunks that don't line up with the rows, interleaved with single
+        // coefficients
+        let coeffs: Vec<u64> = (0..T).map(|_| rng.next_u64()).collect();
+        let poly = MultilinearPolynomial::from(coeffs.clone());
+        let mut state = DoryCommitmentScheme::initialize(T, &prover_setup);
+        for chunk in coeffs.chunks(100) {
+            state = DoryCommitmentScheme::process(state, Fr::from(chunk[0]));
+            state =
+                DoryCommitmentScheme::process_chunk(state, StreamingChunk::U64Scalars(&chunk[1..]));
+        }
+        assert_eq!(
+            DoryCommitmentScheme::commit(&poly, &prover_setup),
+            DoryCommitmentScheme::finalize(state)
+        );
+
+        let indices: Vec<Option<usize>> = (0..T)
+            .map(|_| (rng.next_u32() % 5 != 0).then(|| rng.next_u32() as usize % K))
+            .collect();
+        let poly =
+            MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(indices.clone(), K));
+        let mut state = DoryCommitmentScheme::initialize(K * T, &prover_setup);
+        for chunk in indices.chunks(100) {
+            state = DoryCommitmentScheme::process_chunk(state, StreamingChunk::OneHot(chunk, K));
+        }
+        assert_eq!(
+            DoryCommitmentScheme::commit(&poly, &prover_setup),
+            DoryCommitmentScheme::finalize(state)
+        );
+    }
+
     #[test]
     #[serial]
     fn test_dory_commitment_scheme_all_polynomial_types() {
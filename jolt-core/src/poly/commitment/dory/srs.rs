@@ -0,0 +1,318 @@
+//! Deterministic generation and on-disk caching of Dory setups.
+//!
+//! Dory has a transparent setup: its generators only need to be random group elements
+//! with no known discrete-log relations between them. Instead of sampling them from
+//! `thread_rng`, we sample them from a single ChaCha20 stream, seeded with a hash of a
+//! fixed domain separator: setups of every size are drawn from the start of that stream.
+//! arkworks samples a random point by hashing random bytes to an x-coordinate and mapping
+//! it onto the curve (try-and-increment), so the generators are a hash-to-curve of public
+//! data: every machine derives the same setup, and anyone can regenerate it to audit a
+//! cached copy (see [`SrsCache::audit`]).
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use dory::{setup_with_srs_file, ProverSetup};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use sha3::{Digest, Keccak256};
+
+use super::JoltBn254;
+use crate::utils::errors::SrsError;
+
+/// Domain separator for the seed of the Dory setup. Changing it changes every setup.
+pub const DORY_SRS_DOMAIN: &[u8] = b"Jolt Dory SRS v1";
+/// Environment variable overriding the SRS cache directory. Setting it to the empty
+/// string disables the cache.
+pub const SRS_DIR_ENV: &str = "JOLT_SRS_DIR";
+/// Magic bytes at the start of every cached SRS file.
+pub const SRS_MAGIC: [u8; 4] = *b"JSRS";
+
+/// The seed of the stream from which Dory setups of every size are derived.
+pub fn srs_seed() -> [u8; 32] {
+    Keccak256::digest(DORY_SRS_DOMAIN).into()
+}
+
+/// Generates the Dory setup for `max_num_vars` variables from [`srs_seed`].
+#[tracing::instrument(skip_all, name = "dory::srs::generate")]
+pub fn generate(max_num_vars: usize) -> ProverSetup<JoltBn254> {
+    let mut rng = ChaCha20Rng::from_seed(srs_seed());
+    let (prover_setup, _) = setup_with_srs_file::<JoltBn254, _>(&mut rng, max_num_vars, None);
+    prover_setup
+}
+
+/// A directory of cached Dory setups, one file per setup size.
+///
+/// Each file records the setup size, the seed it was derived from and a checksum of the
+/// serialized setup, which are all checked on load. Files that fail these checks are
+/// regenerated. The checksum only guards against corruption, since whoever can write
+/// the file can also fix it up: on Unix, setups are not loaded from (or stored to) a
+/// directory that other users can write to, and [`SrsCache::audit`] checks a cached
+/// setup against a freshly generated one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SrsCache {
+    dir: Option<PathBuf>,
+}
+
+impl Default for SrsCache {
+    /// `$JOLT_SRS_DIR` if set, otherwise `jolt/srs` in the user's cache directory (e.g.
+    /// `~/.cache/jolt/srs` on Linux). The cache is disabled if there is no such directory.
+    fn default() -> Self {
+        match std::env::var_os(SRS_DIR_ENV) {
+            Some(dir) if dir.is_empty() => Self::disabled(),
+            Some(dir) => Self::new(dir),
+            None => Self::user_cache_dir().map_or_else(Self::disabled, Self::new),
+        }
+    }
+}
+
+impl SrsCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    /// A cache that never reads or writes files: setups are always generated.
+    pub fn disabled() -> Self {
+        Self { dir: None }
+    }
+
+    #[cfg(feature = "host")]
+    fn user_cache_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("jolt").join("srs"))
+    }
+
+    #[cfg(not(feature = "host"))]
+    fn user_cache_dir() -> Option<PathBuf> {
+        None
+    }
+
+    /// The path of the cached setup for `max_num_vars` variables.
+    pub fn path(&self, max_num_vars: usize) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("dory_srs_{max_num_vars}_variables.srs")))
+    }
+
+    /// Loads the setup for `max_num_vars` variables from the cache, generating (and
+    /// caching) it if it is missing or fails the integrity checks.
+    pub fn load_or_generate(&self, max_num_vars: usize) -> ProverSetup<JoltBn254> {
+        match self.load(max_num_vars) {
+            Ok(Some(prover_setup)) => return prover_setup,
+            Ok(None) => {}
+            Err(err) => tracing::warn!("Regenerating cached Dory SRS: {err}"),
+        }
+        let prover_setup = generate(max_num_vars);
+        if let Err(err) = self.store(max_num_vars, &prover_setup) {
+            tracing::warn!("Failed to cache Dory SRS: {err}");
+        }
+        prover_setup
+    }
+
+    /// Loads the setup for `max_num_vars` variables, or `None` if it is not cached.
+    pub fn load(&self, max_num_vars: usize) -> Result<Option<ProverSetup<JoltBn254>>, SrsError> {
+        let Some(path) = self.path(max_num_vars).filter(|path| path.exists()) else {
+            return Ok(None);
+        };
+        check_not_shared(&path)?;
+        read_srs_file(&path, max_num_vars).map(Some)
+    }
+
+    /// Checks the cached setup for `max_num_vars` variables against a freshly generated
+    /// one. Returns `false` if it differs, and `None` if it is not cached.
+    pub fn audit(&self, max_num_vars: usize) -> Result<Option<bool>, SrsError> {
+        let Some(cached) = self.load(max_num_vars)? else {
+            return Ok(None);
+        };
+        let mut cached_bytes = Vec::new();
+        cached.serialize_uncompressed(&mut cached_bytes)?;
+        let mut expected_bytes = Vec::new();
+        generate(max_num_vars).serialize_uncompressed(&mut expected_bytes)?;
+        Ok(Some(cached_bytes == expected_bytes))
+    }
+
+    /// Writes the setup for `max_num_vars` variables to the cache. The file is written
+    /// under a temporary name and renamed, so concurrent readers never see a partial file.
+    pub fn store(
+        &self,
+        max_num_vars: usize,
+        prover_setup: &ProverSetup<JoltBn254>,
+    ) -> Result<(), SrsError> {
+        let Some(path) = self.path(max_num_vars) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            create_private_dir(dir)?;
+            check_not_shared(&path)?;
+        }
+        let tmp_path = path.with_extension(format!("srs.{}.tmp", std::process::id()));
+        write_srs_file(&tmp_path, max_num_vars, prover_setup)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
+
+/// Creates `dir` (and its parents), readable and writable only by the current user on
+/// Unix.
+fn create_private_dir(dir: &Path) -> Result<(), SrsError> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    Ok(builder.create(dir)?)
+}
+
+/// Fails if the SRS file at `path` (if it exists) or its directory can be written by
+/// users other than its owner, who could then plant a setup with a matching checksum.
+fn check_not_shared(path: &Path) -> Result<(), SrsError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for path in [dir, path] {
+            let metadata = match fs::metadata(path) {
+                Ok(metadata) => metadata,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if metadata.permissions().mode() & 0o022 != 0 {
+                return Err(SrsError::SharedCache(path.to_path_buf()));
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+fn write_srs_file(
+    path: &Path,
+    max_num_vars: usize,
+    prover_setup: &ProverSetup<JoltBn254>,
+) -> Result<(), SrsError> {
+    let mut payload = Vec::new();
+    prover_setup.serialize_uncompressed(&mut payload)?;
+    let checksum: [u8; 32] = Keccak256::digest(&payload).into();
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut writer = BufWriter::new(options.open(path)?);
+    writer.write_all(&SRS_MAGIC)?;
+    (max_num_vars as u64).serialize_uncompressed(&mut writer)?;
+    srs_seed().serialize_uncompressed(&mut writer)?;
+    (payload.len() as u64).serialize_uncompressed(&mut writer)?;
+    checksum.serialize_uncompressed(&mut writer)?;
+    writer.write_all(&payload)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_srs_file(path: &Path, max_num_vars: usize) -> Result<ProverSetup<JoltBn254>, SrsError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != SRS_MAGIC {
+        return Err(SrsError::BadMagic(magic));
+    }
+    let num_vars = u64::deserialize_uncompressed(&mut reader)?;
+    if num_vars != max_num_vars as u64 {
+        return Err(SrsError::NumVarsMismatch {
+            found: num_vars,
+            expected: max_num_vars as u64,
+        });
+    }
+    if <[u8; 32]>::deserialize_uncompressed(&mut reader)? != srs_seed() {
+        return Err(SrsError::SeedMismatch);
+    }
+    let payload_len = u64::deserialize_uncompressed(&mut reader)?;
+    let checksum = <[u8; 32]>::deserialize_uncompressed(&mut reader)?;
+
+    let mut payload = Vec::new();
+    reader.take(payload_len).read_to_end(&mut payload)?;
+    if payload.len() as u64 != payload_len
+        || <[u8; 32]>::from(Keccak256::digest(&payload)) != checksum
+    {
+        return Err(SrsError::ChecksumMismatch);
+    }
+    Ok(ProverSetup::deserialize_uncompressed(&payload[..])?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_cache(name: &str) -> SrsCache {
+        let dir = std::env::temp_dir().join(format!("jolt-srs-test-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        SrsCache::new(dir)
+    }
+
+    fn to_bytes(prover_setup: &ProverSetup<JoltBn254>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        prover_setup
+            .to_verifier_setup()
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn setup_is_deterministic_and_cached() {
+        let (a, b) = (scratch_cache("a"), scratch_cache("b"));
+        let setup_a = to_bytes(&a.load_or_generate(4));
+        let setup_b = to_bytes(&b.load_or_generate(4));
+        assert_eq!(setup_a, setup_b);
+
+        let cached = a.load(4).unwrap().expect("setup should be cached");
+        assert_eq!(to_bytes(&cached), setup_a);
+        assert_eq!(a.audit(4).unwrap(), Some(true));
+        assert_eq!(a.audit(3).unwrap(), None);
+    }
+
+    #[test]
+    fn planted_setup_fails_audit() {
+        // A setup that was not derived from the seed, with a valid header and checksum
+        let cache = scratch_cache("planted");
+        let (planted, _) =
+            setup_with_srs_file::<JoltBn254, _>(&mut ark_std::rand::thread_rng(), 4, None);
+        cache.store(4, &planted).unwrap();
+
+        assert!(cache.load(4).unwrap().is_some());
+        assert_eq!(cache.audit(4).unwrap(), Some(false));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shared_cache_dir_is_not_trusted() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let cache = scratch_cache("shared");
+        let expected = to_bytes(&cache.load_or_generate(4));
+        let dir = cache.path(4).unwrap().parent().unwrap().to_path_buf();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(matches!(cache.load(4), Err(SrsError::SharedCache(_))));
+
+        // The setup is regenerated instead
+        assert_eq!(to_bytes(&cache.load_or_generate(4)), expected);
+    }
+
+    #[test]
+    fn corrupted_setup_is_rejected_and_regenerated() {
+        let cache = scratch_cache("corrupt");
+        let expected = to_bytes(&cache.load_or_generate(4));
+
+        let path = cache.path(4).unwrap();
+        let mut bytes = fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        fs::write(&path, bytes).unwrap();
+        assert!(matches!(cache.load(4), Err(SrsError::ChecksumMismatch)));
+
+        assert_eq!(to_bytes(&cache.load_or_generate(4)), expected);
+        assert!(cache.load(4).unwrap().is_some());
+    }
+}
@@ -9,27 +9,36 @@
 //! (2) HyperKZG is specialized to use KZG as the univariate commitment scheme, so it includes several optimizations (both during the transformation of multilinear-to-univariate claims
 //! and within the KZG commitment scheme implementation itself).
 use super::{
-    commitment_scheme::{CommitmentScheme, StreamingCommitmentScheme},
+    commitment_scheme::{
+        AdditivelyHomomorphic, BatchVerification, CommitmentScheme, DeferredOpening,
+        StreamingChunk, StreamingCommitmentScheme,
+    },
     kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG},
+    ptau::{self, PowersOfTau},
 };
 use crate::field::JoltField;
 use crate::poly::multilinear_polynomial::{MultilinearPolynomial, PolynomialEvaluation};
 use crate::utils::transcript::Transcript;
 use crate::{
     msm::VariableBaseMSM,
-    poly::{commitment::kzg::SRS, dense_mlpoly::DensePolynomial, unipoly::UniPoly},
-    utils::{errors::ProofVerifyError, transcript::AppendToTranscript},
+    poly::commitment::kzg::SRS,
+    utils::{
+        errors::{ProofVerifyError, PtauError},
+        thread::unsafe_allocate_zero_vec,
+        transcript::AppendToTranscript,
+    },
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{One, Zero};
-use rand_chacha::ChaCha20Rng;
-use rand_core::{CryptoRng, RngCore, SeedableRng};
+use rand_core::{CryptoRng, RngCore};
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
     IntoParallelRefMutIterator, ParallelIterator,
 };
+use rayon::slice::{ParallelSlice, ParallelSliceMut};
 use std::borrow::Borrow;
+use std::path::Path;
 use std::{marker::PhantomData, sync::Arc};
 
 pub struct HyperKZGSRS<P: Pairing>(Arc<SRS<P>>);
@@ -42,6 +51,14 @@ impl<P: Pairing> HyperKZGSRS<P> {
         Self(Arc::new(SRS::setup(rng, max_degree, 2)))
     }
 
+    /// Loads the SRS from the powers-of-tau ceremony file at `path`.
+    pub fn from_ptau(path: &Path, max_degree: usize) -> Result<Self, PtauError>
+    where
+        P: PowersOfTau,
+    {
+        Ok(Self(Arc::new(P::srs_from_ptau(path, max_degree, 2)?)))
+    }
+
     pub fn trim(self, max_degree: usize) -> (HyperKZGProverKey<P>, HyperKZGVerifierKey<P>) {
         let (kzg_pk, kzg_vk) = SRS::trim(self.0, max_degree);
         (HyperKZGProverKey { kzg_pk }, HyperKZGVerifierKey { kzg_vk })
@@ -94,18 +111,17 @@ pub struct HyperKZGProof<P: Pairing> {
 // same.  One advantage is that computing f(u) could be decoupled
 // from kzg_open, it could be done later or separate from computing W.
 fn kzg_batch_open_no_rem<P: Pairing>(
-    f: &MultilinearPolynomial<P::ScalarField>,
+    f: &[P::ScalarField],
     u: &[P::ScalarField],
     pk: &HyperKZGProverKey<P>,
 ) -> Vec<P::G1Affine>
 where
     <P as Pairing>::ScalarField: JoltField,
 {
-    let f: &DensePolynomial<P::ScalarField> = f.try_into().unwrap();
     let h = u
         .par_iter()
         .map(|ui| {
-            let h = compute_witness_polynomial::<P>(&f.evals(), *ui);
+            let h = compute_witness_polynomial::<P>(f, *ui);
             MultilinearPolynomial::from(h)
         })
         .collect::<Vec<_>>();
@@ -132,7 +148,7 @@ where
 }
 
 fn kzg_open_batch<P: Pairing, ProofTranscript: Transcript>(
-    f: &[MultilinearPolynomial<P::ScalarField>],
+    f: &[Coeffs<P::ScalarField>],
     u: &[P::ScalarField],
     pk: &HyperKZGProverKey<P>,
     transcript: &mut ProofTranscript,
@@ -150,7 +166,7 @@ where
         // for each point u
         v_i.par_iter_mut().zip_eq(f).for_each(|(v_ij, f)| {
             // for each poly f
-            *v_ij = UniPoly::eval_as_univariate(f, &u[i]);
+            *v_ij = f.eval_as_univariate(&u[i]);
         });
     });
 
@@ -158,7 +174,11 @@ where
     let scalars = v.iter().flatten().collect::<Vec<&P::ScalarField>>();
     transcript.append_scalars::<P::ScalarField>(&scalars);
     let q_powers: Vec<P::ScalarField> = transcript.challenge_scalar_powers(f.len());
-    let B = MultilinearPolynomial::linear_combination(&f.iter().collect::<Vec<_>>(), &q_powers);
+    // B is dense even if the f_i are sparse, as are its quotients by (x - u_j)
+    let mut B: Vec<P::ScalarField> = unsafe_allocate_zero_vec(f[0].len());
+    for (f, q_power) in f.iter().zip(q_powers.iter()) {
+        f.scale_add_to(*q_power, &mut B);
+    }
 
     // Now open B at u0, ..., u_{t-1}
     let w = kzg_batch_open_no_rem(&B, u, pk);
@@ -171,15 +191,18 @@ where
     (w, v)
 }
 
+/// Computes the G1 elements (L, R) such that the batched KZG openings are valid iff
+/// e(L, vk.H) == e(R, vk.tau_H). Deferring the pairing check allows the checks for
+/// many openings to be combined into a single one.
 // vk is hashed in transcript already, so we do not add it here
-fn kzg_verify_batch<P: Pairing, ProofTranscript: Transcript>(
+fn kzg_pairing_inputs<P: Pairing, ProofTranscript: Transcript>(
     vk: &HyperKZGVerifierKey<P>,
     C: &[P::G1Affine],
     W: &[P::G1Affine],
     u: &[P::ScalarField],
     v: &[Vec<P::ScalarField>],
     transcript: &mut ProofTranscript,
-) -> bool
+) -> (P::G1, P::G1)
 where
     <P as Pairing>::ScalarField: JoltField,
 {
@@ -251,8 +274,182 @@ where
 
     let R = W[0] + W[1] * d_0 + W[2] * d_1;
 
-    // Check that e(L, vk.H) == e(R, vk.tau_H)
-    P::multi_pairing([L, -R], [vk.kzg_vk.g2, vk.kzg_vk.beta_g2]).is_zero()
+    (L, R)
+}
+
+/// The coefficients of a polynomial being committed to or opened. HyperKZG commits to the
+/// full coefficient vector, in which a one-hot polynomial (or a linear combination including
+/// one) has at most `T` nonzero coefficients out of `K * T`. Those are kept as (index,
+/// coefficient) pairs sorted by index, until folding makes the polynomial dense.
+enum Coeffs<F: JoltField> {
+    Dense(Vec<F>),
+    Sparse {
+        len: usize,
+        entries: Vec<(usize, F)>,
+    },
+}
+
+impl<F: JoltField> Coeffs<F> {
+    fn new(poly: &MultilinearPolynomial<F>) -> Self {
+        match poly {
+            MultilinearPolynomial::LargeScalars(poly) => Self::Dense(poly.evals()),
+            MultilinearPolynomial::OneHot(poly) => {
+                let mut entries: Vec<(usize, F)> = poly
+                    .nonzero_coeff_indices()
+                    .map(|index| (index, F::one()))
+                    .collect();
+                entries.par_sort_unstable_by_key(|(index, _)| *index);
+                Self::sparse(1 << poly.get_num_vars(), entries)
+            }
+            MultilinearPolynomial::RLC(poly) => {
+                Self::sparse(poly.num_coeffs(), poly.sparse_coeffs())
+            }
+            _ => Self::Dense(
+                (0..poly.len())
+                    .into_par_iter()
+                    .map(|i| poly.get_coeff(i))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Switches to the dense representation once at least half of the coefficients are
+    /// nonzero.
+    fn sparse(len: usize, entries: Vec<(usize, F)>) -> Self {
+        if 2 * entries.len() < len {
+            return Self::Sparse { len, entries };
+        }
+        let mut coeffs: Vec<F> = unsafe_allocate_zero_vec(len);
+        for (index, coeff) in entries {
+            coeffs[index] = coeff;
+        }
+        Self::Dense(coeffs)
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Dense(coeffs) => coeffs.len(),
+            Self::Sparse { len, .. } => *len,
+        }
+    }
+
+    /// Binds the lowest variable to `x`, i.e. coefficients `2j` and `2j + 1` become
+    /// coefficient `j` of the result.
+    fn fold(&self, x: F) -> Self {
+        match self {
+            Self::Dense(coeffs) => Self::Dense(
+                coeffs
+                    .par_chunks(2)
+                    .map(|pair| x * (pair[1] - pair[0]) + pair[0])
+                    .collect(),
+            ),
+            Self::Sparse { len, entries } => {
+                let mut folded: Vec<(usize, F)> = entries
+                    .par_iter()
+                    .map(|(index, coeff)| {
+                        let coeff = if index % 2 == 1 {
+                            x * coeff
+                        } else {
+                            *coeff - x * coeff
+                        };
+                        (index / 2, coeff)
+                    })
+                    .collect();
+                // The two coefficients of a pair are adjacent, and land at the same index
+                folded.dedup_by(|(index, coeff), (prev_index, prev_coeff)| {
+                    if index == prev_index {
+                        *prev_coeff += *coeff;
+                        true
+                    } else {
+                        false
+                    }
+                });
+                Self::sparse(len / 2, folded)
+            }
+        }
+    }
+
+    fn eval_as_univariate(&self, u: &F) -> F {
+        match self {
+            Self::Dense(coeffs) => {
+                let mut eval = F::zero();
+                let mut power = F::one();
+                for coeff in coeffs.iter() {
+                    eval += power * coeff;
+                    power *= *u;
+                }
+                eval
+            }
+            Self::Sparse { entries, .. } => entries
+                .par_chunks(1 << 12)
+                .map(|chunk| {
+                    // Consecutive indices are usually close, so the power of u is
+                    // advanced by the gap rather than recomputed
+                    let mut eval = F::zero();
+                    let mut power = F::one();
+                    let mut prev_index = 0;
+                    for (index, coeff) in chunk.iter() {
+                        power *= pow(*u, index - prev_index);
+                        prev_index = *index;
+                        eval += power * coeff;
+                    }
+                    eval
+                })
+                .sum(),
+        }
+    }
+
+    /// Adds `scalar` times these coefficients to `acc`.
+    fn scale_add_to(&self, scalar: F, acc: &mut [F]) {
+        match self {
+            Self::Dense(coeffs) => acc
+                .par_iter_mut()
+                .zip(coeffs.par_iter())
+                .for_each(|(acc, coeff)| *acc += scalar * coeff),
+            Self::Sparse { entries, .. } => {
+                for (index, coeff) in entries.iter() {
+                    acc[*index] += scalar * coeff;
+                }
+            }
+        }
+    }
+
+    fn commit<P: Pairing<ScalarField = F>>(
+        &self,
+        pk: &KZGProverKey<P>,
+    ) -> Result<P::G1Affine, ProofVerifyError> {
+        let g1_powers = pk.g1_powers();
+        if g1_powers.len() < self.len() {
+            return Err(ProofVerifyError::KeyLengthError(
+                g1_powers.len(),
+                self.len(),
+            ));
+        }
+        let commitment = match self {
+            Self::Dense(coeffs) => UnivariateKZG::<P>::commit_inner_helper(pk, coeffs, 0)?,
+            Self::Sparse { entries, .. } => {
+                let (bases, scalars): (Vec<P::G1Affine>, Vec<F>) = entries
+                    .par_iter()
+                    .map(|(index, coeff)| (g1_powers[*index], *coeff))
+                    .unzip();
+                <P::G1 as VariableBaseMSM>::msm_field_elements(&bases, &scalars, None)?
+            }
+        };
+        Ok(commitment.into_affine())
+    }
+}
+
+fn pow<F: JoltField>(base: F, mut exp: usize) -> F {
+    let mut result = F::one();
+    let mut square = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= square;
+        }
+        square = square.square();
+        exp >>= 1;
+    }
+    result
 }
 
 #[derive(Clone)]
@@ -292,31 +489,26 @@ where
         transcript: &mut ProofTranscript,
     ) -> Result<HyperKZGProof<P>, ProofVerifyError> {
         let ell = point.len();
-        let n = poly.len();
+        let mut polys: Vec<Coeffs<P::ScalarField>> = vec![Coeffs::new(poly)];
+        let n = polys[0].len();
         assert_eq!(n, 1 << ell); // Below we assume that n is a power of two
 
         // Phase 1  -- create commitments com_1, ..., com_\ell
         // We do not compute final Pi (and its commitment) as it is constant and equals to 'eval'
         // also known to verifier, so can be derived on its side as well
-        let mut polys: Vec<MultilinearPolynomial<P::ScalarField>> = Vec::new();
-        polys.push(poly.clone());
         for i in 0..ell - 1 {
-            let previous_poly: &DensePolynomial<P::ScalarField> = (&polys[i]).try_into().unwrap();
-            let Pi_len = previous_poly.len() / 2;
-            let mut Pi = vec![P::ScalarField::zero(); Pi_len];
-            Pi.par_iter_mut().enumerate().for_each(|(j, Pi_j)| {
-                *Pi_j = point[ell - i - 1] * (previous_poly[2 * j + 1] - previous_poly[2 * j])
-                    + previous_poly[2 * j];
-            });
-
-            polys.push(MultilinearPolynomial::from(Pi));
+            let Pi = polys[i].fold(point[ell - i - 1]);
+            polys.push(Pi);
         }
 
         assert_eq!(polys.len(), ell);
         assert_eq!(polys[ell - 1].len(), 2);
 
         // We do not need to commit to the first polynomial as it is already committed.
-        let com: Vec<P::G1Affine> = UnivariateKZG::commit_variable_batch(&pk.kzg_pk, &polys[1..])?;
+        let com: Vec<P::G1Affine> = polys[1..]
+            .par_iter()
+            .map(|Pi| Pi.commit(&pk.kzg_pk))
+            .collect::<Result<_, _>>()?;
 
         // Phase 2
         // We do not need to add x to the transcript, because in our context x was obtained from the transcript.
@@ -340,6 +532,26 @@ where
         pi: &HyperKZGProof<P>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        let (L, R) = Self::pairing_inputs(vk, C, point, P_of_x, pi, transcript)?;
+        // Check that e(L, vk.H) == e(R, vk.tau_H)
+        if !P::multi_pairing([L, -R], [vk.kzg_vk.g2, vk.kzg_vk.beta_g2]).is_zero() {
+            return Err(ProofVerifyError::InternalError);
+        }
+
+        Ok(())
+    }
+
+    /// Performs all of the checks in [`HyperKZG::verify`] except for the final pairing
+    /// check, returning the G1 elements (L, R) such that the proof is valid iff
+    /// e(L, vk.H) == e(R, vk.tau_H).
+    fn pairing_inputs<ProofTranscript: Transcript>(
+        vk: &HyperKZGVerifierKey<P>,
+        C: &HyperKZGCommitment<P>,
+        point: &[P::ScalarField],
+        P_of_x: &P::ScalarField,
+        pi: &HyperKZGProof<P>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<(P::G1, P::G1), ProofVerifyError> {
         let y = P_of_x;
 
         let ell = point.len();
@@ -384,16 +596,12 @@ where
             // check below requires it
         }
 
-        // Check commitments to (Y, ypos, yneg) are valid
-        if !kzg_verify_batch(vk, &com, &pi.w, &u, &pi.v, transcript) {
-            return Err(ProofVerifyError::InternalError);
-        }
-
-        Ok(())
+        // Commitments to (Y, ypos, yneg) are valid iff e(L, vk.H) == e(R, vk.tau_H)
+        Ok(kzg_pairing_inputs(vk, &com, &pi.w, &u, &pi.v, transcript))
     }
 }
 
-impl<P: Pairing> CommitmentScheme for HyperKZG<P>
+impl<P: PowersOfTau> CommitmentScheme for HyperKZG<P>
 where
     <P as Pairing>::ScalarField: JoltField,
 {
@@ -406,14 +614,12 @@ where
     type BatchedProof = HyperKZGProof<P>;
     type OpeningProofHint = ();
 
+    /// Loads the SRS from `$JOLT_PTAU`. Panics if it is not set, unless an insecure SRS
+    /// with a publicly known trapdoor is opted into (see `ptau::setup_srs`).
     fn setup_prover(max_num_vars: usize) -> Self::ProverSetup {
-        HyperKZGSRS(Arc::new(SRS::setup(
-            &mut ChaCha20Rng::from_seed(*b"HyperKZG_POLY_COMMITMENT_SCHEMEE"),
-            1 << max_num_vars,
-            2,
-        )))
-        .trim(1 << max_num_vars)
-        .0
+        let srs = ptau::setup_srs(*b"HyperKZG_POLY_COMMITMENT_SCHEMEE", 1 << max_num_vars, 2)
+            .unwrap_or_else(|err| panic!("Failed to set up the HyperKZG SRS: {err}"));
+        HyperKZGSRS(Arc::new(srs)).trim(1 << max_num_vars).0
     }
 
     fn setup_verifier(setup: &Self::ProverSetup) -> Self::VerifierSetup {
@@ -427,6 +633,23 @@ where
         poly: &MultilinearPolynomial<Self::Field>,
         setup: &Self::ProverSetup,
     ) -> (Self::Commitment, Self::OpeningProofHint) {
+        match poly {
+            MultilinearPolynomial::OneHot(poly) => {
+                assert!(
+                    setup.kzg_pk.g1_powers().len() >= 1 << poly.get_num_vars(),
+                    "COMMIT KEY LENGTH ERROR {}, {}",
+                    setup.kzg_pk.g1_powers().len(),
+                    1 << poly.get_num_vars()
+                );
+                let commitment = poly.commit_flat::<P::G1>(setup.kzg_pk.g1_powers());
+                return (HyperKZGCommitment(commitment.into_affine()), ());
+            }
+            MultilinearPolynomial::RLC(_) => {
+                let commitment = Coeffs::new(poly).commit(&setup.kzg_pk).unwrap();
+                return (HyperKZGCommitment(commitment), ());
+            }
+            _ => {}
+        }
         assert!(
             setup.kzg_pk.g1_powers().len() >= poly.len(),
             "COMMIT KEY LENGTH ERROR {}, {}",
@@ -450,18 +673,6 @@ where
             .collect()
     }
 
-    fn combine_commitments<C: Borrow<Self::Commitment>>(
-        commitments: &[C],
-        coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        let combined_commitment: P::G1 = commitments
-            .iter()
-            .zip(coeffs.iter())
-            .map(|(commitment, coeff)| commitment.borrow().0 * coeff)
-            .sum();
-        HyperKZGCommitment(combined_commitment.into_affine())
-    }
-
     fn prove<ProofTranscript: Transcript>(
         setup: &Self::ProverSetup,
         poly: &MultilinearPolynomial<Self::Field>,
@@ -469,8 +680,8 @@ where
         _: Self::OpeningProofHint,
         transcript: &mut ProofTranscript,
     ) -> Self::Proof {
-        let eval = poly.evaluate(opening_point);
-        HyperKZG::<P>::open(setup, poly, opening_point, &eval, transcript).unwrap()
+        // `open` doesn't use the evaluation, which isn't implemented for RLC polynomials
+        HyperKZG::<P>::open(setup, poly, opening_point, &Self::Field::zero(), transcript).unwrap()
     }
 
     fn verify<ProofTranscript: Transcript>(
@@ -489,17 +700,111 @@ where
     }
 }
 
-// #[derive(Clone, Debug)]
+impl<P: PowersOfTau> BatchVerification for HyperKZG<P>
+where
+    <P as Pairing>::ScalarField: JoltField,
+{
+    /// Combines the final pairing checks of all openings into one, using a random
+    /// linear combination: if e(L_i, H) == e(R_i, tau_H) for all i, then
+    /// e(sum_i rho^i L_i, H) == e(sum_i rho^i R_i, tau_H).
+    fn batch_verify<ProofTranscript: Transcript>(
+        openings: Vec<DeferredOpening<Self, ProofTranscript>>,
+        setup: &Self::VerifierSetup,
+    ) -> Result<(), ProofVerifyError> {
+        let pairing_inputs: Vec<(P::G1, P::G1)> = openings
+            .into_par_iter()
+            .map(|mut opening| {
+                HyperKZG::<P>::pairing_inputs(
+                    setup,
+                    &opening.commitment,
+                    &opening.opening_point,
+                    &opening.opening,
+                    &opening.proof,
+                    &mut opening.transcript,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        // The combination coefficients are derived from all of the pairing inputs,
+        // which are fixed by the proofs being verified.
+        let mut transcript = ProofTranscript::new(b"HyperKZG_batch_verify");
+        for (L, R) in pairing_inputs.iter() {
+            transcript.append_point(L);
+            transcript.append_point(R);
+        }
+        let rho_powers: Vec<P::ScalarField> =
+            transcript.challenge_scalar_powers(pairing_inputs.len());
+
+        let (L, R) = pairing_inputs.iter().zip(rho_powers.iter()).fold(
+            (P::G1::zero(), P::G1::zero()),
+            |(L, R), ((L_i, R_i), rho)| (L + *L_i * rho, R + *R_i * rho),
+        );
+        if !P::multi_pairing([L, -R], [setup.kzg_vk.g2, setup.kzg_vk.beta_g2]).is_zero() {
+            return Err(ProofVerifyError::InternalError);
+        }
+
+        Ok(())
+    }
+}
+
+impl<P: PowersOfTau> AdditivelyHomomorphic for HyperKZG<P>
+where
+    <P as Pairing>::ScalarField: JoltField,
+{
+    fn combine_commitments<C: Borrow<Self::Commitment>>(
+        commitments: &[C],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        let combined_commitment: P::G1 = commitments
+            .iter()
+            .zip(coeffs.iter())
+            .map(|(commitment, coeff)| commitment.borrow().0 * coeff)
+            .sum();
+        HyperKZGCommitment(combined_commitment.into_affine())
+    }
+
+    fn combine_hints(
+        _hints: Vec<Self::OpeningProofHint>,
+        _coeffs: &[Self::Field],
+    ) -> Self::OpeningProofHint {
+    }
+}
+
 pub struct HyperKZGState<'a, P: Pairing> {
     acc: P::G1,
     prover_key: &'a KZGProverKey<P>,
+    size: usize,
     current_chunk: Vec<P::ScalarField>,
-    row_count: usize,
+    /// The index of the first coefficient in `current_chunk`
+    offset: usize,
+    /// The number of cycles appended so far, if the polynomial is one-hot
+    num_cycles: usize,
 }
 
 const CHUNK_SIZE: usize = 256;
 
-impl<P: Pairing> StreamingCommitmentScheme for HyperKZG<P>
+impl<P: PowersOfTau> HyperKZG<P>
+where
+    <P as Pairing>::ScalarField: JoltField,
+{
+    /// Adds the commitment to the buffered coefficients of `state` to its accumulator.
+    fn flush(mut state: HyperKZGState<'_, P>) -> HyperKZGState<'_, P> {
+        if !state.current_chunk.is_empty() {
+            let c: P::G1 = UnivariateKZG::commit_inner_helper(
+                state.prover_key,
+                &state.current_chunk,
+                state.offset,
+            )
+            .unwrap();
+            state.acc += c;
+            state.offset += state.current_chunk.len();
+            state.current_chunk.clear();
+        }
+        state
+    }
+}
+
+impl<P: PowersOfTau> StreamingCommitmentScheme for HyperKZG<P>
 where
     <P as Pairing>::ScalarField: JoltField,
 {
@@ -512,19 +817,16 @@ where
             setup.kzg_pk.g1_powers().len(),
             size,
         );
-        assert_eq!(
-            size % CHUNK_SIZE,
-            0,
-            "CHUNK_SIZE ({CHUNK_SIZE}) must evenly divide the size ({size})"
-        );
 
         let current_chunk = Vec::with_capacity(CHUNK_SIZE);
 
         HyperKZGState {
             acc: P::G1::zero(),
             prover_key: &setup.kzg_pk,
+            size,
             current_chunk,
-            row_count: 0,
+            offset: 0,
+            num_cycles: 0,
         }
     }
 
@@ -532,31 +834,57 @@ where
         state.current_chunk.push(eval);
 
         if state.current_chunk.len() == CHUNK_SIZE {
-            let offset = state.row_count * CHUNK_SIZE;
-            let c: P::G1 =
-                UnivariateKZG::commit_inner_helper(state.prover_key, &state.current_chunk, offset)
-                    .unwrap();
-
-            state.acc += c;
-            state.current_chunk.clear();
-            state.row_count += 1;
+            state = Self::flush(state);
         }
 
         state
     }
 
-    fn finalize<'a>(state: Self::State<'a>) -> Self::Commitment {
-        HyperKZGCommitment(state.acc.into())
+    fn process_chunk<'a>(
+        mut state: Self::State<'a>,
+        chunk: StreamingChunk<Self::Field>,
+    ) -> Self::State<'a> {
+        let prover_key = state.prover_key;
+        let g1_powers = prover_key.g1_powers();
+        match chunk {
+            StreamingChunk::OneHot(indices, K) => {
+                // Coefficient (k, t) of a one-hot polynomial is at index k * T + t
+                let (T, offset) = (state.size / K, state.num_cycles);
+                state.acc += indices
+                    .par_iter()
+                    .enumerate()
+                    .filter_map(|(t, k)| k.map(|k| g1_powers[k * T + offset + t]))
+                    .fold(P::G1::zero, |acc, base| acc + base)
+                    .reduce(P::G1::zero, |a, b| a + b);
+                state.num_cycles += indices.len();
+            }
+            _ => {
+                state = Self::flush(state);
+                let bases = &g1_powers[state.offset..state.offset + chunk.len()];
+                state.acc += chunk.msm::<P::G1>(bases).unwrap();
+                state.offset += chunk.len();
+            }
+        }
+        state
+    }
+
+    fn finalize<'a>(state: Self::State<'a>) -> (Self::Commitment, Self::OpeningProofHint) {
+        let state = Self::flush(state);
+        (HyperKZGCommitment(state.acc.into()), ())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::poly::commitment::dory::DoryGlobals;
+    use crate::poly::one_hot_polynomial::OneHotPolynomial;
+    use crate::utils::math::Math;
     use crate::utils::transcript::{KeccakTranscript, Transcript};
     use ark_bn254::{Bn254, Fr};
     use ark_std::UniformRand;
     use rand_core::SeedableRng;
+    use serial_test::serial;
 
     #[test]
     fn test_hyperkzg_eval() {
@@ -711,11 +1039,158 @@ mod tests {
             for p in poly_raw {
                 state = HyperKZG::process(state, p);
             }
-            let C2 = HyperKZG::finalize(state);
+            let (C2, _) = HyperKZG::finalize(state);
             assert_eq!(
                 C, C2,
                 "Streaming commitment did not match non-streaming commitment"
             );
         }
     }
+
+    #[test]
+    #[serial]
+    fn test_hyperkzg_one_hot_rlc() {
+        // A linear combination of a one-hot and a dense polynomial is committed to and
+        // opened from its sparse coefficients, which must give the same commitment and
+        // proof as its dense coefficient vector
+        let (K, T) = (16, 16);
+        let _guard = DoryGlobals::initialize(K, T);
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let srs = HyperKZGSRS::setup(&mut rng, K * T);
+        let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(K * T);
+
+        let indices: Vec<Option<usize>> = (0..T)
+            .map(|t| (t % 3 != 0).then_some(rng.next_u64() as usize % K))
+            .collect();
+        let one_hot =
+            MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(indices.clone(), K));
+        let dense_coeffs: Vec<Fr> = (0..T).map(|_| Fr::rand(&mut rng)).collect();
+        let dense = MultilinearPolynomial::from(dense_coeffs.clone());
+        let rlc_coeffs = [Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        let rlc = MultilinearPolynomial::linear_combination(&[&one_hot, &dense], &rlc_coeffs);
+
+        let mut one_hot_coeffs = vec![Fr::zero(); K * T];
+        let mut expected_coeffs = vec![Fr::zero(); K * T];
+        for (t, k) in indices.iter().enumerate() {
+            if let Some(k) = k {
+                one_hot_coeffs[k * T + t] = Fr::one();
+                expected_coeffs[k * T + t] += rlc_coeffs[0];
+            }
+            expected_coeffs[t] += rlc_coeffs[1] * dense_coeffs[t];
+        }
+        let expected = MultilinearPolynomial::from(expected_coeffs);
+
+        assert_eq!(
+            <HyperKZG<Bn254> as CommitmentScheme>::commit(&one_hot, &pk).0,
+            HyperKZG::commit(&pk, &MultilinearPolynomial::from(one_hot_coeffs)).unwrap()
+        );
+        let C = HyperKZG::commit(&pk, &expected).unwrap();
+        assert_eq!(
+            <HyperKZG<Bn254> as CommitmentScheme>::commit(&rlc, &pk).0,
+            C
+        );
+
+        let point: Vec<Fr> = (0..(K * T).log_2()).map(|_| Fr::rand(&mut rng)).collect();
+        let eval = expected.evaluate(&point);
+        let mut prover_transcript = KeccakTranscript::new(b"TestEval");
+        let proof = <HyperKZG<Bn254> as CommitmentScheme>::prove(
+            &pk,
+            &rlc,
+            &point,
+            (),
+            &mut prover_transcript,
+        );
+        let mut prover_transcript = KeccakTranscript::new(b"TestEval");
+        let expected_proof =
+            HyperKZG::open(&pk, &expected, &point, &eval, &mut prover_transcript).unwrap();
+        let (mut proof_bytes, mut expected_proof_bytes) = (vec![], vec![]);
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+        expected_proof
+            .serialize_compressed(&mut expected_proof_bytes)
+            .unwrap();
+        assert_eq!(proof_bytes, expected_proof_bytes);
+
+        let mut verifier_transcript = KeccakTranscript::new(b"TestEval");
+        assert!(HyperKZG::verify(&vk, &C, &point, &eval, &proof, &mut verifier_transcript).is_ok());
+    }
+
+    #[test]
+    fn test_hyperkzg_streaming_chunks() {
+        let n = 1 << 8;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let srs = HyperKZGSRS::setup(&mut rng, n);
+        let (pk, _): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(n);
+
+        // Dense chunks that don't line up with `CHUNK_SIZE`, interleaved with single
+        // coefficients
+        let coeffs: Vec<u8> = (0..n).map(|i| (i * 7 % 13) as u8).collect();
+        let poly = MultilinearPolynomial::from(coeffs.clone());
+        let mut state = HyperKZG::initialize(n, &pk);
+        for chunk in coeffs.chunks(100) {
+            state = HyperKZG::process(state, Fr::from(chunk[0]));
+            state = HyperKZG::process_chunk(state, StreamingChunk::U8Scalars(&chunk[1..]));
+        }
+        assert_eq!(
+            <HyperKZG<Bn254> as CommitmentScheme>::commit(&poly, &pk).0,
+            HyperKZG::finalize(state).0
+        );
+
+        // Coefficient (k, t) of a one-hot polynomial is at index k * T + t
+        let (K, T) = (4, n / 4);
+        let indices: Vec<Option<usize>> =
+            (0..T).map(|t| (t % 5 != 0).then_some(t * 3 % K)).collect();
+        let mut coeffs = vec![0u8; n];
+        for (t, k) in indices.iter().enumerate() {
+            if let Some(k) = k {
+                coeffs[k * T + t] = 1;
+            }
+        }
+        let poly = MultilinearPolynomial::from(coeffs);
+        let mut state = HyperKZG::initialize(n, &pk);
+        for chunk in indices.chunks(10) {
+            state = HyperKZG::process_chunk(state, StreamingChunk::OneHot(chunk, K));
+        }
+        assert_eq!(
+            <HyperKZG<Bn254> as CommitmentScheme>::commit(&poly, &pk).0,
+            HyperKZG::finalize(state).0
+        );
+    }
+
+    #[test]
+    fn test_hyperkzg_batch_verify() {
+        let ell = 8;
+        let n = 1 << ell;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(ell as u64);
+        let srs = HyperKZGSRS::setup(&mut rng, n);
+        let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(n);
+
+        let openings: Vec<DeferredOpening<HyperKZG<Bn254>, KeccakTranscript>> = (0..4)
+            .map(|_| {
+                let poly = MultilinearPolynomial::from(
+                    (0..n).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>(),
+                );
+                let point = (0..ell).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+                let eval = poly.evaluate(&point);
+                let commitment = HyperKZG::commit(&pk, &poly).unwrap();
+
+                let mut prover_transcript = KeccakTranscript::new(b"TestEval");
+                let proof =
+                    HyperKZG::open(&pk, &poly, &point, &eval, &mut prover_transcript).unwrap();
+                DeferredOpening {
+                    proof,
+                    transcript: KeccakTranscript::new(b"TestEval"),
+                    opening_point: point,
+                    opening: eval,
+                    commitment,
+                }
+            })
+            .collect();
+
+        assert!(HyperKZG::batch_verify(openings.clone(), &vk).is_ok());
+
+        // A single bad opening should cause the whole batch to fail
+        let mut bad_openings = openings;
+        bad_openings[2].proof.w[0] = bad_openings[1].proof.w[0];
+        assert!(HyperKZG::batch_verify(bad_openings, &vk).is_err());
+    }
 }
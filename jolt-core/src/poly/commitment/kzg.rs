@@ -69,9 +69,17 @@ impl<P: Pairing> SRS<P> {
             || P::G2::normalize_batch(&g2_powers_projective),
         );
 
+        Self::from_powers(g1_powers, g2_powers)
+    }
+
+    /// Builds an SRS from powers of tau computed elsewhere, e.g. in a ceremony.
+    pub fn from_powers(g1_powers: Vec<P::G1Affine>, g2_powers: Vec<P::G2Affine>) -> Self
+    where
+        P::ScalarField: JoltField,
+    {
         // Precompute a commitment to each power-of-two length vector of ones, which is just the sum of each power-of-two length prefix of the SRS
         let num_powers = (g1_powers.len() as f64).log2().floor() as usize + 1;
-        let all_ones_coeffs: Vec<u8> = vec![1; num_g1_powers + 1];
+        let all_ones_coeffs: Vec<u8> = vec![1; g1_powers.len()];
         let powers_of_2 = (0..num_powers).into_par_iter().map(|i| 1usize << i);
         let g_products = powers_of_2
             .map(|power| {
@@ -0,0 +1,879 @@
+//! A transparent, hash-based polynomial commitment scheme for multilinear polynomials, in the
+//! style of Ligero and Brakedown (<https://eprint.iacr.org/2021/1043>).
+//!
+//! The coefficients of a polynomial are arranged in a matrix, each row of which is encoded
+//! with a Reed-Solomon code. The commitment is the root of a Merkle tree (using Keccak)
+//! whose leaves are the columns of the encoded matrix. To open the polynomial at
+//! `r = (r_row, r_col)`, the prover sends the linear combination of the rows with
+//! coefficients `eq(r_row, ·)`, from which the verifier computes the evaluation, along with
+//! a random linear combination of the rows. The verifier checks that the encodings of both
+//! are consistent with a random subset of the committed columns. There is no trusted setup,
+//! and security relies only on the collision resistance of the hash function, so unlike the
+//! curve-based schemes in this module, the scheme is plausibly post-quantum.
+//!
+//! Commitments are not additively homomorphic. Instead, a [`LigeroCommitment`] is a formal
+//! linear combination of committed matrices, and the proof of an opening of a linear
+//! combination opens the columns of every matrix in it (see [`BatchOpening`]). The shape of
+//! a matrix depends only on the number of variables of its polynomial, and the matrices of
+//! polynomials with the same number of variables are opened against the same combined rows.
+//!
+//! Ligero is used rather than a Basefold- or FRI-style scheme because a single round of
+//! encoding and column openings is all there is to it: the prover encodes each polynomial
+//! once, with no folding rounds, and the verifier is a few inner products and Merkle path
+//! checks, which keeps it easy to audit. Batching linear combinations also falls out of the
+//! tensor structure, as above. The cost is proof size: opening proofs grow with the square
+//! root of the size of the polynomials rather than polylogarithmically, so they are far
+//! larger than those of FRI-based schemes. A Basefold-style scheme can implement
+//! [`BatchOpening`] in the same way if proof size becomes the bottleneck.
+
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use ark_ff::FftField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
+
+use super::commitment_scheme::{
+    BatchOpening, CommitmentScheme, StreamingChunk, StreamingCommitmentScheme,
+};
+use crate::{
+    field::JoltField,
+    poly::{
+        eq_poly::EqPolynomial, multilinear_polynomial::MultilinearPolynomial,
+        one_hot_polynomial::OneHotPolynomial,
+    },
+    utils::{
+        errors::ProofVerifyError,
+        math::Math,
+        transcript::{AppendToTranscript, Transcript},
+    },
+};
+
+/// log2 of the inverse rate of the Reed-Solomon code.
+pub const LOG_BLOWUP: usize = 2;
+/// The number of columns opened by an opening proof. The code has relative distance 3/4,
+/// so each opened column catches a prover whose matrix is far from the code with
+/// probability at least 1/4, and `(3/4)^NUM_COLUMN_OPENINGS < 2^-100`.
+pub const NUM_COLUMN_OPENINGS: usize = 246;
+
+/// A node of a Merkle tree.
+pub type Node = [u8; 32];
+
+#[derive(Clone)]
+pub struct Ligero<F: JoltField + FftField> {
+    _field: PhantomData<F>,
+}
+
+/// The commitment to a single matrix: the root of the Merkle tree over its encoded columns,
+/// the number of variables of the committed polynomial, which determines the shape of the
+/// matrix, and the number of rows that aren't all zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MatrixCommitment {
+    pub root: Node,
+    pub num_vars: usize,
+    pub num_rows: usize,
+}
+
+/// A commitment to `sum_i coeffs[i] * polys[i]`, where `matrices[i]` is the commitment to
+/// `polys[i]`. The commitment to a single polynomial has a single matrix, with coefficient 1.
+#[derive(Clone, Debug, Default, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LigeroCommitment<F: JoltField> {
+    pub coeffs: Vec<F>,
+    pub matrices: Vec<MatrixCommitment>,
+}
+
+impl<F: JoltField> AppendToTranscript for LigeroCommitment<F> {
+    fn append_to_transcript<ProofTranscript: Transcript>(&self, transcript: &mut ProofTranscript) {
+        transcript.append_message(b"ligero_commitment");
+        for (coeff, matrix) in self.coeffs.iter().zip(self.matrices.iter()) {
+            transcript.append_scalar(coeff);
+            transcript.append_bytes(&matrix.root);
+            transcript.append_u64(matrix.num_vars as u64);
+            transcript.append_u64(matrix.num_rows as u64);
+        }
+    }
+}
+
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LigeroProof<F: JoltField> {
+    /// For each group of opened matrices (see [`group_by_num_vars`]), a random linear
+    /// combination of their rows, used to check that they are close to the code.
+    pub proximity_rows: Vec<Vec<F>>,
+    /// For each group of opened matrices, the linear combination of their rows with
+    /// coefficients `eq(r_row, ·)`.
+    pub evaluation_rows: Vec<Vec<F>>,
+    /// The opened columns of each committed matrix, indexed by matrix and then by query.
+    pub columns: Vec<Vec<Vec<F>>>,
+    /// The Merkle paths of the opened columns, indexed like `columns`.
+    pub paths: Vec<Vec<Vec<Node>>>,
+}
+
+/// A Merkle tree over the columns of an encoded matrix. `layers[0]` holds the hashes of the
+/// columns, and the last layer holds the root.
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    layers: Vec<Vec<Node>>,
+}
+
+impl MerkleTree {
+    fn new(leaves: Vec<Node>) -> Self {
+        debug_assert!(leaves.len().is_power_of_two());
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .par_chunks(2)
+                .map(|pair| hash_nodes(&pair[0], &pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> Node {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// The siblings of the nodes on the path from leaf `index` to the root.
+    fn path(&self, mut index: usize) -> Vec<Node> {
+        self.layers[..self.layers.len() - 1]
+            .iter()
+            .map(|layer| {
+                let sibling = layer[index ^ 1];
+                index >>= 1;
+                sibling
+            })
+            .collect()
+    }
+}
+
+fn hash_nodes(left: &Node, right: &Node) -> Node {
+    let mut hasher = Keccak256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn verify_path(root: &Node, mut index: usize, leaf: Node, path: &[Node]) -> bool {
+    let computed_root = path.iter().fold(leaf, |node, sibling| {
+        let parent = if index & 1 == 0 {
+            hash_nodes(&node, sibling)
+        } else {
+            hash_nodes(sibling, &node)
+        };
+        index >>= 1;
+        parent
+    });
+    computed_root == *root
+}
+
+/// Absorbs `x` into `hasher`, using `bytes` as scratch space.
+fn absorb<F: CanonicalSerialize>(hasher: &mut Keccak256, x: &F, bytes: &mut Vec<u8>) {
+    bytes.clear();
+    x.serialize_uncompressed(&mut *bytes).unwrap();
+    hasher.update(bytes.as_slice());
+}
+
+fn hash_column<F: CanonicalSerialize>(column: &[F]) -> Node {
+    let mut hasher = Keccak256::new();
+    let mut bytes = Vec::new();
+    for x in column {
+        absorb(&mut hasher, x, &mut bytes);
+    }
+    hasher.finalize().into()
+}
+
+/// Encodes a row with the Reed-Solomon code: the row is viewed as the coefficients of a
+/// univariate polynomial, which is evaluated over the subgroup of order
+/// `num_columns << LOG_BLOWUP`.
+fn encode<F: JoltField + FftField>(row: &[F], num_columns: usize) -> Vec<F> {
+    let mut codeword = vec![F::zero(); num_columns << LOG_BLOWUP];
+    codeword[..row.len()].copy_from_slice(row);
+    fft(&mut codeword);
+    codeword
+}
+
+/// In-place radix-2 FFT, evaluating the polynomial with coefficients `values` over the
+/// subgroup of order `values.len()`.
+fn fft<F: JoltField + FftField>(values: &mut [F]) {
+    let n = values.len();
+    if n == 1 {
+        return;
+    }
+    let log_n = n.log_2();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS as usize - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let omega = F::get_root_of_unity(n as u64).expect("FFT size exceeds the field's 2-adicity");
+    let mut half_size = 1;
+    while half_size < n {
+        let step = omega.pow([(n / (2 * half_size)) as u64]);
+        let twiddles: Vec<F> = std::iter::successors(Some(F::one()), |w| Some(*w * step))
+            .take(half_size)
+            .collect();
+        for chunk in values.chunks_mut(2 * half_size) {
+            let (lo, hi) = chunk.split_at_mut(half_size);
+            for ((a, b), w) in lo.iter_mut().zip(hi.iter_mut()).zip(twiddles.iter()) {
+                let t = *b * w;
+                *b = *a - t;
+                *a += t;
+            }
+        }
+        half_size *= 2;
+    }
+}
+
+/// log2 of the number of rows of the matrix of a polynomial with `num_vars` variables. A
+/// quarter of the variables index the rows, which keeps the opened columns (and so the
+/// proof) small.
+fn log_num_rows(num_vars: usize) -> usize {
+    num_vars / 4
+}
+
+/// log2 of the number of columns of the matrix of a polynomial with `num_vars` variables.
+fn log_num_columns(num_vars: usize) -> usize {
+    num_vars - log_num_rows(num_vars)
+}
+
+fn matrix_num_vars<F: JoltField>(poly: &MultilinearPolynomial<F>) -> usize {
+    match poly {
+        MultilinearPolynomial::RLC(poly) => poly.num_coeffs().log_2(),
+        _ => poly.get_num_vars(),
+    }
+}
+
+/// The number of rows of the matrix of `poly` that aren't all zero.
+fn num_rows<F: JoltField>(poly: &MultilinearPolynomial<F>, num_columns: usize) -> usize {
+    let num_coeffs = match poly {
+        MultilinearPolynomial::OneHot(poly) => poly.K * poly.nonzero_indices.len(),
+        MultilinearPolynomial::RLC(poly) => poly.num_coeffs(),
+        _ => poly.original_len(),
+    };
+    num_coeffs.div_ceil(num_columns)
+}
+
+/// Row `index` of the matrix of `poly`, zero-padded to `num_columns` coefficients.
+fn row<F: JoltField>(poly: &MultilinearPolynomial<F>, index: usize, num_columns: usize) -> Vec<F> {
+    let mut row = vec![F::zero(); num_columns];
+    let start = index * num_columns;
+    match poly {
+        MultilinearPolynomial::OneHot(poly) => poly.scale_add_range_to(F::one(), start, &mut row),
+        MultilinearPolynomial::RLC(poly) => poly.add_range_to(start, &mut row),
+        _ => {
+            let len = poly.original_len().saturating_sub(start).min(num_columns);
+            for (i, coeff) in row[..len].iter_mut().enumerate() {
+                *coeff = poly.get_coeff(start + i);
+            }
+        }
+    }
+    row
+}
+
+/// Groups the matrices of a batched opening by the number of variables of their
+/// polynomials, in increasing order. Matrices in a group have the same shape, and are
+/// opened against the same combined rows.
+fn group_by_num_vars(num_vars: impl Iterator<Item = usize>) -> BTreeMap<usize, Vec<usize>> {
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, num_vars) in num_vars.enumerate() {
+        groups.entry(num_vars).or_default().push(i);
+    }
+    groups
+}
+
+/// Splits `opening_point` for a polynomial with `num_vars` variables. A polynomial with
+/// fewer variables than the opening point is embedded in the first coefficients of the
+/// opened linear combination, i.e. it is evaluated at the last `num_vars` variables and
+/// scaled by `prod_i (1 - r_i)` over the others. Returns the variables indexing the rows
+/// and the columns of its matrix, and that factor.
+fn split_point<F: JoltField>(opening_point: &[F], num_vars: usize) -> (&[F], &[F], F) {
+    let (r_prefix, r) = opening_point.split_at(opening_point.len() - num_vars);
+    let (r_row, r_col) = r.split_at(log_num_rows(num_vars));
+    let lagrange = r_prefix.iter().map(|r| F::one() - r).product();
+    (r_row, r_col, lagrange)
+}
+
+/// Samples the indices of the columns to open.
+fn sample_queries<F: JoltField, ProofTranscript: Transcript>(
+    transcript: &mut ProofTranscript,
+    codeword_len: usize,
+) -> Vec<usize> {
+    let mut bytes = Vec::new();
+    (0..NUM_COLUMN_OPENINGS)
+        .map(|_| {
+            let challenge: F = transcript.challenge_scalar();
+            bytes.clear();
+            challenge.serialize_uncompressed(&mut bytes).unwrap();
+            u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize % codeword_len
+        })
+        .collect()
+}
+
+fn inner_product<F: JoltField>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b.iter()).map(|(a, b)| *a * b).sum()
+}
+
+impl<F: JoltField + FftField> Ligero<F> {
+    /// Commits to the rows of `poly`, hashing each column of the encoded matrix as the
+    /// rows are encoded so that the encoded matrix is never held in memory at once.
+    #[tracing::instrument(skip_all, name = "Ligero::commit_rows")]
+    fn commit_rows(
+        poly: &MultilinearPolynomial<F>,
+        num_rows: usize,
+        num_columns: usize,
+    ) -> MerkleTree {
+        let mut leaves = vec![Keccak256::new(); num_columns << LOG_BLOWUP];
+        let chunk_size = rayon::current_num_threads();
+        for chunk_start in (0..num_rows).step_by(chunk_size) {
+            let codewords: Vec<Vec<F>> = (chunk_start..num_rows.min(chunk_start + chunk_size))
+                .into_par_iter()
+                .map(|i| encode(&row(poly, i, num_columns), num_columns))
+                .collect();
+            leaves
+                .par_iter_mut()
+                .enumerate()
+                .for_each_init(Vec::new, |bytes, (j, leaf)| {
+                    for codeword in codewords.iter() {
+                        absorb(leaf, &codeword[j], bytes);
+                    }
+                });
+        }
+        MerkleTree::new(
+            leaves
+                .into_par_iter()
+                .map(|leaf| leaf.finalize().into())
+                .collect(),
+        )
+    }
+
+    /// Adds `coeff * sum_i weights[i] * M[i]` to `out` for both sets of weights, where
+    /// `M` is the matrix of `poly`.
+    fn add_row_combinations(
+        poly: &MultilinearPolynomial<F>,
+        coeff: F,
+        weights: [&[F]; 2],
+        num_columns: usize,
+        out: [&mut [F]; 2],
+    ) {
+        let zero = || [vec![F::zero(); num_columns], vec![F::zero(); num_columns]];
+        let add = |mut a: [Vec<F>; 2], b: [Vec<F>; 2]| {
+            for (a, b) in a.iter_mut().zip(b) {
+                a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+            }
+            a
+        };
+        let combinations = match poly {
+            // Only the coefficients that are 1 contribute, so the rows of a one-hot
+            // matrix aren't materialized
+            MultilinearPolynomial::OneHot(poly) => poly
+                .nonzero_coeff_indices()
+                .fold(zero, |mut acc, index| {
+                    let (i, j) = (index / num_columns, index % num_columns);
+                    for (acc, weights) in acc.iter_mut().zip(weights) {
+                        acc[j] += weights[i];
+                    }
+                    acc
+                })
+                .reduce(zero, add),
+            _ => (0..num_rows(poly, num_columns))
+                .into_par_iter()
+                .fold(zero, |mut acc, i| {
+                    let row = row(poly, i, num_columns);
+                    for (acc, weights) in acc.iter_mut().zip(weights) {
+                        for (acc, x) in acc.iter_mut().zip(row.iter()) {
+                            *acc += weights[i] * x;
+                        }
+                    }
+                    acc
+                })
+                .reduce(zero, add),
+        };
+        for (out, combination) in out.into_iter().zip(combinations) {
+            for (out, x) in out.iter_mut().zip(combination) {
+                *out += coeff * x;
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, name = "Ligero::prove")]
+    fn prove_matrices<ProofTranscript: Transcript>(
+        polynomials: &[&MultilinearPolynomial<F>],
+        coeffs: &[F],
+        trees: Vec<Arc<MerkleTree>>,
+        opening_point: &[F],
+        transcript: &mut ProofTranscript,
+    ) -> LigeroProof<F> {
+        let num_vars: Vec<usize> = polynomials
+            .iter()
+            .map(|poly| matrix_num_vars(poly))
+            .collect();
+        assert!(
+            num_vars.iter().all(|n| *n <= opening_point.len()),
+            "polynomial has more variables than the opening point"
+        );
+        let groups = group_by_num_vars(num_vars.iter().copied());
+
+        transcript.append_message(b"ligero_open");
+        for tree in trees.iter() {
+            transcript.append_bytes(&tree.root());
+        }
+
+        let (proximity_rows, evaluation_rows): (Vec<Vec<F>>, Vec<Vec<F>>) = groups
+            .iter()
+            .map(|(num_vars, group)| {
+                let num_columns = 1 << log_num_columns(*num_vars);
+                let (r_row, _, _) = split_point(opening_point, *num_vars);
+                let gamma: Vec<F> = transcript.challenge_vector(1 << r_row.len());
+                let eq_row = EqPolynomial::evals(r_row);
+
+                let mut proximity_row = vec![F::zero(); num_columns];
+                let mut evaluation_row = vec![F::zero(); num_columns];
+                for i in group.iter() {
+                    Self::add_row_combinations(
+                        polynomials[*i],
+                        coeffs[*i],
+                        [&gamma, &eq_row],
+                        num_columns,
+                        [&mut proximity_row, &mut evaluation_row],
+                    );
+                }
+                (proximity_row, evaluation_row)
+            })
+            .unzip();
+        for (proximity_row, evaluation_row) in proximity_rows.iter().zip(evaluation_rows.iter()) {
+            transcript.append_scalars(proximity_row);
+            transcript.append_scalars(evaluation_row);
+        }
+
+        let mut queries: Vec<&[usize]> = vec![&[][..]; polynomials.len()];
+        let group_queries: Vec<Vec<usize>> = groups
+            .keys()
+            .map(|num_vars| {
+                sample_queries::<F, _>(transcript, 1 << (log_num_columns(*num_vars) + LOG_BLOWUP))
+            })
+            .collect();
+        for (group, group_queries) in groups.values().zip(group_queries.iter()) {
+            for i in group.iter() {
+                queries[*i] = group_queries.as_slice();
+            }
+        }
+
+        let (columns, paths) = polynomials
+            .iter()
+            .zip(num_vars.iter())
+            .zip(trees.iter())
+            .zip(queries.iter())
+            .map(|(((poly, num_vars), tree), queries)| {
+                let num_columns = 1 << log_num_columns(*num_vars);
+                // The encoded matrix isn't kept after committing, so the opened entries of
+                // each row are recomputed here
+                let opened_rows: Vec<Vec<F>> = (0..num_rows(poly, num_columns))
+                    .into_par_iter()
+                    .map(|i| {
+                        let codeword = encode(&row(poly, i, num_columns), num_columns);
+                        queries.iter().map(|j| codeword[*j]).collect()
+                    })
+                    .collect();
+                let columns: Vec<Vec<F>> = (0..queries.len())
+                    .map(|q| opened_rows.iter().map(|row| row[q]).collect())
+                    .collect();
+                let paths: Vec<Vec<Node>> = queries.iter().map(|j| tree.path(*j)).collect();
+                (columns, paths)
+            })
+            .unzip();
+
+        LigeroProof {
+            proximity_rows,
+            evaluation_rows,
+            columns,
+            paths,
+        }
+    }
+}
+
+impl<F: JoltField + FftField> CommitmentScheme for Ligero<F> {
+    type Field = F;
+    type ProverSetup = ();
+    type VerifierSetup = ();
+    type Commitment = LigeroCommitment<F>;
+    type Proof = LigeroProof<F>;
+    type BatchedProof = LigeroProof<F>;
+    type OpeningProofHint = Arc<MerkleTree>;
+
+    fn setup_prover(_max_num_vars: usize) -> Self::ProverSetup {}
+
+    fn setup_verifier(_setup: &Self::ProverSetup) -> Self::VerifierSetup {}
+
+    #[tracing::instrument(skip_all, name = "Ligero::commit")]
+    fn commit(
+        poly: &MultilinearPolynomial<Self::Field>,
+        _setup: &Self::ProverSetup,
+    ) -> (Self::Commitment, Self::OpeningProofHint) {
+        let num_vars = matrix_num_vars(poly);
+        let num_columns = 1 << log_num_columns(num_vars);
+        let num_rows = num_rows(poly, num_columns);
+        let tree = Self::commit_rows(poly, num_rows, num_columns);
+        let commitment = LigeroCommitment {
+            coeffs: vec![F::one()],
+            matrices: vec![MatrixCommitment {
+                root: tree.root(),
+                num_vars,
+                num_rows,
+            }],
+        };
+        (commitment, Arc::new(tree))
+    }
+
+    fn batch_commit<U>(polys: &[U], gens: &Self::ProverSetup) -> Vec<Self::Commitment>
+    where
+        U: Borrow<MultilinearPolynomial<Self::Field>> + Sync,
+    {
+        polys
+            .par_iter()
+            .map(|poly| Self::commit(poly.borrow(), gens).0)
+            .collect()
+    }
+
+    fn prove<ProofTranscript: Transcript>(
+        _setup: &Self::ProverSetup,
+        poly: &MultilinearPolynomial<Self::Field>,
+        opening_point: &[Self::Field],
+        hint: Self::OpeningProofHint,
+        transcript: &mut ProofTranscript,
+    ) -> Self::Proof {
+        Self::prove_matrices(&[poly], &[F::one()], vec![hint], opening_point, transcript)
+    }
+
+    #[tracing::instrument(skip_all, name = "Ligero::verify")]
+    fn verify<ProofTranscript: Transcript>(
+        proof: &Self::Proof,
+        _setup: &Self::VerifierSetup,
+        transcript: &mut ProofTranscript,
+        opening_point: &[Self::Field],
+        opening: &Self::Field,
+        commitment: &Self::Commitment,
+    ) -> Result<(), ProofVerifyError> {
+        let LigeroCommitment { coeffs, matrices } = commitment;
+        let groups = group_by_num_vars(matrices.iter().map(|matrix| matrix.num_vars));
+        let well_formed = !matrices.is_empty()
+            && coeffs.len() == matrices.len()
+            && matrices.iter().all(|matrix| {
+                matrix.num_vars <= opening_point.len()
+                    && matrix.num_rows <= 1 << log_num_rows(matrix.num_vars)
+            })
+            && proof.proximity_rows.len() == groups.len()
+            && proof.evaluation_rows.len() == groups.len()
+            && groups
+                .keys()
+                .zip(
+                    proof
+                        .proximity_rows
+                        .iter()
+                        .zip(proof.evaluation_rows.iter()),
+                )
+                .all(|(num_vars, (proximity_row, evaluation_row))| {
+                    let num_columns = 1 << log_num_columns(*num_vars);
+                    proximity_row.len() == num_columns && evaluation_row.len() == num_columns
+                })
+            && proof.columns.len() == matrices.len()
+            && proof.paths.len() == matrices.len()
+            && matrices
+                .iter()
+                .zip(proof.columns.iter().zip(proof.paths.iter()))
+                .all(|(matrix, (columns, paths))| {
+                    columns.len() == NUM_COLUMN_OPENINGS
+                        && columns.iter().all(|column| column.len() == matrix.num_rows)
+                        && paths.len() == NUM_COLUMN_OPENINGS
+                        && paths
+                            .iter()
+                            .all(|path| path.len() == log_num_columns(matrix.num_vars) + LOG_BLOWUP)
+                });
+        if !well_formed {
+            return Err(ProofVerifyError::InvalidOpeningProof);
+        }
+
+        transcript.append_message(b"ligero_open");
+        for matrix in matrices.iter() {
+            transcript.append_bytes(&matrix.root);
+        }
+        let (gammas, eq_rows): (Vec<Vec<F>>, Vec<Vec<F>>) = groups
+            .keys()
+            .map(|num_vars| {
+                let (r_row, _, _) = split_point(opening_point, *num_vars);
+                let gamma: Vec<F> = transcript.challenge_vector(1 << r_row.len());
+                (gamma, EqPolynomial::evals(r_row))
+            })
+            .unzip();
+        for (proximity_row, evaluation_row) in proof
+            .proximity_rows
+            .iter()
+            .zip(proof.evaluation_rows.iter())
+        {
+            transcript.append_scalars(proximity_row);
+            transcript.append_scalars(evaluation_row);
+        }
+        let queries: Vec<Vec<usize>> = groups
+            .keys()
+            .map(|num_vars| {
+                sample_queries::<F, _>(transcript, 1 << (log_num_columns(*num_vars) + LOG_BLOWUP))
+            })
+            .collect();
+
+        let evaluation: F = groups
+            .keys()
+            .zip(proof.evaluation_rows.iter())
+            .map(|(num_vars, evaluation_row)| {
+                let (_, r_col, lagrange) = split_point(opening_point, *num_vars);
+                lagrange * inner_product(evaluation_row, &EqPolynomial::evals(r_col))
+            })
+            .sum();
+        if evaluation != *opening {
+            return Err(ProofVerifyError::InvalidOpeningProof);
+        }
+
+        // Both rows of a group are linear combinations of the rows of its matrices, so by
+        // linearity of the code, their encodings must be the same combinations of the
+        // matrices' columns
+        for (g, (num_vars, group)) in groups.iter().enumerate() {
+            let num_columns = 1 << log_num_columns(*num_vars);
+            let proximity_codeword = encode(&proof.proximity_rows[g], num_columns);
+            let evaluation_codeword = encode(&proof.evaluation_rows[g], num_columns);
+            queries[g].par_iter().enumerate().try_for_each(|(q, j)| {
+                let mut proximity = F::zero();
+                let mut evaluation = F::zero();
+                for i in group.iter() {
+                    let column = &proof.columns[*i][q];
+                    if !verify_path(
+                        &matrices[*i].root,
+                        *j,
+                        hash_column(column),
+                        &proof.paths[*i][q],
+                    ) {
+                        return Err(ProofVerifyError::InvalidOpeningProof);
+                    }
+                    proximity += coeffs[*i] * inner_product(&gammas[g], column);
+                    evaluation += coeffs[*i] * inner_product(&eq_rows[g], column);
+                }
+                if proximity != proximity_codeword[*j] || evaluation != evaluation_codeword[*j] {
+                    return Err(ProofVerifyError::InvalidOpeningProof);
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn protocol_name() -> &'static [u8] {
+        b"Ligero"
+    }
+}
+
+impl<F: JoltField + FftField> BatchOpening for Ligero<F> {
+    fn linear_combination_commitment(
+        commitments: Vec<Self::Commitment>,
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        let (coeffs, matrices): (Vec<F>, Vec<MatrixCommitment>) = commitments
+            .into_iter()
+            .zip(coeffs.iter())
+            .flat_map(|(commitment, coeff)| {
+                commitment
+                    .coeffs
+                    .into_iter()
+                    .map(move |c| c * coeff)
+                    .zip(commitment.matrices)
+            })
+            .unzip();
+        LigeroCommitment { coeffs, matrices }
+    }
+
+    fn prove_linear_combination<ProofTranscript: Transcript>(
+        _setup: &Self::ProverSetup,
+        polynomials: &[&MultilinearPolynomial<Self::Field>],
+        coeffs: &[Self::Field],
+        hints: Vec<Self::OpeningProofHint>,
+        opening_point: &[Self::Field],
+        transcript: &mut ProofTranscript,
+    ) -> Self::Proof {
+        Self::prove_matrices(polynomials, coeffs, hints, opening_point, transcript)
+    }
+}
+
+/// The state of a streaming Ligero commitment. The leaves of the Merkle tree hash whole
+/// columns of the encoded matrix, so rows must be absorbed in order, and the rows of a
+/// one-hot polynomial are only complete once every cycle has been appended. A hasher per
+/// column for every polynomial being committed to would take far more memory than the
+/// polynomials themselves, so the appended chunks are buffered and committed to in
+/// [`StreamingCommitmentScheme::finalize`].
+pub struct LigeroState<F: JoltField> {
+    size: usize,
+    coeffs: Vec<F>,
+    /// The nonzero indices appended so far and `K`, if the polynomial is one-hot
+    one_hot: Option<(Vec<Option<usize>>, usize)>,
+}
+
+impl<F: JoltField + FftField> StreamingCommitmentScheme for Ligero<F> {
+    type State<'a> = LigeroState<F>;
+
+    fn initialize(size: usize, _setup: &Self::ProverSetup) -> Self::State<'_> {
+        LigeroState {
+            size,
+            coeffs: vec![],
+            one_hot: None,
+        }
+    }
+
+    fn process<'a>(mut state: Self::State<'a>, eval: Self::Field) -> Self::State<'a> {
+        state.coeffs.push(eval);
+        state
+    }
+
+    fn process_chunk<'a>(
+        mut state: Self::State<'a>,
+        chunk: StreamingChunk<Self::Field>,
+    ) -> Self::State<'a> {
+        match chunk {
+            StreamingChunk::OneHot(indices, K) => state
+                .one_hot
+                .get_or_insert_with(|| (vec![], K))
+                .0
+                .extend_from_slice(indices),
+            _ => state
+                .coeffs
+                .extend((0..chunk.len()).map(|i| chunk.get_coeff(i))),
+        }
+        state
+    }
+
+    fn finalize<'a>(state: Self::State<'a>) -> (Self::Commitment, Self::OpeningProofHint) {
+        let poly = match state.one_hot {
+            Some((indices, K)) => {
+                MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(indices, K))
+            }
+            None => {
+                let mut coeffs = state.coeffs;
+                coeffs.resize(state.size, F::zero());
+                MultilinearPolynomial::from(coeffs)
+            }
+        };
+        Self::commit(&poly, &())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::dory::DoryGlobals;
+    use crate::poly::multilinear_polynomial::PolynomialEvaluation;
+    use crate::poly::one_hot_polynomial::OneHotPolynomial;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_core::RngCore;
+    use serial_test::serial;
+
+    fn random_point(num_vars: usize) -> Vec<Fr> {
+        let mut rng = test_rng();
+        std::iter::repeat_with(|| Fr::random(&mut rng))
+            .take(num_vars)
+            .collect()
+    }
+
+    #[test]
+    fn commit_prove_verify() {
+        let num_vars = 10;
+        let mut rng = test_rng();
+        let poly = MultilinearPolynomial::from(
+            std::iter::repeat_with(|| rng.next_u32())
+                .take(1 << num_vars)
+                .collect::<Vec<_>>(),
+        );
+        let point = random_point(num_vars);
+        let eval = poly.evaluate(&point);
+
+        let (commitment, hint) = Ligero::commit(&poly, &());
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        let proof = Ligero::prove(&(), &poly, &point, hint, &mut transcript);
+
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        assert!(Ligero::verify(&proof, &(), &mut transcript, &point, &eval, &commitment).is_ok());
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        let wrong_eval = eval + Fr::from(1u64);
+        assert!(Ligero::verify(
+            &proof,
+            &(),
+            &mut transcript,
+            &point,
+            &wrong_eval,
+            &commitment
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn open_linear_combination() {
+        const LOG_K: usize = 3;
+        const LOG_T: usize = 8;
+        let (K, T) = (1 << LOG_K, 1 << LOG_T);
+        let _guard = DoryGlobals::initialize(K, T);
+        let mut rng = test_rng();
+
+        let dense = MultilinearPolynomial::from(
+            std::iter::repeat_with(|| Fr::random(&mut rng))
+                .take(T)
+                .collect::<Vec<_>>(),
+        );
+        let nonzero_indices = std::iter::repeat_with(|| Some(rng.next_u64() as usize % K))
+            .take(T)
+            .collect();
+        let one_hot =
+            MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(nonzero_indices, K));
+        let coeffs = [Fr::random(&mut rng), Fr::random(&mut rng)];
+
+        // The dense polynomial has fewer variables, so it is embedded in the first T
+        // coefficients of the linear combination
+        let point = random_point(LOG_K + LOG_T);
+        let lagrange: Fr = point[..LOG_K].iter().map(|r| Fr::from(1u64) - r).product();
+        let eval = coeffs[0] * lagrange * dense.evaluate(&point[LOG_K..])
+            + coeffs[1] * one_hot.evaluate(&point);
+
+        let (commitments, hints): (Vec<_>, Vec<_>) = [&dense, &one_hot]
+            .iter()
+            .map(|poly| Ligero::commit(poly, &()))
+            .unzip();
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        let proof = Ligero::prove_linear_combination(
+            &(),
+            &[&dense, &one_hot],
+            &coeffs,
+            hints,
+            &point,
+            &mut transcript,
+        );
+
+        let commitment = Ligero::linear_combination_commitment(commitments.clone(), &coeffs);
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        assert!(Ligero::verify(&proof, &(), &mut transcript, &point, &eval, &commitment).is_ok());
+
+        let commitment =
+            Ligero::linear_combination_commitment(commitments, &[coeffs[1], coeffs[0]]);
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        assert!(Ligero::verify(&proof, &(), &mut transcript, &point, &eval, &commitment).is_err());
+
+        // The linear combination can also be committed to and opened as a single polynomial
+        let rlc = MultilinearPolynomial::linear_combination(&[&dense, &one_hot], &coeffs);
+        let (commitment, hint) = Ligero::commit(&rlc, &());
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        let proof = Ligero::prove(&(), &rlc, &point, hint, &mut transcript);
+        let mut transcript = KeccakTranscript::new(b"ligero_test");
+        assert!(Ligero::verify(&proof, &(), &mut transcript, &point, &eval, &commitment).is_ok());
+    }
+}
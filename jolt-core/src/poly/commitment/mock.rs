@@ -12,7 +12,9 @@ use crate::{
     },
 };
 
-use super::commitment_scheme::CommitmentScheme;
+use super::commitment_scheme::{
+    AdditivelyHomomorphic, CommitmentScheme, StreamingChunk, StreamingCommitmentScheme,
+};
 
 #[derive(Clone)]
 pub struct MockCommitScheme<F: JoltField> {
@@ -68,20 +70,6 @@ where
             .collect()
     }
 
-    fn combine_commitments<C: Borrow<Self::Commitment>>(
-        _commitments: &[C],
-        _coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        MockCommitment::default()
-    }
-
-    fn combine_hints(
-        _hints: Vec<Self::OpeningProofHint>,
-        _coeffs: &[Self::Field],
-    ) -> Self::OpeningProofHint {
-        ()
-    }
-
     fn prove<ProofTranscript: Transcript>(
         _setup: &Self::ProverSetup,
         _poly: &MultilinearPolynomial<Self::Field>,
@@ -110,3 +98,37 @@ where
         b"mock_commit"
     }
 }
+
+impl<F: JoltField> AdditivelyHomomorphic for MockCommitScheme<F> {
+    fn combine_commitments<C: Borrow<Self::Commitment>>(
+        _commitments: &[C],
+        _coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        MockCommitment::default()
+    }
+
+    fn combine_hints(
+        _hints: Vec<Self::OpeningProofHint>,
+        _coeffs: &[Self::Field],
+    ) -> Self::OpeningProofHint {
+        ()
+    }
+}
+
+impl<F: JoltField> StreamingCommitmentScheme for MockCommitScheme<F> {
+    type State<'a> = ();
+
+    fn initialize(_size: usize, _setup: &Self::ProverSetup) -> Self::State<'_> {}
+
+    fn process<'a>(_state: Self::State<'a>, _eval: Self::Field) -> Self::State<'a> {}
+
+    fn process_chunk<'a>(
+        _state: Self::State<'a>,
+        _chunk: StreamingChunk<Self::Field>,
+    ) -> Self::State<'a> {
+    }
+
+    fn finalize<'a>(_state: Self::State<'a>) -> (Self::Commitment, Self::OpeningProofHint) {
+        (MockCommitment::default(), ())
+    }
+}
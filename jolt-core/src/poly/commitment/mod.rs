@@ -3,7 +3,9 @@ pub mod dory;
 pub mod hyperkzg;
 pub mod hyrax;
 pub mod kzg;
+pub mod ligero;
 pub mod pedersen;
+pub mod ptau;
 pub mod zeromorph;
 
 pub mod bmmtv;
@@ -0,0 +1,440 @@
+//! Loading KZG powers of tau from a trusted-setup ceremony.
+//!
+//! `HyperKZG` and `Zeromorph` are only sound if nobody knows the trapdoor `tau` of their SRS.
+//! [`PowersOfTau::srs_from_ptau`] reads the powers from a snarkjs `.ptau` file, the format of
+//! the BN254 Perpetual Powers of Tau ceremony and of snarkjs' own ceremonies, and checks
+//! them before use: every point must be on the curve and in the prime-order subgroup, and
+//! consecutive powers must share the same ratio, which is checked with a batched pairing
+//! equation. Only a prefix of the powers is read, so large ceremony files can be trimmed to
+//! the size of the program.
+//!
+//! The `.ptau` layout is a magic `ptau`, a version and a list of sections, each a `u32` id
+//! and a `u64` length (all little-endian). Section 1 is the header (the byte length and
+//! modulus of the base field and the ceremony's power), section 2 holds the
+//! `2^(power + 1) - 1` G1 powers and section 3 the `2^power` G2 powers. Coordinates are
+//! little-endian field elements in Montgomery form, and the point at infinity is all zeros.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::scalar_mul::variable_base::VariableBaseMSM as ArkVariableBaseMSM;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInt, BigInteger, Field, PrimeField};
+use ark_std::{rand::thread_rng, UniformRand, Zero};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use rayon::prelude::*;
+
+use super::kzg::SRS;
+use crate::field::JoltField;
+use crate::utils::errors::PtauError;
+
+/// Environment variable pointing `HyperKZG` and `Zeromorph` at a `.ptau` file.
+pub const PTAU_ENV: &str = "JOLT_PTAU";
+/// Environment variable which, when set to `1`, lets `HyperKZG` and `Zeromorph` fall back
+/// to an SRS with a publicly known trapdoor if `$JOLT_PTAU` is not set.
+pub const INSECURE_SRS_ENV: &str = "JOLT_INSECURE_SRS";
+
+const PTAU_MAGIC: [u8; 4] = *b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+const FQ_BYTES: usize = 32;
+/// snarkjs `.ptau` files have about a dozen sections. The count in the file is only
+/// trusted up to this many for preallocation.
+const MAX_PREALLOCATED_SECTIONS: usize = 16;
+
+/// Pairings for which an SRS can be loaded from a powers-of-tau ceremony.
+pub trait PowersOfTau: Pairing {
+    /// Loads `num_g1_powers + 1` G1 powers and `num_g2_powers + 1` G2 powers of tau (the
+    /// same counts as [`SRS::setup`]) from the `.ptau` file at `path`.
+    fn srs_from_ptau(
+        path: &Path,
+        num_g1_powers: usize,
+        num_g2_powers: usize,
+    ) -> Result<SRS<Self>, PtauError>;
+}
+
+impl PowersOfTau for Bn254 {
+    #[tracing::instrument(skip_all, name = "PowersOfTau::srs_from_ptau")]
+    fn srs_from_ptau(
+        path: &Path,
+        num_g1_powers: usize,
+        num_g2_powers: usize,
+    ) -> Result<SRS<Self>, PtauError> {
+        let mut ptau = PtauFile::open(path)?;
+        let power = ptau.read_header()?;
+
+        let g1_bytes = ptau.read_points(
+            TAU_G1_SECTION,
+            "G1",
+            num_g1_powers + 1,
+            (1 << (power + 1)) - 1,
+            2 * FQ_BYTES,
+        )?;
+        let g2_bytes = ptau.read_points(
+            TAU_G2_SECTION,
+            "G2",
+            num_g2_powers + 1,
+            1 << power,
+            4 * FQ_BYTES,
+        )?;
+
+        let r_inv = montgomery_r_inv();
+        let g1_powers = g1_bytes
+            .par_chunks(2 * FQ_BYTES)
+            .enumerate()
+            .map(|(index, bytes)| {
+                decode_g1(bytes, &r_inv).ok_or(PtauError::InvalidPoint("G1", index))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2_powers = g2_bytes
+            .par_chunks(4 * FQ_BYTES)
+            .enumerate()
+            .map(|(index, bytes)| {
+                decode_g2(bytes, &r_inv).ok_or(PtauError::InvalidPoint("G2", index))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        check_powers(&g1_powers, &g2_powers)?;
+        Ok(SRS::from_powers(g1_powers, g2_powers))
+    }
+}
+
+/// The SRS used by `HyperKZG` and `Zeromorph`: loaded from the `.ptau` file at `$JOLT_PTAU`
+/// if it is set. Otherwise, if `$JOLT_INSECURE_SRS` is `1` (or in this crate's tests), it
+/// is derived from `seed`.
+///
+/// The seeded SRS has a publicly known trapdoor, so proofs over it can be forged. It is
+/// only meant for tests and benchmarks.
+pub(crate) fn setup_srs<P: PowersOfTau>(
+    seed: [u8; 32],
+    num_g1_powers: usize,
+    num_g2_powers: usize,
+) -> Result<SRS<P>, PtauError>
+where
+    P::ScalarField: JoltField,
+{
+    if let Some(path) = std::env::var_os(PTAU_ENV).filter(|path| !path.is_empty()) {
+        return P::srs_from_ptau(path.as_ref(), num_g1_powers, num_g2_powers);
+    }
+    let insecure_srs_allowed =
+        cfg!(test) || std::env::var_os(INSECURE_SRS_ENV).is_some_and(|value| value == "1");
+    if !insecure_srs_allowed {
+        return Err(PtauError::NoPowersOfTau);
+    }
+    tracing::warn!("Using an SRS with a publicly known trapdoor: proofs can be forged");
+    Ok(SRS::setup(
+        &mut ChaCha20Rng::from_seed(seed),
+        num_g1_powers,
+        num_g2_powers,
+    ))
+}
+
+struct PtauFile {
+    reader: BufReader<File>,
+    /// (id, offset, length) of each section.
+    sections: Vec<(u32, u64, u64)>,
+}
+
+impl PtauFile {
+    fn open(path: &Path) -> Result<Self, PtauError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != PTAU_MAGIC {
+            return Err(PtauError::BadMagic(magic));
+        }
+        let _version = read_u32(&mut reader)?;
+        let num_sections = read_u32(&mut reader)?;
+        let file_len = reader.get_ref().metadata()?.len();
+
+        let mut sections =
+            Vec::with_capacity((num_sections as usize).min(MAX_PREALLOCATED_SECTIONS));
+        for _ in 0..num_sections {
+            let id = read_u32(&mut reader)?;
+            let len = read_u64(&mut reader)?;
+            let offset = reader.stream_position()?;
+            if len > file_len - offset {
+                return Err(PtauError::TruncatedSection(id));
+            }
+            sections.push((id, offset, len));
+            reader.seek(SeekFrom::Start(offset + len))?;
+        }
+        Ok(Self { reader, sections })
+    }
+
+    /// Seeks to the start of section `id`, returning its length.
+    fn seek_section(&mut self, id: u32) -> Result<u64, PtauError> {
+        let &(_, offset, len) = self
+            .sections
+            .iter()
+            .find(|(section, _, _)| *section == id)
+            .ok_or(PtauError::MissingSection(id))?;
+        self.reader.seek(SeekFrom::Start(offset))?;
+        Ok(len)
+    }
+
+    /// Checks that the file is over BN254 and returns the ceremony's power.
+    fn read_header(&mut self) -> Result<u32, PtauError> {
+        self.seek_section(HEADER_SECTION)?;
+        let n8 = read_u32(&mut self.reader)?;
+        if n8 as usize != FQ_BYTES {
+            return Err(PtauError::CurveMismatch);
+        }
+        let mut modulus = [0u8; FQ_BYTES];
+        self.reader.read_exact(&mut modulus)?;
+        if modulus[..] != Fq::MODULUS.to_bytes_le()[..] {
+            return Err(PtauError::CurveMismatch);
+        }
+        let power = read_u32(&mut self.reader)?;
+        if power >= usize::BITS - 1 {
+            return Err(PtauError::InvalidPower(power));
+        }
+        Ok(power)
+    }
+
+    /// Reads the first `count` points of section `id`, which holds `available` points of
+    /// `point_size` bytes each.
+    fn read_points(
+        &mut self,
+        id: u32,
+        group: &'static str,
+        count: usize,
+        available: usize,
+        point_size: usize,
+    ) -> Result<Vec<u8>, PtauError> {
+        if count > available {
+            return Err(PtauError::NotEnoughPowers {
+                group,
+                required: count,
+                available,
+            });
+        }
+        let len = self.seek_section(id)?;
+        let mut bytes = vec![0u8; count * point_size];
+        if (bytes.len() as u64) > len {
+            return Err(PtauError::TruncatedSection(id));
+        }
+        self.reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Checks that the powers are consistent: for random `r_i`,
+/// `e(sum r_i g1[i + 1], h) = e(sum r_i g1[i], tau h)` and
+/// `e(g, sum r_i g2[i + 1]) = e(tau g, sum r_i g2[i])`.
+/// The first check binds the G1 powers to the ratio of the first two G2 powers and the
+/// second binds the G2 powers to the ratio of the first two G1 powers, so together they
+/// show that both are powers of the same tau.
+fn check_powers(g1_powers: &[G1Affine], g2_powers: &[G2Affine]) -> Result<(), PtauError> {
+    if g1_powers.len() < 2 || g2_powers.len() < 2 {
+        return Err(PtauError::PairingCheckFailed("fewer than two powers"));
+    }
+    if g1_powers[..2].iter().any(|g| g.is_zero()) || g2_powers[..2].iter().any(|h| h.is_zero()) {
+        return Err(PtauError::PairingCheckFailed("the first powers are zero"));
+    }
+
+    let mut rng = thread_rng();
+    let g1_challenges: Vec<Fr> = (1..g1_powers.len()).map(|_| Fr::rand(&mut rng)).collect();
+    let g2_challenges: Vec<Fr> = (1..g2_powers.len()).map(|_| Fr::rand(&mut rng)).collect();
+    let msm_g1 = |bases: &[G1Affine]| {
+        <G1Projective as ArkVariableBaseMSM>::msm(bases, &g1_challenges).unwrap()
+    };
+    let msm_g2 = |bases: &[G2Affine]| {
+        <G2Projective as ArkVariableBaseMSM>::msm(bases, &g2_challenges).unwrap()
+    };
+
+    let (g1_high, g1_low) = rayon::join(
+        || msm_g1(&g1_powers[1..]),
+        || msm_g1(&g1_powers[..g1_powers.len() - 1]),
+    );
+    if !Bn254::multi_pairing(
+        [g1_high.into_affine(), (-g1_low).into_affine()],
+        [g2_powers[0], g2_powers[1]],
+    )
+    .is_zero()
+    {
+        return Err(PtauError::PairingCheckFailed("G1"));
+    }
+
+    let (g2_high, g2_low) = rayon::join(
+        || msm_g2(&g2_powers[1..]),
+        || msm_g2(&g2_powers[..g2_powers.len() - 1]),
+    );
+    if !Bn254::multi_pairing(
+        [g1_powers[0], -g1_powers[1]],
+        [g2_high.into_affine(), g2_low.into_affine()],
+    )
+    .is_zero()
+    {
+        return Err(PtauError::PairingCheckFailed("G2"));
+    }
+    Ok(())
+}
+
+/// `R^-1`, where `R = 2^256` is the Montgomery constant of `Fq`.
+fn montgomery_r_inv() -> Fq {
+    Fq::from(2u64)
+        .pow([(8 * FQ_BYTES) as u64])
+        .inverse()
+        .unwrap()
+}
+
+/// Decodes a little-endian field element in Montgomery form.
+fn decode_fq(bytes: &[u8], r_inv: &Fq) -> Option<Fq> {
+    let limbs =
+        std::array::from_fn(|i| u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap()));
+    Fq::from_bigint(BigInt(limbs)).map(|montgomery| montgomery * r_inv)
+}
+
+fn decode_g1(bytes: &[u8], r_inv: &Fq) -> Option<G1Affine> {
+    if bytes.iter().all(|&b| b == 0) {
+        return Some(G1Affine::zero());
+    }
+    let (x, y) = bytes.split_at(FQ_BYTES);
+    let point = G1Affine::new_unchecked(decode_fq(x, r_inv)?, decode_fq(y, r_inv)?);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+fn decode_g2(bytes: &[u8], r_inv: &Fq) -> Option<G2Affine> {
+    if bytes.iter().all(|&b| b == 0) {
+        return Some(G2Affine::zero());
+    }
+    let fq2 = |bytes: &[u8]| -> Option<Fq2> {
+        let (c0, c1) = bytes.split_at(FQ_BYTES);
+        Some(Fq2::new(decode_fq(c0, r_inv)?, decode_fq(c1, r_inv)?))
+    };
+    let (x, y) = bytes.split_at(2 * FQ_BYTES);
+    let point = G2Affine::new_unchecked(fq2(x)?, fq2(y)?);
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, PtauError> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, PtauError> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const POWER: u32 = 3;
+
+    fn encode_fq(x: Fq, out: &mut Vec<u8>) {
+        let r = Fq::from(2u64).pow([(8 * FQ_BYTES) as u64]);
+        out.extend((x * r).into_bigint().to_bytes_le());
+    }
+
+    /// Writes a `.ptau` file with the powers of `tau`, or of `tau` with the G1 power at
+    /// `tamper` replaced by its double.
+    fn write_ptau(name: &str, tau: Fr, tamper: Option<usize>) -> std::path::PathBuf {
+        let g = G1Affine::generator();
+        let h = G2Affine::generator();
+        let mut header = vec![];
+        header.extend((FQ_BYTES as u32).to_le_bytes());
+        header.extend(Fq::MODULUS.to_bytes_le());
+        header.extend(POWER.to_le_bytes());
+        header.extend(POWER.to_le_bytes());
+
+        let mut tau_g1 = vec![];
+        let mut power = Fr::from(1u64);
+        for i in 0..(1 << (POWER + 1)) - 1 {
+            let mut point = (g * power).into_affine();
+            if tamper == Some(i) {
+                point = (point + point).into_affine();
+            }
+            encode_fq(point.x, &mut tau_g1);
+            encode_fq(point.y, &mut tau_g1);
+            power *= tau;
+        }
+        let mut tau_g2 = vec![];
+        let mut power = Fr::from(1u64);
+        for _ in 0..1 << POWER {
+            let point = (h * power).into_affine();
+            for c in [point.x.c0, point.x.c1, point.y.c0, point.y.c1] {
+                encode_fq(c, &mut tau_g2);
+            }
+            power *= tau;
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("jolt-ptau-{}-{name}.ptau", std::process::id()));
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"ptau").unwrap();
+        file.write_all(&1u32.to_le_bytes()).unwrap();
+        file.write_all(&3u32.to_le_bytes()).unwrap();
+        for (id, section) in [
+            (HEADER_SECTION, header),
+            (TAU_G1_SECTION, tau_g1),
+            (TAU_G2_SECTION, tau_g2),
+        ] {
+            file.write_all(&id.to_le_bytes()).unwrap();
+            file.write_all(&(section.len() as u64).to_le_bytes())
+                .unwrap();
+            file.write_all(&section).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn loads_and_trims_powers() {
+        let tau = Fr::from(7u64);
+        let path = write_ptau("valid", tau, None);
+        let srs = Bn254::srs_from_ptau(&path, 8, 2).unwrap();
+        assert_eq!(srs.g1_powers.len(), 9);
+        assert_eq!(srs.g2_powers.len(), 3);
+        assert_eq!(srs.g1_powers[0], G1Affine::generator());
+        assert_eq!(
+            srs.g1_powers[3],
+            (G1Affine::generator() * tau.pow([3u64])).into_affine()
+        );
+        assert_eq!(
+            srs.g2_powers[2],
+            (G2Affine::generator() * tau.pow([2u64])).into_affine()
+        );
+
+        assert!(matches!(
+            Bn254::srs_from_ptau(&path, 15, 2),
+            Err(PtauError::NotEnoughPowers { group: "G1", .. })
+        ));
+        assert!(matches!(
+            Bn254::srs_from_ptau(&path, 8, 8),
+            Err(PtauError::NotEnoughPowers { group: "G2", .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_inconsistent_powers() {
+        let path = write_ptau("tampered", Fr::from(7u64), Some(5));
+        assert!(matches!(
+            Bn254::srs_from_ptau(&path, 8, 2),
+            Err(PtauError::PairingCheckFailed("G1"))
+        ));
+        // Powers past the tampered one are never read.
+        assert!(Bn254::srs_from_ptau(&path, 4, 2).is_ok());
+    }
+
+    #[test]
+    fn rejects_sections_past_end_of_file() {
+        let path = write_ptau("truncated", Fr::from(7u64), None);
+        let mut bytes = std::fs::read(&path).unwrap();
+        // The length of the first (header) section
+        bytes[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        std::fs::write(&path, bytes).unwrap();
+        assert!(matches!(
+            Bn254::srs_from_ptau(&path, 8, 2),
+            Err(PtauError::TruncatedSection(HEADER_SECTION))
+        ));
+    }
+}
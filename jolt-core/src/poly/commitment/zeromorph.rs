@@ -5,7 +5,7 @@ use crate::msm::VariableBaseMSM;
 use crate::poly::multilinear_polynomial::{MultilinearPolynomial, PolynomialEvaluation};
 use crate::poly::{dense_mlpoly::DensePolynomial, unipoly::UniPoly};
 use crate::utils::{
-    errors::ProofVerifyError,
+    errors::{ProofVerifyError, PtauError},
     transcript::{AppendToTranscript, Transcript},
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
@@ -13,15 +13,16 @@ use ark_ff::batch_inversion;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{One, Zero};
 use itertools::izip;
-use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 use rand_core::{CryptoRng, RngCore};
 use std::borrow::Borrow;
+use std::path::Path;
 use std::sync::Arc;
 use std::{iter, marker::PhantomData};
 
 use super::{
-    commitment_scheme::CommitmentScheme,
+    commitment_scheme::{AdditivelyHomomorphic, CommitmentScheme},
     kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG, SRS},
+    ptau::{self, PowersOfTau},
 };
 use crate::field::JoltField;
 use rayon::prelude::*;
@@ -36,6 +37,16 @@ impl<P: Pairing> ZeromorphSRS<P> {
         Self(Arc::new(SRS::setup(rng, max_degree, max_degree)))
     }
 
+    /// Loads the SRS from the powers-of-tau ceremony file at `path`.
+    pub fn from_ptau(path: &Path, max_degree: usize) -> Result<Self, PtauError>
+    where
+        P: PowersOfTau,
+    {
+        Ok(Self(Arc::new(P::srs_from_ptau(
+            path, max_degree, max_degree,
+        )?)))
+    }
+
     pub fn trim(self, max_degree: usize) -> (ZeromorphProverKey<P>, ZeromorphVerifierKey<P>) {
         let (commit_pp, kzg_vk) = SRS::trim(self.0.clone(), max_degree);
         let offset = self.0.g1_powers.len() - max_degree;
@@ -414,7 +425,7 @@ where
     }
 }
 
-impl<P: Pairing> CommitmentScheme for Zeromorph<P>
+impl<P: PowersOfTau> CommitmentScheme for Zeromorph<P>
 where
     <P as Pairing>::ScalarField: JoltField,
 {
@@ -426,18 +437,16 @@ where
     type BatchedProof = ZeromorphProof<P>;
     type OpeningProofHint = ();
 
+    /// Loads the SRS from `$JOLT_PTAU`. Panics if it is not set, unless an insecure SRS
+    /// with a publicly known trapdoor is opted into (see `ptau::setup_srs`).
     fn setup_prover(max_num_vars: usize) -> Self::ProverSetup
     where
         P::ScalarField: JoltField,
     {
         let max_len = 1 << max_num_vars;
-        ZeromorphSRS(Arc::new(SRS::setup(
-            &mut ChaCha20Rng::from_seed(*b"ZEROMORPH_POLY_COMMITMENT_SCHEME"),
-            max_len,
-            max_len,
-        )))
-        .trim(max_len)
-        .0
+        let srs = ptau::setup_srs(*b"ZEROMORPH_POLY_COMMITMENT_SCHEME", max_len, max_len)
+            .unwrap_or_else(|err| panic!("Failed to set up the Zeromorph SRS: {err}"));
+        ZeromorphSRS(Arc::new(srs)).trim(max_len).0
     }
 
     fn setup_verifier(setup: &Self::ProverSetup) -> Self::VerifierSetup {
@@ -471,18 +480,6 @@ where
             .collect()
     }
 
-    fn combine_commitments<C: Borrow<Self::Commitment>>(
-        commitments: &[C],
-        coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        let combined_commitment: P::G1 = commitments
-            .iter()
-            .zip(coeffs.iter())
-            .map(|(commitment, coeff)| commitment.borrow().0 * coeff)
-            .sum();
-        ZeromorphCommitment(combined_commitment.into_affine())
-    }
-
     fn prove<ProofTranscript: Transcript>(
         setup: &Self::ProverSetup,
         poly: &MultilinearPolynomial<Self::Field>,
@@ -510,6 +507,29 @@ where
     }
 }
 
+impl<P: PowersOfTau> AdditivelyHomomorphic for Zeromorph<P>
+where
+    <P as Pairing>::ScalarField: JoltField,
+{
+    fn combine_commitments<C: Borrow<Self::Commitment>>(
+        commitments: &[C],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        let combined_commitment: P::G1 = commitments
+            .iter()
+            .zip(coeffs.iter())
+            .map(|(commitment, coeff)| commitment.borrow().0 * coeff)
+            .sum();
+        ZeromorphCommitment(combined_commitment.into_affine())
+    }
+
+    fn combine_hints(
+        _hints: Vec<Self::OpeningProofHint>,
+        _coeffs: &[Self::Field],
+    ) -> Self::OpeningProofHint {
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
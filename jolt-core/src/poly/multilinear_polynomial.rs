@@ -224,6 +224,27 @@ impl<F: JoltField> MultilinearPolynomial<F> {
         }
     }
 
+    /// Sets the (unbound) polynomial coefficient at the given `index`.
+    /// Panics if `value` does not fit in the polynomial's scalar type.
+    pub fn set_coeff(&mut self, index: usize, value: u64) {
+        match self {
+            MultilinearPolynomial::U8Scalars(poly) => {
+                poly.coeffs[index] = u8::try_from(value).unwrap()
+            }
+            MultilinearPolynomial::U16Scalars(poly) => {
+                poly.coeffs[index] = u16::try_from(value).unwrap()
+            }
+            MultilinearPolynomial::U32Scalars(poly) => {
+                poly.coeffs[index] = u32::try_from(value).unwrap()
+            }
+            MultilinearPolynomial::U64Scalars(poly) => poly.coeffs[index] = value,
+            MultilinearPolynomial::I64Scalars(poly) => {
+                poly.coeffs[index] = i64::try_from(value).unwrap()
+            }
+            _ => unimplemented!("Unexpected MultilinearPolynomial variant"),
+        }
+    }
+
     /// Gets the polynomial coefficient at the given `index`, as an `i64`.
     /// Panics if the polynomial is a large-scalar polynomial.
     pub fn get_coeff_i64(&self, index: usize) -> i64 {
@@ -343,6 +343,41 @@ impl<F: JoltField> OneHotPolynomial<F> {
             .sum()
     }
 
+    /// Adds `coeff` times coefficients `start..start + coeffs.len()` of the polynomial to
+    /// `coeffs`. The polynomial has `K * T` coefficients, and `(k, t)` is at index
+    /// `k * T + t`.
+    pub fn scale_add_range_to(&self, coeff: F, start: usize, coeffs: &mut [F]) {
+        let T = self.nonzero_indices.len();
+        let end = (start + coeffs.len()).min(self.K * T);
+        for index in start..end {
+            let (k, t) = (index / T, index % T);
+            if self.nonzero_indices[t] == Some(k) {
+                coeffs[index - start] += coeff;
+            }
+        }
+    }
+
+    /// Computes `sum_i c_i * bases[i]` over the coefficients `c_i` of the polynomial,
+    /// laid out as in [`Self::scale_add_range_to`]. Every coefficient is 0 or 1, so this is a
+    /// sum of bases rather than an MSM. This is the commitment for schemes that commit to
+    /// the coefficient vector as a whole, e.g. HyperKZG.
+    #[tracing::instrument(skip_all, name = "OneHotPolynomial::commit_flat")]
+    pub fn commit_flat<G: CurveGroup<ScalarField = F>>(&self, bases: &[G::Affine]) -> G {
+        self.nonzero_coeff_indices()
+            .map(|index| bases[index])
+            .fold(G::zero, |acc, base| acc + base)
+            .reduce(G::zero, |a, b| a + b)
+    }
+
+    /// The indices of the coefficients that are 1, laid out as in [`Self::scale_add_range_to`].
+    pub fn nonzero_coeff_indices(&self) -> impl ParallelIterator<Item = usize> + '_ {
+        let T = self.nonzero_indices.len();
+        self.nonzero_indices
+            .par_iter()
+            .enumerate()
+            .filter_map(move |(t, k)| k.map(|k| k * T + t))
+    }
+
     pub fn from_indices(nonzero_indices: Vec<Option<usize>>, K: usize) -> Self {
         debug_assert_eq!(DoryGlobals::get_T(), nonzero_indices.len());
 
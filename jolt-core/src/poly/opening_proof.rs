@@ -1,9 +1,10 @@
 //! This is a port of the sumcheck-based batch opening proof protocol implemented
 //! in Nova: https://github.com/microsoft/Nova/blob/2772826ba296b66f1cd5deecf7aca3fd1d10e1f4/src/spartan/snark.rs#L410-L424
 //! and such code is Copyright (c) Microsoft Corporation.
-//! For additively homomorphic commitment schemes (including Zeromorph, HyperKZG) we
-//! can use a sumcheck to reduce multiple opening proofs (multiple polynomials, not
-//! necessarily of the same size, each opened at a different point) into a single opening.
+//! For commitment schemes that can open linear combinations of committed polynomials
+//! (see `BatchOpening`), including all additively homomorphic ones, we can use a
+//! sumcheck to reduce multiple opening proofs (multiple polynomials, not necessarily
+//! of the same size, each opened at a different point) into a single opening.
 
 use num_derive::FromPrimitive;
 use rayon::prelude::*;
@@ -15,7 +16,7 @@ use std::{
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use super::{
-    commitment::commitment_scheme::CommitmentScheme,
+    commitment::commitment_scheme::{BatchOpening, CommitmentScheme, DeferredOpening},
     eq_poly::EqPolynomial,
     multilinear_polynomial::{BindingOrder, MultilinearPolynomial, PolynomialBinding},
     split_eq_poly::GruenSplitEqPolynomial,
@@ -569,7 +570,7 @@ pub struct ReducedOpeningProof<
     #[cfg(test)]
     joint_poly: MultilinearPolynomial<F>,
     #[cfg(test)]
-    joint_commitment: PCS::Commitment,
+    joint_commitment: Option<PCS::Commitment>,
 }
 
 impl<F> Default for ProverOpeningAccumulator<F>
@@ -735,7 +736,7 @@ where
     /// Reduces the multiple openings accumulated into a single opening proof,
     /// using a single sumcheck.
     #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::reduce_and_prove")]
-    pub fn reduce_and_prove<ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>>(
+    pub fn reduce_and_prove<ProofTranscript: Transcript, PCS: BatchOpening<Field = F>>(
         &mut self,
         mut polynomials: HashMap<CommittedPolynomial, MultilinearPolynomial<F>>,
         mut opening_hints: HashMap<CommittedPolynomial, PCS::OpeningProofHint>,
@@ -776,67 +777,56 @@ where
             gamma_powers.push(gamma_powers[i - 1] * gamma);
         }
 
-        // Combines the individual polynomials into the RLC that will be used for the
-        // batched opening proof.
-        let joint_poly = {
-            let mut rlc_map = BTreeMap::new();
-            for (gamma, sumcheck) in gamma_powers.iter().zip(self.sumchecks.iter()) {
-                for (coeff, polynomial) in
-                    sumcheck.rlc_coeffs.iter().zip(sumcheck.polynomials.iter())
-                {
-                    if let Some(value) = rlc_map.get_mut(&polynomial) {
-                        *value += *coeff * gamma;
-                    } else {
-                        rlc_map.insert(polynomial, *coeff * gamma);
-                    }
+        // Combines the coefficients of each polynomial across the individual sumchecks, giving
+        // the linear combination of polynomials opened by the reduced opening proof.
+        let mut rlc_map = BTreeMap::new();
+        for (gamma, sumcheck) in gamma_powers.iter().zip(self.sumchecks.iter()) {
+            for (coeff, polynomial) in sumcheck.rlc_coeffs.iter().zip(sumcheck.polynomials.iter()) {
+                if let Some(value) = rlc_map.get_mut(&polynomial) {
+                    *value += *coeff * gamma;
+                } else {
+                    rlc_map.insert(polynomial, *coeff * gamma);
                 }
             }
+        }
 
-            let (coeffs, polynomials): (Vec<F>, Vec<MultilinearPolynomial<F>>) = rlc_map
-                .into_iter()
-                .map(|(k, v)| (v, polynomials.remove(k).unwrap()))
-                .unzip();
-
-            MultilinearPolynomial::linear_combination(
-                &polynomials.iter().collect::<Vec<_>>(),
-                &coeffs,
-            )
-        };
+        #[allow(clippy::type_complexity)]
+        let (coeffs, (polys, hints)): (
+            Vec<F>,
+            (Vec<MultilinearPolynomial<F>>, Vec<PCS::OpeningProofHint>),
+        ) = rlc_map
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    v,
+                    (
+                        polynomials.remove(k).unwrap(),
+                        opening_hints.remove(k).unwrap(),
+                    ),
+                )
+            })
+            .unzip();
+        debug_assert!(
+            opening_hints.is_empty(),
+            "Commitments to {:?} are not used",
+            opening_hints.keys()
+        );
+        let polys: Vec<_> = polys.iter().collect();
 
         #[cfg(test)]
-        let joint_commitment = PCS::commit(&joint_poly, pcs_setup).0;
-
-        // Compute the opening proof hint for the reduced opening by homomorphically combining
-        // the hints for the individual sumchecks.
-        let hint = {
-            let mut rlc_map = BTreeMap::new();
-            for (gamma, sumcheck) in gamma_powers.iter().zip(self.sumchecks.iter()) {
-                for (coeff, polynomial) in
-                    sumcheck.rlc_coeffs.iter().zip(sumcheck.polynomials.iter())
-                {
-                    if let Some(value) = rlc_map.get_mut(&polynomial) {
-                        *value += *coeff * gamma;
-                    } else {
-                        rlc_map.insert(polynomial, *coeff * gamma);
-                    }
-                }
-            }
-
-            let (coeffs, hints): (Vec<F>, Vec<PCS::OpeningProofHint>) = rlc_map
-                .into_iter()
-                .map(|(k, v)| (v, opening_hints.remove(k).unwrap()))
-                .unzip();
-            debug_assert!(
-                opening_hints.is_empty(),
-                "Commitments to {:?} are not used",
-                opening_hints.keys()
-            );
-
-            PCS::combine_hints(hints, &coeffs)
-        };
+        let joint_poly = MultilinearPolynomial::linear_combination(&polys, &coeffs);
+        #[cfg(test)]
+        let joint_commitment = PCS::commit_linear_combination(&joint_poly, pcs_setup);
 
         // Reduced opening proof
-        let joint_opening_proof = PCS::prove(pcs_setup, &joint_poly, &r_sumcheck, hint, transcript);
+        let joint_opening_proof = PCS::prove_linear_combination(
+            pcs_setup,
+            &polys,
+            &coeffs,
+            hints,
+            &r_sumcheck,
+            transcript,
+        );
 
         #[cfg(not(test))]
         {
@@ -1065,13 +1055,58 @@ where
 
     /// Verifies that the given `reduced_opening_proof` (consisting of a sumcheck proof
     /// and a single opening proof) indeed proves the openings accumulated.
-    pub fn reduce_and_verify<ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>>(
+    pub fn reduce_and_verify<ProofTranscript: Transcript, PCS: BatchOpening<Field = F>>(
         &mut self,
         pcs_setup: &PCS::VerifierSetup,
         commitment_map: &mut HashMap<CommittedPolynomial, PCS::Commitment>,
         reduced_opening_proof: &ReducedOpeningProof<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        let (r_sumcheck, joint_claim, joint_commitment) =
+            self.reduce_openings(commitment_map, reduced_opening_proof, transcript)?;
+
+        // Verify the reduced opening proof
+        PCS::verify(
+            &reduced_opening_proof.joint_opening_proof,
+            pcs_setup,
+            transcript,
+            &r_sumcheck,
+            &joint_claim,
+            &joint_commitment,
+        )
+    }
+
+    /// Like `reduce_and_verify`, but defers verification of the reduced opening proof,
+    /// so that it can be batched with other openings via `BatchVerification::batch_verify`.
+    pub fn reduce_and_defer_verification<
+        ProofTranscript: Transcript,
+        PCS: BatchOpening<Field = F>,
+    >(
+        &mut self,
+        commitment_map: &mut HashMap<CommittedPolynomial, PCS::Commitment>,
+        reduced_opening_proof: &ReducedOpeningProof<F, PCS, ProofTranscript>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<DeferredOpening<PCS, ProofTranscript>, ProofVerifyError> {
+        let (opening_point, opening, commitment) =
+            self.reduce_openings(commitment_map, reduced_opening_proof, transcript)?;
+        Ok(DeferredOpening {
+            proof: reduced_opening_proof.joint_opening_proof.clone(),
+            transcript: transcript.clone(),
+            opening_point,
+            opening,
+            commitment,
+        })
+    }
+
+    /// Verifies the sumcheck reducing the accumulated openings to a single opening, and
+    /// returns that opening's point, claimed evaluation, and commitment.
+    #[allow(clippy::type_complexity)]
+    fn reduce_openings<ProofTranscript: Transcript, PCS: BatchOpening<Field = F>>(
+        &mut self,
+        commitment_map: &mut HashMap<CommittedPolynomial, PCS::Commitment>,
+        reduced_opening_proof: &ReducedOpeningProof<F, PCS, ProofTranscript>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<(Vec<F>, F, PCS::Commitment), ProofVerifyError> {
         #[cfg(test)]
         if let Some(prover_openings) = &self.prover_opening_accumulator {
             assert_eq!(prover_openings.len(), self.len());
@@ -1108,10 +1143,10 @@ where
             gamma_powers.push(gamma_powers[i - 1] * gamma);
         }
 
-        // Compute the commitment for the reduced opening proof by homomorphically combining
-        // the commitments of the individual polynomials.
+        // Compute the commitment for the reduced opening proof from the commitments of the
+        // individual polynomials, in the same order as the prover.
         let joint_commitment = {
-            let mut rlc_map = HashMap::new();
+            let mut rlc_map = BTreeMap::new();
             for (gamma, sumcheck) in gamma_powers.iter().zip(self.sumchecks.iter()) {
                 for (coeff, polynomial) in
                     sumcheck.rlc_coeffs.iter().zip(sumcheck.polynomials.iter())
@@ -1130,14 +1165,13 @@ where
                 .unzip();
             debug_assert!(commitment_map.is_empty(), "Every commitment should be used");
 
-            PCS::combine_commitments(&commitments, &coeffs)
+            PCS::linear_combination_commitment(commitments, &coeffs)
         };
 
         #[cfg(test)]
-        assert_eq!(
-            joint_commitment, reduced_opening_proof.joint_commitment,
-            "joint commitment mismatch"
-        );
+        if let Some(expected) = &reduced_opening_proof.joint_commitment {
+            assert_eq!(&joint_commitment, expected, "joint commitment mismatch");
+        }
 
         // Compute joint claim = ∑ᵢ γⁱ⋅ claimᵢ
         let joint_claim: F = gamma_powers
@@ -1151,15 +1185,7 @@ where
             })
             .sum();
 
-        // Verify the reduced opening proof
-        PCS::verify(
-            &reduced_opening_proof.joint_opening_proof,
-            pcs_setup,
-            transcript,
-            &r_sumcheck,
-            &joint_claim,
-            &joint_commitment,
-        )
+        Ok((r_sumcheck, joint_claim, joint_commitment))
     }
 
     /// Verifies the sumcheck proven in `ProverOpeningAccumulator::prove_batch_opening_reduction`.
@@ -55,7 +55,7 @@ impl<F: JoltField> ProgramIOPolynomial<F> {
             .unwrap() as usize;
         coeffs[panic_index] = program_io.panic as u32;
 
-        if !program_io.panic {
+        if !program_io.panic && program_io.fault.is_none() {
             // Set termination bit
             let termination_index = remap_address(
                 program_io.memory_layout.termination,
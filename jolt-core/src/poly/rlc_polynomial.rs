@@ -34,6 +34,64 @@ impl<F: JoltField> RLCPolynomial<F> {
         }
     }
 
+    /// Adds coefficients `start..start + coeffs.len()` of the linear combination to `coeffs`,
+    /// viewing it as a single vector of [`Self::num_coeffs`] coefficients: the dense
+    /// polynomials occupy the first `T` coefficients, and the one-hot polynomials are laid
+    /// out as in [`OneHotPolynomial::scale_add_range_to`]. Used by schemes that don't view
+    /// the coefficients as a matrix with `T` columns, e.g. Ligero.
+    pub fn add_range_to(&self, start: usize, coeffs: &mut [F]) {
+        let T = self.dense_rlc.len();
+        if start < T {
+            let end = T.min(start + coeffs.len());
+            for (coeff, dense) in coeffs.iter_mut().zip(self.dense_rlc[start..end].iter()) {
+                *coeff += *dense;
+            }
+        }
+        for (coeff, poly) in self.one_hot_rlc.iter() {
+            poly.scale_add_range_to(*coeff, start, coeffs);
+        }
+    }
+
+    /// The number of coefficients of the linear combination, viewed as a single vector as
+    /// in [`Self::add_range_to`].
+    pub fn num_coeffs(&self) -> usize {
+        let T = self.dense_rlc.len();
+        self.one_hot_rlc
+            .iter()
+            .map(|(_, poly)| poly.K * T)
+            .max()
+            .unwrap_or(T)
+            .max(T)
+    }
+
+    /// The nonzero coefficients of the linear combination, viewed as in
+    /// [`Self::add_range_to`], as (index, coefficient) pairs sorted by index. Each one-hot
+    /// polynomial contributes at most `T` of the `K * T` coefficients, so this is much
+    /// smaller than the dense vector.
+    #[tracing::instrument(skip_all, name = "RLCPolynomial::sparse_coeffs")]
+    pub fn sparse_coeffs(&self) -> Vec<(usize, F)> {
+        let mut coeffs: Vec<(usize, F)> = self
+            .dense_rlc
+            .par_iter()
+            .enumerate()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(index, coeff)| (index, *coeff))
+            .collect();
+        for (coeff, poly) in self.one_hot_rlc.iter() {
+            coeffs.par_extend(poly.nonzero_coeff_indices().map(|index| (index, *coeff)));
+        }
+        coeffs.par_sort_unstable_by_key(|(index, _)| *index);
+        coeffs.dedup_by(|(index, coeff), (prev_index, prev_coeff)| {
+            if index == prev_index {
+                *prev_coeff += *coeff;
+                true
+            } else {
+                false
+            }
+        });
+        coeffs
+    }
+
     /// Commits to the rows of `RLCPolynomial`, viewing its coefficients
     /// as a matrix (used in Dory).
     /// We do so by computing the row commitments for the individual
@@ -1,3 +1,5 @@
+use ark_serialize::SerializationError;
+use common::jolt_device::GuestFault;
 use core::fmt::Debug;
 use thiserror::Error;
 
@@ -28,4 +30,165 @@ pub enum ProofVerifyError {
     DoryError(String),
     #[error("Sumcheck verification failed")]
     SumcheckVerificationError,
+    #[error("Proof {0} of batch failed to verify: {1}")]
+    BatchedProofError(usize, String),
+    #[error("Continuation failed to verify: {0}")]
+    Continuation(#[from] ContinuationError),
+    #[error("Faulting access does not match the claimed guest fault")]
+    FaultMismatch,
+    #[error("{stage} failed: {reason}")]
+    StageError { stage: String, reason: String },
+}
+
+impl From<anyhow::Error> for ProofVerifyError {
+    fn from(err: anyhow::Error) -> Self {
+        let (stage, reason) = stage_and_reason(&err);
+        ProofVerifyError::StageError { stage, reason }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum JoltProverError {
+    #[error("Failed to build guest: {0}")]
+    GuestBuildError(String),
+    #[error("Trace too long: padded trace length {trace_length} exceeds max trace length {max_trace_length}")]
+    TraceTooLong {
+        trace_length: usize,
+        max_trace_length: usize,
+    },
+    #[error("Input too large: {input_size} bytes exceeds max input size {max_input_size}")]
+    InputTooLarge {
+        input_size: usize,
+        max_input_size: usize,
+    },
+    #[error(
+        "Private input too large: {input_size} bytes exceeds max private input size {max_private_input_size}"
+    )]
+    PrivateInputTooLarge {
+        input_size: usize,
+        max_private_input_size: usize,
+    },
+    #[error("Guest panicked: {0}")]
+    GuestPanic(String),
+    #[error("Guest stopped: {0}")]
+    GuestFault(GuestFault),
+    #[error("Guest read past the end of the advice tape")]
+    AdviceExhausted,
+    #[error("{stage} failed: {reason}")]
+    StageError { stage: String, reason: String },
+    #[error("Invalid segment: {0}")]
+    Continuation(#[from] ContinuationError),
+}
+
+impl From<anyhow::Error> for JoltProverError {
+    fn from(err: anyhow::Error) -> Self {
+        let (stage, reason) = stage_and_reason(&err);
+        JoltProverError::StageError { stage, reason }
+    }
+}
+
+/// `JoltDAG::prove` and `JoltDAG::verify` attach the stage name as the outermost
+/// `anyhow::Context`, so the chain is unpacked into the stage name and the
+/// underlying cause.
+fn stage_and_reason(err: &anyhow::Error) -> (String, String) {
+    let stage = if err.chain().count() > 1 {
+        err.to_string()
+    } else {
+        "Jolt DAG".to_string()
+    };
+    (stage, err.root_cause().to_string())
+}
+
+#[derive(Error, Debug)]
+pub enum ProofFormatError {
+    #[error("Not a Jolt proof: bad magic bytes {0:?}")]
+    BadMagic([u8; 4]),
+    #[error("Unsupported proof format version {found}, expected {expected}")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error("Protocol name in proof header is too long: {0} bytes")]
+    ProtocolNameTooLong(u64),
+    #[error("Proof was produced over a different field")]
+    FieldMismatch,
+    #[error("Register count mismatch: expected {expected}, found {found}")]
+    RegisterCountMismatch { expected: u8, found: u8 },
+    #[error("Commitment scheme mismatch: expected {expected}, found {found}")]
+    CommitmentSchemeMismatch { expected: String, found: String },
+    #[error("Transcript mismatch: expected {expected}, found {found}")]
+    TranscriptMismatch { expected: String, found: String },
+    #[error("Proof was serialized with compress = {found}, expected {expected}")]
+    CompressionMismatch { found: bool, expected: bool },
+    #[error("Proof payload is truncated: expected {expected} bytes, found {found}")]
+    TruncatedPayload { expected: u64, found: u64 },
+    #[error("Proof payload checksum mismatch")]
+    ChecksumMismatch,
+    #[error("Failed to (de)serialize proof: {0}")]
+    Serialization(#[from] SerializationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ContinuationError {
+    #[error("Continuation has no segments")]
+    NoSegments,
+    #[error("Initial state of segment 0 does not match the program's initial state")]
+    InitialStateMismatch,
+    #[error("Segment {0} is empty")]
+    EmptySegment(usize),
+    #[error("Final state of segment {0} does not match the initial state of segment {}", .0 + 1)]
+    BrokenLink(usize),
+    #[error("Segment {0} is not proven with the same trace length and RAM size as segment 0")]
+    SegmentShapeMismatch(usize),
+    #[error("Malformed segment claim: {0}")]
+    MalformedClaim(&'static str),
+    #[error("Segment {0} failed to verify: {1}")]
+    InvalidSegmentProof(usize, String),
+    #[error("Continuations do not support guest faults, but the guest stopped: {0}")]
+    GuestFault(GuestFault),
+}
+
+#[derive(Error, Debug)]
+pub enum SrsError {
+    #[error("Not a Jolt SRS file: bad magic bytes {0:?}")]
+    BadMagic([u8; 4]),
+    #[error("SRS file is for {found} variables, expected {expected}")]
+    NumVarsMismatch { found: u64, expected: u64 },
+    #[error("SRS file was derived from a different seed")]
+    SeedMismatch,
+    #[error("SRS payload checksum mismatch")]
+    ChecksumMismatch,
+    #[error("SRS cache {} can be written by other users", .0.display())]
+    SharedCache(std::path::PathBuf),
+    #[error("Failed to read or write SRS file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to (de)serialize SRS: {0}")]
+    Serialization(#[from] SerializationError),
+}
+
+#[derive(Error, Debug)]
+pub enum PtauError {
+    #[error("Not a ptau file: bad magic bytes {0:?}")]
+    BadMagic([u8; 4]),
+    #[error("ptau file has no section {0}")]
+    MissingSection(u32),
+    #[error("ptau section {0} is truncated")]
+    TruncatedSection(u32),
+    #[error("ptau file is not over BN254")]
+    CurveMismatch,
+    #[error("Invalid ceremony power {0}")]
+    InvalidPower(u32),
+    #[error("Not enough {group} powers of tau: {required} required, {available} available")]
+    NotEnoughPowers {
+        group: &'static str,
+        required: usize,
+        available: usize,
+    },
+    #[error("{0} power {1} is not a valid curve point")]
+    InvalidPoint(&'static str, usize),
+    #[error("Powers of tau failed the pairing check: {0}")]
+    PairingCheckFailed(&'static str),
+    #[error(
+        "No powers of tau: set JOLT_PTAU to a .ptau file, or JOLT_INSECURE_SRS=1 to use an SRS with a publicly known trapdoor"
+    )]
+    NoPowersOfTau,
+    #[error("Failed to read ptau file: {0}")]
+    Io(#[from] std::io::Error),
 }
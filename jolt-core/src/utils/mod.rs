@@ -8,6 +8,7 @@ pub mod expanding_table;
 pub mod gaussian_elimination;
 pub mod lookup_bits;
 pub mod math;
+pub mod poseidon;
 pub mod profiling;
 pub mod small_value;
 pub mod thread;
@@ -216,6 +217,30 @@ pub fn interleave_bits(even_bits: u32, odd_bits: u32) -> u64 {
     (x_bits << 1) | y_bits
 }
 
+/// 128-bit analogue of [`uninterleave_bits`], used for lookup indices of
+/// 64-bit instructions.
+pub fn uninterleave_bits_u128(val: u128) -> (u64, u64) {
+    let (x_hi, y_hi) = uninterleave_bits((val >> 64) as u64);
+    let (x_lo, y_lo) = uninterleave_bits(val as u64);
+    (
+        ((x_hi as u64) << 32) | x_lo as u64,
+        ((y_hi as u64) << 32) | y_lo as u64,
+    )
+}
+
+/// 128-bit analogue of [`interleave_bits`], used for lookup indices of
+/// 64-bit instructions.
+///
+/// ```
+/// # use jolt_core::utils::interleave_bits_u128;
+/// assert_eq!(interleave_bits_u128(1 << 32, 0), 1 << 65);
+/// ```
+pub fn interleave_bits_u128(even_bits: u64, odd_bits: u64) -> u128 {
+    let hi = interleave_bits((even_bits >> 32) as u32, (odd_bits >> 32) as u32);
+    let lo = interleave_bits(even_bits as u32, odd_bits as u32);
+    ((hi as u128) << 64) | lo as u128
+}
+
 #[cfg(test)]
 mod tests {
     use ark_std::test_rng;
@@ -238,4 +263,20 @@ mod tests {
             assert_eq!((even, odd), uninterleave_bits(interleave_bits(even, odd)));
         }
     }
+
+    #[test]
+    fn interleave_uninterleave_bits_u128() {
+        let mut rng = test_rng();
+        for _ in 0..1000 {
+            let even = rng.next_u64();
+            let odd = rng.next_u64();
+            let val = interleave_bits_u128(even, odd);
+            assert_eq!((even, odd), uninterleave_bits_u128(val));
+            // Agrees with the 64-bit interleaving on 32-bit operands
+            assert_eq!(
+                interleave_bits(even as u32, odd as u32) as u128,
+                interleave_bits_u128(even as u32 as u64, odd as u32 as u64)
+            );
+        }
+    }
 }
@@ -0,0 +1,261 @@
+//! The Poseidon permutation over the BN254 scalar field, with width 3 (rate 2, capacity 1)
+//! and the x^5 S-box. The round constants and the (Cauchy) MDS matrix are generated with the
+//! Grain LFSR, as specified in the Poseidon paper (<https://eprint.iacr.org/2019/458>).
+//! These are the parameters of the reference implementation, which circomlib, poseidon-rs
+//! and light-poseidon also use, so `permute` agrees with all of them.
+
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use std::sync::LazyLock;
+
+pub const WIDTH: usize = 3;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 57;
+const ALPHA: u64 = 5;
+
+struct PoseidonParameters {
+    round_constants: Vec<[Fr; WIDTH]>,
+    mds: [[Fr; WIDTH]; WIDTH],
+}
+
+static PARAMETERS: LazyLock<PoseidonParameters> = LazyLock::new(PoseidonParameters::generate);
+
+impl PoseidonParameters {
+    fn generate() -> Self {
+        let mut lfsr = GrainLFSR::new(
+            Fr::MODULUS_BIT_SIZE as usize,
+            WIDTH,
+            FULL_ROUNDS,
+            PARTIAL_ROUNDS,
+        );
+
+        let round_constants = (0..FULL_ROUNDS + PARTIAL_ROUNDS)
+            .map(|_| {
+                let mut constants = [Fr::zero(); WIDTH];
+                for constant in constants.iter_mut() {
+                    *constant = lfsr.field_element_rejection_sampling();
+                }
+                constants
+            })
+            .collect();
+
+        // Cauchy matrix: mds[i][j] = 1 / (x_i + y_j)
+        let xs: Vec<Fr> = (0..WIDTH).map(|_| lfsr.field_element_mod_p()).collect();
+        let ys: Vec<Fr> = (0..WIDTH).map(|_| lfsr.field_element_mod_p()).collect();
+        let mut mds = [[Fr::zero(); WIDTH]; WIDTH];
+        for i in 0..WIDTH {
+            for j in 0..WIDTH {
+                mds[i][j] = (xs[i] + ys[j]).inverse().unwrap();
+            }
+        }
+
+        Self {
+            round_constants,
+            mds,
+        }
+    }
+}
+
+/// Applies the Poseidon permutation to `state` in place.
+pub fn permute(state: &mut [Fr; WIDTH]) {
+    let parameters = &*PARAMETERS;
+    let half_full_rounds = FULL_ROUNDS / 2;
+    for (round, constants) in parameters.round_constants.iter().enumerate() {
+        for (x, constant) in state.iter_mut().zip(constants.iter()) {
+            *x += constant;
+        }
+
+        let is_full_round = round < half_full_rounds || round >= half_full_rounds + PARTIAL_ROUNDS;
+        if is_full_round {
+            for x in state.iter_mut() {
+                *x = x.pow([ALPHA]);
+            }
+        } else {
+            state[0] = state[0].pow([ALPHA]);
+        }
+
+        let mut mixed = [Fr::zero(); WIDTH];
+        for (i, row) in parameters.mds.iter().enumerate() {
+            for (entry, x) in row.iter().zip(state.iter()) {
+                mixed[i] += *entry * x;
+            }
+        }
+        *state = mixed;
+    }
+}
+
+/// Hashes `inputs` using the sponge construction, with the capacity element first, as in
+/// circomlib: inputs are absorbed into `state[1..]` and the output is squeezed from
+/// `state[1]`. For domain separation, the capacity element is initialized to
+/// `inputs.len() * 2^64`, as specified for fixed-length hashing in Section 4.2 of the
+/// Poseidon paper; this also makes zero-padding the last chunk unambiguous.
+pub fn hash(inputs: &[Fr]) -> Fr {
+    let mut state = [Fr::zero(); WIDTH];
+    state[0] = Fr::from(inputs.len() as u64) * Fr::from(1u128 << 64);
+    if inputs.is_empty() {
+        permute(&mut state);
+    }
+    for chunk in inputs.chunks(WIDTH - 1) {
+        for (x, input) in state[1..].iter_mut().zip(chunk.iter()) {
+            *x += input;
+        }
+        permute(&mut state);
+    }
+    state[1]
+}
+
+/// The Grain LFSR in self-shrinking mode, used to generate Poseidon parameters.
+struct GrainLFSR {
+    prime_num_bits: usize,
+    state: [bool; 80],
+    head: usize,
+}
+
+impl GrainLFSR {
+    fn new(
+        prime_num_bits: usize,
+        width: usize,
+        num_full_rounds: usize,
+        num_partial_rounds: usize,
+    ) -> Self {
+        let mut state = [false; 80];
+        // b0, b1: the field is a prime field
+        state[1] = true;
+        // b2, ..., b5: the S-box is x^alpha (rather than x^-1), so all zero
+        // b6, ..., b17: the number of bits of the prime
+        // b18, ..., b29: the width of the state
+        // b30, ..., b39: the number of full rounds
+        // b40, ..., b49: the number of partial rounds
+        for (range, value) in [
+            (6..=17, prime_num_bits),
+            (18..=29, width),
+            (30..=39, num_full_rounds),
+            (40..=49, num_partial_rounds),
+        ] {
+            let mut value = value;
+            for i in range.rev() {
+                state[i] = value & 1 == 1;
+                value >>= 1;
+            }
+        }
+        // b50, ..., b79: all one
+        for bit in state[50..].iter_mut() {
+            *bit = true;
+        }
+
+        let mut lfsr = Self {
+            prime_num_bits,
+            state,
+            head: 0,
+        };
+        // Discard the first 160 bits
+        for _ in 0..160 {
+            lfsr.update();
+        }
+        lfsr
+    }
+
+    fn update(&mut self) -> bool {
+        let new_bit = self.state[(self.head + 62) % 80]
+            ^ self.state[(self.head + 51) % 80]
+            ^ self.state[(self.head + 38) % 80]
+            ^ self.state[(self.head + 23) % 80]
+            ^ self.state[(self.head + 13) % 80]
+            ^ self.state[self.head];
+        self.state[self.head] = new_bit;
+        self.head = (self.head + 1) % 80;
+        new_bit
+    }
+
+    /// Returns `num_bits` bits, most significant first, using self-shrinking: bits are
+    /// generated in pairs, and the second bit is output only if the first is set.
+    fn bits(&mut self, num_bits: usize) -> Vec<bool> {
+        (0..num_bits)
+            .map(|_| {
+                while !self.update() {
+                    self.update();
+                }
+                self.update()
+            })
+            .collect()
+    }
+
+    fn field_element_rejection_sampling(&mut self) -> Fr {
+        loop {
+            let mut bits = self.bits(self.prime_num_bits);
+            bits.reverse();
+            let bigint = <Fr as PrimeField>::BigInt::from_bits_le(&bits);
+            if let Some(element) = Fr::from_bigint(bigint) {
+                return element;
+            }
+        }
+    }
+
+    fn field_element_mod_p(&mut self) -> Fr {
+        let mut bits = self.bits(self.prime_num_bits);
+        bits.reverse();
+        let bytes: Vec<u8> = bits
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |byte, (i, bit)| byte | ((*bit as u8) << i))
+            })
+            .collect();
+        Fr::from_le_bytes_mod_order(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_deterministic_and_length_separated() {
+        let inputs: Vec<Fr> = (0..5u64).map(Fr::from).collect();
+        assert_eq!(hash(&inputs), hash(&inputs));
+        assert_ne!(hash(&inputs), hash(&inputs[..4]));
+        // Appending a zero must not collide with the shorter input
+        let mut padded = inputs.clone();
+        padded.push(Fr::zero());
+        assert_ne!(hash(&inputs), hash(&padded));
+        assert_ne!(hash(&[]), hash(&[Fr::zero()]));
+    }
+
+    /// Test vector from the reference implementation
+    /// (<https://extgit.iaik.tugraz.at/krypto/hadeshash>, `poseidonperm_x5_254_3`). The
+    /// first element is also circomlib's `Poseidon(2)` hash of `[1, 2]`.
+    #[test]
+    fn permutation_matches_reference_vector() {
+        let mut state = [Fr::from(0u64), Fr::from(1u64), Fr::from(2u64)];
+        permute(&mut state);
+
+        let expected = [
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+            "0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29",
+            "0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c",
+        ]
+        .map(|hex| {
+            let bytes: Vec<u8> = (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+                .collect();
+            Fr::from_be_bytes_mod_order(&bytes)
+        });
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn permutation_is_nontrivial() {
+        let mut zero = [Fr::zero(); WIDTH];
+        permute(&mut zero);
+        assert_ne!(zero, [Fr::zero(); WIDTH]);
+
+        let mut a = [Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)];
+        let mut b = [Fr::from(1u64), Fr::from(2u64), Fr::from(4u64)];
+        permute(&mut a);
+        permute(&mut b);
+        assert_ne!(a, b);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::field::JoltField;
+use crate::utils::poseidon;
+use ark_bn254::Fr;
 use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
 use ark_serialize::CanonicalSerialize;
 use sha3::{Digest, Keccak256};
 use std::borrow::Borrow;
@@ -216,6 +219,176 @@ impl Transcript for KeccakTranscript {
         }
         q_powers
     }
+
+    fn protocol_name() -> &'static [u8] {
+        b"keccak256_transcript"
+    }
+}
+
+/// Fiat-Shamir transcript built on the Poseidon hash over the BN254 scalar field.
+/// Unlike `KeccakTranscript`, it is cheap to verify inside a circuit or a recursive
+/// Jolt guest: scalars are absorbed natively as field elements, and only bytes and
+/// (non-native) curve points need to be packed into field elements.
+#[derive(Default, Clone)]
+pub struct PoseidonTranscript {
+    /// The running state
+    pub state: Fr,
+    /// We append an ordinal to each invocation of the hash
+    n_rounds: u32,
+    #[cfg(test)]
+    /// A complete history of the transcript's `state`; used for testing.
+    state_history: Vec<Fr>,
+    #[cfg(test)]
+    /// For a proof to be valid, the verifier's `state_history` should always match
+    /// the prover's. In testing, the Jolt verifier may be provided the prover's
+    /// `state_history` so that we can detect any deviations and the backtrace can
+    /// tell us where it happened.
+    expected_state_history: Option<Vec<Fr>>,
+}
+
+impl PoseidonTranscript {
+    /// Hashes the given elements together with the running state and round ordinal
+    fn absorb(&mut self, elements: &[Fr]) {
+        let mut inputs = Vec::with_capacity(elements.len() + 2);
+        inputs.push(self.state);
+        inputs.push(Fr::from(self.n_rounds as u64));
+        inputs.extend_from_slice(elements);
+        self.update_state(poseidon::hash(&inputs));
+    }
+
+    /// Packs bytes into field elements, preceded by the number of bytes. Each element
+    /// holds 31 bytes, so that it is always less than the modulus.
+    fn bytes_to_elements(bytes: &[u8]) -> Vec<Fr> {
+        std::iter::once(Fr::from(bytes.len() as u64))
+            .chain(bytes.chunks(31).map(Fr::from_le_bytes_mod_order))
+            .collect()
+    }
+
+    fn scalar_to_element<F: JoltField>(scalar: &F) -> Fr {
+        let mut buf = vec![];
+        scalar.serialize_uncompressed(&mut buf).unwrap();
+        Fr::from_le_bytes_mod_order(&buf)
+    }
+
+    fn update_state(&mut self, new_state: Fr) {
+        self.state = new_state;
+        self.n_rounds += 1;
+        #[cfg(test)]
+        {
+            if let Some(expected_state_history) = &self.expected_state_history {
+                assert!(
+                    new_state == expected_state_history[self.n_rounds as usize],
+                    "Fiat-Shamir transcript mismatch"
+                );
+            }
+            self.state_history.push(new_state);
+        }
+    }
+}
+
+impl Transcript for PoseidonTranscript {
+    fn new(label: &'static [u8]) -> Self {
+        assert!(label.len() < 33);
+        let state = poseidon::hash(&Self::bytes_to_elements(label));
+
+        Self {
+            state,
+            n_rounds: 0,
+            #[cfg(test)]
+            state_history: vec![state],
+            #[cfg(test)]
+            expected_state_history: None,
+        }
+    }
+
+    #[cfg(test)]
+    /// Compare this transcript to `other` and panic if/when they deviate.
+    /// Typically used to compare the verifier's transcript to the prover's.
+    fn compare_to(&mut self, other: Self) {
+        self.expected_state_history = Some(other.state_history);
+    }
+
+    fn append_message(&mut self, msg: &'static [u8]) {
+        assert!(msg.len() < 33);
+        self.absorb(&Self::bytes_to_elements(msg));
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.absorb(&Self::bytes_to_elements(bytes));
+    }
+
+    fn append_u64(&mut self, x: u64) {
+        self.absorb(&[Fr::from(x)]);
+    }
+
+    fn append_scalar<F: JoltField>(&mut self, scalar: &F) {
+        self.absorb(&[Self::scalar_to_element(scalar)]);
+    }
+
+    fn append_serializable<F: CanonicalSerialize>(&mut self, scalar: &F) {
+        let mut buf = vec![];
+        scalar.serialize_uncompressed(&mut buf).unwrap();
+        self.append_bytes(&buf);
+    }
+
+    fn append_scalars<F: JoltField>(&mut self, scalars: &[impl Borrow<F>]) {
+        // The sponge is domain-separated by input length, so the scalars can be
+        // absorbed in a single hash invocation
+        let elements: Vec<Fr> = scalars
+            .iter()
+            .map(|scalar| Self::scalar_to_element(scalar.borrow()))
+            .collect();
+        self.absorb(&elements);
+    }
+
+    fn append_point<G: CurveGroup>(&mut self, point: &G) {
+        // If we add the point at infinity then we hash over a region of zeros
+        if point.is_zero() {
+            self.append_bytes(&[0_u8; 64]);
+            return;
+        }
+
+        let aff = point.into_affine();
+        let mut bytes = vec![];
+        aff.x().unwrap().serialize_uncompressed(&mut bytes).unwrap();
+        aff.y().unwrap().serialize_uncompressed(&mut bytes).unwrap();
+        self.append_bytes(&bytes);
+    }
+
+    fn append_points<G: CurveGroup>(&mut self, points: &[G]) {
+        self.append_message(b"begin_append_vector");
+        for item in points.iter() {
+            self.append_point(item);
+        }
+        self.append_message(b"end_append_vector");
+    }
+
+    fn challenge_scalar<F: JoltField>(&mut self) -> F {
+        self.absorb(&[]);
+        let mut buf = vec![];
+        self.state.serialize_uncompressed(&mut buf).unwrap();
+        F::from_bytes(&buf[..F::NUM_BYTES])
+    }
+
+    fn challenge_vector<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        (0..len)
+            .map(|_i| self.challenge_scalar())
+            .collect::<Vec<F>>()
+    }
+
+    // Compute powers of scalar q : (1, q, q^2, ..., q^(len-1))
+    fn challenge_scalar_powers<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        let q: F = self.challenge_scalar();
+        let mut q_powers = vec![F::one(); len];
+        for i in 1..len {
+            q_powers[i] = q_powers[i - 1] * q;
+        }
+        q_powers
+    }
+
+    fn protocol_name() -> &'static [u8] {
+        b"poseidon_bn254_transcript"
+    }
 }
 
 pub trait Transcript: Default + Clone + Sync + Send + 'static {
@@ -234,6 +407,8 @@ pub trait Transcript: Default + Clone + Sync + Send + 'static {
     fn challenge_vector<F: JoltField>(&mut self, len: usize) -> Vec<F>;
     // Compute powers of scalar q : (1, q, q^2, ..., q^(len-1))
     fn challenge_scalar_powers<F: JoltField>(&mut self, len: usize) -> Vec<F>;
+    /// Identifies the transcript construction, e.g. in serialized proof headers.
+    fn protocol_name() -> &'static [u8];
 }
 
 pub trait AppendToTranscript {
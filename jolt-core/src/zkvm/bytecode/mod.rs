@@ -15,7 +15,7 @@ use crate::{
     utils::{thread::unsafe_allocate_zero_vec, transcript::Transcript},
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use common::constants::{BYTES_PER_INSTRUCTION, RAM_START_ADDRESS};
+use common::constants::{BYTES_PER_COMPRESSED_INSTRUCTION, RAM_START_ADDRESS};
 use rayon::prelude::*;
 use tracer::instruction::{RV32IMCycle, RV32IMInstruction};
 
@@ -48,7 +48,9 @@ impl BytecodePreprocessing {
             }
             let instr = instruction.normalize();
             debug_assert!(instr.address >= RAM_START_ADDRESS as usize);
-            debug_assert!(instr.address.is_multiple_of(BYTES_PER_INSTRUCTION));
+            debug_assert!(instr
+                .address
+                .is_multiple_of(BYTES_PER_COMPRESSED_INSTRUCTION));
             assert_eq!(
                 virtual_address_map.insert(
                     (instr.address, instr.virtual_sequence_remaining.unwrap_or(0)),
@@ -91,6 +93,21 @@ impl BytecodePreprocessing {
             .get(&(instr.address, instr.virtual_sequence_remaining.unwrap_or(0)))
             .unwrap()
     }
+
+    /// The virtual address of the first (potentially virtual) instruction executed
+    /// for the instruction at the given ELF address, i.e. the PC of a cycle whose
+    /// unexpanded PC is `address` and which is not in the middle of a virtual sequence.
+    /// Returns `None` if there is no instruction at `address`.
+    pub fn entry_pc(&self, address: usize) -> Option<usize> {
+        if address == 0 {
+            return None;
+        }
+        // The first instruction of a virtual sequence has the most instructions remaining
+        self.virtual_address_map
+            .range((address, 0)..=(address, u16::MAX))
+            .next_back()
+            .map(|(_, pc)| *pc)
+    }
 }
 
 #[derive(Default)]
@@ -0,0 +1,683 @@
+//! Continuations split a long execution into segments, each starting and ending on an
+//! instruction boundary. Each segment is proven on its own, with the architectural state
+//! at its boundaries as public input and output: a segment proof attests that executing
+//! the program from the segment's initial state for the claimed number of cycles ends in
+//! its final state. A chain of verified segments is then checked to link up from the
+//! program's initial state to a final state consistent with the claimed program outputs.
+//!
+//! The program counter and registers at a boundary are public, but RAM is not: a segment
+//! commits to its initial RAM state if it follows another segment, and to its final RAM
+//! state if another segment follows it, and these commitments must match across each
+//! boundary. The first segment instead starts in the program's initial RAM, which the
+//! verifier evaluates on its own, and the last segment ends in a RAM state whose I/O region
+//! is checked against the claimed program outputs, as in a proof of the whole execution.
+//! The verifier's work per segment is thus independent of the size of RAM, except for
+//! the first segment.
+//!
+//! A committed RAM state is zero-padded to the trace length, so all segments are proven
+//! with the same trace length and RAM size, and the trace must be at least as long as
+//! RAM is large.
+//!
+//! Continuations do not support private inputs.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::{
+    constants::{RAM_START_ADDRESS, REGISTER_COUNT, RESERVATION_ADDRESS},
+    jolt_device::MemoryLayout,
+};
+use rayon::prelude::*;
+use tracer::{
+    instruction::{RAMAccess, RV32IMCycle},
+    JoltDevice, LazyTraceIterator, Segment,
+};
+
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::utils::errors::ContinuationError;
+use crate::zkvm::{
+    bytecode::BytecodePreprocessing,
+    instruction::{CircuitFlags, InstructionFlags},
+    ram::{ram_state_polynomial, remap_address},
+    witness::CommittedPolynomial,
+};
+
+/// The public machine state at a segment boundary. `C` is the commitment type of the
+/// segment proofs' commitment scheme.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BoundaryState<C: CanonicalSerialize + CanonicalDeserialize> {
+    /// Number of cycles executed before this boundary
+    pub cycle: u64,
+    /// Program counter of the next instruction to be executed
+    pub pc: u64,
+    /// Values of all registers, including virtual registers
+    pub registers: Vec<u64>,
+    /// Commitment to the contents of RAM (see `ram::ram_state_polynomial`), or `None` at
+    /// the start and end of the program, where RAM is determined by the program's inputs
+    /// and checked against its outputs respectively
+    pub ram: Option<C>,
+}
+
+impl<C: CanonicalSerialize + CanonicalDeserialize> BoundaryState<C> {
+    /// The state in which the program starts executing: all registers are zero, and RAM
+    /// holds the program's bytecode and inputs. Guests start at `RAM_START_ADDRESS`, where
+    /// the linker script places the `.text.boot` section holding `_start`.
+    pub fn initial() -> Self {
+        Self {
+            cycle: 0,
+            pc: RAM_START_ADDRESS,
+            registers: vec![0; REGISTER_COUNT as usize],
+            ram: None,
+        }
+    }
+}
+
+/// The machine state at a segment boundary in full, as known to the prover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundaryWitness {
+    /// Number of cycles executed before this boundary
+    pub cycle: u64,
+    /// Program counter of the next instruction to be executed
+    pub pc: u64,
+    /// Values of all registers, including virtual registers
+    pub registers: Vec<u64>,
+    /// Contents of RAM, as words indexed by remapped address (see `ram::remap_address`),
+    /// with trailing zeros trimmed
+    pub ram: Vec<u32>,
+}
+
+impl BoundaryWitness {
+    /// Captures the boundary state of the given emulator checkpoint, which must be
+    /// on an instruction boundary. `terminated` is whether the guest has set its
+    /// termination bit, which the emulator does not keep track of.
+    #[tracing::instrument(skip_all, name = "BoundaryWitness::capture")]
+    pub fn capture(checkpoint: &LazyTraceIterator, cycle: usize, terminated: bool) -> Self {
+        debug_assert!(checkpoint.at_tick_boundary());
+        // The I/O region lies below `RAM_START_ADDRESS`, and is followed by the
+        // contents of the emulator's memory
+        let memory_layout = &checkpoint.jolt_device().memory_layout;
+        let mut ram = io_words(checkpoint.jolt_device(), terminated);
+        // The LR/SC reservation is emulator state, kept alongside the memory
+        ram[remap_address(RESERVATION_ADDRESS, memory_layout).unwrap() as usize] =
+            checkpoint.memory().reservation;
+        ram.extend(
+            checkpoint
+                .memory()
+                .data
+                .iter()
+                .flat_map(|doubleword| [*doubleword as u32, (doubleword >> 32) as u32]),
+        );
+        Self {
+            cycle: cycle as u64,
+            pc: checkpoint.pc(),
+            registers: checkpoint.registers(),
+            ram: trim_trailing_zeros(ram),
+        }
+    }
+
+    /// The contents of RAM, zero-padded to `K` words.
+    pub fn padded_ram(&self, K: usize) -> Vec<u32> {
+        let mut ram = self.ram.clone();
+        ram.resize(K, 0);
+        ram
+    }
+
+    /// The public boundary state, with `ram` as the commitment to RAM (if any).
+    pub fn to_state<C: CanonicalSerialize + CanonicalDeserialize>(
+        &self,
+        ram: Option<C>,
+    ) -> BoundaryState<C> {
+        BoundaryState {
+            cycle: self.cycle,
+            pc: self.pc,
+            registers: self.registers.clone(),
+            ram,
+        }
+    }
+}
+
+/// The words of the I/O region of RAM, i.e. the addresses below `RAM_START_ADDRESS`,
+/// holding the inputs, outputs, and panic and termination bits. The LR/SC reservation word is not part of the program's I/O and is left as 0.
+fn io_words(program_io: &JoltDevice, terminated: bool) -> Vec<u32> {
+    let memory_layout = &program_io.memory_layout;
+    let remap = |address| remap_address(address, memory_layout).unwrap() as usize;
+
+    let mut words = vec![0; remap(RAM_START_ADDRESS)];
+    write_bytes(
+        &mut words,
+        memory_layout.input_start,
+        &program_io.inputs,
+        memory_layout,
+    );
+    write_bytes(
+        &mut words,
+        memory_layout.private_input_start,
+        &program_io.private_inputs,
+        memory_layout,
+    );
+    write_bytes(
+        &mut words,
+        memory_layout.output_start,
+        &program_io.outputs,
+        memory_layout,
+    );
+    words[remap(memory_layout.panic)] = program_io.panic as u32;
+    words[remap(memory_layout.termination)] = terminated as u32;
+    words
+}
+
+/// Writes `bytes` into `words` as little-endian words, starting at the word for `address`.
+fn write_bytes(words: &mut [u32], address: u64, bytes: &[u8], memory_layout: &MemoryLayout) {
+    let start = remap_address(address, memory_layout).unwrap() as usize;
+    for (word, chunk) in words[start..].iter_mut().zip(bytes.chunks(4)) {
+        let mut word_bytes = [0u8; 4];
+        word_bytes[..chunk.len()].copy_from_slice(chunk);
+        *word = u32::from_le_bytes(word_bytes);
+    }
+}
+
+fn trim_trailing_zeros(mut words: Vec<u32>) -> Vec<u32> {
+    words.truncate(
+        words
+            .iter()
+            .rposition(|word| *word != 0)
+            .map_or(0, |i| i + 1),
+    );
+    words
+}
+
+/// The boundary claims of a single segment.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SegmentClaim<C: CanonicalSerialize + CanonicalDeserialize> {
+    pub initial_state: BoundaryState<C>,
+    pub final_state: BoundaryState<C>,
+}
+
+/// A segment claim that has been checked to be provable in a trace of a given length,
+/// together with the (virtual) PCs of the instructions at its initial and final
+/// program counters. Proving a segment constrains, in addition to the usual Jolt
+/// constraints:
+/// - the first cycle of the trace to execute the instruction at the initial PC;
+/// - the last cycle of the segment to be followed by the instruction at the final PC
+///   (rather than by a no-op), and by no-ops from then on;
+/// - the registers to start and end in the initial and final states rather than all
+///   zeros;
+/// - RAM to start in the committed initial RAM state, if any, rather than the program's
+///   bytecode and inputs, and to end in the committed final RAM state, if any, rather
+///   than one holding the program outputs (see `CommittedPolynomial::RamInitialState`
+///   and `CommittedPolynomial::RamFinalState`).
+pub struct SegmentIO<C: CanonicalSerialize + CanonicalDeserialize> {
+    pub claim: SegmentClaim<C>,
+    /// The virtual PC of the first cycle of the segment
+    pub initial_pc: usize,
+    /// The virtual PC of the cycle following the last cycle of the segment
+    pub final_pc: usize,
+    /// The initial and final RAM states, zero-padded to the RAM size. Only known to the
+    /// prover.
+    pub ram: Option<(Vec<u32>, Vec<u32>)>,
+}
+
+impl<C: CanonicalSerialize + CanonicalDeserialize> SegmentIO<C> {
+    /// Checks that `claim` can be proven in a trace of (padded) length `trace_length`,
+    /// with a RAM of `ram_K` words.
+    pub fn new(
+        claim: SegmentClaim<C>,
+        bytecode: &BytecodePreprocessing,
+        trace_length: usize,
+        ram_K: usize,
+    ) -> Result<Self, ContinuationError> {
+        for state in [&claim.initial_state, &claim.final_state] {
+            if state.registers.len() != REGISTER_COUNT as usize {
+                return Err(ContinuationError::MalformedClaim(
+                    "wrong number of registers",
+                ));
+            }
+            if state.registers[0] != 0 {
+                return Err(ContinuationError::MalformedClaim("nonzero zero register"));
+            }
+            // A committed RAM state is padded to the trace length
+            if state.ram.is_some() && ram_K > trace_length {
+                return Err(ContinuationError::MalformedClaim(
+                    "RAM exceeds the trace length",
+                ));
+            }
+        }
+        // The last cycle of the segment must be followed by (at least) one no-op,
+        // which the program counter constraints at the boundary rely on
+        let num_cycles = claim
+            .final_state
+            .cycle
+            .checked_sub(claim.initial_state.cycle)
+            .unwrap_or(0);
+        if num_cycles == 0 || num_cycles >= trace_length as u64 {
+            return Err(ContinuationError::MalformedClaim(
+                "number of cycles does not fit the trace",
+            ));
+        }
+        let initial_pc = bytecode.entry_pc(claim.initial_state.pc as usize).ok_or(
+            ContinuationError::MalformedClaim("no instruction at initial PC"),
+        )?;
+        let final_pc = bytecode.entry_pc(claim.final_state.pc as usize).ok_or(
+            ContinuationError::MalformedClaim("no instruction at final PC"),
+        )?;
+
+        Ok(Self {
+            claim,
+            initial_pc,
+            final_pc,
+            ram: None,
+        })
+    }
+
+    /// The number of cycles executed in this segment.
+    pub fn num_cycles(&self) -> usize {
+        (self.claim.final_state.cycle - self.claim.initial_state.cycle) as usize
+    }
+
+    /// The value of `ShouldJump` at the last cycle of the segment, which is followed
+    /// by the instruction at the final PC rather than by a no-op.
+    pub fn last_should_jump(&self, trace: &[RV32IMCycle]) -> bool {
+        trace[self.num_cycles() - 1].instruction().circuit_flags()[CircuitFlags::Jump]
+    }
+
+    /// Whether the segment starts in a committed RAM state, i.e. follows another segment.
+    pub fn commits_initial_ram(&self) -> bool {
+        self.claim.initial_state.ram.is_some()
+    }
+
+    /// Whether the segment ends in a committed RAM state, i.e. is followed by another
+    /// segment.
+    pub fn commits_final_ram(&self) -> bool {
+        self.claim.final_state.ram.is_some()
+    }
+
+    /// The commitment to `polynomial`, if it is a RAM state this segment commits to.
+    pub fn ram_commitment(&self, polynomial: CommittedPolynomial) -> Option<&C> {
+        match polynomial {
+            CommittedPolynomial::RamInitialState => self.claim.initial_state.ram.as_ref(),
+            CommittedPolynomial::RamFinalState => self.claim.final_state.ram.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The contents of `polynomial`, if it is a RAM state this segment commits to.
+    /// Only available to the prover.
+    pub fn ram_state(&self, polynomial: CommittedPolynomial) -> Option<&[u32]> {
+        let (initial_ram, final_ram) = self.ram.as_ref()?;
+        match polynomial {
+            CommittedPolynomial::RamInitialState if self.commits_initial_ram() => Some(initial_ram),
+            CommittedPolynomial::RamFinalState if self.commits_final_ram() => Some(final_ram),
+            _ => None,
+        }
+    }
+}
+
+/// Computes the boundary states of the given segments, as produced by
+/// `tracer::trace_segments`. `final_checkpoint` is the emulator state after execution
+/// has completed. Each segment is re-executed once, to find out when the guest sets its
+/// termination bit. Also returns the number of RAM words spanned by the accesses of
+/// all segments.
+pub fn segment_boundaries(
+    segments: &[Segment],
+    final_checkpoint: &LazyTraceIterator,
+) -> (Vec<BoundaryWitness>, usize) {
+    let memory_layout = &final_checkpoint.jolt_device().memory_layout;
+    let termination_index = remap_address(memory_layout.termination, memory_layout);
+
+    let mut boundaries = Vec::with_capacity(segments.len() + 1);
+    let mut terminated = false;
+    let mut num_words = 0;
+    for segment in segments {
+        boundaries.push(BoundaryWitness::capture(
+            &segment.checkpoint,
+            segment.start_cycle,
+            terminated,
+        ));
+        for cycle in segment.trace() {
+            let Some(index) = remap_address(cycle.ram_access().address() as u64, memory_layout)
+            else {
+                continue;
+            };
+            num_words = num_words.max(index as usize + 1);
+            if let RAMAccess::Write(write) = cycle.ram_access() {
+                terminated |= Some(index) == termination_index && write.post_value != 0;
+            }
+        }
+    }
+    let total_cycles = segments
+        .last()
+        .map_or(0, |segment| segment.start_cycle + segment.len);
+    boundaries.push(BoundaryWitness::capture(
+        final_checkpoint,
+        total_cycles,
+        terminated,
+    ));
+
+    (boundaries, num_words)
+}
+
+/// Computes the claim of each segment from the boundary states computed by
+/// [`segment_boundaries`], committing to the RAM states between segments. The
+/// commitments are to RAM zero-padded to `padded_trace_length`, so that they can be
+/// opened alongside the witness polynomials of the segment proofs.
+pub fn segment_claims<PCS: CommitmentScheme>(
+    boundaries: &[BoundaryWitness],
+    padded_trace_length: usize,
+    setup: &PCS::ProverSetup,
+) -> Vec<SegmentClaim<PCS::Commitment>> {
+    let states: Vec<BoundaryState<PCS::Commitment>> = boundaries
+        .par_iter()
+        .enumerate()
+        .map(|(i, boundary)| {
+            let ram = (i > 0 && i < boundaries.len() - 1).then(|| {
+                let polynomial = ram_state_polynomial(&boundary.ram, padded_trace_length);
+                PCS::commit(&polynomial, setup).0
+            });
+            boundary.to_state(ram)
+        })
+        .collect();
+
+    states
+        .windows(2)
+        .map(|window| SegmentClaim {
+            initial_state: window[0].clone(),
+            final_state: window[1].clone(),
+        })
+        .collect()
+}
+
+/// Checks that a chain of segment claims links up from the program's initial state:
+/// each segment must be non-empty, and must end in the state in which the next segment
+/// begins. RAM must be committed to exactly at the boundaries between segments. A guest
+/// fault cannot be proven with continuations, and is rejected. The claims themselves,
+/// and the final state's consistency with `program_io`, are proven by the segment proofs
+/// (see [`crate::zkvm::Jolt::verify_segments`]).
+pub fn verify_segment_chain<C: CanonicalSerialize + CanonicalDeserialize + PartialEq>(
+    claims: &[SegmentClaim<C>],
+    program_io: &JoltDevice,
+) -> Result<(), ContinuationError> {
+    if let Some(fault) = program_io.fault {
+        return Err(ContinuationError::GuestFault(fault));
+    }
+    let (first, last) = match (claims.first(), claims.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(ContinuationError::NoSegments),
+    };
+    if first.initial_state != BoundaryState::initial() {
+        return Err(ContinuationError::InitialStateMismatch);
+    }
+    if last.final_state.ram.is_some() {
+        return Err(ContinuationError::MalformedClaim(
+            "committed RAM at the end of the last segment",
+        ));
+    }
+    for (i, claim) in claims.iter().enumerate() {
+        if claim.final_state.cycle <= claim.initial_state.cycle {
+            return Err(ContinuationError::EmptySegment(i));
+        }
+    }
+    for (i, window) in claims.windows(2).enumerate() {
+        if window[0].final_state.ram.is_none() {
+            return Err(ContinuationError::MalformedClaim(
+                "uncommitted RAM between segments",
+            ));
+        }
+        if window[0].final_state != window[1].initial_state {
+            return Err(ContinuationError::BrokenLink(i));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host;
+    use crate::poly::commitment::dory::DoryCommitmentScheme;
+    use crate::poly::commitment::mock::MockCommitScheme;
+    use crate::utils::errors::ProofVerifyError;
+    use crate::zkvm::dag::proof_serialization::JoltProof;
+    use crate::zkvm::{Jolt, JoltRV32IM, JoltVerifierPreprocessing};
+    use ark_bn254::Fr;
+    use common::jolt_device::{GuestFault, MemoryConfig};
+    use serial_test::serial;
+    use tracer::TraceOutput;
+
+    fn program_io() -> JoltDevice {
+        let mut program_io = JoltDevice::new(&MemoryConfig {
+            program_size: Some(1024),
+            ..Default::default()
+        });
+        program_io.inputs = vec![1, 2, 3];
+        program_io.outputs = vec![4, 5];
+        program_io
+    }
+
+    /// A boundary state with a stand-in for the RAM commitment
+    fn boundary(cycle: u64, pc: u64, ram: Option<u64>) -> BoundaryState<u64> {
+        BoundaryState {
+            cycle,
+            pc,
+            registers: vec![0; REGISTER_COUNT as usize],
+            ram,
+        }
+    }
+
+    fn claim(
+        initial_state: BoundaryState<u64>,
+        final_state: BoundaryState<u64>,
+    ) -> SegmentClaim<u64> {
+        SegmentClaim {
+            initial_state,
+            final_state,
+        }
+    }
+
+    #[test]
+    fn rejects_broken_chains() {
+        let program_io = program_io();
+        let (a, b, c) = (
+            BoundaryState::initial(),
+            boundary(10, 4, Some(1)),
+            boundary(20, 8, None),
+        );
+        let claims = vec![claim(a.clone(), b.clone()), claim(b.clone(), c.clone())];
+        assert_eq!(verify_segment_chain(&claims, &program_io), Ok(()));
+
+        assert_eq!(
+            verify_segment_chain::<u64>(&[], &program_io),
+            Err(ContinuationError::NoSegments)
+        );
+        assert_eq!(
+            verify_segment_chain(&[claim(b.clone(), c.clone())], &program_io),
+            Err(ContinuationError::InitialStateMismatch)
+        );
+
+        let mut faulted = program_io.clone();
+        faulted.fault = Some(GuestFault::StackOverflow);
+        assert_eq!(
+            verify_segment_chain(&claims, &faulted),
+            Err(ContinuationError::GuestFault(GuestFault::StackOverflow))
+        );
+
+        // RAM must be committed to at the boundaries between segments, and only there
+        assert!(matches!(
+            verify_segment_chain(&[claim(a.clone(), b.clone())], &program_io),
+            Err(ContinuationError::MalformedClaim(_))
+        ));
+        let uncommitted = boundary(10, 4, None);
+        let claims_uncommitted = vec![
+            claim(a.clone(), uncommitted.clone()),
+            claim(uncommitted, c.clone()),
+        ];
+        assert!(matches!(
+            verify_segment_chain(&claims_uncommitted, &program_io),
+            Err(ContinuationError::MalformedClaim(_))
+        ));
+
+        let mut tampered = b.clone();
+        tampered.registers[1] = 1;
+        let claims = vec![claim(a.clone(), b.clone()), claim(tampered, c.clone())];
+        assert_eq!(
+            verify_segment_chain(&claims, &program_io),
+            Err(ContinuationError::BrokenLink(0))
+        );
+
+        let mut tampered = b.clone();
+        tampered.ram = Some(2);
+        let claims = vec![claim(a.clone(), b.clone()), claim(tampered, c.clone())];
+        assert_eq!(
+            verify_segment_chain(&claims, &program_io),
+            Err(ContinuationError::BrokenLink(0))
+        );
+
+        let a_committed = BoundaryState {
+            ram: Some(1),
+            ..a.clone()
+        };
+        let claims = vec![
+            claim(a.clone(), a_committed.clone()),
+            claim(a_committed, c.clone()),
+        ];
+        assert_eq!(
+            verify_segment_chain(&claims, &program_io),
+            Err(ContinuationError::EmptySegment(0))
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn segments_cover_trace() {
+        let mut program = host::Program::new("fibonacci-guest");
+        let inputs = postcard::to_stdvec(&9u32).unwrap();
+        let TraceOutput {
+            trace,
+            io_device: program_io,
+            ..
+        } = program.trace(&inputs);
+        let (segments, final_checkpoint) = program.trace_segments(&inputs, 100);
+        assert!(segments.len() > 1);
+
+        let segmented_trace: Vec<RV32IMCycle> = segments
+            .iter()
+            .flat_map(|segment| segment.trace())
+            .collect();
+        assert_eq!(segmented_trace, trace);
+
+        let (boundaries, num_words) = segment_boundaries(&segments, &final_checkpoint);
+        assert_eq!(boundaries.len(), segments.len() + 1);
+        let memory_layout = &program_io.memory_layout;
+        let max_index = trace
+            .iter()
+            .filter_map(|cycle| remap_address(cycle.ram_access().address() as u64, memory_layout))
+            .max()
+            .unwrap();
+        assert_eq!(num_words, max_index as usize + 1);
+
+        // The first boundary holds the program's bytecode and inputs
+        let (bytecode, memory_init, _) = program.decode();
+        let preprocessing =
+            JoltRV32IM::shared_preprocess(bytecode, memory_layout.clone(), memory_init);
+        let mut initial_io = program_io.clone();
+        initial_io.outputs.clear();
+        let mut initial_ram = io_words(&initial_io, false);
+        initial_ram.resize(
+            remap_address(preprocessing.ram.min_bytecode_address, memory_layout).unwrap() as usize,
+            0,
+        );
+        initial_ram.extend(&preprocessing.ram.bytecode_words);
+        assert_eq!(boundaries[0].ram, trim_trailing_zeros(initial_ram));
+
+        let padded_trace_length = num_words
+            .max(boundaries.iter().map(|b| b.ram.len()).max().unwrap())
+            .next_power_of_two();
+        let claims = segment_claims::<MockCommitScheme<Fr>>(&boundaries, padded_trace_length, &());
+        assert_eq!(claims.len(), segments.len());
+        assert_eq!(claims[0].initial_state, BoundaryState::initial());
+        assert_eq!(verify_segment_chain(&claims, &program_io), Ok(()));
+    }
+
+    #[test]
+    #[serial]
+    fn fib_segments_e2e_dory() {
+        let mut program = host::Program::new("fibonacci-guest");
+        let inputs = postcard::to_stdvec(&9u32).unwrap();
+        let (bytecode, memory_init, _) = program.decode();
+        let io_device = program.trace(&inputs).io_device;
+
+        let preprocessing = JoltRV32IM::prover_preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 16,
+        );
+        let verifier_preprocessing =
+            JoltVerifierPreprocessing::<_, DoryCommitmentScheme>::from(&preprocessing);
+
+        let (segments, program_io) =
+            JoltRV32IM::prove_segments(&preprocessing, &mut program, &inputs, 1 << 7).unwrap();
+        assert!(segments.len() > 2);
+        assert_eq!(program_io.outputs, io_device.outputs);
+
+        // Keep a copy of the proofs around, to check them against tampered claims
+        let (proof_bytes, claims): (Vec<_>, Vec<_>) = segments
+            .iter()
+            .map(|(proof, claim)| {
+                let mut bytes = Vec::new();
+                proof.serialize_compressed(&mut bytes).unwrap();
+                (bytes, claim.clone())
+            })
+            .unzip();
+        let proofs = || {
+            proof_bytes
+                .iter()
+                .map(|bytes| JoltProof::deserialize_compressed(&bytes[..]).unwrap())
+        };
+        JoltRV32IM::verify_segments(&verifier_preprocessing, segments, program_io.clone())
+            .expect("Continuation should verify");
+
+        // A segment proof does not verify against a claim other than its own
+        let mut tampered_claims = claims.clone();
+        tampered_claims[0].final_state.registers[10] ^= 1;
+        tampered_claims[1].initial_state.registers[10] ^= 1;
+        assert!(matches!(
+            JoltRV32IM::verify_segments(
+                &verifier_preprocessing,
+                proofs().zip(tampered_claims).collect(),
+                program_io.clone()
+            ),
+            Err(ProofVerifyError::Continuation(
+                ContinuationError::InvalidSegmentProof(0, _)
+            ))
+        ));
+
+        // ...including the commitment to RAM between two segments
+        let mut tampered_claims = claims.clone();
+        let ram = tampered_claims[1].final_state.ram.clone();
+        tampered_claims[0].final_state.ram = ram.clone();
+        tampered_claims[1].initial_state.ram = ram;
+        assert!(matches!(
+            JoltRV32IM::verify_segments(
+                &verifier_preprocessing,
+                proofs().zip(tampered_claims).collect(),
+                program_io.clone()
+            ),
+            Err(ProofVerifyError::Continuation(
+                ContinuationError::InvalidSegmentProof(0, _)
+            ))
+        ));
+
+        // The last segment must end with the claimed outputs
+        let mut wrong_outputs = program_io.clone();
+        wrong_outputs.outputs[0] ^= 1;
+        let last = claims.len() - 1;
+        assert!(matches!(
+            JoltRV32IM::verify_segments(
+                &verifier_preprocessing,
+                proofs().zip(claims).collect(),
+                wrong_outputs
+            ),
+            Err(ProofVerifyError::Continuation(
+                ContinuationError::InvalidSegmentProof(i, _)
+            )) if i == last
+        ));
+    }
+}
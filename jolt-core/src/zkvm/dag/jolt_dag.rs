@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::field::JoltField;
-use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::commitment::commitment_scheme::{
+    BatchOpening, DeferredOpening, StreamingCommitmentScheme,
+};
 use crate::poly::commitment::dory::DoryGlobals;
 use crate::subprotocols::sumcheck::{BatchedSumcheck, SumcheckInstance};
 use crate::utils::thread::drop_in_background_thread;
@@ -10,16 +12,20 @@ use crate::zkvm::bytecode::BytecodeDag;
 use crate::zkvm::dag::proof_serialization::JoltProof;
 use crate::zkvm::dag::stage::SumcheckStages;
 use crate::zkvm::dag::state_manager::{ProofData, ProofKeys, StateManager};
+use crate::zkvm::instruction::{CircuitFlags, InstructionFlags};
 use crate::zkvm::instruction_lookups::LookupsDag;
 use crate::zkvm::r1cs::spartan::SpartanDag;
-use crate::zkvm::ram::RamDag;
+use crate::zkvm::ram::{has_private_inputs, ram_state_polynomial, RamDag};
 use crate::zkvm::registers::RegistersDag;
 use crate::zkvm::witness::{
     compute_d_parameter, AllCommittedPolynomials, CommittedPolynomial, DTH_ROOT_OF_K,
 };
 use crate::zkvm::ProverDebugInfo;
 use anyhow::Context;
-use rayon::prelude::*;
+
+/// The number of cycles of the trace whose witnesses are generated and appended to the
+/// commitments at a time (see [`CommittedPolynomial::commit_streaming`]).
+const WITNESS_CHUNK_SIZE: usize = 1 << 16;
 
 pub enum JoltDAG {}
 
@@ -29,7 +35,7 @@ impl JoltDAG {
         'a,
         F: JoltField,
         ProofTranscript: Transcript,
-        PCS: CommitmentScheme<Field = F>,
+        PCS: BatchOpening<Field = F> + StreamingCommitmentScheme,
     >(
         mut state_manager: StateManager<'a, F, ProofTranscript, PCS>,
     ) -> Result<
@@ -41,18 +47,33 @@ impl JoltDAG {
     > {
         state_manager.fiat_shamir_preamble();
 
-        // Initialize DoryGlobals at the beginning to keep it alive for the entire proof
-        let (preprocessing, trace, _, _) = state_manager.get_prover_data();
+        // Initialize DoryGlobals at the beginning to keep it alive for the entire proof.
+        // Besides Dory's matrix dimensions, it fixes the coefficient layout of the one-hot
+        // and RLC polynomials, so it is needed whichever PCS is used.
+        let (preprocessing, trace, program_io, _) = state_manager.get_prover_data();
         let trace_length = trace.len();
         let padded_trace_length = trace_length.next_power_of_two();
+        let private_inputs = has_private_inputs(&program_io.memory_layout);
 
         println!("bytecode size: {}", preprocessing.shared.bytecode.code_size);
 
         let ram_K = state_manager.ram_K;
         let bytecode_d = preprocessing.shared.bytecode.d;
+        let (initial_ram, final_ram) = state_manager
+            .segment
+            .as_ref()
+            .map_or((false, false), |segment| {
+                (segment.commits_initial_ram(), segment.commits_final_ram())
+            });
         let _guard = (
             DoryGlobals::initialize(DTH_ROOT_OF_K, padded_trace_length),
-            AllCommittedPolynomials::initialize(compute_d_parameter(ram_K), bytecode_d),
+            AllCommittedPolynomials::initialize(
+                compute_d_parameter(ram_K),
+                bytecode_d,
+                private_inputs,
+                initial_ram,
+                final_ram,
+            ),
         );
 
         // Generate and commit to all witness polynomials
@@ -177,13 +198,29 @@ impl JoltDAG {
         drop(span);
 
         // Batch-prove all openings
-        let (_, trace, _, _) = state_manager.get_prover_data();
+        let (_, trace, program_io, _) = state_manager.get_prover_data();
         let mut polynomials_map = HashMap::new();
         for polynomial in AllCommittedPolynomials::iter() {
-            polynomials_map.insert(
-                *polynomial,
-                polynomial.generate_witness(preprocessing, trace),
-            );
+            let witness = match state_manager
+                .segment
+                .as_ref()
+                .and_then(|segment| segment.ram_state(*polynomial))
+            {
+                Some(ram) => ram_state_polynomial(ram, trace.len()),
+                None => polynomial.generate_witness_with_io(preprocessing, trace, program_io),
+            };
+            polynomials_map.insert(*polynomial, witness);
+        }
+        if let Some(segment) = &state_manager.segment {
+            // The last cycle of a segment is followed by the instruction at the
+            // segment's final PC, rather than by a no-op
+            polynomials_map
+                .get_mut(&CommittedPolynomial::ShouldJump)
+                .unwrap()
+                .set_coeff(
+                    segment.num_cycles() - 1,
+                    segment.last_should_jump(trace) as u64,
+                );
         }
         let opening_proof = accumulator.borrow_mut().reduce_and_prove(
             polynomials_map,
@@ -231,14 +268,35 @@ impl JoltDAG {
         Ok((proof, debug_info))
     }
 
-    pub fn verify<
+    pub fn verify<'a, F: JoltField, ProofTranscript: Transcript, PCS: BatchOpening<Field = F>>(
+        state_manager: StateManager<'a, F, ProofTranscript, PCS>,
+    ) -> Result<(), anyhow::Error> {
+        let (preprocessing, _, _) = state_manager.get_verifier_data();
+        let mut opening = Self::verify_deferred(state_manager)?;
+        PCS::verify(
+            &opening.proof,
+            &preprocessing.generators,
+            &mut opening.transcript,
+            &opening.opening_point,
+            &opening.opening,
+            &opening.commitment,
+        )
+        .context("Stage 5")?;
+
+        Ok(())
+    }
+
+    /// Verifies stages 1-4 and the reduction of all openings to a single opening, but
+    /// defers verification of the final PCS opening proof, so that it can be batched
+    /// with those of other proofs via `BatchVerification::batch_verify`.
+    pub fn verify_deferred<
         'a,
         F: JoltField,
         ProofTranscript: Transcript,
-        PCS: CommitmentScheme<Field = F>,
+        PCS: BatchOpening<Field = F>,
     >(
         mut state_manager: StateManager<'a, F, ProofTranscript, PCS>,
-    ) -> Result<(), anyhow::Error> {
+    ) -> Result<DeferredOpening<PCS, ProofTranscript>, anyhow::Error> {
         state_manager.fiat_shamir_preamble();
         // #[cfg(test)]
         // {
@@ -260,8 +318,30 @@ impl JoltDAG {
         // }
 
         let ram_K = state_manager.ram_K;
-        let bytecode_d = state_manager.get_verifier_data().0.shared.bytecode.d;
-        let _guard = AllCommittedPolynomials::initialize(compute_d_parameter(ram_K), bytecode_d);
+        let (preprocessing, _, trace_length) = state_manager.get_verifier_data();
+        let (initial_ram, final_ram) = state_manager
+            .segment
+            .as_ref()
+            .map_or((false, false), |segment| {
+                (segment.commits_initial_ram(), segment.commits_final_ram())
+            });
+        let _guard = AllCommittedPolynomials::initialize(
+            compute_d_parameter(ram_K),
+            preprocessing.shared.bytecode.d,
+            has_private_inputs(&preprocessing.shared.memory_layout),
+            initial_ram,
+            final_ram,
+        );
+
+        // The `PrivateInputs` witness is padded to the trace length, so the trace
+        // must be at least as long as the private input region
+        let private_input_words =
+            (preprocessing.shared.memory_layout.max_private_input_size / 4) as usize;
+        if trace_length.next_power_of_two() < private_input_words {
+            return Err(anyhow::anyhow!(
+                "Trace length {trace_length} is shorter than the private input region ({private_input_words} words)"
+            ));
+        }
 
         // Append commitments to transcript
         let commitments = state_manager.get_commitments();
@@ -274,7 +354,7 @@ impl JoltDAG {
         // self.receive_claims().context("Receive claims")?;
 
         // Stage 1:
-        let (preprocessing, _, trace_length) = state_manager.get_verifier_data();
+        let (_, _, trace_length) = state_manager.get_verifier_data();
         let padded_trace_length = trace_length.next_power_of_two();
         let mut spartan_dag = SpartanDag::<F>::new::<ProofTranscript>(padded_trace_length);
         let mut lookups_dag = LookupsDag::default();
@@ -384,25 +464,31 @@ impl JoltDAG {
             _ => panic!("Invalid proof type for stage 4"),
         };
 
+        // The commitments to the RAM states at the boundaries of a segment are part of
+        // its claim rather than of the proof
         let mut commitments_map = HashMap::new();
         for polynomial in AllCommittedPolynomials::iter() {
-            commitments_map.insert(
-                *polynomial,
-                commitments.borrow()[polynomial.to_index()].clone(),
-            );
+            let commitment = match state_manager
+                .segment
+                .as_ref()
+                .and_then(|segment| segment.ram_commitment(*polynomial))
+            {
+                Some(commitment) => commitment.clone(),
+                None => commitments.borrow()[polynomial.to_index()].clone(),
+            };
+            commitments_map.insert(*polynomial, commitment);
         }
         let accumulator = state_manager.get_verifier_accumulator();
-        accumulator
+        let opening = accumulator
             .borrow_mut()
-            .reduce_and_verify(
-                &preprocessing.generators,
+            .reduce_and_defer_verification(
                 &mut commitments_map,
                 batched_opening_proof,
                 &mut *transcript.borrow_mut(),
             )
             .context("Stage 5")?;
 
-        Ok(())
+        Ok(opening)
     }
 
     // Prover utility to commit to all the polynomials for the PCS
@@ -411,30 +497,74 @@ impl JoltDAG {
         'a,
         F: JoltField,
         ProofTranscript: Transcript,
-        PCS: CommitmentScheme<Field = F>,
+        PCS: StreamingCommitmentScheme<Field = F>,
     >(
         prover_state_manager: &mut StateManager<'a, F, ProofTranscript, PCS>,
     ) -> Result<HashMap<CommittedPolynomial, PCS::OpeningProofHint>, anyhow::Error> {
-        let (preprocessing, trace, _program_io, _final_memory_state) =
+        let (preprocessing, trace, program_io, _final_memory_state) =
             prover_state_manager.get_prover_data();
 
-        let committed_polys: Vec<_> = AllCommittedPolynomials::par_iter()
-            .map(|poly| poly.generate_witness(preprocessing, trace))
-            .collect();
+        // Stream the witnesses into their commitments chunk by chunk, rather than
+        // materializing them first. The last cycle of a segment of a continuation is
+        // followed by the instruction at the segment's final PC, rather than by a no-op;
+        // the remainder of its trace is padding.
+        let (cycles, ends_in_noop) = match &prover_state_manager.segment {
+            Some(segment) => (&trace[..segment.num_cycles()], false),
+            None => (&trace[..], true),
+        };
+        let chunks = cycles
+            .chunks(WITNESS_CHUNK_SIZE)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let next_is_noop = cycles
+                    .get((i + 1) * WITNESS_CHUNK_SIZE)
+                    .map_or(ends_in_noop, |next_cycle| {
+                        next_cycle.instruction().circuit_flags()[CircuitFlags::IsNoop]
+                    });
+                (chunk, next_is_noop)
+            });
+        let mut committed =
+            CommittedPolynomial::commit_streaming(preprocessing, chunks, trace.len());
+
+        // The private inputs are not derived from the trace
+        if has_private_inputs(&program_io.memory_layout) {
+            let private_inputs = CommittedPolynomial::PrivateInputs.generate_witness_with_io(
+                preprocessing,
+                trace,
+                program_io,
+            );
+            committed.push(PCS::commit(&private_inputs, &preprocessing.generators));
+        }
 
         let (commitments, hints): (Vec<PCS::Commitment>, Vec<PCS::OpeningProofHint>) =
-            committed_polys
-                .iter()
-                .map(|poly| PCS::commit(poly, &preprocessing.generators))
-                .unzip();
-        let mut hint_map = HashMap::with_capacity(committed_polys.len());
+            committed.into_iter().unzip();
+        let mut hint_map = HashMap::with_capacity(hints.len());
         for (poly, hint) in AllCommittedPolynomials::iter().zip(hints) {
             hint_map.insert(*poly, hint);
         }
 
-        prover_state_manager.set_commitments(commitments);
+        // The RAM states at the boundaries of a segment were committed to when computing
+        // its claim, so only the opening hints are needed here
+        if let Some(segment) = &prover_state_manager.segment {
+            for polynomial in [
+                CommittedPolynomial::RamInitialState,
+                CommittedPolynomial::RamFinalState,
+            ] {
+                if let Some(ram) = segment.ram_state(polynomial) {
+                    let (commitment, hint) = PCS::commit(
+                        &ram_state_polynomial(ram, trace.len()),
+                        &preprocessing.generators,
+                    );
+                    debug_assert!(
+                        segment.ram_commitment(polynomial) == Some(&commitment),
+                        "{polynomial:?} does not match the segment's claim"
+                    );
+                    hint_map.insert(polynomial, hint);
+                }
+            }
+        }
 
-        drop_in_background_thread(committed_polys);
+        prover_state_manager.set_commitments(commitments);
 
         Ok(hint_map)
     }
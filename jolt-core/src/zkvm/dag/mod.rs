@@ -22,7 +22,12 @@ mod tests {
         let mut program = host::Program::new("fibonacci-guest");
         let (bytecode, init_memory_state, _) = program.decode();
         let inputs = postcard::to_stdvec(&9u8).unwrap();
-        let (mut trace, final_memory_state, mut program_io) = program.trace(&inputs);
+        let tracer::TraceOutput {
+            mut trace,
+            final_memory_state,
+            io_device: mut program_io,
+            ..
+        } = program.trace(&inputs);
         trace.truncate(100);
         program_io.outputs[0] = 0; // change the output to 0
 
@@ -56,8 +61,8 @@ mod tests {
 
         let verifier_preprocessing =
             JoltVerifierPreprocessing::<Fr, DoryCommitmentScheme>::from(&preprocessing);
-        let _verification_result =
-            JoltRV32IM::verify(&verifier_preprocessing, proof, program_io, None);
+        JoltRV32IM::verify(&verifier_preprocessing, proof, program_io, None)
+            .expect("Verification failed");
     }
 
     #[test]
@@ -67,7 +72,12 @@ mod tests {
         let mut program = host::Program::new("fibonacci-guest");
         let inputs = postcard::to_stdvec(&1u8).unwrap();
         let (bytecode, init_memory_state, _) = program.decode();
-        let (mut trace, final_memory_state, mut program_io) = program.trace(&inputs);
+        let tracer::TraceOutput {
+            mut trace,
+            final_memory_state,
+            io_device: mut program_io,
+            ..
+        } = program.trace(&inputs);
 
         // Since the preprocessing is done with the original memory layout, the verifier should fail
         let preprocessing = JoltRV32IM::prover_preprocess(
@@ -106,7 +116,7 @@ mod tests {
 
         let verifier_preprocessing =
             JoltVerifierPreprocessing::<Fr, DoryCommitmentScheme>::from(&preprocessing);
-        let _verification_result =
-            JoltRV32IM::verify(&verifier_preprocessing, proof, program_io, None);
+        JoltRV32IM::verify(&verifier_preprocessing, proof, program_io, None)
+            .expect("Verification failed");
     }
 }
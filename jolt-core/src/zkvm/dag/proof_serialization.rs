@@ -8,7 +8,9 @@ use std::{
 use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
 };
+use common::constants::REGISTER_COUNT;
 use num::FromPrimitive;
+use sha3::{Digest, Keccak256};
 use tracer::JoltDevice;
 
 use crate::zkvm::witness::AllCommittedPolynomials;
@@ -22,9 +24,10 @@ use crate::{
         },
     },
     subprotocols::sumcheck::SumcheckInstanceProof,
-    utils::transcript::Transcript,
+    utils::{errors::ProofFormatError, transcript::Transcript},
     zkvm::{
         dag::state_manager::{ProofData, ProofKeys, Proofs, StateManager, VerifierState},
+        fault::FaultingAccess,
         witness::{CommittedPolynomial, VirtualPolynomial},
         JoltVerifierPreprocessing,
     },
@@ -35,9 +38,165 @@ pub struct JoltProof<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcr
     commitments: Vec<PCS::Commitment>,
     proofs: Proofs<F, PCS, FS>,
     pub trace_length: usize,
-    ram_K: usize,
+    pub ram_K: usize,
     bytecode_d: usize,
     twist_sumcheck_switch_index: usize,
+    /// The access at which the guest was stopped, if it faulted (see `zkvm::fault`)
+    pub fault: Option<FaultingAccess>,
+}
+
+/// Magic bytes at the start of every serialized [`JoltProof`].
+pub const PROOF_MAGIC: [u8; 4] = *b"JOLT";
+/// Bumped whenever the serialized layout of [`JoltProof`] changes, together with
+/// regenerating the `jolt-verifier` test fixtures (see `update-test-fixtures.sh`).
+pub const PROOF_FORMAT_VERSION: u32 = 5;
+
+/// Upper bound on the length of the protocol names in a [`ProofHeader`]. The length prefix
+/// is checked against it before allocating, so a crafted header cannot exhaust memory.
+pub const MAX_PROTOCOL_NAME_LEN: u64 = 256;
+
+/// Self-describing envelope preceding the serialized proof payload. It identifies the
+/// format version and the field, register file size, commitment scheme, and transcript the
+/// proof was produced with, so that incompatible proofs are rejected before the payload is
+/// decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofHeader {
+    pub version: u32,
+    /// Hash of the canonical serialization of -1, which identifies the field modulus
+    pub field_id: [u8; 32],
+    /// Number of registers addressed by the prover, which depends on the `float` feature
+    pub register_count: u8,
+    pub commitment_scheme: Vec<u8>,
+    pub transcript: Vec<u8>,
+    pub compressed: bool,
+    pub payload_len: u64,
+    /// Keccak256 hash of the payload
+    pub checksum: [u8; 32],
+}
+
+impl ProofHeader {
+    pub fn new<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript>(
+        compress: Compress,
+        payload: &[u8],
+    ) -> Self {
+        Self {
+            version: PROOF_FORMAT_VERSION,
+            field_id: Self::field_id::<F>(),
+            register_count: REGISTER_COUNT,
+            commitment_scheme: PCS::protocol_name().to_vec(),
+            transcript: FS::protocol_name().to_vec(),
+            compressed: compress == Compress::Yes,
+            payload_len: payload.len() as u64,
+            checksum: Keccak256::digest(payload).into(),
+        }
+    }
+
+    fn field_id<F: JoltField>() -> [u8; 32] {
+        let mut bytes = Vec::new();
+        (-F::one()).serialize_compressed(&mut bytes).unwrap();
+        Keccak256::digest(&bytes).into()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        writer.write_all(&PROOF_MAGIC)?;
+        self.version.serialize_uncompressed(&mut writer)?;
+        self.field_id.serialize_uncompressed(&mut writer)?;
+        self.register_count.serialize_uncompressed(&mut writer)?;
+        self.commitment_scheme.serialize_uncompressed(&mut writer)?;
+        self.transcript.serialize_uncompressed(&mut writer)?;
+        self.compressed.serialize_uncompressed(&mut writer)?;
+        self.payload_len.serialize_uncompressed(&mut writer)?;
+        self.checksum.serialize_uncompressed(&mut writer)?;
+        Ok(())
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, ProofFormatError> {
+        let mut magic = [0u8; 4];
+        reader
+            .read_exact(&mut magic)
+            .map_err(SerializationError::from)?;
+        if magic != PROOF_MAGIC {
+            return Err(ProofFormatError::BadMagic(magic));
+        }
+        let version = u32::deserialize_uncompressed(&mut reader)?;
+        if version != PROOF_FORMAT_VERSION {
+            return Err(ProofFormatError::UnsupportedVersion {
+                found: version,
+                expected: PROOF_FORMAT_VERSION,
+            });
+        }
+        Ok(Self {
+            version,
+            field_id: <[u8; 32]>::deserialize_uncompressed(&mut reader)?,
+            register_count: u8::deserialize_uncompressed(&mut reader)?,
+            commitment_scheme: Self::read_protocol_name(&mut reader)?,
+            transcript: Self::read_protocol_name(&mut reader)?,
+            compressed: bool::deserialize_uncompressed(&mut reader)?,
+            payload_len: u64::deserialize_uncompressed(&mut reader)?,
+            checksum: <[u8; 32]>::deserialize_uncompressed(&mut reader)?,
+        })
+    }
+
+    /// Reads a length-prefixed protocol name, in the layout written by `Vec<u8>`'s
+    /// `CanonicalSerialize` impl.
+    fn read_protocol_name<R: Read>(mut reader: R) -> Result<Vec<u8>, ProofFormatError> {
+        let len = u64::deserialize_uncompressed(&mut reader)?;
+        if len > MAX_PROTOCOL_NAME_LEN {
+            return Err(ProofFormatError::ProtocolNameTooLong(len));
+        }
+        let mut name = vec![0u8; len as usize];
+        reader
+            .read_exact(&mut name)
+            .map_err(SerializationError::from)?;
+        Ok(name)
+    }
+
+    /// Checks that a proof with this header can be decoded as a `JoltProof<F, PCS, FS>`.
+    pub fn check<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript>(
+        &self,
+        compress: Compress,
+    ) -> Result<(), ProofFormatError> {
+        if self.field_id != Self::field_id::<F>() {
+            return Err(ProofFormatError::FieldMismatch);
+        }
+        if self.register_count != REGISTER_COUNT {
+            return Err(ProofFormatError::RegisterCountMismatch {
+                expected: REGISTER_COUNT,
+                found: self.register_count,
+            });
+        }
+        if self.commitment_scheme != PCS::protocol_name() {
+            return Err(ProofFormatError::CommitmentSchemeMismatch {
+                expected: String::from_utf8_lossy(PCS::protocol_name()).into_owned(),
+                found: String::from_utf8_lossy(&self.commitment_scheme).into_owned(),
+            });
+        }
+        if self.transcript != FS::protocol_name() {
+            return Err(ProofFormatError::TranscriptMismatch {
+                expected: String::from_utf8_lossy(FS::protocol_name()).into_owned(),
+                found: String::from_utf8_lossy(&self.transcript).into_owned(),
+            });
+        }
+        if self.compressed != (compress == Compress::Yes) {
+            return Err(ProofFormatError::CompressionMismatch {
+                found: self.compressed,
+                expected: compress == Compress::Yes,
+            });
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self) -> usize {
+        PROOF_MAGIC.len()
+            + self.version.uncompressed_size()
+            + self.field_id.uncompressed_size()
+            + self.register_count.uncompressed_size()
+            + self.commitment_scheme.uncompressed_size()
+            + self.transcript.uncompressed_size()
+            + self.compressed.uncompressed_size()
+            + self.payload_len.uncompressed_size()
+            + self.checksum.uncompressed_size()
+    }
 }
 
 impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalSerialize
@@ -47,12 +206,105 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalSe
         &self,
         mut writer: W,
         compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let mut payload = Vec::with_capacity(self.payload_size(compress));
+        self.serialize_payload(&mut payload, compress)?;
+        ProofHeader::new::<F, PCS, FS>(compress, &payload).write(&mut writer)?;
+        writer.write_all(&payload)?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        let payload_size = self.payload_size(compress);
+        let header = ProofHeader {
+            version: PROOF_FORMAT_VERSION,
+            field_id: [0; 32],
+            register_count: REGISTER_COUNT,
+            commitment_scheme: PCS::protocol_name().to_vec(),
+            transcript: FS::protocol_name().to_vec(),
+            compressed: compress == Compress::Yes,
+            payload_len: payload_size as u64,
+            checksum: [0; 32],
+        };
+        header.serialized_size() + payload_size
+    }
+}
+
+impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> Valid
+    for JoltProof<F, PCS, FS>
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.opening_claims.check()?;
+        self.commitments.check()?;
+        self.proofs.check()?;
+        self.trace_length.check()?;
+        self.ram_K.check()?;
+        self.bytecode_d.check()?;
+        self.twist_sumcheck_switch_index.check()?;
+        self.fault.check()?;
+        Ok(())
+    }
+}
+
+impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalDeserialize
+    for JoltProof<F, PCS, FS>
+{
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Self::deserialize_envelope(reader, compress, validate).map_err(|err| match err {
+            ProofFormatError::Serialization(err) => err,
+            err => SerializationError::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                err,
+            )),
+        })
+    }
+}
+
+impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> JoltProof<F, PCS, FS> {
+    /// Reads the envelope header, rejecting proofs for a different format version, field,
+    /// commitment scheme, or transcript before the payload is decoded.
+    pub fn deserialize_envelope<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, ProofFormatError> {
+        let header = ProofHeader::read(&mut reader)?;
+        header.check::<F, PCS, FS>(compress)?;
+
+        let mut payload = Vec::new();
+        (&mut reader)
+            .take(header.payload_len)
+            .read_to_end(&mut payload)
+            .map_err(SerializationError::from)?;
+        if payload.len() as u64 != header.payload_len {
+            return Err(ProofFormatError::TruncatedPayload {
+                expected: header.payload_len,
+                found: payload.len() as u64,
+            });
+        }
+        let checksum: [u8; 32] = Keccak256::digest(&payload).into();
+        if checksum != header.checksum {
+            return Err(ProofFormatError::ChecksumMismatch);
+        }
+
+        Ok(Self::deserialize_payload(&payload[..], compress, validate)?)
+    }
+
+    fn serialize_payload<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
     ) -> Result<(), SerializationError> {
         // serialize ram_K and bytecode_d first
         self.ram_K.serialize_with_mode(&mut writer, compress)?;
         self.bytecode_d.serialize_with_mode(&mut writer, compress)?;
         // ensure that all committed polys are set up before serializing proofs
-        let guard = AllCommittedPolynomials::initialize(self.ram_K, self.bytecode_d);
+        let guard =
+            AllCommittedPolynomials::initialize(self.ram_K, self.bytecode_d, true, true, true);
         self.opening_claims
             .serialize_with_mode(&mut writer, compress)?;
         self.commitments
@@ -62,11 +314,13 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalSe
             .serialize_with_mode(&mut writer, compress)?;
         self.twist_sumcheck_switch_index
             .serialize_with_mode(&mut writer, compress)?;
+        self.fault.serialize_with_mode(&mut writer, compress)?;
 
         drop(guard);
         Ok(())
     }
-    fn serialized_size(&self, compress: Compress) -> usize {
+
+    fn payload_size(&self, compress: Compress) -> usize {
         self.opening_claims.serialized_size(compress)
             + self.commitments.serialized_size(compress)
             + self.proofs.serialized_size(compress)
@@ -74,28 +328,10 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalSe
             + self.ram_K.serialized_size(compress)
             + self.bytecode_d.serialized_size(compress)
             + self.twist_sumcheck_switch_index.serialized_size(compress)
+            + self.fault.serialized_size(compress)
     }
-}
-
-impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> Valid
-    for JoltProof<F, PCS, FS>
-{
-    fn check(&self) -> Result<(), SerializationError> {
-        self.opening_claims.check()?;
-        self.commitments.check()?;
-        self.proofs.check()?;
-        self.trace_length.check()?;
-        self.ram_K.check()?;
-        self.bytecode_d.check()?;
-        self.twist_sumcheck_switch_index.check()?;
-        Ok(())
-    }
-}
 
-impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalDeserialize
-    for JoltProof<F, PCS, FS>
-{
-    fn deserialize_with_mode<R: Read>(
+    fn deserialize_payload<R: Read>(
         mut reader: R,
         compress: Compress,
         validate: Validate,
@@ -104,7 +340,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalDe
         let bytecode_d = usize::deserialize_with_mode(&mut reader, compress, validate)?;
 
         // ensure that all committed polys are set up before deserializing proofs
-        let guard = AllCommittedPolynomials::initialize(ram_K, bytecode_d);
+        let guard = AllCommittedPolynomials::initialize(ram_K, bytecode_d, true, true, true);
         let opening_claims = Claims::deserialize_with_mode(&mut reader, compress, validate)?;
         let commitments =
             Vec::<PCS::Commitment>::deserialize_with_mode(&mut reader, compress, validate)?;
@@ -112,6 +348,8 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalDe
         let trace_length = usize::deserialize_with_mode(&mut reader, compress, validate)?;
         let twist_sumcheck_switch_index =
             usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let fault =
+            Option::<FaultingAccess>::deserialize_with_mode(&mut reader, compress, validate)?;
         drop(guard);
 
         Ok(Self {
@@ -122,6 +360,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> CanonicalDe
             ram_K,
             bytecode_d,
             twist_sumcheck_switch_index,
+            fault,
         })
     }
 }
@@ -135,6 +374,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> JoltProof<F
         let trace_length = prover_state.trace.len();
         let ram_K = state_manager.ram_K;
         let twist_sumcheck_switch_index = state_manager.twist_sumcheck_switch_index;
+        let fault = state_manager.fault.take();
 
         Self {
             opening_claims: Claims(openings),
@@ -144,6 +384,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> JoltProof<F
             ram_K,
             bytecode_d: prover_state.preprocessing.shared.bytecode.d,
             twist_sumcheck_switch_index,
+            fault,
         }
     }
 
@@ -171,6 +412,8 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>, FS: Transcript> JoltProof<F
             program_io,
             ram_K: self.ram_K,
             twist_sumcheck_switch_index: self.twist_sumcheck_switch_index,
+            segment: None,
+            fault: self.fault,
             prover_state: None,
             verifier_state: Some(VerifierState {
                 preprocessing,
@@ -470,3 +713,122 @@ pub fn serialize_and_print_size(
     println!("{item_name} size: {file_size_kb:.1} kB");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::dory::DoryCommitmentScheme;
+    use crate::poly::commitment::mock::MockCommitScheme;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::Fr;
+
+    type DoryProof = JoltProof<Fr, DoryCommitmentScheme, KeccakTranscript>;
+
+    fn envelope<PCS: CommitmentScheme<Field = Fr>>(payload: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![];
+        ProofHeader::new::<Fr, PCS, KeccakTranscript>(Compress::Yes, payload)
+            .write(&mut bytes)
+            .unwrap();
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    #[test]
+    fn header_round_trip() {
+        let payload = [1u8, 2, 3];
+        let bytes = envelope::<DoryCommitmentScheme>(&payload);
+        let header = ProofHeader::read(&bytes[..]).unwrap();
+        assert_eq!(
+            header,
+            ProofHeader::new::<Fr, DoryCommitmentScheme, KeccakTranscript>(Compress::Yes, &payload)
+        );
+        assert!(header
+            .check::<Fr, DoryCommitmentScheme, KeccakTranscript>(Compress::Yes)
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut bytes = envelope::<DoryCommitmentScheme>(&[1, 2, 3]);
+        bytes[0] ^= 1;
+        let result = DoryProof::deserialize_envelope(&bytes[..], Compress::Yes, Validate::Yes);
+        assert!(matches!(result, Err(ProofFormatError::BadMagic(_))));
+    }
+
+    #[test]
+    fn rejects_huge_protocol_name_length() {
+        let mut bytes = PROOF_MAGIC.to_vec();
+        PROOF_FORMAT_VERSION
+            .serialize_uncompressed(&mut bytes)
+            .unwrap();
+        bytes.extend_from_slice(&ProofHeader::field_id::<Fr>());
+        bytes.push(REGISTER_COUNT);
+        // Length prefix of the commitment scheme name, with no bytes following it
+        u64::MAX.serialize_uncompressed(&mut bytes).unwrap();
+        let result = DoryProof::deserialize_envelope(&bytes[..], Compress::Yes, Validate::Yes);
+        assert!(matches!(
+            result,
+            Err(ProofFormatError::ProtocolNameTooLong(u64::MAX))
+        ));
+
+        // A length within the bound but past the end of the input is reported as truncated
+        let mut bytes = bytes[..bytes.len() - 8].to_vec();
+        MAX_PROTOCOL_NAME_LEN
+            .serialize_uncompressed(&mut bytes)
+            .unwrap();
+        let result = DoryProof::deserialize_envelope(&bytes[..], Compress::Yes, Validate::Yes);
+        assert!(matches!(result, Err(ProofFormatError::Serialization(_))));
+    }
+
+    #[test]
+    fn rejects_commitment_scheme_mismatch() {
+        let bytes = envelope::<MockCommitScheme<Fr>>(&[1, 2, 3]);
+        let result = DoryProof::deserialize_envelope(&bytes[..], Compress::Yes, Validate::Yes);
+        assert!(matches!(
+            result,
+            Err(ProofFormatError::CommitmentSchemeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_register_count_mismatch() {
+        // Proofs produced with and without the `float` feature are incompatible
+        let mut bytes = envelope::<DoryCommitmentScheme>(&[1, 2, 3]);
+        let offset = PROOF_MAGIC.len() + 4 + 32;
+        bytes[offset] = REGISTER_COUNT.wrapping_mul(2);
+        let result = DoryProof::deserialize_envelope(&bytes[..], Compress::Yes, Validate::Yes);
+        assert!(matches!(
+            result,
+            Err(ProofFormatError::RegisterCountMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_compression_mismatch() {
+        let bytes = envelope::<DoryCommitmentScheme>(&[1, 2, 3]);
+        let result = DoryProof::deserialize_envelope(&bytes[..], Compress::No, Validate::Yes);
+        assert!(matches!(
+            result,
+            Err(ProofFormatError::CompressionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_corrupted_payload() {
+        let mut bytes = envelope::<DoryCommitmentScheme>(&[1, 2, 3]);
+        *bytes.last_mut().unwrap() ^= 1;
+        let result = DoryProof::deserialize_envelope(&bytes[..], Compress::Yes, Validate::Yes);
+        assert!(matches!(result, Err(ProofFormatError::ChecksumMismatch)));
+
+        let bytes = envelope::<DoryCommitmentScheme>(&[1, 2, 3]);
+        let result = DoryProof::deserialize_envelope(
+            &bytes[..bytes.len() - 1],
+            Compress::Yes,
+            Validate::Yes,
+        );
+        assert!(matches!(
+            result,
+            Err(ProofFormatError::TruncatedPayload { .. })
+        ));
+    }
+}
@@ -9,10 +9,14 @@ use crate::poly::opening_proof::{
     VerifierOpeningAccumulator, BIG_ENDIAN,
 };
 use crate::subprotocols::sumcheck::SumcheckInstanceProof;
+use crate::utils::errors::ContinuationError;
 use crate::utils::math::Math;
 use crate::utils::transcript::Transcript;
+use crate::zkvm::continuations::{SegmentClaim, SegmentIO};
+use crate::zkvm::fault::FaultingAccess;
 use crate::zkvm::witness::{CommittedPolynomial, VirtualPolynomial};
-use crate::zkvm::{JoltProverPreprocessing, JoltVerifierPreprocessing};
+use crate::zkvm::{JoltProverPreprocessing, JoltSharedPreprocessing, JoltVerifierPreprocessing};
+use common::jolt_device::GuestFault;
 use num_derive::FromPrimitive;
 use rayon::prelude::*;
 use tracer::emulator::memory::Memory;
@@ -67,6 +71,11 @@ pub struct StateManager<
     pub ram_K: usize,
     pub twist_sumcheck_switch_index: usize,
     pub program_io: JoltDevice,
+    /// The boundary states of the segment being proven, if proving a single
+    /// segment of a continuation (see `zkvm::continuations`)
+    pub segment: Option<SegmentIO<PCS::Commitment>>,
+    /// The access at which the guest was stopped, if it faulted (see `zkvm::fault`)
+    pub fault: Option<FaultingAccess>,
     pub prover_state: Option<ProverState<'a, F, PCS>>,
     pub verifier_state: Option<VerifierState<'a, F, PCS>>,
 }
@@ -112,6 +121,7 @@ impl<'a, F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field
         let num_chunks = rayon::current_num_threads().next_power_of_two().min(T);
         let chunk_size = T / num_chunks;
         let twist_sumcheck_switch_index = chunk_size.log_2();
+        let fault = FaultingAccess::from_trace(&trace, &program_io);
 
         Self {
             transcript,
@@ -120,6 +130,8 @@ impl<'a, F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field
             program_io,
             ram_K,
             twist_sumcheck_switch_index,
+            segment: None,
+            fault,
             prover_state: Some(ProverState {
                 preprocessing,
                 trace,
@@ -153,6 +165,8 @@ impl<'a, F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field
             program_io,
             ram_K,
             twist_sumcheck_switch_index,
+            segment: None,
+            fault: None,
             prover_state: None,
             verifier_state: Some(VerifierState {
                 preprocessing,
@@ -162,6 +176,46 @@ impl<'a, F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field
         }
     }
 
+    /// Verifies a single segment of a continuation, with the boundary states in `claim`
+    /// as public input and output. The prover uses [`Self::with_segment_witness`].
+    pub fn with_segment(
+        mut self,
+        claim: SegmentClaim<PCS::Commitment>,
+    ) -> Result<Self, ContinuationError> {
+        let trace_length = if let Some(ref prover_state) = self.prover_state {
+            prover_state.trace.len()
+        } else if let Some(ref verifier_state) = self.verifier_state {
+            verifier_state.trace_length
+        } else {
+            panic!("Neither prover nor verifier state initialized");
+        };
+        self.segment = Some(SegmentIO::new(
+            claim,
+            &self.get_shared_preprocessing().bytecode,
+            trace_length.next_power_of_two(),
+            self.ram_K,
+        )?);
+        Ok(self)
+    }
+
+    /// Proves a single segment of a continuation, with the boundary states in `claim` as
+    /// public input and output. RAM starts in `initial_ram` and ends in `final_ram`, both
+    /// zero-padded to the RAM size used for all segments, which may exceed the RAM this
+    /// segment accesses.
+    pub fn with_segment_witness(
+        mut self,
+        claim: SegmentClaim<PCS::Commitment>,
+        initial_ram: Vec<u32>,
+        final_ram: Vec<u32>,
+    ) -> Result<Self, ContinuationError> {
+        assert!(initial_ram.len() >= self.ram_K && initial_ram.len().is_power_of_two());
+        assert_eq!(initial_ram.len(), final_ram.len());
+        self.ram_K = initial_ram.len();
+        let mut state_manager = self.with_segment(claim)?;
+        state_manager.segment.as_mut().unwrap().ram = Some((initial_ram, final_ram));
+        Ok(state_manager)
+    }
+
     pub fn get_prover_data(
         &self,
     ) -> (
@@ -194,6 +248,16 @@ impl<'a, F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field
         }
     }
 
+    pub fn get_shared_preprocessing(&self) -> &'a JoltSharedPreprocessing {
+        if let Some(ref verifier_state) = self.verifier_state {
+            &verifier_state.preprocessing.shared
+        } else if let Some(ref prover_state) = self.prover_state {
+            &prover_state.preprocessing.shared
+        } else {
+            panic!("Neither prover nor verifier state initialized");
+        }
+    }
+
     pub fn get_bytecode(&self) -> &[RV32IMInstruction] {
         if let Some(ref verifier_state) = self.verifier_state {
             &verifier_state.preprocessing.shared.bytecode.bytecode
@@ -276,12 +340,21 @@ impl<'a, F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field
 
     pub fn fiat_shamir_preamble(&mut self) {
         let transcript = self.get_transcript();
+        transcript
+            .borrow_mut()
+            .append_bytes(&self.get_shared_preprocessing().image_id());
+        transcript
+            .borrow_mut()
+            .append_u64(self.get_shared_preprocessing().register_count as u64);
         transcript
             .borrow_mut()
             .append_u64(self.program_io.memory_layout.max_input_size);
         transcript
             .borrow_mut()
             .append_u64(self.program_io.memory_layout.max_output_size);
+        transcript
+            .borrow_mut()
+            .append_u64(self.program_io.memory_layout.max_private_input_size);
         transcript
             .borrow_mut()
             .append_u64(self.program_io.memory_layout.memory_size);
@@ -294,7 +367,16 @@ impl<'a, F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field
         transcript
             .borrow_mut()
             .append_u64(self.program_io.panic as u64);
+        transcript
+            .borrow_mut()
+            .append_u64(GuestFault::code(self.program_io.fault) as u64);
         transcript.borrow_mut().append_u64(self.ram_K as u64);
+        if let Some(ref segment) = self.segment {
+            transcript.borrow_mut().append_serializable(&segment.claim);
+        }
+        if let Some(ref fault) = self.fault {
+            transcript.borrow_mut().append_serializable(fault);
+        }
 
         if let Some(ref verifier_state) = self.verifier_state {
             transcript
@@ -0,0 +1,108 @@
+//! A guest that overflows its stack or heap is stopped at the faulting load or store
+//! (see `GuestFault`), which is the last cycle of its trace. That cycle is described
+//! by a public [`FaultingAccess`], from which the verifier derives the fault itself:
+//! the R1CS constraints bind the descriptor to the trace, so that the faulting cycle
+//! must be a load or store of the described address, and must be followed by no-ops.
+//! The faulting access is not performed, i.e. its RAM address is 0, and the PC update
+//! constraints do not apply to it.
+//!
+//! This proves that the guest made the faulting access, not that it is the first
+//! access which would have faulted: the RAM checking does not stop the guest from
+//! writing to the stack canary before.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use tracer::{instruction::RV32IMCycle, JoltDevice};
+
+use crate::field::JoltField;
+use crate::poly::eq_poly::EqPolynomial;
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::index_to_field_bitvector;
+use crate::zkvm::instruction::{CircuitFlags, InstructionFlags};
+use crate::zkvm::r1cs::inputs::JoltR1CSInputs;
+
+/// The R1CS inputs describing the faulting access. They are public: each is zero
+/// except at the faulting cycle, where it takes the value given by
+/// [`FaultingAccess::r1cs_input_values`].
+pub const FAULT_R1CS_INPUTS: [JoltR1CSInputs; 4] = [
+    JoltR1CSInputs::Fault,
+    JoltR1CSInputs::StoreFault,
+    JoltR1CSInputs::InlineFault,
+    JoltR1CSInputs::FaultAddress,
+];
+
+/// The load or store at which a faulted guest was stopped.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FaultingAccess {
+    /// Index of the faulting cycle in the trace
+    pub cycle: usize,
+    /// The accessed address, i.e. `rs1 + imm`
+    pub address: u64,
+    /// Whether the access is a store
+    pub is_store: bool,
+    /// Whether the access is part of an inline sequence (e.g. that of `SB`)
+    pub inline: bool,
+}
+
+impl FaultingAccess {
+    /// Describes the last cycle of a faulted trace, which is the faulting access.
+    /// Returns `None` if the guest did not fault.
+    pub fn from_trace(trace: &[RV32IMCycle], program_io: &JoltDevice) -> Option<Self> {
+        program_io.fault?;
+        let cycle = trace
+            .iter()
+            .rposition(|cycle| !matches!(cycle, RV32IMCycle::NoOp))?;
+        let instr = trace[cycle].instruction();
+        Some(Self {
+            cycle,
+            address: (trace[cycle].rs1_read().1 as i64 + instr.normalize().operands.imm) as u64,
+            is_store: matches!(trace[cycle], RV32IMCycle::SW(_)),
+            inline: instr.circuit_flags()[CircuitFlags::InlineSequenceInstruction],
+        })
+    }
+
+    /// The values of [`FAULT_R1CS_INPUTS`] at the faulting cycle.
+    pub fn r1cs_input_values(&self) -> [u64; 4] {
+        [1, self.is_store as u64, self.inline as u64, self.address]
+    }
+
+    /// Evaluates the MLEs of [`FAULT_R1CS_INPUTS`] at `r_cycle`.
+    pub fn evaluate_r1cs_inputs<F: JoltField>(fault: Option<&Self>, r_cycle: &[F]) -> [F; 4] {
+        match fault {
+            Some(fault) => {
+                let cycle = index_to_field_bitvector(fault.cycle as u64, r_cycle.len());
+                let eq_cycle = EqPolynomial::mle(r_cycle, &cycle);
+                fault
+                    .r1cs_input_values()
+                    .map(|value| eq_cycle * F::from_u64(value))
+            }
+            None => [F::zero(); 4],
+        }
+    }
+
+    /// Checks that `fault` describes an access at one of the `trace_length` cycles which
+    /// causes the fault claimed by `program_io`, and that there is one iff the guest
+    /// faulted.
+    pub fn check(
+        fault: Option<&Self>,
+        program_io: &JoltDevice,
+        trace_length: usize,
+    ) -> Result<(), ProofVerifyError> {
+        let matches = match fault {
+            Some(fault) => {
+                fault.cycle < trace_length
+                    && fault.address <= u32::MAX as u64
+                    && program_io.fault.is_some()
+                    && program_io
+                        .memory_layout
+                        .fault(fault.address, fault.is_store)
+                        == program_io.fault
+            }
+            None => program_io.fault.is_none(),
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(ProofVerifyError::FaultMismatch)
+        }
+    }
+}
@@ -4,7 +4,7 @@ use strum::EnumCount;
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 use tracer::instruction::{RV32IMCycle, RV32IMInstruction};
 
-use crate::utils::interleave_bits;
+use crate::utils::{interleave_bits, interleave_bits_u128};
 
 use super::lookup_table::LookupTables;
 
@@ -27,9 +27,13 @@ pub trait LookupQuery<const WORD_SIZE: usize> {
 
     /// Converts this instruction's operands into a lookup index (as used in sparse-dense Shout).
     /// By default, interleaves the two bits of the two operands together.
-    fn to_lookup_index(&self) -> u64 {
+    /// Lookup indices are `2 * WORD_SIZE` bits wide, so 64-bit instructions need a `u128`.
+    fn to_lookup_index(&self) -> u128 {
         let (x, y) = LookupQuery::<WORD_SIZE>::to_lookup_operands(self);
-        interleave_bits(x as u32, y as u32)
+        match WORD_SIZE {
+            64 => interleave_bits_u128(x, y),
+            _ => interleave_bits(x as u32, y as u32) as u128,
+        }
     }
 
     /// Computes the output lookup entry for this instruction as a u64.
@@ -75,6 +79,9 @@ pub enum CircuitFlags {
     Advice,
     /// Is noop instruction
     IsNoop,
+    /// 1 if the instruction was decoded from a 16-bit compressed encoding and is the
+    /// instruction (in its virtual sequence, if any) that advances the PC by 2 instead of 4.
+    IsCompressed,
 }
 
 pub const NUM_CIRCUIT_FLAGS: usize = CircuitFlags::COUNT;
@@ -136,7 +143,12 @@ macro_rules! define_rv32im_trait_impls {
                         flags
                     },
                     $(
-                        RV32IMInstruction::$instr(instr) => instr.circuit_flags(),
+                        RV32IMInstruction::$instr(instr) => {
+                            let mut flags = instr.circuit_flags();
+                            flags[CircuitFlags::IsCompressed] = instr.is_compressed
+                                && !flags[CircuitFlags::DoNotUpdateUnexpandedPC];
+                            flags
+                        },
                     )*
                     RV32IMInstruction::UNIMPL => [false; NUM_CIRCUIT_FLAGS],
                     _ => panic!("Unexpected instruction: {:?}", self),
@@ -167,7 +179,7 @@ macro_rules! define_rv32im_trait_impls {
                 }
             }
 
-            fn to_lookup_index(&self) -> u64 {
+            fn to_lookup_index(&self) -> u128 {
                 match self {
                     RV32IMCycle::NoOp => 0,
                     $(
@@ -31,8 +31,9 @@ impl<const WORD_SIZE: usize> LookupQuery<WORD_SIZE> for RISCVCycle<MUL> {
         (0, x * y as u64)
     }
 
-    fn to_lookup_index(&self) -> u64 {
-        LookupQuery::<WORD_SIZE>::to_lookup_operands(self).1
+    fn to_lookup_index(&self) -> u128 {
+        let (x, y) = LookupQuery::<WORD_SIZE>::to_instruction_inputs(self);
+        x as u128 * y as u64 as u128
     }
 
     fn to_instruction_inputs(&self) -> (u64, i64) {
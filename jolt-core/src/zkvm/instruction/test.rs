@@ -16,7 +16,7 @@ where
         let random_cycle = cycle.random(&mut rng);
         assert_eq!(
             random_cycle.to_lookup_output(),
-            table.materialize_entry(random_cycle.to_lookup_index()),
+            table.materialize_entry(random_cycle.to_lookup_index() as u64),
             "{:?}",
             random_cycle.register_state
         );
@@ -38,8 +38,8 @@ impl<const WORD_SIZE: usize> LookupQuery<WORD_SIZE> for RISCVCycle<VirtualAdvice
         }
     }
 
-    fn to_lookup_index(&self) -> u64 {
-        LookupQuery::<WORD_SIZE>::to_lookup_operands(self).1
+    fn to_lookup_index(&self) -> u128 {
+        LookupQuery::<WORD_SIZE>::to_lookup_operands(self).1 as u128
     }
 
     fn to_lookup_output(&self) -> u64 {
@@ -41,12 +41,12 @@ impl<const WORD_SIZE: usize> LookupQuery<WORD_SIZE> for RISCVCycle<VirtualShiftR
         (0, x + y as u64)
     }
 
-    fn to_lookup_index(&self) -> u64 {
-        LookupQuery::<WORD_SIZE>::to_lookup_operands(self).1
+    fn to_lookup_index(&self) -> u128 {
+        LookupQuery::<WORD_SIZE>::to_lookup_operands(self).1 as u128
     }
 
     fn to_lookup_output(&self) -> u64 {
-        let y = LookupQuery::<WORD_SIZE>::to_lookup_index(self);
+        let y = LookupQuery::<WORD_SIZE>::to_lookup_index(self) as u64;
         match WORD_SIZE {
             #[cfg(test)]
             8 => {
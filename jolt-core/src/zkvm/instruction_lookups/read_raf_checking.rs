@@ -176,7 +176,11 @@ impl<'a, F: JoltField> ReadRafProverState<F> {
         // TODO: This was probably already calculated in Spartan, maybe we should just get it.
         let lookup_indices: Vec<_> = trace
             .par_iter()
-            .map(|cycle| LookupBits::new(LookupQuery::<WORD_SIZE>::to_lookup_index(cycle), LOG_K))
+            .map(|cycle| {
+                let lookup_index = u64::try_from(LookupQuery::<WORD_SIZE>::to_lookup_index(cycle))
+                    .expect("lookup index exceeds LOG_K bits");
+                LookupBits::new(lookup_index, LOG_K)
+            })
             .collect();
         let lookup_indices_by_table: Vec<_> = LookupTables::<WORD_SIZE>::iter()
             .collect::<Vec<_>>()
@@ -853,15 +857,17 @@ mod tests {
         let bytecode = vec![RV32IMInstruction::NoOp];
         let bytecode_preprocessing = BytecodePreprocessing::preprocess(bytecode);
         let memory_layout = MemoryLayout::default();
-        let shared_preprocessing = JoltSharedPreprocessing {
-            bytecode: bytecode_preprocessing,
-            ram: RAMPreprocessing::preprocess(vec![]),
-            memory_layout: memory_layout.clone(),
-        };
+        let ram_preprocessing = RAMPreprocessing::preprocess(vec![]);
+        let shared_preprocessing = JoltSharedPreprocessing::new(
+            bytecode_preprocessing,
+            ram_preprocessing,
+            memory_layout.clone(),
+        );
         let prover_preprocessing: JoltProverPreprocessing<Fr, MockCommitScheme<Fr>> =
             JoltProverPreprocessing {
                 generators: (),
                 shared: shared_preprocessing.clone(),
+                max_trace_length: T,
                 field: Default::default(),
             };
 
@@ -873,8 +879,10 @@ mod tests {
         let program_io = JoltDevice {
             memory_layout,
             inputs: vec![],
+            private_inputs: vec![],
             outputs: vec![],
             panic: false,
+            fault: None,
         };
         let final_memory_state = Memory::default();
 
@@ -901,7 +909,8 @@ mod tests {
         let mut right_operand_claim = Fr::zero();
 
         for (i, cycle) in trace.iter().enumerate() {
-            let lookup_index = LookupQuery::<WORD_SIZE>::to_lookup_index(cycle);
+            let lookup_index = u64::try_from(LookupQuery::<WORD_SIZE>::to_lookup_index(cycle))
+                .expect("lookup index exceeds LOG_K bits");
             let table: Option<LookupTables<WORD_SIZE>> = cycle.lookup_table();
             if let Some(table) = table {
                 rv_claim += eq_r_cycle[i].mul_u64(table.materialize_entry(lookup_index));
@@ -8,39 +8,159 @@ use std::{
 use crate::host::Program;
 #[cfg(test)]
 use crate::poly::commitment::dory::DoryGlobals;
+#[cfg(feature = "prover")]
+use crate::utils::errors::JoltProverError;
 use crate::{
     field::JoltField,
     poly::{
-        commitment::commitment_scheme::CommitmentScheme, opening_proof::ProverOpeningAccumulator,
+        commitment::commitment_scheme::{
+            BatchOpening, BatchVerification, CommitmentScheme, StreamingCommitmentScheme,
+        },
+        opening_proof::ProverOpeningAccumulator,
+    },
+    utils::{
+        errors::{ContinuationError, ProofVerifyError},
+        math::Math,
+        transcript::Transcript,
     },
-    utils::{errors::ProofVerifyError, math::Math, transcript::Transcript},
     zkvm::{
         bytecode::BytecodePreprocessing,
+        continuations::SegmentClaim,
         dag::{jolt_dag::JoltDAG, proof_serialization::JoltProof},
+        fault::FaultingAccess,
         ram::RAMPreprocessing,
         witness::DTH_ROOT_OF_K,
     },
 };
-use ark_bn254::Fr;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_bn254::{Bn254, Fr};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use common::constants::REGISTER_COUNT;
 use common::jolt_device::MemoryLayout;
+use sha3::{Digest, Keccak256};
+#[cfg(feature = "prover")]
+use tracer::GuestInputs;
 use tracer::{instruction::RV32IMInstruction, JoltDevice};
 
 pub mod bytecode;
+pub mod continuations;
 pub mod dag;
+pub mod fault;
 pub mod instruction;
 pub mod instruction_lookups;
 pub mod lookup_table;
 pub mod r1cs;
 pub mod ram;
 pub mod registers;
+pub mod solidity;
 pub mod witness;
 
-#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Debug, Clone)]
 pub struct JoltSharedPreprocessing {
     pub bytecode: BytecodePreprocessing,
     pub ram: RAMPreprocessing,
     pub memory_layout: MemoryLayout,
+    /// Size of the register file, `REGISTER_COUNT`. It depends on the `float` feature,
+    /// so it is recorded here and absorbed into the Fiat-Shamir transcript, and
+    /// preprocessing from a build with a different register file is rejected.
+    pub register_count: u8,
+    /// Digest of the bytecode and initial RAM state, i.e. the program's "image ID".
+    /// Absorbed into the Fiat-Shamir transcript so that a proof is bound to the program.
+    /// Never serialized: it is always recomputed from `bytecode` and `ram`.
+    program_digest: [u8; 32],
+}
+
+impl JoltSharedPreprocessing {
+    pub fn new(
+        bytecode: BytecodePreprocessing,
+        ram: RAMPreprocessing,
+        memory_layout: MemoryLayout,
+    ) -> Self {
+        let program_digest = Self::compute_program_digest(&bytecode, &ram);
+        Self {
+            bytecode,
+            ram,
+            memory_layout,
+            register_count: REGISTER_COUNT,
+            program_digest,
+        }
+    }
+
+    /// Computes the canonical program digest: the Keccak256 hash of the (compressed)
+    /// canonical serializations of the bytecode and RAM preprocessing.
+    pub fn compute_program_digest(
+        bytecode: &BytecodePreprocessing,
+        ram: &RAMPreprocessing,
+    ) -> [u8; 32] {
+        let mut bytes = Vec::new();
+        bytecode.serialize_compressed(&mut bytes).unwrap();
+        ram.serialize_compressed(&mut bytes).unwrap();
+        Keccak256::new_with_prefix(b"JoltProgramDigest")
+            .chain_update(&bytes)
+            .finalize()
+            .into()
+    }
+
+    /// The public identifier of the guest program this preprocessing was computed for.
+    pub fn image_id(&self) -> [u8; 32] {
+        self.program_digest
+    }
+}
+
+impl CanonicalSerialize for JoltSharedPreprocessing {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.bytecode.serialize_with_mode(&mut writer, compress)?;
+        self.ram.serialize_with_mode(&mut writer, compress)?;
+        self.memory_layout
+            .serialize_with_mode(&mut writer, compress)?;
+        self.register_count
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.bytecode.serialized_size(compress)
+            + self.ram.serialized_size(compress)
+            + self.memory_layout.serialized_size(compress)
+            + self.register_count.serialized_size(compress)
+    }
+}
+
+impl Valid for JoltSharedPreprocessing {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.bytecode.check()?;
+        self.ram.check()?;
+        self.memory_layout.check()?;
+        if self.register_count != REGISTER_COUNT {
+            return Err(SerializationError::InvalidData);
+        }
+        if self.program_digest != Self::compute_program_digest(&self.bytecode, &self.ram) {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for JoltSharedPreprocessing {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let bytecode =
+            BytecodePreprocessing::deserialize_with_mode(&mut reader, compress, validate)?;
+        let ram = RAMPreprocessing::deserialize_with_mode(&mut reader, compress, validate)?;
+        let memory_layout = MemoryLayout::deserialize_with_mode(&mut reader, compress, validate)?;
+        let register_count = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        if register_count != REGISTER_COUNT {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(Self::new(bytecode, ram, memory_layout))
+    }
 }
 
 #[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
@@ -65,6 +185,10 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
+    pub fn image_id(&self) -> [u8; 32] {
+        self.shared.image_id()
+    }
+
     pub fn save_to_target_dir(&self, target_dir: &str) -> std::io::Result<()> {
         let filename = Path::new(target_dir).join("jolt_verifier_preprocessing.dat");
         let mut file = File::create(filename.as_path())?;
@@ -91,6 +215,7 @@ where
 {
     pub generators: PCS::ProverSetup,
     pub shared: JoltSharedPreprocessing,
+    pub max_trace_length: usize,
     field: F::SmallValueLookupTables,
 }
 
@@ -153,7 +278,7 @@ where
 pub trait Jolt<F, PCS, FS: Transcript>
 where
     F: JoltField,
-    PCS: CommitmentScheme<Field = F>,
+    PCS: BatchOpening<Field = F> + StreamingCommitmentScheme,
 {
     fn shared_preprocess(
         bytecode: Vec<RV32IMInstruction>,
@@ -163,11 +288,7 @@ where
         let bytecode_preprocessing = BytecodePreprocessing::preprocess(bytecode);
         let ram_preprocessing = RAMPreprocessing::preprocess(memory_init);
 
-        JoltSharedPreprocessing {
-            memory_layout,
-            bytecode: bytecode_preprocessing,
-            ram: ram_preprocessing,
-        }
+        JoltSharedPreprocessing::new(bytecode_preprocessing, ram_preprocessing, memory_layout)
     }
 
     #[tracing::instrument(skip_all, name = "Jolt::prover_preprocess")]
@@ -189,6 +310,7 @@ where
         JoltProverPreprocessing {
             generators,
             shared,
+            max_trace_length,
             field: small_value_lookup_tables,
         }
     }
@@ -199,16 +321,116 @@ where
         preprocessing: &JoltProverPreprocessing<F, PCS>,
         program: &mut Program,
         inputs: &[u8],
-    ) -> (
-        JoltProof<F, PCS, FS>,
-        JoltDevice,
-        Option<ProverDebugInfo<F, FS, PCS>>,
-    ) {
+    ) -> Result<
+        (
+            JoltProof<F, PCS, FS>,
+            JoltDevice,
+            Option<ProverDebugInfo<F, FS, PCS>>,
+        ),
+        JoltProverError,
+    > {
+        Self::prove_with_advice(preprocessing, program, inputs, &[])
+    }
+
+    /// Proves an execution of `program` in which the guest may read from the
+    /// untrusted `advice` tape (see [`crate::host::advice::AdviceTape`]).
+    /// The advice tape is not part of the returned `JoltDevice`, so the
+    /// verifier never sees it.
+    #[allow(clippy::type_complexity)]
+    #[cfg(feature = "prover")]
+    fn prove_with_advice(
+        preprocessing: &JoltProverPreprocessing<F, PCS>,
+        program: &mut Program,
+        inputs: &[u8],
+        advice: &[u8],
+    ) -> Result<
+        (
+            JoltProof<F, PCS, FS>,
+            JoltDevice,
+            Option<ProverDebugInfo<F, FS, PCS>>,
+        ),
+        JoltProverError,
+    > {
+        Self::prove_with_witness(
+            preprocessing,
+            program,
+            GuestInputs {
+                inputs,
+                advice,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Proves an execution of `program` with public inputs, private inputs and
+    /// advice. The private inputs are part of the guest's initial memory (see
+    /// `MemoryLayout::private_input_start`) and are committed to rather than
+    /// revealed: they are cleared from the returned `JoltDevice`.
+    ///
+    /// The whole trace is held in memory, since the sumcheck stages need random
+    /// access to it; only the committed witnesses are streamed (see
+    /// [`witness::CommittedPolynomial::commit_streaming`]). To bound the prover's
+    /// memory, prove the execution in segments with [`Jolt::prove_segments`].
+    #[allow(clippy::type_complexity)]
+    #[cfg(feature = "prover")]
+    fn prove_with_witness(
+        preprocessing: &JoltProverPreprocessing<F, PCS>,
+        program: &mut Program,
+        guest_inputs: GuestInputs,
+    ) -> Result<
+        (
+            JoltProof<F, PCS, FS>,
+            JoltDevice,
+            Option<ProverDebugInfo<F, FS, PCS>>,
+        ),
+        JoltProverError,
+    > {
         use crate::zkvm::dag::state_manager::StateManager;
         use rayon::prelude::*;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
         use tracer::instruction::RV32IMCycle;
+        use tracer::TraceOutput;
+
+        let max_input_size = preprocessing.shared.memory_layout.max_input_size as usize;
+        if guest_inputs.inputs.len() > max_input_size {
+            return Err(JoltProverError::InputTooLarge {
+                input_size: guest_inputs.inputs.len(),
+                max_input_size,
+            });
+        }
+        let max_private_input_size =
+            preprocessing.shared.memory_layout.max_private_input_size as usize;
+        if guest_inputs.private_inputs.len() > max_private_input_size {
+            return Err(JoltProverError::PrivateInputTooLarge {
+                input_size: guest_inputs.private_inputs.len(),
+                max_private_input_size,
+            });
+        }
+
+        program.try_build_in_target_dir()?;
+        // The emulator panics if the guest faults (e.g. an out-of-bounds memory access),
+        // so surface that as an error rather than unwinding through the caller.
+        let TraceOutput {
+            mut trace,
+            final_memory_state,
+            io_device: mut program_io,
+            advice_exhausted,
+            ..
+        } = catch_unwind(AssertUnwindSafe(|| {
+            program.trace_with_witness(guest_inputs)
+        }))
+        .map_err(|payload| {
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_else(|| "emulator aborted".to_string());
+            JoltProverError::GuestPanic(message)
+        })?;
+        if advice_exhausted {
+            return Err(JoltProverError::AdviceExhausted);
+        }
 
-        let (mut trace, final_memory_state, mut program_io) = program.trace(inputs);
         let num_riscv_cycles: usize = trace
             .par_iter()
             .map(|cycle| {
@@ -231,8 +453,18 @@ where
             trace.len(),
         );
 
-        // Setup trace length and padding
-        let padded_trace_length = (trace.len() + 1).next_power_of_two();
+        // Setup trace length and padding. The trace must be at least as long as the
+        // private input region, whose witness is padded to the trace length.
+        let padded_trace_length = (trace.len() + 1)
+            .next_power_of_two()
+            .max(max_private_input_size / 4);
+        let max_trace_length = preprocessing.max_trace_length.next_power_of_two();
+        if padded_trace_length > max_trace_length {
+            return Err(JoltProverError::TraceTooLong {
+                trace_length: padded_trace_length,
+                max_trace_length,
+            });
+        }
         trace.resize(padded_trace_length, RV32IMCycle::NoOp);
 
         // truncate trailing zeros on device outputs
@@ -246,9 +478,132 @@ where
 
         let state_manager =
             StateManager::new_prover(preprocessing, trace, program_io.clone(), final_memory_state);
-        let (proof, debug_info) = JoltDAG::prove(state_manager).ok().unwrap();
+        let (proof, debug_info) = JoltDAG::prove(state_manager)?;
+
+        // The private inputs must not be handed to the verifier
+        program_io.private_inputs.clear();
+
+        Ok((proof, program_io, debug_info))
+    }
+
+    /// Proves an execution of `program` as a continuation: execution is split into
+    /// segments of (at least) `segment_length` cycles, each of which is proven with
+    /// its boundary states as public input and output (see [`continuations`]).
+    /// Returns the proof and claim of each segment, along with the program I/O.
+    #[allow(clippy::type_complexity)]
+    #[cfg(feature = "prover")]
+    fn prove_segments(
+        preprocessing: &JoltProverPreprocessing<F, PCS>,
+        program: &mut Program,
+        inputs: &[u8],
+        segment_length: usize,
+    ) -> Result<
+        (
+            Vec<(JoltProof<F, PCS, FS>, SegmentClaim<PCS::Commitment>)>,
+            JoltDevice,
+        ),
+        JoltProverError,
+    > {
+        use crate::zkvm::dag::state_manager::StateManager;
+        use tracer::instruction::RV32IMCycle;
+
+        let max_input_size = preprocessing.shared.memory_layout.max_input_size as usize;
+        if inputs.len() > max_input_size {
+            return Err(JoltProverError::InputTooLarge {
+                input_size: inputs.len(),
+                max_input_size,
+            });
+        }
+
+        program.try_build_in_target_dir()?;
+        let (segments, final_checkpoint) = program.trace_segments(inputs, segment_length);
+        let (boundaries, num_words) =
+            continuations::segment_boundaries(&segments, &final_checkpoint);
+
+        let mut program_io = final_checkpoint.jolt_device().clone();
+        // A faulting access can only be proven as the last cycle of a single proof
+        if let Some(fault) = program_io.fault {
+            return Err(JoltProverError::GuestFault(fault));
+        }
+        // truncate trailing zeros on device outputs
+        program_io.outputs.truncate(
+            program_io
+                .outputs
+                .iter()
+                .rposition(|&b| b != 0)
+                .map_or(0, |pos| pos + 1),
+        );
+
+        // All segments are proven with the same RAM size and trace length, so that the
+        // commitments to the RAM states between them match up. The RAM states are padded
+        // to the trace length, and the last cycle of each segment must be followed by a
+        // no-op.
+        let memory_layout = &preprocessing.shared.memory_layout;
+        let bytecode_words =
+            ram::remap_address(preprocessing.shared.ram.min_bytecode_address, memory_layout)
+                .unwrap() as usize
+                + preprocessing.shared.ram.bytecode_words.len()
+                + 1;
+        let ram_K = boundaries
+            .iter()
+            .map(|boundary| boundary.ram.len())
+            .chain([num_words, bytecode_words])
+            .max()
+            .unwrap()
+            .next_power_of_two();
+        let max_segment_length = segments
+            .iter()
+            .map(|segment| segment.len)
+            .max()
+            .unwrap_or(0);
+        let padded_trace_length = (max_segment_length + 1)
+            .next_power_of_two()
+            .max(ram_K)
+            .max(memory_layout.max_private_input_size as usize / 4);
+        let max_trace_length = preprocessing.max_trace_length.next_power_of_two();
+        if padded_trace_length > max_trace_length {
+            return Err(JoltProverError::TraceTooLong {
+                trace_length: padded_trace_length,
+                max_trace_length,
+            });
+        }
+
+        let claims = {
+            let _guard = DoryGlobals::initialize(DTH_ROOT_OF_K, padded_trace_length);
+            continuations::segment_claims::<PCS>(
+                &boundaries,
+                padded_trace_length,
+                &preprocessing.generators,
+            )
+        };
+
+        let mut proofs = Vec::with_capacity(segments.len());
+        for (i, (segment, claim)) in segments.iter().zip(claims).enumerate() {
+            let mut trace: Vec<RV32IMCycle> = segment.trace().collect();
+            trace.resize(padded_trace_length, RV32IMCycle::NoOp);
+
+            // The final RAM state is taken from the segment's boundary
+            let final_memory_state = segments
+                .get(i + 1)
+                .map_or(&final_checkpoint, |next_segment| &next_segment.checkpoint)
+                .memory()
+                .clone();
+            let state_manager = StateManager::new_prover(
+                preprocessing,
+                trace,
+                program_io.clone(),
+                final_memory_state,
+            )
+            .with_segment_witness(
+                claim.clone(),
+                boundaries[i].padded_ram(ram_K),
+                boundaries[i + 1].padded_ram(ram_K),
+            )?;
+            let (proof, _) = JoltDAG::prove(state_manager)?;
+            proofs.push((proof, claim));
+        }
 
-        (proof, program_io, debug_info)
+        Ok((proofs, program_io))
     }
 
     fn verify(
@@ -264,25 +619,8 @@ where
         #[cfg(test)]
         let _guard = DoryGlobals::initialize(DTH_ROOT_OF_K, T);
 
-        // Memory layout checks
-        if program_io.memory_layout != preprocessing.shared.memory_layout {
-            return Err(ProofVerifyError::MemoryLayoutMismatch);
-        }
-        if program_io.inputs.len() > preprocessing.shared.memory_layout.max_input_size as usize {
-            return Err(ProofVerifyError::InputTooLarge);
-        }
-        if program_io.outputs.len() > preprocessing.shared.memory_layout.max_output_size as usize {
-            return Err(ProofVerifyError::OutputTooLarge);
-        }
-
-        // truncate trailing zeros on device outputs
-        program_io.outputs.truncate(
-            program_io
-                .outputs
-                .iter()
-                .rposition(|&b| b != 0)
-                .map_or(0, |pos| pos + 1),
-        );
+        check_program_io(preprocessing, &mut program_io)?;
+        FaultingAccess::check(proof.fault.as_ref(), &program_io, proof.trace_length)?;
 
         let state_manager = proof.to_verifier_state_manager(preprocessing, program_io);
 
@@ -298,18 +636,142 @@ where
             }
         }
 
-        JoltDAG::verify(state_manager).expect("Verification failed");
+        JoltDAG::verify(state_manager)?;
 
         Ok(())
     }
+
+    /// Verifies many proofs of the same program. Stages 1-4 are verified per proof, but
+    /// the final PCS opening checks are combined into one via
+    /// [`BatchVerification::batch_verify`], so that only the sumchecks are verified at a
+    /// cost linear in the number of proofs. Only available for commitment schemes that
+    /// support such a combined check: proofs meant to be batch verified must be made with
+    /// e.g. [`JoltRV32IMHyperKZG`]. Dory proofs ([`RV32IMJoltProof`]) cannot be, since the
+    /// `dory` crate only exposes the complete verification of a single opening.
+    fn verify_batch(
+        preprocessing: &JoltVerifierPreprocessing<F, PCS>,
+        proofs: Vec<(JoltProof<F, PCS, FS>, JoltDevice)>,
+    ) -> Result<(), ProofVerifyError>
+    where
+        PCS: BatchVerification,
+    {
+        let openings = proofs
+            .into_iter()
+            .enumerate()
+            .map(|(i, (proof, mut program_io))| {
+                #[cfg(test)]
+                let _guard =
+                    DoryGlobals::initialize(DTH_ROOT_OF_K, proof.trace_length.next_power_of_two());
+
+                check_program_io(preprocessing, &mut program_io)?;
+                FaultingAccess::check(proof.fault.as_ref(), &program_io, proof.trace_length)?;
+                let state_manager = proof.to_verifier_state_manager(preprocessing, program_io);
+                JoltDAG::verify_deferred(state_manager)
+                    .map_err(|err| ProofVerifyError::BatchedProofError(i, err.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        PCS::batch_verify(openings, &preprocessing.generators)
+    }
+
+    /// Verifies a continuation produced by [`Jolt::prove_segments`]: each segment proof
+    /// is verified against its claim, and the claims must chain from the program's
+    /// initial state (given its inputs). The last segment proof checks that the program
+    /// ends with the claimed outputs.
+    fn verify_segments(
+        preprocessing: &JoltVerifierPreprocessing<F, PCS>,
+        segments: Vec<(JoltProof<F, PCS, FS>, SegmentClaim<PCS::Commitment>)>,
+        mut program_io: JoltDevice,
+    ) -> Result<(), ProofVerifyError> {
+        check_program_io(preprocessing, &mut program_io)?;
+
+        let claims: Vec<_> = segments.iter().map(|(_, claim)| claim.clone()).collect();
+        continuations::verify_segment_chain(&claims, &program_io)?;
+        // The commitments to the RAM states between segments are only comparable if
+        // all segments have the same RAM size and trace length
+        let (first_proof, _) = &segments[0];
+        let shape =
+            |proof: &JoltProof<F, PCS, FS>| (proof.ram_K, proof.trace_length.next_power_of_two());
+        if let Some(i) = segments
+            .iter()
+            .position(|(proof, _)| shape(proof) != shape(first_proof))
+        {
+            return Err(ContinuationError::SegmentShapeMismatch(i).into());
+        }
+
+        for (i, (proof, claim)) in segments.into_iter().enumerate() {
+            #[cfg(test)]
+            let _guard =
+                DoryGlobals::initialize(DTH_ROOT_OF_K, proof.trace_length.next_power_of_two());
+
+            FaultingAccess::check(proof.fault.as_ref(), &program_io, proof.trace_length)?;
+            let state_manager = proof
+                .to_verifier_state_manager(preprocessing, program_io.clone())
+                .with_segment(claim)
+                .map_err(|err| ContinuationError::InvalidSegmentProof(i, err.to_string()))?;
+            JoltDAG::verify(state_manager)
+                .map_err(|err| ContinuationError::InvalidSegmentProof(i, err.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks the program I/O against the memory layout, and truncates trailing zeros
+/// on the device outputs.
+fn check_program_io<F: JoltField, PCS: CommitmentScheme<Field = F>>(
+    preprocessing: &JoltVerifierPreprocessing<F, PCS>,
+    program_io: &mut JoltDevice,
+) -> Result<(), ProofVerifyError> {
+    // Memory layout checks
+    if program_io.memory_layout != preprocessing.shared.memory_layout {
+        return Err(ProofVerifyError::MemoryLayoutMismatch);
+    }
+    if program_io.inputs.len() > preprocessing.shared.memory_layout.max_input_size as usize {
+        return Err(ProofVerifyError::InputTooLarge);
+    }
+    if program_io.outputs.len() > preprocessing.shared.memory_layout.max_output_size as usize {
+        return Err(ProofVerifyError::OutputTooLarge);
+    }
+
+    // truncate trailing zeros on device outputs
+    program_io.outputs.truncate(
+        program_io
+            .outputs
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(0, |pos| pos + 1),
+    );
+    Ok(())
 }
 
 pub struct JoltRV32IM;
 impl Jolt<Fr, DoryCommitmentScheme, KeccakTranscript> for JoltRV32IM {}
 pub type RV32IMJoltProof = JoltProof<Fr, DoryCommitmentScheme, KeccakTranscript>;
 
+/// RV32IM Jolt using the Poseidon transcript, for proofs that will be verified inside
+/// a circuit or a recursive Jolt guest.
+pub struct JoltRV32IMPoseidon;
+impl Jolt<Fr, DoryCommitmentScheme, PoseidonTranscript> for JoltRV32IMPoseidon {}
+pub type RV32IMPoseidonJoltProof = JoltProof<Fr, DoryCommitmentScheme, PoseidonTranscript>;
+
+/// RV32IM Jolt using HyperKZG, whose opening proofs are constant-size and cheap to
+/// verify on-chain, and can be batch verified (see [`Jolt::verify_batch`]). See
+/// `poly::commitment::ptau` for loading a production SRS.
+pub struct JoltRV32IMHyperKZG;
+impl Jolt<Fr, HyperKZG<Bn254>, KeccakTranscript> for JoltRV32IMHyperKZG {}
+pub type RV32IMHyperKZGJoltProof = JoltProof<Fr, HyperKZG<Bn254>, KeccakTranscript>;
+
+/// RV32IM Jolt using Ligero, a hash-based PCS with no trusted setup whose security does
+/// not rely on discrete logarithms or pairings, for proofs that must stay sound against
+/// quantum adversaries. Its opening proofs are much larger than those of the other schemes.
+pub struct JoltRV32IMLigero;
+impl Jolt<Fr, Ligero<Fr>, KeccakTranscript> for JoltRV32IMLigero {}
+pub type RV32IMLigeroJoltProof = JoltProof<Fr, Ligero<Fr>, KeccakTranscript>;
+
 use crate::poly::commitment::dory::DoryCommitmentScheme;
-use crate::utils::transcript::KeccakTranscript;
+use crate::poly::commitment::hyperkzg::HyperKZG;
+use crate::poly::commitment::ligero::Ligero;
+use crate::utils::transcript::{KeccakTranscript, PoseidonTranscript};
 use eyre::Result;
 use std::io::Cursor;
 use std::path::PathBuf;
@@ -350,21 +812,40 @@ pub trait Serializable: CanonicalSerialize + CanonicalDeserialize + Sized {
 }
 
 impl Serializable for RV32IMJoltProof {}
+impl Serializable for RV32IMHyperKZGJoltProof {}
+impl Serializable for RV32IMLigeroJoltProof {}
 impl Serializable for JoltDevice {}
 
 // ==================== TEST ====================
 
 #[cfg(test)]
 mod tests {
-    use ark_bn254::Fr;
+    use ark_bn254::{Bn254, Fr};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
     use crate::host;
+    use crate::poly::commitment::commitment_scheme::{
+        BatchOpening, CommitmentScheme, StreamingCommitmentScheme,
+    };
+    use crate::poly::commitment::dory::{DoryCommitmentScheme, DoryGlobals};
+    use crate::poly::commitment::hyperkzg::HyperKZG;
+    use crate::poly::commitment::ligero::Ligero;
     use crate::poly::commitment::mock::MockCommitScheme;
-    use crate::zkvm::JoltVerifierPreprocessing;
-    use crate::zkvm::{Jolt, JoltRV32IM};
+    use crate::utils::errors::ProofVerifyError;
+    use crate::zkvm::dag::proof_serialization::JoltProof;
+    use crate::zkvm::instruction::{CircuitFlags, InstructionFlags};
+    use crate::zkvm::witness::{
+        compute_d_parameter, AllCommittedPolynomials, CommittedPolynomial, DTH_ROOT_OF_K,
+    };
+    use crate::zkvm::{Jolt, JoltRV32IM, JoltRV32IMHyperKZG, JoltRV32IMLigero, JoltRV32IMPoseidon};
+    use crate::zkvm::{JoltSharedPreprocessing, JoltVerifierPreprocessing};
+    use common::constants::{DEFAULT_STACK_SIZE, REGISTER_COUNT};
+    use common::jolt_device::GuestFault;
     use serial_test::serial;
+    use tracer::instruction::RV32IMCycle;
+    use tracer::TraceOutput;
 
-    use crate::utils::transcript::KeccakTranscript;
+    use crate::utils::transcript::{KeccakTranscript, PoseidonTranscript, Transcript};
 
     pub struct JoltRV32IMMockPCS;
     impl Jolt<Fr, MockCommitScheme<Fr>, KeccakTranscript> for JoltRV32IMMockPCS {}
@@ -375,7 +856,7 @@ mod tests {
         let mut program = host::Program::new("fibonacci-guest");
         let inputs = postcard::to_stdvec(&9u32).unwrap();
         let (bytecode, init_memory_state, _) = program.decode();
-        let (_, _, io_device) = program.trace(&inputs);
+        let io_device = program.trace(&inputs).io_device;
 
         let preprocessing = JoltRV32IMMockPCS::prover_preprocess(
             bytecode.clone(),
@@ -384,7 +865,7 @@ mod tests {
             1 << 16,
         );
         let (jolt_proof, io_device, debug_info) =
-            JoltRV32IMMockPCS::prove(&preprocessing, &mut program, &inputs);
+            JoltRV32IMMockPCS::prove(&preprocessing, &mut program, &inputs).unwrap();
 
         let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
         let verification_result =
@@ -402,7 +883,7 @@ mod tests {
         let mut program = host::Program::new("fibonacci-guest");
         let inputs = postcard::to_stdvec(&100u32).unwrap();
         let (bytecode, init_memory_state, _) = program.decode();
-        let (_, _, io_device) = program.trace(&inputs);
+        let io_device = program.trace(&inputs).io_device;
 
         let preprocessing = JoltRV32IM::prover_preprocess(
             bytecode.clone(),
@@ -411,7 +892,7 @@ mod tests {
             1 << 16,
         );
         let (jolt_proof, io_device, debug_info) =
-            JoltRV32IM::prove(&preprocessing, &mut program, &inputs);
+            JoltRV32IM::prove(&preprocessing, &mut program, &inputs).unwrap();
 
         let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
         let verification_result =
@@ -423,13 +904,64 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn fib_batch_verify_hyperkzg() {
+        let mut program = host::Program::new("fibonacci-guest");
+        let (bytecode, init_memory_state, _) = program.decode();
+        let io_device = program
+            .trace(&postcard::to_stdvec(&9u32).unwrap())
+            .io_device;
+
+        let preprocessing = JoltRV32IMHyperKZG::prover_preprocess(
+            bytecode.clone(),
+            io_device.memory_layout.clone(),
+            init_memory_state,
+            1 << 16,
+        );
+        let mut proofs: Vec<_> = [9u32, 50, 100]
+            .iter()
+            .map(|n| {
+                let inputs = postcard::to_stdvec(n).unwrap();
+                let (jolt_proof, io_device, _) =
+                    JoltRV32IMHyperKZG::prove(&preprocessing, &mut program, &inputs).unwrap();
+                (jolt_proof, io_device)
+            })
+            .collect();
+
+        // Keep a copy of the last proof around, to check that the batch is rejected if
+        // any one of its proofs is invalid
+        let mut proof_bytes = Vec::new();
+        proofs[2].0.serialize_compressed(&mut proof_bytes).unwrap();
+        let mut wrong_io = proofs[2].1.clone();
+        wrong_io.outputs[0] ^= 1;
+
+        let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
+        let verification_result =
+            JoltRV32IMHyperKZG::verify_batch(&verifier_preprocessing, proofs.drain(..2).collect());
+        assert!(
+            verification_result.is_ok(),
+            "Batch verification failed with error: {:?}",
+            verification_result.err()
+        );
+
+        proofs.push((
+            JoltProof::deserialize_compressed(&proof_bytes[..]).unwrap(),
+            wrong_io,
+        ));
+        assert!(matches!(
+            JoltRV32IMHyperKZG::verify_batch(&verifier_preprocessing, proofs),
+            Err(ProofVerifyError::BatchedProofError(1, _))
+        ));
+    }
+
     #[test]
     #[serial]
     fn sha3_e2e_dory() {
         let mut program = host::Program::new("sha3-guest");
         let (bytecode, init_memory_state, _) = program.decode();
         let inputs = postcard::to_stdvec(&[5u8; 32]).unwrap();
-        let (_, _, io_device) = program.trace(&inputs);
+        let io_device = program.trace(&inputs).io_device;
 
         let preprocessing = JoltRV32IM::prover_preprocess(
             bytecode.clone(),
@@ -438,7 +970,7 @@ mod tests {
             1 << 16,
         );
         let (jolt_proof, io_device, debug_info) =
-            JoltRV32IM::prove(&preprocessing, &mut program, &inputs);
+            JoltRV32IM::prove(&preprocessing, &mut program, &inputs).unwrap();
 
         let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
         let verification_result =
@@ -461,7 +993,7 @@ mod tests {
         let mut program = host::Program::new("sha2-guest");
         let (bytecode, init_memory_state, _) = program.decode();
         let inputs = postcard::to_stdvec(&[5u8; 32]).unwrap();
-        let (_, _, io_device) = program.trace(&inputs);
+        let io_device = program.trace(&inputs).io_device;
 
         let preprocessing = JoltRV32IM::prover_preprocess(
             bytecode.clone(),
@@ -470,7 +1002,7 @@ mod tests {
             1 << 16,
         );
         let (jolt_proof, io_device, debug_info) =
-            JoltRV32IM::prove(&preprocessing, &mut program, &inputs);
+            JoltRV32IM::prove(&preprocessing, &mut program, &inputs).unwrap();
 
         let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
         let verification_result =
@@ -482,12 +1014,109 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn program_digest_binds_program() {
+        let mut program = host::Program::new("fibonacci-guest");
+        let (bytecode, init_memory_state, _) = program.decode();
+        let io_device = program
+            .trace(&postcard::to_stdvec(&9u32).unwrap())
+            .io_device;
+
+        let shared = JoltRV32IM::shared_preprocess(
+            bytecode.clone(),
+            io_device.memory_layout.clone(),
+            init_memory_state.clone(),
+        );
+        let shared_again = JoltRV32IM::shared_preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            init_memory_state,
+        );
+        assert_eq!(shared.image_id(), shared_again.image_id());
+
+        // The digest is recomputed on deserialization rather than trusted from the bytes
+        let mut bytes = Vec::new();
+        shared.serialize_compressed(&mut bytes).unwrap();
+        let deserialized = JoltSharedPreprocessing::deserialize_compressed(&*bytes).unwrap();
+        assert_eq!(shared.image_id(), deserialized.image_id());
+
+        // Preprocessing for a different register file (i.e. built with a different
+        // `float` feature) is rejected
+        *bytes.last_mut().unwrap() = REGISTER_COUNT.wrapping_mul(2);
+        assert!(JoltSharedPreprocessing::deserialize_compressed(&*bytes).is_err());
+
+        let mut program = host::Program::new("sha3-guest");
+        let (bytecode, init_memory_state, _) = program.decode();
+        let other_shared =
+            JoltRV32IM::shared_preprocess(bytecode, io_device.memory_layout, init_memory_state);
+        assert_ne!(shared.image_id(), other_shared.image_id());
+    }
+
+    #[test]
+    #[serial]
+    fn streaming_commitments_match_materialized() {
+        let mut program = host::Program::new("fibonacci-guest");
+        let inputs = postcard::to_stdvec(&9u32).unwrap();
+        let (bytecode, init_memory_state, _) = program.decode();
+        let TraceOutput {
+            mut trace,
+            io_device,
+            ..
+        } = program.trace(&inputs);
+
+        let preprocessing = JoltRV32IM::prover_preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            init_memory_state,
+            1 << 16,
+        );
+
+        let trace_length = trace.len();
+        let padded_trace_length = trace_length.next_power_of_two();
+        trace.resize(padded_trace_length, RV32IMCycle::NoOp);
+        let _guard = (
+            DoryGlobals::initialize(DTH_ROOT_OF_K, padded_trace_length),
+            AllCommittedPolynomials::initialize(
+                compute_d_parameter(1 << 16),
+                preprocessing.shared.bytecode.d,
+                false,
+                false,
+                false,
+            ),
+        );
+
+        // Use a chunk size that doesn't divide the trace length, so that chunk
+        // boundaries fall in the middle of the rows of the commitment matrices
+        const CHUNK_SIZE: usize = 100;
+        let cycles = &trace[..trace_length];
+        let chunks = cycles.chunks(CHUNK_SIZE).enumerate().map(|(i, chunk)| {
+            let next_is_noop = cycles.get((i + 1) * CHUNK_SIZE).is_none_or(|next_cycle| {
+                next_cycle.instruction().circuit_flags()[CircuitFlags::IsNoop]
+            });
+            (chunk, next_is_noop)
+        });
+        let streamed = CommittedPolynomial::commit_streaming::<_, DoryCommitmentScheme, _>(
+            &preprocessing,
+            chunks,
+            padded_trace_length,
+        );
+        for (poly, streamed) in AllCommittedPolynomials::iter().zip(streamed) {
+            let witness = poly.generate_witness(&preprocessing, &trace);
+            assert_eq!(
+                DoryCommitmentScheme::commit(&witness, &preprocessing.generators),
+                streamed,
+                "{poly:?}"
+            );
+        }
+    }
+
     #[test]
     #[serial]
     fn memory_ops_e2e_dory() {
         let mut program = host::Program::new("memory-ops-guest");
         let (bytecode, init_memory_state, _) = program.decode();
-        let (_, _, io_device) = program.trace(&[]);
+        let io_device = program.trace(&[]).io_device;
 
         let preprocessing = JoltRV32IM::prover_preprocess(
             bytecode.clone(),
@@ -496,7 +1125,7 @@ mod tests {
             1 << 16,
         );
         let (jolt_proof, io_device, debug_info) =
-            JoltRV32IM::prove(&preprocessing, &mut program, &[]);
+            JoltRV32IM::prove(&preprocessing, &mut program, &[]).unwrap();
 
         let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
         let verification_result =
@@ -508,12 +1137,77 @@ mod tests {
         );
     }
 
+    /// The guests every Jolt instance is tested end to end on, with their inputs.
+    fn e2e_guests() -> [(&'static str, Vec<u8>); 5] {
+        [
+            ("fibonacci-guest", postcard::to_stdvec(&9u32).unwrap()),
+            ("sha2-guest", postcard::to_stdvec(&[5u8; 32]).unwrap()),
+            ("sha3-guest", postcard::to_stdvec(&[5u8; 32]).unwrap()),
+            ("memory-ops-guest", vec![]),
+            ("muldiv-guest", vec![50]),
+        ]
+    }
+
+    /// Proves and verifies every guest in [`e2e_guests`] with the Jolt instance `J`.
+    fn e2e<J, PCS, FS>()
+    where
+        J: Jolt<Fr, PCS, FS>,
+        PCS: BatchOpening<Field = Fr> + StreamingCommitmentScheme,
+        FS: Transcript,
+    {
+        // Ensure SHA2 inline library is linked and auto-registered
+        #[cfg(feature = "host")]
+        extern crate sha2_inline;
+
+        for (guest, inputs) in e2e_guests() {
+            let mut program = host::Program::new(guest);
+            let (bytecode, init_memory_state, _) = program.decode();
+            let io_device = program.trace(&inputs).io_device;
+
+            let preprocessing = J::prover_preprocess(
+                bytecode.clone(),
+                io_device.memory_layout.clone(),
+                init_memory_state,
+                1 << 16,
+            );
+            let (jolt_proof, io_device, debug_info) =
+                J::prove(&preprocessing, &mut program, &inputs).unwrap();
+
+            let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
+            let verification_result =
+                J::verify(&verifier_preprocessing, jolt_proof, io_device, debug_info);
+            assert!(
+                verification_result.is_ok(),
+                "Verification of {guest} failed with error: {:?}",
+                verification_result.err()
+            );
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn e2e_dory_poseidon() {
+        e2e::<JoltRV32IMPoseidon, DoryCommitmentScheme, PoseidonTranscript>();
+    }
+
+    #[test]
+    #[serial]
+    fn e2e_hyperkzg() {
+        e2e::<JoltRV32IMHyperKZG, HyperKZG<Bn254>, KeccakTranscript>();
+    }
+
+    #[test]
+    #[serial]
+    fn e2e_ligero() {
+        e2e::<JoltRV32IMLigero, Ligero<Fr>, KeccakTranscript>();
+    }
+
     #[test]
     #[serial]
     fn muldiv_e2e_dory() {
         let mut program = host::Program::new("muldiv-guest");
         let (bytecode, init_memory_state, _) = program.decode();
-        let (_, _, io_device) = program.trace(&[]);
+        let io_device = program.trace(&[]).io_device;
 
         let preprocessing = JoltRV32IM::prover_preprocess(
             bytecode.clone(),
@@ -522,7 +1216,7 @@ mod tests {
             1 << 16,
         );
         let (jolt_proof, io_device, debug_info) =
-            JoltRV32IM::prove(&preprocessing, &mut program, &[50]);
+            JoltRV32IM::prove(&preprocessing, &mut program, &[50]).unwrap();
 
         let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
         let verification_result =
@@ -533,4 +1227,105 @@ mod tests {
             verification_result.err()
         );
     }
+
+    /// Proves a run of the overflow guest's `func`, which faults with `fault`, and checks
+    /// that the proof is bound to that fault and to the faulting access.
+    fn overflow_e2e_dory(func: &str, stack_size: u64, fault: GuestFault) {
+        let mut program = host::Program::new("overflow-guest");
+        program.set_func(func);
+        program.set_stack_size(stack_size);
+        program.set_memory_size(10240);
+        let (bytecode, init_memory_state, _) = program.decode();
+        let io_device = program.trace(&[]).io_device;
+
+        let preprocessing = JoltRV32IM::prover_preprocess(
+            bytecode.clone(),
+            io_device.memory_layout.clone(),
+            init_memory_state,
+            1 << 16,
+        );
+        let (jolt_proof, io_device, debug_info) =
+            JoltRV32IM::prove(&preprocessing, &mut program, &[]).unwrap();
+        assert_eq!(io_device.fault, Some(fault));
+        assert!(!io_device.panic);
+
+        // Keep a copy of the proof around, to check it against other fault statuses
+        let mut proof_bytes = Vec::new();
+        jolt_proof.serialize_compressed(&mut proof_bytes).unwrap();
+
+        let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
+        let verification_result = JoltRV32IM::verify(
+            &verifier_preprocessing,
+            jolt_proof,
+            io_device.clone(),
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+
+        // The proof does not verify against any other fault status
+        for other_fault in [
+            None,
+            Some(GuestFault::StackOverflow),
+            Some(GuestFault::HeapOverflow),
+        ] {
+            if other_fault == Some(fault) {
+                continue;
+            }
+            let mut io_device = io_device.clone();
+            io_device.fault = other_fault;
+            let jolt_proof = JoltProof::deserialize_compressed(&proof_bytes[..]).unwrap();
+            assert!(
+                JoltRV32IM::verify(&verifier_preprocessing, jolt_proof, io_device, None).is_err(),
+                "Proof verified with fault status {other_fault:?}"
+            );
+        }
+
+        // Nor can the fault be moved to another cycle
+        let mut jolt_proof = JoltProof::deserialize_compressed(&proof_bytes[..]).unwrap();
+        jolt_proof.fault.as_mut().unwrap().cycle -= 1;
+        assert!(
+            JoltRV32IM::verify(&verifier_preprocessing, jolt_proof, io_device, None).is_err(),
+            "Proof verified with the fault at another cycle"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn overflow_stack_e2e_dory() {
+        overflow_e2e_dory("overflow_stack", 1024, GuestFault::StackOverflow);
+    }
+
+    #[test]
+    #[serial]
+    fn overflow_heap_e2e_dory() {
+        overflow_e2e_dory(
+            "write_past_heap",
+            DEFAULT_STACK_SIZE,
+            GuestFault::HeapOverflow,
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn fib_cycle_tracking_report() {
+        let mut program = host::Program::new("fibonacci-guest");
+        let inputs = postcard::to_stdvec(&100u32).unwrap();
+        let report = program.trace(&inputs).cycle_tracking;
+
+        assert_eq!(report.events.len(), 1);
+        let event = &report.events[0];
+        assert_eq!(event.label, "fib_loop");
+        assert_eq!(event.depth, 0);
+        assert!(event.rv32im_cycles() > 0);
+        assert!(event.virtual_cycles() >= event.rv32im_cycles());
+
+        let summary = report.summary();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].calls, 1);
+        assert_eq!(summary[0].rv32im_cycles, event.rv32im_cycles());
+    }
 }
@@ -73,15 +73,39 @@ impl<F: JoltField> R1CSConstraints<F> for JoltRV32IMConstraints {
             0,
         );
 
-        // if Load || Store {
+        // if Fault {
+        //     assert!(Load || Store)
+        //     assert!(Rs1Value + Imm == FaultAddress)
+        //     assert!(NextIsNoop)
+        // }
+        // The faulting access is the last cycle of the trace (see `zkvm::fault`)
+        let is_load_or_store = JoltR1CSInputs::OpFlags(CircuitFlags::Load)
+            + JoltR1CSInputs::OpFlags(CircuitFlags::Store);
+        cs.constrain_eq_conditional(JoltR1CSInputs::Fault, is_load_or_store.clone(), 1);
+        cs.constrain_eq_conditional(
+            JoltR1CSInputs::Fault,
+            JoltR1CSInputs::Rs1Value + JoltR1CSInputs::Imm,
+            JoltR1CSInputs::FaultAddress,
+        );
+        cs.constrain_eq_conditional(JoltR1CSInputs::Fault, JoltR1CSInputs::NextIsNoop, 1);
+
+        // if StoreFault {
+        //     assert!(Store)
+        // }
+        cs.constrain_eq_conditional(
+            JoltR1CSInputs::StoreFault,
+            JoltR1CSInputs::OpFlags(CircuitFlags::Store),
+            1,
+        );
+
+        // if (Load || Store) && !Fault {
         //     assert!(RamAddress == Rs1Value + Imm)
         // } else {
         //     assert!(RamAddress == 0)
         // }
-        let is_load_or_store = JoltR1CSInputs::OpFlags(CircuitFlags::Load)
-            + JoltR1CSInputs::OpFlags(CircuitFlags::Store);
+        // Note that Fault is only set together with Load or Store
         cs.constrain_if_else(
-            is_load_or_store,
+            is_load_or_store - JoltR1CSInputs::Fault,
             JoltR1CSInputs::Rs1Value + JoltR1CSInputs::Imm,
             0,
             JoltR1CSInputs::RamAddress,
@@ -105,11 +129,12 @@ impl<F: JoltField> R1CSConstraints<F> for JoltRV32IMConstraints {
             JoltR1CSInputs::RdWriteValue,
         );
 
-        // if Store {
+        // if Store && !StoreFault {
         //     assert!(Rs2Value == RamWriteValue)
         // }
+        // Note that StoreFault is only set together with Store
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::Store),
+            JoltR1CSInputs::OpFlags(CircuitFlags::Store) - JoltR1CSInputs::StoreFault,
             JoltR1CSInputs::Rs2Value,
             JoltR1CSInputs::RamWriteValue,
         );
@@ -200,7 +225,11 @@ impl<F: JoltField> R1CSConstraints<F> for JoltRV32IMConstraints {
         );
 
         // if Rd != 0 && Jump {
-        //     assert!(RdWriteValue == UnexpandedPC + 4)
+        //     if IsCompressed {
+        //         assert!(RdWriteValue == UnexpandedPC + 2)
+        //     } else {
+        //         assert!(RdWriteValue == UnexpandedPC + 4)
+        //     }
         // }
         cs.constrain_prod(
             JoltR1CSInputs::Rd,
@@ -210,7 +239,8 @@ impl<F: JoltField> R1CSConstraints<F> for JoltRV32IMConstraints {
         cs.constrain_eq_conditional(
             JoltR1CSInputs::WritePCtoRD,
             JoltR1CSInputs::RdWriteValue,
-            JoltR1CSInputs::UnexpandedPC + 4,
+            JoltR1CSInputs::UnexpandedPC + 4
+                - 2 * JoltR1CSInputs::OpFlags(CircuitFlags::IsCompressed),
         );
 
         // if Jump && !NextIsNoop {
@@ -241,26 +271,34 @@ impl<F: JoltField> R1CSConstraints<F> for JoltRV32IMConstraints {
             JoltR1CSInputs::UnexpandedPC + JoltR1CSInputs::Imm,
         );
 
-        // if !(ShouldBranch || Jump) {
+        // if !(ShouldBranch || Jump || Fault) {
         //     if DoNotUpdatePC {
         //         assert!(NextUnexpandedPC == UnexpandedPC)
+        //     } else if IsCompressed {
+        //         assert!(NextUnexpandedPC == UnexpandedPC + 2)
         //     } else {
         //         assert!(NextUnexpandedPC == UnexpandedPC + 4)
         //     }
         // }
-        // Note that ShouldBranch and Jump instructions are mutually exclusive
+        // Note that ShouldBranch, Jump and Fault are mutually exclusive, and
+        // IsCompressed is never set together with DoNotUpdatePC
         cs.constrain_eq_conditional(
-            1 - JoltR1CSInputs::ShouldBranch - JoltR1CSInputs::OpFlags(CircuitFlags::Jump),
+            1 - JoltR1CSInputs::ShouldBranch
+                - JoltR1CSInputs::OpFlags(CircuitFlags::Jump)
+                - JoltR1CSInputs::Fault,
             JoltR1CSInputs::NextUnexpandedPC,
             JoltR1CSInputs::UnexpandedPC + 4
-                - 4 * JoltR1CSInputs::OpFlags(CircuitFlags::DoNotUpdateUnexpandedPC),
+                - 4 * JoltR1CSInputs::OpFlags(CircuitFlags::DoNotUpdateUnexpandedPC)
+                - 2 * JoltR1CSInputs::OpFlags(CircuitFlags::IsCompressed),
         );
 
-        // if Inline {
+        // if Inline && !InlineFault {
         //     assert!(NextPC == PC + 1)
         // }
+        // Note that InlineFault is only set together with Inline (see `zkvm::fault`)
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::InlineSequenceInstruction),
+            JoltR1CSInputs::OpFlags(CircuitFlags::InlineSequenceInstruction)
+                - JoltR1CSInputs::InlineFault,
             JoltR1CSInputs::NextPC,
             JoltR1CSInputs::PC + 1,
         );
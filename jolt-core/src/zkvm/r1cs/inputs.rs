@@ -55,6 +55,10 @@ pub enum JoltR1CSInputs {
     LookupOutput,     // Virtual (instruction rv)
     NextIsNoop,       // Virtual (spartan shift sumcheck)
     ShouldJump,
+    Fault,        // Public (see `zkvm::fault`)
+    StoreFault,   // Public (see `zkvm::fault`)
+    InlineFault,  // Public (see `zkvm::fault`)
+    FaultAddress, // Public (see `zkvm::fault`)
     OpFlags(CircuitFlags),
 }
 
@@ -96,6 +100,10 @@ impl TryFrom<JoltR1CSInputs> for VirtualPolynomial {
             JoltR1CSInputs::NextPC => Ok(VirtualPolynomial::NextPC),
             JoltR1CSInputs::NextIsNoop => Ok(VirtualPolynomial::NextIsNoop),
             JoltR1CSInputs::LookupOutput => Ok(VirtualPolynomial::LookupOutput),
+            JoltR1CSInputs::Fault => Ok(VirtualPolynomial::Fault),
+            JoltR1CSInputs::StoreFault => Ok(VirtualPolynomial::StoreFault),
+            JoltR1CSInputs::InlineFault => Ok(VirtualPolynomial::InlineFault),
+            JoltR1CSInputs::FaultAddress => Ok(VirtualPolynomial::FaultAddress),
             JoltR1CSInputs::OpFlags(flag) => Ok(VirtualPolynomial::OpFlags(flag)),
             _ => Err("{value} is not a virtual polynomial"),
         }
@@ -118,7 +126,7 @@ impl TryFrom<JoltR1CSInputs> for OpeningId {
 
 /// This const serves to define a canonical ordering over inputs (and thus indices
 /// for each input). This is needed for sumcheck.
-pub const ALL_R1CS_INPUTS: [JoltR1CSInputs; 40] = [
+pub const ALL_R1CS_INPUTS: [JoltR1CSInputs; 45] = [
     JoltR1CSInputs::LeftInstructionInput,
     JoltR1CSInputs::RightInstructionInput,
     JoltR1CSInputs::Product,
@@ -142,6 +150,10 @@ pub const ALL_R1CS_INPUTS: [JoltR1CSInputs; 40] = [
     JoltR1CSInputs::LookupOutput,
     JoltR1CSInputs::NextIsNoop,
     JoltR1CSInputs::ShouldJump,
+    JoltR1CSInputs::Fault,
+    JoltR1CSInputs::StoreFault,
+    JoltR1CSInputs::InlineFault,
+    JoltR1CSInputs::FaultAddress,
     JoltR1CSInputs::OpFlags(CircuitFlags::LeftOperandIsRs1Value),
     JoltR1CSInputs::OpFlags(CircuitFlags::RightOperandIsRs2Value),
     JoltR1CSInputs::OpFlags(CircuitFlags::LeftOperandIsPC),
@@ -159,6 +171,7 @@ pub const ALL_R1CS_INPUTS: [JoltR1CSInputs; 40] = [
     JoltR1CSInputs::OpFlags(CircuitFlags::DoNotUpdateUnexpandedPC),
     JoltR1CSInputs::OpFlags(CircuitFlags::Advice),
     JoltR1CSInputs::OpFlags(CircuitFlags::IsNoop),
+    JoltR1CSInputs::OpFlags(CircuitFlags::IsCompressed),
 ];
 
 /// The subset of `ALL_R1CS_INPUTS` that are committed. The rest of
@@ -343,6 +356,10 @@ impl JoltR1CSInputs {
                                 LookupQuery::<32>::to_lookup_output(cycle)
                             } else if do_not_update_pc {
                                 instr.address as u64
+                            } else if cycle.instruction().circuit_flags()
+                                [CircuitFlags::IsCompressed]
+                            {
+                                instr.address as u64 + 2
                             } else {
                                 instr.address as u64 + 4
                             }
@@ -367,6 +384,11 @@ impl JoltR1CSInputs {
                     .collect();
                 coeffs.into()
             }
+            JoltR1CSInputs::Fault | JoltR1CSInputs::StoreFault | JoltR1CSInputs::InlineFault => {
+                // Set at the faulting cycle, if any (see `SpartanDag::stage1_prove`)
+                vec![0u8; trace.len()].into()
+            }
+            JoltR1CSInputs::FaultAddress => vec![0u64; trace.len()].into(),
             JoltR1CSInputs::OpFlags(flag) => {
                 // TODO(moodlezoup): Boolean polynomial
                 let coeffs: Vec<u8> = trace
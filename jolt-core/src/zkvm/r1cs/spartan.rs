@@ -12,9 +12,12 @@ use crate::poly::multilinear_polynomial::{BindingOrder, MultilinearPolynomial, P
 use crate::poly::opening_proof::{
     OpeningPoint, ProverOpeningAccumulator, SumcheckId, VerifierOpeningAccumulator, BIG_ENDIAN,
 };
+use crate::utils::index_to_field_bitvector;
 use crate::utils::math::Math;
+use crate::zkvm::continuations::SegmentIO;
 use crate::zkvm::dag::stage::SumcheckStages;
 use crate::zkvm::dag::state_manager::{ProofData, ProofKeys, StateManager};
+use crate::zkvm::fault::{FaultingAccess, FAULT_R1CS_INPUTS};
 use crate::zkvm::instruction::CircuitFlags;
 use crate::zkvm::r1cs::builder::Constraint;
 use crate::zkvm::r1cs::constraints::{JoltRV32IMConstraints, R1CSConstraints};
@@ -364,6 +367,9 @@ struct PCSumcheckVerifierState<F: JoltField> {
     unexpanded_pc_eval_at_shift_r: F,
     pc_eval_at_shift_r: F,
     is_noop_eval_at_shift_r: F,
+    /// Batching challenge for the first cycle of a segment of a continuation
+    /// (see [`segment_boundary_claim`])
+    delta: Option<F>,
 }
 
 pub struct PCSumcheck<F: JoltField> {
@@ -428,6 +434,7 @@ impl<F: JoltField> PCSumcheck<F> {
                 unexpanded_pc_eval_at_shift_r,
                 pc_eval_at_shift_r,
                 is_noop_eval_at_shift_r,
+                delta: None,
             }),
             gamma,
             gamma_squared,
@@ -435,6 +442,35 @@ impl<F: JoltField> PCSumcheck<F> {
     }
 }
 
+/// In a segment of a continuation (see `zkvm::continuations`), the last cycle n - 1
+/// is followed by the instruction at the segment's final PC rather than by a no-op,
+/// so the Next* witnesses at n - 1 differ from the shifted witnesses there, which
+/// must instead be those of a no-op. Moreover, the first cycle must execute the
+/// instruction at the segment's initial PC. Batching the latter with a random delta,
+///   NextUnexpandedPC(r) + gamma * NextPC(r) + gamma^2 * NextIsNoop(r)
+///     - eq(r, n - 1) * (final_unexpanded_pc + gamma * final_pc - gamma^2)
+///     + delta * (initial_unexpanded_pc + gamma * initial_pc)
+///   = \sum_t (UnexpandedPC(t) + gamma * PC(t) + gamma^2 * IsNoop(t))
+///       * (eq_plus_one(r, t) + delta * eq(0, t))
+/// Returns the offset to the input claim of `PCSumcheck` along with delta.
+fn segment_boundary_claim<F: JoltField, ProofTranscript: Transcript>(
+    segment: &SegmentIO,
+    r_cycle: &[F],
+    gamma: F,
+    transcript: &mut ProofTranscript,
+) -> (F, F) {
+    let delta: F = transcript.challenge_scalar();
+    let last_cycle = index_to_field_bitvector(segment.num_cycles() as u64 - 1, r_cycle.len());
+    let eq_last_cycle = EqPolynomial::mle(r_cycle, &last_cycle);
+
+    let final_state = F::from_u64(segment.claim.final_state.pc)
+        + gamma * F::from_u64(segment.final_pc as u64)
+        - gamma.square();
+    let initial_state = F::from_u64(segment.claim.initial_state.pc)
+        + gamma * F::from_u64(segment.initial_pc as u64);
+    (delta * initial_state - eq_last_cycle * final_state, delta)
+}
+
 impl<F: JoltField> SumcheckInstance<F> for PCSumcheck<F> {
     fn degree(&self) -> usize {
         2
@@ -541,8 +577,12 @@ impl<F: JoltField> SumcheckInstance<F> for PCSumcheck<F> {
             + self.gamma * verifier_state.pc_eval_at_shift_r
             + self.gamma_squared * verifier_state.is_noop_eval_at_shift_r;
 
-        let eq_plus_one_shift_sumcheck =
+        let mut eq_plus_one_shift_sumcheck =
             EqPlusOnePolynomial::new(verifier_state.r_cycle.clone()).evaluate(r);
+        if let Some(delta) = verifier_state.delta {
+            // delta * eq(0, r)
+            eq_plus_one_shift_sumcheck += delta * r.iter().map(|r_i| F::one() - r_i).product::<F>();
+        }
 
         batched_eval_at_shift_r * eq_plus_one_shift_sumcheck
     }
@@ -644,10 +684,27 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
         let key = self.key.clone();
 
         // Create input polynomials from trace
-        let input_polys: Vec<MultilinearPolynomial<F>> = ALL_R1CS_INPUTS
+        let mut input_polys: Vec<MultilinearPolynomial<F>> = ALL_R1CS_INPUTS
             .par_iter()
             .map(|var| var.generate_witness(trace, preprocessing))
             .collect();
+        if let Some(segment) = &state_manager.segment {
+            // The last cycle of a segment is followed by the instruction at the
+            // segment's final PC, rather than by a no-op
+            let last_cycle = segment.num_cycles() - 1;
+            input_polys[JoltR1CSInputs::NextUnexpandedPC.to_index()]
+                .set_coeff(last_cycle, segment.claim.final_state.pc);
+            input_polys[JoltR1CSInputs::NextPC.to_index()]
+                .set_coeff(last_cycle, segment.final_pc as u64);
+            input_polys[JoltR1CSInputs::NextIsNoop.to_index()].set_coeff(last_cycle, 0);
+            input_polys[JoltR1CSInputs::ShouldJump.to_index()]
+                .set_coeff(last_cycle, segment.last_should_jump(trace) as u64);
+        }
+        if let Some(fault) = &state_manager.fault {
+            for (input, value) in FAULT_R1CS_INPUTS.iter().zip(fault.r1cs_input_values()) {
+                input_polys[input.to_index()].set_coeff(fault.cycle, value);
+            }
+        }
 
         let num_rounds_x = key.num_rows_bits();
 
@@ -871,6 +928,19 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
             }
         });
 
+        // The fault inputs are public, so the verifier evaluates them itself
+        let fault_evals =
+            FaultingAccess::evaluate_r1cs_inputs(state_manager.fault.as_ref(), r_cycle);
+        for (input, expected_eval) in FAULT_R1CS_INPUTS.iter().zip(fault_evals) {
+            let (_, eval) = accumulator.borrow().get_virtual_polynomial_opening(
+                VirtualPolynomial::try_from(*input).ok().unwrap(),
+                SumcheckId::SpartanOuter,
+            );
+            if eval != expected_eval {
+                return Err(anyhow::anyhow!("Invalid claim for public input {input:?}"));
+            }
+        }
+
         Ok(())
     }
 
@@ -1014,15 +1084,27 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
 
         let (r_cycle, _rx_var) = outer_sumcheck_r.split_at(num_cycles_bits);
 
-        let (_, eq_plus_one_r_cycle) = EqPlusOnePolynomial::evals(&r_cycle.r, None);
+        let (_, mut eq_plus_one_r_cycle) = EqPlusOnePolynomial::evals(&r_cycle.r, None);
 
         let gamma: F = state_manager.transcript.borrow_mut().challenge_scalar();
         let gamma_squared = gamma.square();
 
+        let mut input_claim =
+            next_unexpanded_pc_eval + gamma * next_pc_eval + gamma_squared * next_is_noop_eval;
+        if let Some(segment) = &state_manager.segment {
+            let (claim_offset, delta) = segment_boundary_claim(
+                segment,
+                &r_cycle.r,
+                gamma,
+                &mut *state_manager.transcript.borrow_mut(),
+            );
+            input_claim += claim_offset;
+            // eq(0, t) is the indicator of the first cycle
+            eq_plus_one_r_cycle[0] += delta;
+        }
+
         let pc_sumcheck = PCSumcheck {
-            input_claim: next_unexpanded_pc_eval
-                + gamma * next_pc_eval
-                + gamma_squared * next_is_noop_eval,
+            input_claim,
             log_T: r_cycle.len(),
             prover_state: Some(PCSumcheckProverState {
                 unexpanded_pc_poly,
@@ -1068,8 +1150,19 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
 
         let (r_cycle, _rx_var) = outer_sumcheck_r.split_at(num_cycles_bits);
 
-        let shift_sumcheck_claim =
+        let mut shift_sumcheck_claim =
             next_unexpanded_pc_eval + gamma * next_pc_eval + gamma.square() * next_is_noop_eval;
+        let mut delta = None;
+        if let Some(segment) = &state_manager.segment {
+            let (claim_offset, segment_delta) = segment_boundary_claim(
+                segment,
+                &r_cycle.r,
+                gamma,
+                &mut *state_manager.transcript.borrow_mut(),
+            );
+            shift_sumcheck_claim += claim_offset;
+            delta = Some(segment_delta);
+        }
 
         // Get shift sumcheck witness evaluations from openings (UnexpandedPC and PC at shift_r)
         let (_, pc_eval_at_shift_r) = accumulator
@@ -1085,7 +1178,7 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
             SumcheckId::SpartanShift,
         );
 
-        let pc_sumcheck = PCSumcheck::<F>::new_verifier(
+        let mut pc_sumcheck = PCSumcheck::<F>::new_verifier(
             shift_sumcheck_claim,
             r_cycle.into(),
             gamma,
@@ -1093,6 +1186,9 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
             pc_eval_at_shift_r,
             is_noop_eval_at_shift_r,
         );
+        if let Some(verifier_state) = pc_sumcheck.verifier_state.as_mut() {
+            verifier_state.delta = delta;
+        }
 
         vec![Box::new(pc_sumcheck)]
     }
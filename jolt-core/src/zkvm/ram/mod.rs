@@ -4,9 +4,14 @@ use std::vec;
 
 use crate::{
     field::JoltField,
-    poly::commitment::commitment_scheme::CommitmentScheme,
+    poly::{
+        commitment::commitment_scheme::CommitmentScheme,
+        eq_poly::EqPolynomial,
+        multilinear_polynomial::MultilinearPolynomial,
+        opening_proof::{ProverOpeningAccumulator, SumcheckId, VerifierOpeningAccumulator},
+    },
     subprotocols::sumcheck::SumcheckInstance,
-    utils::transcript::Transcript,
+    utils::{math::Math, transcript::Transcript},
     zkvm::dag::{stage::SumcheckStages, state_manager::StateManager},
     zkvm::ram::{
         booleanity::BooleanitySumcheck,
@@ -18,13 +23,15 @@ use crate::{
         read_write_checking::RamReadWriteChecking,
         val_evaluation::ValEvaluationSumcheck,
     },
+    zkvm::witness::CommittedPolynomial,
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use common::{
-    constants::{BYTES_PER_INSTRUCTION, RAM_START_ADDRESS},
-    jolt_device::MemoryLayout,
+    constants::{BYTES_PER_INSTRUCTION, RAM_START_ADDRESS, RESERVATION_ADDRESS},
+    jolt_device::{JoltDevice, MemoryLayout},
 };
 use rayon::prelude::*;
+use tracer::emulator::memory::Memory;
 
 pub mod booleanity;
 pub mod hamming_booleanity;
@@ -91,6 +98,382 @@ pub fn remap_address(address: u64, memory_layout: &MemoryLayout) -> Option<u64>
     }
 }
 
+/// Whether the private input region is non-empty, in which case the prover
+/// commits to its contents as `CommittedPolynomial::PrivateInputs`.
+pub fn has_private_inputs(memory_layout: &MemoryLayout) -> bool {
+    memory_layout.max_private_input_size > 0
+}
+
+/// The number of variables of the (non-empty) private input region, i.e. log2
+/// of the number of words it spans.
+pub fn private_input_num_vars(memory_layout: &MemoryLayout) -> usize {
+    ((memory_layout.max_private_input_size / 4) as usize).log_2()
+}
+
+/// The private input words, zero-padded to the size of the private input region.
+pub fn private_input_words(program_io: &JoltDevice) -> Vec<u32> {
+    let mut words = vec![0; (program_io.memory_layout.max_private_input_size / 4) as usize];
+    for (word, chunk) in words.iter_mut().zip(program_io.private_inputs.chunks(4)) {
+        let mut bytes = [0u8; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        *word = u32::from_le_bytes(bytes);
+    }
+    words
+}
+
+/// The witness for `CommittedPolynomial::PrivateInputs`: the private input words,
+/// zero-padded to length `T` so that it can be batched with the other dense
+/// committed polynomials.
+pub fn private_input_polynomial<F: JoltField>(
+    program_io: &JoltDevice,
+    T: usize,
+) -> MultilinearPolynomial<F> {
+    let mut words = private_input_words(program_io);
+    assert!(
+        words.len() <= T,
+        "Private input region ({} words) exceeds padded trace length {T}",
+        words.len()
+    );
+    words.resize(T, 0);
+    words.into()
+}
+
+/// The private input region is a power-of-two sized block of RAM aligned to its
+/// own size, so its contribution to Val_init(r_address) factors as
+///   eq(r_hi, b) * PrivateInputs(0, ..., 0, r_lo)
+/// where r_lo are the last `private_input_num_vars` variables of `r_address`, r_hi
+/// are the rest, and b is the index of the block. Returns the opening point (of
+/// length `log_T`) for `PrivateInputs` along with the eq(r_hi, b) factor.
+pub fn private_input_opening_point<F: JoltField>(
+    r_address: &[F],
+    memory_layout: &MemoryLayout,
+    log_T: usize,
+) -> (Vec<F>, F) {
+    let num_vars = private_input_num_vars(memory_layout);
+    let (r_hi, r_lo) = r_address.split_at(r_address.len() - num_vars);
+    let block_index =
+        remap_address(memory_layout.private_input_start, memory_layout).unwrap() >> num_vars;
+    let eq_eval = r_hi
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, r)| {
+            if (block_index >> i) & 1 == 1 {
+                *r
+            } else {
+                F::one() - r
+            }
+        })
+        .product();
+    let opening_point = [vec![F::zero(); log_T - num_vars], r_lo.to_vec()].concat();
+    (opening_point, eq_eval)
+}
+
+/// Adds the opening of `PrivateInputs` at the point corresponding to `r_address`
+/// to the prover's `accumulator`, returning the claimed evaluation.
+pub fn append_private_input_opening_prover<F: JoltField>(
+    r_address: &[F],
+    sumcheck: SumcheckId,
+    program_io: &JoltDevice,
+    log_T: usize,
+    accumulator: &mut ProverOpeningAccumulator<F>,
+) -> F {
+    let memory_layout = &program_io.memory_layout;
+    let (opening_point, _) = private_input_opening_point(r_address, memory_layout, log_T);
+    let r_lo = &r_address[r_address.len() - private_input_num_vars(memory_layout)..];
+    let claim: F = EqPolynomial::evals(r_lo)
+        .into_iter()
+        .zip(private_input_words(program_io))
+        .map(|(eq, word)| eq.mul_u64(word as u64))
+        .sum();
+    accumulator.append_dense(
+        vec![CommittedPolynomial::PrivateInputs],
+        sumcheck,
+        opening_point,
+        &[claim],
+    );
+    claim
+}
+
+/// Verifier: counterpart of [`append_private_input_opening_prover`]. Returns the
+/// private inputs' contribution to Val_init(r_address), i.e. the claimed
+/// evaluation of `PrivateInputs` times eq(r_hi, b).
+pub fn append_private_input_opening_verifier<F: JoltField>(
+    r_address: &[F],
+    sumcheck: SumcheckId,
+    memory_layout: &MemoryLayout,
+    log_T: usize,
+    accumulator: &mut VerifierOpeningAccumulator<F>,
+) -> F {
+    let (opening_point, eq_eval) = private_input_opening_point(r_address, memory_layout, log_T);
+    accumulator.append_dense(
+        vec![CommittedPolynomial::PrivateInputs],
+        sumcheck,
+        opening_point,
+    );
+    let (_, claim) =
+        accumulator.get_committed_polynomial_opening(CommittedPolynomial::PrivateInputs, sumcheck);
+    eq_eval * claim
+}
+
+/// Prover: opens `PrivateInputs` at the point corresponding to `r_address`, so
+/// that the verifier can account for the private inputs in Val_init(r_address).
+/// Does nothing if there are no private inputs, or when proving a segment of a
+/// continuation, whose initial RAM state is public in full.
+pub fn prove_private_input_opening<F, ProofTranscript, PCS>(
+    r_address: &[F],
+    sumcheck: SumcheckId,
+    state_manager: &StateManager<'_, F, ProofTranscript, PCS>,
+) where
+    F: JoltField,
+    ProofTranscript: Transcript,
+    PCS: CommitmentScheme<Field = F>,
+{
+    let (_, trace, program_io, _) = state_manager.get_prover_data();
+    if state_manager.segment.is_some() || !has_private_inputs(&program_io.memory_layout) {
+        return;
+    }
+    let claim = append_private_input_opening_prover(
+        r_address,
+        sumcheck,
+        program_io,
+        trace.len().log_2(),
+        &mut state_manager.get_prover_accumulator().borrow_mut(),
+    );
+    state_manager
+        .get_transcript()
+        .borrow_mut()
+        .append_scalar(&claim);
+}
+
+/// Verifier: counterpart of [`prove_private_input_opening`]. Returns the private
+/// inputs' contribution to Val_init(r_address), which is zero if there are no
+/// private inputs or when verifying a segment of a continuation.
+pub fn verify_private_input_opening<F, ProofTranscript, PCS>(
+    r_address: &[F],
+    sumcheck: SumcheckId,
+    state_manager: &StateManager<'_, F, ProofTranscript, PCS>,
+) -> F
+where
+    F: JoltField,
+    ProofTranscript: Transcript,
+    PCS: CommitmentScheme<Field = F>,
+{
+    let (_, program_io, T) = state_manager.get_verifier_data();
+    if state_manager.segment.is_some() || !has_private_inputs(&program_io.memory_layout) {
+        return F::zero();
+    }
+    let contribution = append_private_input_opening_verifier(
+        r_address,
+        sumcheck,
+        &program_io.memory_layout,
+        T.log_2(),
+        &mut state_manager.get_verifier_accumulator().borrow_mut(),
+    );
+    let (_, claim) = state_manager
+        .get_committed_polynomial_opening(CommittedPolynomial::PrivateInputs, sumcheck);
+    state_manager
+        .get_transcript()
+        .borrow_mut()
+        .append_scalar(&claim);
+    contribution
+}
+
+/// The witness for `CommittedPolynomial::RamInitialState` or
+/// `CommittedPolynomial::RamFinalState`: a RAM state at a boundary between two
+/// segments of a continuation, zero-padded to length `T` so that it can be batched
+/// with the other dense committed polynomials.
+pub fn ram_state_polynomial<F: JoltField>(ram: &[u32], T: usize) -> MultilinearPolynomial<F> {
+    assert!(
+        ram.len() <= T,
+        "RAM state ({} words) exceeds padded trace length {T}",
+        ram.len()
+    );
+    let mut words = ram.to_vec();
+    words.resize(T, 0);
+    words.into()
+}
+
+/// The RAM state is zero-padded at the end, so its evaluation at `r_address` is that
+/// of the committed polynomial at (0, ..., 0, r_address), of length `log_T`.
+pub fn ram_state_opening_point<F: JoltField>(r_address: &[F], log_T: usize) -> Vec<F> {
+    [vec![F::zero(); log_T - r_address.len()], r_address.to_vec()].concat()
+}
+
+/// Prover: opens `RamInitialState` at the point corresponding to `r_address`, if
+/// proving a segment of a continuation that starts in a committed RAM state, so that
+/// the verifier can obtain Val_init(r_address) = `init_eval`.
+pub fn prove_initial_ram_opening<F, ProofTranscript, PCS>(
+    r_address: &[F],
+    init_eval: F,
+    sumcheck: SumcheckId,
+    state_manager: &StateManager<'_, F, ProofTranscript, PCS>,
+) where
+    F: JoltField,
+    ProofTranscript: Transcript,
+    PCS: CommitmentScheme<Field = F>,
+{
+    if !state_manager
+        .segment
+        .as_ref()
+        .is_some_and(|segment| segment.commits_initial_ram())
+    {
+        return;
+    }
+    let (_, trace, _, _) = state_manager.get_prover_data();
+    state_manager
+        .get_prover_accumulator()
+        .borrow_mut()
+        .append_dense(
+            vec![CommittedPolynomial::RamInitialState],
+            sumcheck,
+            ram_state_opening_point(r_address, trace.len().log_2()),
+            &[init_eval],
+        );
+    state_manager
+        .get_transcript()
+        .borrow_mut()
+        .append_scalar(&init_eval);
+}
+
+/// Verifier: counterpart of [`prove_initial_ram_opening`]. Returns Val_init(r_address)
+/// as claimed by the opening of `RamInitialState`.
+pub fn verify_initial_ram_opening<F, ProofTranscript, PCS>(
+    r_address: &[F],
+    sumcheck: SumcheckId,
+    state_manager: &StateManager<'_, F, ProofTranscript, PCS>,
+) -> F
+where
+    F: JoltField,
+    ProofTranscript: Transcript,
+    PCS: CommitmentScheme<Field = F>,
+{
+    let (_, _, T) = state_manager.get_verifier_data();
+    state_manager
+        .get_verifier_accumulator()
+        .borrow_mut()
+        .append_dense(
+            vec![CommittedPolynomial::RamInitialState],
+            sumcheck,
+            ram_state_opening_point(r_address, T.log_2()),
+        );
+    let (_, claim) = state_manager
+        .get_committed_polynomial_opening(CommittedPolynomial::RamInitialState, sumcheck);
+    state_manager
+        .get_transcript()
+        .borrow_mut()
+        .append_scalar(&claim);
+    claim
+}
+
+/// The initial and final RAM states of a (complete) execution of the program:
+/// the bytecode and inputs, and the final contents of `final_memory` along with
+/// the program's I/O.
+fn program_memory_states(
+    ram_preprocessing: &RAMPreprocessing,
+    program_io: &JoltDevice,
+    final_memory: &Memory,
+    K: usize,
+) -> (Vec<u32>, Vec<u32>) {
+    let mut initial_memory_state = vec![0; K];
+    // Copy bytecode
+    let mut index = remap_address(
+        ram_preprocessing.min_bytecode_address,
+        &program_io.memory_layout,
+    )
+    .unwrap() as usize;
+    for word in ram_preprocessing.bytecode_words.iter() {
+        initial_memory_state[index] = *word;
+        index += 1;
+    }
+
+    let dram_start_index =
+        remap_address(RAM_START_ADDRESS, &program_io.memory_layout).unwrap() as usize;
+    let mut final_memory_state = vec![0; K];
+    // Note that `final_memory` only contains memory at addresses >= `RAM_START_ADDRESS`
+    // so we will still need to populate `final_memory_state` with the contents of
+    // `program_io`, which lives at addresses < `RAM_START_ADDRESS`
+    final_memory_state[dram_start_index..]
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(k, word)| {
+            *word = final_memory.read_word(4 * k as u64);
+        });
+
+    index = remap_address(
+        program_io.memory_layout.input_start,
+        &program_io.memory_layout,
+    )
+    .unwrap() as usize;
+    // Convert input bytes into words and populate
+    // `initial_memory_state` and `final_memory_state`
+    for chunk in program_io.inputs.chunks(4) {
+        let mut word = [0u8; 4];
+        for (i, byte) in chunk.iter().enumerate() {
+            word[i] = *byte;
+        }
+        let word = u32::from_le_bytes(word);
+        initial_memory_state[index] = word;
+        final_memory_state[index] = word;
+        index += 1;
+    }
+
+    // Private inputs are also part of the initial and final memory state (the
+    // output check constrains the guest to leave them unchanged), but are not
+    // known to the verifier
+    index = remap_address(
+        program_io.memory_layout.private_input_start,
+        &program_io.memory_layout,
+    )
+    .unwrap() as usize;
+    for word in private_input_words(program_io) {
+        initial_memory_state[index] = word;
+        final_memory_state[index] = word;
+        index += 1;
+    }
+
+    // The LR/SC reservation word starts out as 0 and is only written
+    // by the LR.W/SC.W virtual sequences. It is emulator state, kept in
+    // `final_memory` rather than in the program's I/O.
+    let reservation_index =
+        remap_address(RESERVATION_ADDRESS, &program_io.memory_layout).unwrap() as usize;
+    final_memory_state[reservation_index] = final_memory.reservation;
+
+    // Convert output bytes into words and populate
+    // `final_memory_state`
+    index = remap_address(
+        program_io.memory_layout.output_start,
+        &program_io.memory_layout,
+    )
+    .unwrap() as usize;
+    for chunk in program_io.outputs.chunks(4) {
+        let mut word = [0u8; 4];
+        for (i, byte) in chunk.iter().enumerate() {
+            word[i] = *byte;
+        }
+        let word = u32::from_le_bytes(word);
+        final_memory_state[index] = word;
+        index += 1;
+    }
+
+    // Copy panic bit
+    let panic_index =
+        remap_address(program_io.memory_layout.panic, &program_io.memory_layout).unwrap() as usize;
+    final_memory_state[panic_index] = program_io.panic as u32;
+    // A guest stopped by a stack or heap overflow never reaches its termination bit
+    if !program_io.panic && program_io.fault.is_none() {
+        // Set termination bit
+        let termination_index = remap_address(
+            program_io.memory_layout.termination,
+            &program_io.memory_layout,
+        )
+        .unwrap() as usize;
+        final_memory_state[termination_index] = 1;
+    }
+
+    (initial_memory_state, final_memory_state)
+}
+
 pub struct RamDag {
     K: usize,
     T: usize,
@@ -107,85 +490,16 @@ impl RamDag {
         state_manager: &StateManager<'_, F, ProofTranscript, PCS>,
     ) -> Self {
         let (preprocessing, trace, program_io, final_memory) = state_manager.get_prover_data();
-        let ram_preprocessing = &preprocessing.shared.ram;
 
         let K = state_manager.ram_K;
 
         let T = trace.len();
 
-        let mut initial_memory_state = vec![0; K];
-        // Copy bytecode
-        let mut index = remap_address(
-            ram_preprocessing.min_bytecode_address,
-            &program_io.memory_layout,
-        )
-        .unwrap() as usize;
-        for word in ram_preprocessing.bytecode_words.iter() {
-            initial_memory_state[index] = *word;
-            index += 1;
-        }
-
-        let dram_start_index =
-            remap_address(RAM_START_ADDRESS, &program_io.memory_layout).unwrap() as usize;
-        let mut final_memory_state = vec![0; K];
-        // Note that `final_memory` only contains memory at addresses >= `RAM_START_ADDRESS`
-        // so we will still need to populate `final_memory_state` with the contents of
-        // `program_io`, which lives at addresses < `RAM_START_ADDRESS`
-        final_memory_state[dram_start_index..]
-            .par_iter_mut()
-            .enumerate()
-            .for_each(|(k, word)| {
-                *word = final_memory.read_word(4 * k as u64);
-            });
-
-        index = remap_address(
-            program_io.memory_layout.input_start,
-            &program_io.memory_layout,
-        )
-        .unwrap() as usize;
-        // Convert input bytes into words and populate
-        // `initial_memory_state` and `final_memory_state`
-        for chunk in program_io.inputs.chunks(4) {
-            let mut word = [0u8; 4];
-            for (i, byte) in chunk.iter().enumerate() {
-                word[i] = *byte;
-            }
-            let word = u32::from_le_bytes(word);
-            initial_memory_state[index] = word;
-            final_memory_state[index] = word;
-            index += 1;
-        }
-
-        // Convert output bytes into words and populate
-        // `final_memory_state`
-        index = remap_address(
-            program_io.memory_layout.output_start,
-            &program_io.memory_layout,
-        )
-        .unwrap() as usize;
-        for chunk in program_io.outputs.chunks(4) {
-            let mut word = [0u8; 4];
-            for (i, byte) in chunk.iter().enumerate() {
-                word[i] = *byte;
-            }
-            let word = u32::from_le_bytes(word);
-            final_memory_state[index] = word;
-            index += 1;
-        }
-
-        // Copy panic bit
-        let panic_index = remap_address(program_io.memory_layout.panic, &program_io.memory_layout)
-            .unwrap() as usize;
-        final_memory_state[panic_index] = program_io.panic as u32;
-        if !program_io.panic {
-            // Set termination bit
-            let termination_index = remap_address(
-                program_io.memory_layout.termination,
-                &program_io.memory_layout,
-            )
-            .unwrap() as usize;
-            final_memory_state[termination_index] = 1;
-        }
+        // A segment of a continuation starts and ends in the RAM states at its boundaries
+        let (initial_memory_state, final_memory_state) = match &state_manager.segment {
+            Some(segment) => segment.ram.clone().expect("Segment RAM states not set"),
+            None => program_memory_states(&preprocessing.shared.ram, program_io, final_memory, K),
+        };
 
         #[cfg(test)]
         {
@@ -232,6 +546,19 @@ impl RamDag {
 
         let K = state_manager.ram_K;
 
+        // A segment of a continuation which follows another segment starts in a
+        // committed RAM state, whose evaluations are opened by the prover
+        if let Some(segment) = &state_manager.segment {
+            if segment.commits_initial_ram() {
+                return Self {
+                    K,
+                    T,
+                    initial_memory_state: None,
+                    final_memory_state: None,
+                };
+            }
+        }
+
         let mut initial_memory_state = vec![0; K];
         // Copy bytecode
         let mut index = remap_address(
@@ -260,6 +587,8 @@ impl RamDag {
             initial_memory_state[index] = word;
             index += 1;
         }
+        // Note that the private inputs are omitted: their contribution to
+        // Val_init is proven via an opening of `CommittedPolynomial::PrivateInputs`
 
         Self {
             K,
@@ -339,13 +668,11 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
     ) -> Vec<Box<dyn SumcheckInstance<F>>> {
         let val_evaluation = ValEvaluationSumcheck::new_verifier(
             self.K,
-            self.initial_memory_state.as_ref().unwrap(),
-            state_manager,
-        );
-        let val_final_evaluation = ValFinalSumcheck::new_verifier(
-            self.initial_memory_state.as_ref().unwrap(),
+            self.initial_memory_state.as_deref(),
             state_manager,
         );
+        let val_final_evaluation =
+            ValFinalSumcheck::new_verifier(self.initial_memory_state.as_deref(), state_manager);
         let hamming_booleanity = HammingBooleanitySumcheck::new_verifier(state_manager);
 
         vec![
@@ -385,3 +712,62 @@ impl<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::multilinear_polynomial::PolynomialEvaluation;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use common::jolt_device::MemoryConfig;
+
+    #[test]
+    fn no_private_input_region_by_default() {
+        let program_io = JoltDevice::new(&MemoryConfig {
+            program_size: Some(1024),
+            ..Default::default()
+        });
+        let memory_layout = &program_io.memory_layout;
+        assert!(!has_private_inputs(memory_layout));
+        assert_eq!(
+            memory_layout.private_input_start,
+            memory_layout.private_input_end
+        );
+        assert!(private_input_words(&program_io).is_empty());
+    }
+
+    #[test]
+    fn private_input_opening_matches_val_init() {
+        let mut program_io = JoltDevice::new(&MemoryConfig {
+            max_private_input_size: 64,
+            program_size: Some(1024),
+            ..Default::default()
+        });
+        program_io.private_inputs = (1..=40).collect();
+        let memory_layout = program_io.memory_layout.clone();
+
+        // Val_init restricted to the private input region
+        let K = (remap_address(RAM_START_ADDRESS, &memory_layout).unwrap() as usize)
+            .next_power_of_two();
+        let mut val_init = vec![0u32; K];
+        let private_input_start =
+            remap_address(memory_layout.private_input_start, &memory_layout).unwrap() as usize;
+        for (i, word) in private_input_words(&program_io).into_iter().enumerate() {
+            val_init[private_input_start + i] = word;
+        }
+        let val_init: MultilinearPolynomial<Fr> = val_init.into();
+
+        let log_T = 8;
+        let private_inputs: MultilinearPolynomial<Fr> =
+            private_input_polynomial(&program_io, 1 << log_T);
+
+        let mut rng = test_rng();
+        let r_address: Vec<Fr> = (0..K.log_2()).map(|_| Fr::random(&mut rng)).collect();
+        let (opening_point, eq_eval) =
+            private_input_opening_point(&r_address, &memory_layout, log_T);
+        assert_eq!(
+            val_init.evaluate(&r_address),
+            eq_eval * private_inputs.evaluate(&opening_point)
+        );
+    }
+}
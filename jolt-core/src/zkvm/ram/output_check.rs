@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, ops::Range, rc::Rc};
 
 use crate::{
     field::JoltField,
@@ -19,12 +19,15 @@ use crate::{
     utils::{expanding_table::ExpandingTable, math::Math, transcript::Transcript},
     zkvm::dag::state_manager::StateManager,
     zkvm::{
-        ram::remap_address,
+        ram::{
+            append_private_input_opening_prover, append_private_input_opening_verifier,
+            has_private_inputs, private_input_opening_point, ram_state_opening_point,
+            remap_address,
+        },
         witness::{CommittedPolynomial, VirtualPolynomial},
     },
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use common::constants::RAM_START_ADDRESS;
 use rayon::prelude::*;
 use tracer::JoltDevice;
 
@@ -48,6 +51,7 @@ struct OutputSumcheckProverState<F: JoltField> {
     /// EQ(x_1, ..., x_k, r_1, ..., r_k), where r_i is the
     /// random challenge for the i'th round of sumcheck.
     eq_table: ExpandingTable<F>,
+    program_io: JoltDevice,
 }
 
 impl<F: JoltField> OutputSumcheckProverState<F> {
@@ -55,21 +59,17 @@ impl<F: JoltField> OutputSumcheckProverState<F> {
     fn new(
         initial_ram_state: Vec<u32>,
         final_ram_state: Vec<u32>,
-        program_io: &JoltDevice,
+        io_range: Range<usize>,
         r_address: &[F],
+        program_io: &JoltDevice,
     ) -> Self {
         let K = final_ram_state.len();
         debug_assert_eq!(initial_ram_state.len(), final_ram_state.len());
         debug_assert!(K.is_power_of_two());
-
-        // Compute the witness indices corresponding to the start and end of the IO
-        // region of memory
-        let io_start = remap_address(
-            program_io.memory_layout.input_start,
-            &program_io.memory_layout,
-        )
-        .unwrap() as usize;
-        let io_end = remap_address(RAM_START_ADDRESS, &program_io.memory_layout).unwrap() as usize;
+        let Range {
+            start: io_start,
+            end: io_end,
+        } = io_range;
 
         // Compute Val_io by copying the relevant slice of Val_final
         let mut val_io = vec![0; K];
@@ -95,6 +95,7 @@ impl<F: JoltField> OutputSumcheckProverState<F> {
             eq_poly: EqPolynomial::evals(r_address).into(),
             io_mask: io_mask.into(),
             eq_table,
+            program_io: program_io.clone(),
         }
     }
 }
@@ -120,8 +121,21 @@ pub struct OutputProof<F: JoltField, ProofTranscript: Transcript> {
 /// In plain English: the final memory state (Val_final) should be consistent with
 /// the expected program outputs (Val_io) at the indices where the program
 /// inputs/outputs are stored (io_range).
+/// The private input region is part of io_range, with Val_io equal to the
+/// committed private inputs there, so the guest must leave it unchanged.
 pub struct OutputSumcheck<F: JoltField> {
     K: usize,
+    /// log2 of the trace length, if the private inputs are opened at the end of
+    /// this sumcheck (i.e. they are non-empty and this is not a segment of a
+    /// continuation)
+    private_inputs_log_T: Option<usize>,
+    /// log2 of the trace length, if this is a segment of a continuation that starts
+    /// in a committed RAM state, which is opened at the end of this sumcheck
+    initial_ram_log_T: Option<usize>,
+    /// log2 of the trace length, if this is a segment of a continuation that ends in
+    /// a committed RAM state. Val_final must then equal that state everywhere, rather
+    /// than just in the IO region.
+    final_ram_log_T: Option<usize>,
     verifier_state: Option<OutputSumcheckVerifierState<F>>,
     prover_state: Option<OutputSumcheckProverState<F>>,
 }
@@ -133,9 +147,26 @@ impl<F: JoltField> OutputSumcheck<F> {
         final_ram_state: Vec<u32>,
         state_manager: &mut StateManager<'_, F, ProofTranscript, PCS>,
     ) -> Self {
-        let (_, _, program_io, _) = state_manager.get_prover_data();
+        let (_, trace, program_io, _) = state_manager.get_prover_data();
         let K = final_ram_state.len();
 
+        let (initial_ram_log_T, final_ram_log_T) =
+            committed_ram_log_T(state_manager, trace.len().log_2());
+
+        // The witness indices corresponding to the start and end of the IO region of
+        // memory, private inputs included. A segment of a continuation that ends in a
+        // committed RAM state is checked against that state in full.
+        let io_range = if final_ram_log_T.is_some() {
+            0..K
+        } else {
+            let memory_layout = &program_io.memory_layout;
+            remap_address(memory_layout.input_start, memory_layout).unwrap() as usize
+                ..remap_address(memory_layout.private_input_end, memory_layout).unwrap() as usize
+        };
+        let private_inputs_log_T = (state_manager.segment.is_none()
+            && has_private_inputs(&program_io.memory_layout))
+        .then(|| trace.len().log_2());
+
         let r_address = state_manager
             .transcript
             .borrow_mut()
@@ -144,12 +175,16 @@ impl<F: JoltField> OutputSumcheck<F> {
         let output_sumcheck_prover_state = OutputSumcheckProverState::new(
             initial_ram_state,
             final_ram_state,
-            program_io,
+            io_range,
             &r_address,
+            program_io,
         );
 
         OutputSumcheck {
             K,
+            private_inputs_log_T,
+            initial_ram_log_T,
+            final_ram_log_T,
             verifier_state: None,
             prover_state: Some(output_sumcheck_prover_state),
         }
@@ -159,7 +194,11 @@ impl<F: JoltField> OutputSumcheck<F> {
         K: usize,
         state_manager: &mut StateManager<'_, F, ProofTranscript, PCS>,
     ) -> Self {
-        let (_, program_io, _) = state_manager.get_verifier_data();
+        let (_, program_io, T) = state_manager.get_verifier_data();
+        let private_inputs_log_T = (state_manager.segment.is_none()
+            && has_private_inputs(&program_io.memory_layout))
+        .then(|| T.log_2());
+        let (initial_ram_log_T, final_ram_log_T) = committed_ram_log_T(state_manager, T.log_2());
 
         let r_address = state_manager
             .transcript
@@ -173,6 +212,9 @@ impl<F: JoltField> OutputSumcheck<F> {
 
         OutputSumcheck {
             K,
+            private_inputs_log_T,
+            initial_ram_log_T,
+            final_ram_log_T,
             verifier_state: Some(output_sumcheck_verifier_state),
             prover_state: None,
         }
@@ -275,6 +317,22 @@ impl<F: JoltField> SumcheckInstance<F> for OutputSumcheck<F> {
             .1;
 
         let r_address_prime = &r[..r_address.len()];
+        let eq_eval = EqPolynomial::mle(r_address, r_address_prime);
+
+        if self.final_ram_log_T.is_some() {
+            // The IO region spans all of memory, and Val_io is the committed final
+            // RAM state, opened in `cache_openings_verifier`
+            let final_ram_claim = accumulator
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .get_committed_polynomial_opening(
+                    CommittedPolynomial::RamFinalState,
+                    SumcheckId::RamOutputCheck,
+                )
+                .1;
+            return eq_eval * (val_final_claim - final_ram_claim);
+        }
 
         let io_mask = RangeMaskPolynomial::new(
             remap_address(
@@ -282,13 +340,32 @@ impl<F: JoltField> SumcheckInstance<F> for OutputSumcheck<F> {
                 &program_io.memory_layout,
             )
             .unwrap(),
-            remap_address(RAM_START_ADDRESS, &program_io.memory_layout).unwrap(),
+            remap_address(
+                program_io.memory_layout.private_input_end,
+                &program_io.memory_layout,
+            )
+            .unwrap(),
         );
         let val_io = ProgramIOPolynomial::new(program_io);
 
-        let eq_eval = EqPolynomial::mle(r_address, r_address_prime);
         let io_mask_eval = io_mask.evaluate_mle(r_address_prime);
-        let val_io_eval = val_io.evaluate(r_address_prime);
+        let mut val_io_eval = val_io.evaluate(r_address_prime);
+        if let Some(log_T) = self.private_inputs_log_T {
+            // The final contents of the private input region must equal the
+            // committed private inputs, opened in `cache_openings_verifier`
+            let (_, eq_eval) =
+                private_input_opening_point(r_address_prime, &program_io.memory_layout, log_T);
+            let private_inputs_claim = accumulator
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .get_committed_polynomial_opening(
+                    CommittedPolynomial::PrivateInputs,
+                    SumcheckId::RamOutputCheck,
+                )
+                .1;
+            val_io_eval += eq_eval * private_inputs_claim;
+        }
 
         // Recall that the sumcheck expression is:
         //   0 = \sum_k eq(r_address, k) * io_range(k) * (Val_final(k) - Val_io(k))
@@ -307,9 +384,41 @@ impl<F: JoltField> SumcheckInstance<F> for OutputSumcheck<F> {
         let OutputSumcheckProverState {
             val_final,
             val_init,
+            program_io,
             ..
         } = self.prover_state.as_ref().unwrap();
 
+        if let Some(log_T) = self.private_inputs_log_T {
+            append_private_input_opening_prover(
+                &opening_point.r,
+                SumcheckId::RamOutputCheck,
+                program_io,
+                log_T,
+                &mut accumulator.borrow_mut(),
+            );
+        }
+        for (polynomial, log_T, poly) in [
+            (
+                CommittedPolynomial::RamInitialState,
+                self.initial_ram_log_T,
+                val_init,
+            ),
+            (
+                CommittedPolynomial::RamFinalState,
+                self.final_ram_log_T,
+                val_final,
+            ),
+        ] {
+            if let Some(log_T) = log_T {
+                accumulator.borrow_mut().append_dense(
+                    vec![polynomial],
+                    SumcheckId::RamOutputCheck,
+                    ram_state_opening_point(&opening_point.r, log_T),
+                    &[poly.final_sumcheck_claim()],
+                );
+            }
+        }
+
         accumulator.borrow_mut().append_virtual(
             VirtualPolynomial::RamValFinal,
             SumcheckId::RamOutputCheck,
@@ -329,6 +438,29 @@ impl<F: JoltField> SumcheckInstance<F> for OutputSumcheck<F> {
         accumulator: Rc<RefCell<VerifierOpeningAccumulator<F>>>,
         opening_point: OpeningPoint<BIG_ENDIAN, F>,
     ) {
+        if let Some(log_T) = self.private_inputs_log_T {
+            let OutputSumcheckVerifierState { program_io, .. } =
+                self.verifier_state.as_ref().unwrap();
+            append_private_input_opening_verifier(
+                &opening_point.r,
+                SumcheckId::RamOutputCheck,
+                &program_io.memory_layout,
+                log_T,
+                &mut accumulator.borrow_mut(),
+            );
+        }
+        for (polynomial, log_T) in [
+            (CommittedPolynomial::RamInitialState, self.initial_ram_log_T),
+            (CommittedPolynomial::RamFinalState, self.final_ram_log_T),
+        ] {
+            if let Some(log_T) = log_T {
+                accumulator.borrow_mut().append_dense(
+                    vec![polynomial],
+                    SumcheckId::RamOutputCheck,
+                    ram_state_opening_point(&opening_point.r, log_T),
+                );
+            }
+        }
         accumulator.borrow_mut().append_virtual(
             VirtualPolynomial::RamValFinal,
             SumcheckId::RamOutputCheck,
@@ -342,6 +474,26 @@ impl<F: JoltField> SumcheckInstance<F> for OutputSumcheck<F> {
     }
 }
 
+/// log2 of the trace length for each of the initial and final RAM states, if proving a
+/// segment of a continuation that commits to them (see `SegmentIO`).
+fn committed_ram_log_T<F, ProofTranscript, PCS>(
+    state_manager: &StateManager<'_, F, ProofTranscript, PCS>,
+    log_T: usize,
+) -> (Option<usize>, Option<usize>)
+where
+    F: JoltField,
+    ProofTranscript: Transcript,
+    PCS: CommitmentScheme<Field = F>,
+{
+    match &state_manager.segment {
+        Some(segment) => (
+            segment.commits_initial_ram().then_some(log_T),
+            segment.commits_final_ram().then_some(log_T),
+        ),
+        None => (None, None),
+    }
+}
+
 struct ValFinalSumcheckProverState<F: JoltField> {
     inc: MultilinearPolynomial<F>,
     wa: MultilinearPolynomial<F>,
@@ -354,7 +506,7 @@ struct ValFinalSumcheckProverState<F: JoltField> {
 /// We feed the output claim Val_final(r_address) from `OutputSumcheck`
 /// into this sumcheck, which reduces it to claims about `Inc` and `wa`.
 /// Note that the verifier is assumed to be able to evaluate Val_init
-/// on its own.
+/// on its own, unless it is a committed RAM state opened by `OutputSumcheck`.
 pub struct ValFinalSumcheck<F: JoltField> {
     T: usize,
     prover_state: Option<ValFinalSumcheckProverState<F>>,
@@ -444,11 +596,12 @@ impl<F: JoltField> ValFinalSumcheck<F> {
         }
     }
 
+    /// `initial_ram_state` is `None` if RAM starts in a committed state.
     pub fn new_verifier<ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>>(
-        initial_ram_state: &[u32],
+        initial_ram_state: Option<&[u32]>,
         state_manager: &mut StateManager<'_, F, ProofTranscript, PCS>,
     ) -> Self {
-        let (_, _, T) = state_manager.get_verifier_data();
+        let (_, program_io, T) = state_manager.get_verifier_data();
 
         let r_address = state_manager
             .get_virtual_polynomial_opening(
@@ -458,9 +611,40 @@ impl<F: JoltField> ValFinalSumcheck<F> {
             .0
             .r;
 
+        let Some(initial_ram_state) = initial_ram_state else {
+            // The committed initial RAM state was opened by `OutputSumcheck`
+            let (_, val_init_eval) = state_manager.get_committed_polynomial_opening(
+                CommittedPolynomial::RamInitialState,
+                SumcheckId::RamOutputCheck,
+            );
+            let val_final_claim = state_manager
+                .get_virtual_polynomial_opening(
+                    VirtualPolynomial::RamValFinal,
+                    SumcheckId::RamOutputCheck,
+                )
+                .1;
+            return Self {
+                T,
+                prover_state: None,
+                val_init_eval,
+                val_final_claim,
+            };
+        };
         let val_init: MultilinearPolynomial<F> =
             MultilinearPolynomial::from(initial_ram_state.to_vec());
-        let val_init_eval = val_init.evaluate(&r_address);
+        let mut val_init_eval = val_init.evaluate(&r_address);
+        if state_manager.segment.is_none() && has_private_inputs(&program_io.memory_layout) {
+            // `initial_ram_state` omits the private inputs, so their contribution is
+            // added via the opening of `PrivateInputs` made by `OutputSumcheck` at
+            // the same point
+            let (_, eq_eval) =
+                private_input_opening_point(&r_address, &program_io.memory_layout, T.log_2());
+            let (_, private_inputs_claim) = state_manager.get_committed_polynomial_opening(
+                CommittedPolynomial::PrivateInputs,
+                SumcheckId::RamOutputCheck,
+            );
+            val_init_eval += eq_eval * private_inputs_claim;
+        }
         let val_final_claim = state_manager
             .get_virtual_polynomial_opening(
                 VirtualPolynomial::RamValFinal,
@@ -17,7 +17,10 @@ use crate::{
     utils::{math::Math, thread::unsafe_allocate_zero_vec, transcript::Transcript},
     zkvm::dag::state_manager::StateManager,
     zkvm::{
-        ram::remap_address,
+        ram::{
+            prove_initial_ram_opening, prove_private_input_opening, remap_address,
+            verify_initial_ram_opening, verify_private_input_opening,
+        },
         witness::{CommittedPolynomial, VirtualPolynomial},
     },
 };
@@ -62,6 +65,13 @@ impl<F: JoltField> ValEvaluationSumcheck<F> {
         let val_init: MultilinearPolynomial<F> =
             MultilinearPolynomial::from(initial_ram_state.to_vec());
         let init_eval = val_init.evaluate(&r_address.r);
+        prove_private_input_opening(&r_address.r, SumcheckId::RamValEvaluation, state_manager);
+        prove_initial_ram_opening(
+            &r_address.r,
+            init_eval,
+            SumcheckId::RamValEvaluation,
+            state_manager,
+        );
 
         // Compute the size-K table storing all eq(r_address, k) evaluations for
         // k \in {0, 1}^log(K)
@@ -113,9 +123,11 @@ impl<F: JoltField> ValEvaluationSumcheck<F> {
         }
     }
 
+    /// `initial_ram_state` is `None` if RAM starts in a committed state (see
+    /// `ram::verify_initial_ram_opening`).
     pub fn new_verifier<ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>>(
         K: usize,
-        initial_ram_state: &[u32],
+        initial_ram_state: Option<&[u32]>,
         state_manager: &mut StateManager<'_, F, ProofTranscript, PCS>,
     ) -> Self {
         let (_, _, T) = state_manager.get_verifier_data();
@@ -126,9 +138,25 @@ impl<F: JoltField> ValEvaluationSumcheck<F> {
         );
         let (r_address, r_cycle) = r.split_at(K.log_2());
 
-        let val_init: MultilinearPolynomial<F> =
-            MultilinearPolynomial::from(initial_ram_state.to_vec());
-        let init_eval = val_init.evaluate(&r_address.r);
+        let init_eval = match initial_ram_state {
+            Some(initial_ram_state) => {
+                let val_init: MultilinearPolynomial<F> =
+                    MultilinearPolynomial::from(initial_ram_state.to_vec());
+                // `initial_ram_state` omits the private inputs, so their contribution is
+                // added via an opening of the `PrivateInputs` commitment
+                val_init.evaluate(&r_address.r)
+                    + verify_private_input_opening(
+                        &r_address.r,
+                        SumcheckId::RamValEvaluation,
+                        state_manager,
+                    )
+            }
+            None => verify_initial_ram_opening(
+                &r_address.r,
+                SumcheckId::RamValEvaluation,
+                state_manager,
+            ),
+        };
 
         ValEvaluationSumcheck {
             claimed_evaluation,
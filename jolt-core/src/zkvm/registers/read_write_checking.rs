@@ -77,6 +77,7 @@ impl<F: JoltField> ReadWriteCheckingProverState<F> {
     fn initialize<PCS: CommitmentScheme<Field = F>>(
         preprocessing: &JoltProverPreprocessing<F, PCS>,
         trace: &[RV32IMCycle],
+        initial_registers: Option<&[u64]>,
         r_prime: &[F],
     ) -> Self {
         let T = trace.len();
@@ -106,7 +107,11 @@ impl<F: JoltField> ReadWriteCheckingProverState<F> {
 
         // Value in register k before the jth cycle, for j \in {0, chunk_size, 2 * chunk_size, ...}
         let mut checkpoints: Vec<[i128; K]> = Vec::with_capacity(num_chunks);
-        checkpoints.push([0; K]);
+        // Registers start out as zero, except in a segment of a continuation
+        checkpoints.push(match initial_registers {
+            Some(registers) => std::array::from_fn(|k| registers[k] as i128),
+            None => [0; K],
+        });
 
         for (chunk_index, delta) in deltas.into_iter().enumerate() {
             let next_checkpoint: [i128; K] =
@@ -263,8 +268,16 @@ impl<F: JoltField> RegistersReadWriteChecking<F> {
         let gamma: F = transcript.challenge_scalar();
         let input_claim = rd_wv_claim + gamma * rs1_rv_claim + gamma.square() * rs2_rv_claim;
 
-        let prover_state =
-            ReadWriteCheckingProverState::initialize(preprocessing, trace, &r_cycle.r);
+        let initial_registers = state_manager
+            .segment
+            .as_ref()
+            .map(|segment| segment.claim.initial_state.registers.as_slice());
+        let prover_state = ReadWriteCheckingProverState::initialize(
+            preprocessing,
+            trace,
+            initial_registers,
+            &r_cycle.r,
+        );
 
         Self {
             T: trace.len(),
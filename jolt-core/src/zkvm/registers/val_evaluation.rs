@@ -30,6 +30,9 @@ pub(crate) struct ValEvaluationSumcheck<F: JoltField> {
     pub input_claim: F,
     pub num_rounds: usize,
     pub r_cycle: Vec<F>,
+    /// Batching challenge for the final register values of a segment of a
+    /// continuation (see [`segment_claim`])
+    pub mu: Option<F>,
     pub prover_state: Option<ValEvaluationProverState<F>>,
 }
 
@@ -76,14 +79,19 @@ impl<F: JoltField> ValEvaluationSumcheck<F> {
                     *x += *r - *y;
                 });
         }
+        let (input_claim, mu) = segment_claim(state_manager, &r_address, val_claim);
+        if let Some(mu) = mu {
+            lt.par_iter_mut().for_each(|x| *x += mu);
+        }
         let lt = MultilinearPolynomial::from(lt);
 
         let num_rounds = r_cycle.len().pow2().log_2();
         Self {
-            input_claim: val_claim,
+            input_claim,
             r_address,
             num_rounds,
             r_cycle,
+            mu,
             prover_state: Some(ValEvaluationProverState { inc, wa, lt }),
         }
     }
@@ -106,16 +114,52 @@ impl<F: JoltField> ValEvaluationSumcheck<F> {
         let r_address: Vec<F> = r_address_slice.into();
         let r_cycle: Vec<F> = r_cycle_slice.into();
 
+        let (input_claim, mu) = segment_claim(state_manager, &r_address, val_claim);
         Self {
-            input_claim: val_claim,
+            input_claim,
             r_address,
             num_rounds: trace_length.log_2(),
             r_cycle,
+            mu,
             prover_state: None,
         }
     }
 }
 
+/// Registers start out as zero, so that
+///   Val(r_address, r_cycle) = \sum_j Inc(j) * wa(r_address, j) * LT(j, r_cycle)
+/// In a segment of a continuation, they instead start out in the segment's initial
+/// state Init and must end in its final state Final, where
+///   Final(r_address) - Init(r_address) = \sum_j Inc(j) * wa(r_address, j)
+/// Batching the two with a random mu, the sumcheck becomes
+///   Val(r_address, r_cycle) - Init(r_address) + mu * (Final(r_address) - Init(r_address))
+///     = \sum_j Inc(j) * wa(r_address, j) * (LT(j, r_cycle) + mu)
+/// Returns the input claim for the sumcheck along with mu, if proving a segment.
+fn segment_claim<F: JoltField, ProofTranscript: Transcript, PCS: CommitmentScheme<Field = F>>(
+    state_manager: &StateManager<'_, F, ProofTranscript, PCS>,
+    r_address: &[F],
+    val_claim: F,
+) -> (F, Option<F>) {
+    let Some(segment) = &state_manager.segment else {
+        return (val_claim, None);
+    };
+    let mu: F = state_manager.transcript.borrow_mut().challenge_scalar();
+    let eq_r_address = EqPolynomial::evals(r_address);
+    let evaluate = |registers: &[u64]| -> F {
+        eq_r_address
+            .iter()
+            .zip(registers)
+            .map(|(eq, value)| eq.mul_u64(*value))
+            .sum()
+    };
+    let init_eval = evaluate(&segment.claim.initial_state.registers);
+    let final_eval = evaluate(&segment.claim.final_state.registers);
+    (
+        val_claim - init_eval + mu * (final_eval - init_eval),
+        Some(mu),
+    )
+}
+
 impl<F: JoltField> SumcheckInstance<F> for ValEvaluationSumcheck<F> {
     fn degree(&self) -> usize {
         3
@@ -209,8 +253,8 @@ impl<F: JoltField> SumcheckInstance<F> for ValEvaluationSumcheck<F> {
             SumcheckId::RegistersValEvaluation,
         );
 
-        // Return inc_claim * wa_claim * lt_eval
-        inc_claim * wa_claim * lt_eval
+        // Return inc_claim * wa_claim * (lt_eval + mu)
+        inc_claim * wa_claim * (lt_eval + self.mu.unwrap_or(F::zero()))
     }
 
     fn normalize_opening_point(&self, opening_point: &[F]) -> OpeningPoint<BIG_ENDIAN, F> {
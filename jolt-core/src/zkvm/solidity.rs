@@ -0,0 +1,250 @@
+//! Building blocks for verifying Jolt proofs on-chain: a Solidity port of the
+//! Fiat-Shamir transcript, a generated contract that recomputes the verifier's
+//! Fiat-Shamir preamble, and ABI encoding of a proof and its program I/O.
+//!
+//! `KeccakTranscript` was designed to be EVM-friendly (all values are absorbed
+//! big-endian, in 32-byte words), and `JoltTranscript` below is a faithful port of it.
+//! This is *not* a verifier: the generated `JoltPreamble` contract only derives the
+//! transcript state that the sumcheck stages start from. The sumcheck stages and the
+//! final opening check are not generated, so nothing here checks a proof.
+
+use alloy_primitives::Bytes;
+use alloy_sol_types::{sol, SolValue};
+use ark_bn254::Fr;
+use ark_serialize::CanonicalSerialize;
+use common::jolt_device::GuestFault;
+use tracer::JoltDevice;
+
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::utils::transcript::Transcript;
+use crate::zkvm::dag::proof_serialization::JoltProof;
+use crate::zkvm::JoltVerifierPreprocessing;
+
+sol! {
+    /// A proof and its program I/O, as passed to the generated contract.
+    #[derive(Debug, PartialEq, Eq)]
+    struct JoltProofInput {
+        bytes inputs;
+        bytes outputs;
+        bool panic;
+        uint8 fault;
+        uint64 traceLength;
+        uint64 ramK;
+        bytes proof;
+    }
+}
+
+/// ABI-encodes a proof and its program I/O as a `JoltProofInput`, the argument
+/// expected by the generated preamble contract.
+pub fn encode_proof_input<PCS, FS>(
+    proof: &JoltProof<Fr, PCS, FS>,
+    program_io: &JoltDevice,
+) -> Vec<u8>
+where
+    PCS: CommitmentScheme<Field = Fr>,
+    FS: Transcript,
+{
+    // The verifier truncates trailing zeros on device outputs
+    let outputs_len = program_io
+        .outputs
+        .iter()
+        .rposition(|&b| b != 0)
+        .map_or(0, |pos| pos + 1);
+
+    let mut proof_bytes = Vec::new();
+    proof.serialize_compressed(&mut proof_bytes).unwrap();
+
+    JoltProofInput {
+        inputs: Bytes::from(program_io.inputs.clone()),
+        outputs: Bytes::from(program_io.outputs[..outputs_len].to_vec()),
+        panic: program_io.panic,
+        fault: GuestFault::code(program_io.fault),
+        traceLength: proof.trace_length as u64,
+        ramK: proof.ram_K as u64,
+        proof: Bytes::from(proof_bytes),
+    }
+    .abi_encode()
+}
+
+/// Generates the Solidity source of a contract that recomputes the Fiat-Shamir preamble
+/// of proofs for the program described by `preprocessing`. Segment proofs (see
+/// `zkvm::continuations`) additionally absorb their boundary claim, and proofs of faulted
+/// runs their faulting access (see `zkvm::fault`), which the contract does not support.
+pub fn generate_preamble_contract<PCS: CommitmentScheme<Field = Fr>>(
+    preprocessing: &JoltVerifierPreprocessing<Fr, PCS>,
+) -> String {
+    let memory_layout = &preprocessing.shared.memory_layout;
+    let program_digest: String = preprocessing
+        .image_id()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    format!(
+        r#"// SPDX-License-Identifier: MIT
+// This file was generated by jolt-core. Do not edit.
+pragma solidity ^0.8.20;
+
+{TRANSCRIPT_LIBRARY}
+struct JoltProofInput {{
+    bytes inputs;
+    bytes outputs;
+    bool panic;
+    uint8 fault;
+    uint64 traceLength;
+    uint64 ramK;
+    bytes proof;
+}}
+
+contract JoltPreamble {{
+    bytes32 public constant PROGRAM_DIGEST = 0x{program_digest};
+    uint64 public constant REGISTER_COUNT = {register_count};
+    uint64 public constant MAX_INPUT_SIZE = {max_input_size};
+    uint64 public constant MAX_OUTPUT_SIZE = {max_output_size};
+    uint64 public constant MAX_PRIVATE_INPUT_SIZE = {max_private_input_size};
+    uint64 public constant MEMORY_SIZE = {memory_size};
+
+    /// Absorbs the program digest and public I/O into a fresh transcript, exactly as
+    /// `StateManager::fiat_shamir_preamble` does, and returns the transcript state.
+    function fiatShamirPreamble(JoltProofInput calldata input)
+        public
+        pure
+        returns (bytes32 state, uint32 nRounds)
+    {{
+        require(input.inputs.length <= MAX_INPUT_SIZE, "Input too large");
+        require(input.outputs.length <= MAX_OUTPUT_SIZE, "Output too large");
+
+        JoltTranscript.Transcript memory transcript = JoltTranscript.create("Jolt");
+        JoltTranscript.appendBytes(transcript, abi.encodePacked(PROGRAM_DIGEST));
+        JoltTranscript.appendU64(transcript, REGISTER_COUNT);
+        JoltTranscript.appendU64(transcript, MAX_INPUT_SIZE);
+        JoltTranscript.appendU64(transcript, MAX_OUTPUT_SIZE);
+        JoltTranscript.appendU64(transcript, MAX_PRIVATE_INPUT_SIZE);
+        JoltTranscript.appendU64(transcript, MEMORY_SIZE);
+        JoltTranscript.appendBytes(transcript, input.inputs);
+        JoltTranscript.appendBytes(transcript, input.outputs);
+        JoltTranscript.appendU64(transcript, input.panic ? 1 : 0);
+        JoltTranscript.appendU64(transcript, input.fault);
+        JoltTranscript.appendU64(transcript, input.ramK);
+        JoltTranscript.appendU64(transcript, input.traceLength);
+        return (transcript.state, transcript.nRounds);
+    }}
+}}
+"#,
+        register_count = preprocessing.shared.register_count,
+        max_input_size = memory_layout.max_input_size,
+        max_output_size = memory_layout.max_output_size,
+        max_private_input_size = memory_layout.max_private_input_size,
+        memory_size = memory_layout.memory_size,
+    )
+}
+
+/// Solidity port of `KeccakTranscript`.
+const TRANSCRIPT_LIBRARY: &str = r#"library JoltTranscript {
+    /// Modulus of the BN254 scalar field
+    uint256 internal constant MODULUS =
+        21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+    struct Transcript {
+        bytes32 state;
+        uint32 nRounds;
+    }
+
+    /// `label` must be right-padded with zeros, as Solidity does for string literals.
+    function create(bytes32 label) internal pure returns (Transcript memory) {
+        return Transcript(keccak256(abi.encodePacked(label)), 0);
+    }
+
+    function appendMessage(Transcript memory transcript, bytes32 message) internal pure {
+        update(transcript, keccak256(abi.encodePacked(prefix(transcript), message)));
+    }
+
+    function appendBytes(Transcript memory transcript, bytes memory data) internal pure {
+        update(transcript, keccak256(abi.encodePacked(prefix(transcript), data)));
+    }
+
+    function appendU64(Transcript memory transcript, uint64 x) internal pure {
+        update(transcript, keccak256(abi.encodePacked(prefix(transcript), uint256(x))));
+    }
+
+    function appendScalar(Transcript memory transcript, uint256 scalar) internal pure {
+        appendBytes(transcript, abi.encodePacked(scalar));
+    }
+
+    function appendScalars(Transcript memory transcript, uint256[] memory scalars) internal pure {
+        appendMessage(transcript, "begin_append_vector");
+        for (uint256 i = 0; i < scalars.length; i++) {
+            appendScalar(transcript, scalars[i]);
+        }
+        appendMessage(transcript, "end_append_vector");
+    }
+
+    /// Appends an affine G1 point; the point at infinity is encoded as (0, 0).
+    function appendPoint(Transcript memory transcript, uint256 x, uint256 y) internal pure {
+        appendBytes(transcript, abi.encodePacked(x, y));
+    }
+
+    function challengeScalar(Transcript memory transcript) internal pure returns (uint256) {
+        bytes32 rand = keccak256(abi.encodePacked(prefix(transcript)));
+        update(transcript, rand);
+        return uint256(rand) % MODULUS;
+    }
+
+    /// The running state followed by the round counter, left-padded to 32 bytes.
+    function prefix(Transcript memory transcript) private pure returns (bytes memory) {
+        return abi.encodePacked(transcript.state, bytes28(0), transcript.nRounds);
+    }
+
+    function update(Transcript memory transcript, bytes32 newState) private pure {
+        transcript.state = newState;
+        transcript.nRounds += 1;
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host;
+    use crate::zkvm::{Jolt, JoltRV32IM};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn proof_input_round_trip() {
+        let mut program = host::Program::new("fibonacci-guest");
+        let inputs = postcard::to_stdvec(&9u32).unwrap();
+        let (bytecode, init_memory_state, _) = program.decode();
+        let io_device = program.trace(&inputs).io_device;
+
+        let preprocessing = JoltRV32IM::prover_preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            init_memory_state,
+            1 << 16,
+        );
+        let (jolt_proof, io_device, _) =
+            JoltRV32IM::prove(&preprocessing, &mut program, &inputs).unwrap();
+
+        let encoded = encode_proof_input(&jolt_proof, &io_device);
+        let decoded = JoltProofInput::abi_decode(&encoded, true).unwrap();
+        assert_eq!(decoded.inputs.to_vec(), io_device.inputs);
+        assert_eq!(decoded.panic, io_device.panic);
+        assert_eq!(decoded.fault, 0);
+        assert_eq!(decoded.traceLength, jolt_proof.trace_length as u64);
+
+        let verifier_preprocessing = JoltVerifierPreprocessing::from(&preprocessing);
+        let contract = generate_preamble_contract(&verifier_preprocessing);
+        let program_digest: String = verifier_preprocessing
+            .image_id()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        assert!(contract.contains(&format!("PROGRAM_DIGEST = 0x{program_digest};")));
+        assert!(contract.contains(&format!(
+            "REGISTER_COUNT = {};",
+            verifier_preprocessing.shared.register_count
+        )));
+        assert!(contract.contains("contract JoltPreamble"));
+    }
+}
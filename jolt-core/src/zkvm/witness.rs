@@ -7,17 +7,25 @@ use once_cell::sync::OnceCell;
 use rayon::prelude::*;
 use strum::IntoEnumIterator;
 use tracer::instruction::RV32IMCycle;
+use tracer::JoltDevice;
 
 use crate::{
     field::JoltField,
     poly::{
-        commitment::commitment_scheme::CommitmentScheme,
-        multilinear_polynomial::MultilinearPolynomial, one_hot_polynomial::OneHotPolynomial,
+        commitment::commitment_scheme::{
+            CommitmentScheme, StreamingChunk, StreamingCommitmentScheme,
+        },
+        multilinear_polynomial::MultilinearPolynomial,
+        one_hot_polynomial::OneHotPolynomial,
     },
     utils::math::Math,
     zkvm::{
         lookup_table::LookupTables,
-        {instruction_lookups, ram::remap_address, JoltProverPreprocessing},
+        {
+            instruction_lookups,
+            ram::{self, remap_address},
+            JoltProverPreprocessing,
+        },
     },
 };
 
@@ -66,13 +74,35 @@ pub enum CommittedPolynomial {
     /// Note that for RAM, ra and wa are the same polynomial because
     /// there is at most one load or store per cycle.
     RamRa(usize),
+    /// The private inputs, which are part of the initial RAM state but are not
+    /// known to the verifier. Unlike the other committed polynomials, this is
+    /// derived from the program I/O rather than the trace; see
+    /// `ram::private_input_opening_point`.
+    PrivateInputs,
+    /// The initial RAM state of a segment of a continuation that follows another
+    /// segment. Its commitment is part of the segment's claim rather than of the proof;
+    /// see `ram::ram_state_polynomial`.
+    RamInitialState,
+    /// The final RAM state of a segment of a continuation that is followed by another
+    /// segment. Its commitment is part of the segment's claim rather than of the proof.
+    RamFinalState,
 }
 
 pub static mut ALL_COMMITTED_POLYNOMIALS: OnceCell<Vec<CommittedPolynomial>> = OnceCell::new();
 
 pub struct AllCommittedPolynomials();
 impl AllCommittedPolynomials {
-    pub fn initialize(ram_d: usize, bytecode_d: usize) -> Self {
+    /// `private_inputs` is whether `PrivateInputs` is committed to, i.e. whether the
+    /// private input region is non-empty. `initial_ram` and `final_ram` are whether
+    /// `RamInitialState` and `RamFinalState` are committed to, i.e. whether proving a
+    /// segment of a continuation that follows or is followed by another segment.
+    pub fn initialize(
+        ram_d: usize,
+        bytecode_d: usize,
+        private_inputs: bool,
+        initial_ram: bool,
+        final_ram: bool,
+    ) -> Self {
         let mut polynomials = vec![
             CommittedPolynomial::LeftInstructionInput,
             CommittedPolynomial::RightInstructionInput,
@@ -98,6 +128,15 @@ impl AllCommittedPolynomials {
         for i in 0..bytecode_d {
             polynomials.push(CommittedPolynomial::BytecodeRa(i));
         }
+        if private_inputs {
+            polynomials.push(CommittedPolynomial::PrivateInputs);
+        }
+        if initial_ram {
+            polynomials.push(CommittedPolynomial::RamInitialState);
+        }
+        if final_ram {
+            polynomials.push(CommittedPolynomial::RamFinalState);
+        }
 
         unsafe {
             ALL_COMMITTED_POLYNOMIALS
@@ -187,6 +226,42 @@ impl CommittedPolynomial {
         preprocessing: &JoltProverPreprocessing<F, PCS>,
         trace: &[RV32IMCycle],
     ) -> MultilinearPolynomial<F>
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<Field = F>,
+    {
+        self.generate_coeffs(preprocessing, trace, true).into()
+    }
+
+    /// Like [`CommittedPolynomial::generate_witness`], but also supports
+    /// `PrivateInputs`, whose witness is derived from `program_io`.
+    pub fn generate_witness_with_io<F, PCS>(
+        &self,
+        preprocessing: &JoltProverPreprocessing<F, PCS>,
+        trace: &[RV32IMCycle],
+        program_io: &JoltDevice,
+    ) -> MultilinearPolynomial<F>
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<Field = F>,
+    {
+        match self {
+            CommittedPolynomial::PrivateInputs => {
+                ram::private_input_polynomial(program_io, trace.len())
+            }
+            _ => self.generate_witness(preprocessing, trace),
+        }
+    }
+
+    /// Computes the coefficients of this polynomial corresponding to the given chunk
+    /// of the trace. `next_is_noop` is whether the cycle immediately following the
+    /// chunk (if any) is a no-op.
+    fn generate_coeffs<F, PCS>(
+        &self,
+        preprocessing: &JoltProverPreprocessing<F, PCS>,
+        trace: &[RV32IMCycle],
+        next_is_noop: bool,
+    ) -> WitnessCoeffs
     where
         F: JoltField,
         PCS: CommitmentScheme<Field = F>,
@@ -197,14 +272,14 @@ impl CommittedPolynomial {
                     .par_iter()
                     .map(|cycle| LookupQuery::<32>::to_instruction_inputs(cycle).0)
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::U64(coeffs)
             }
             CommittedPolynomial::RightInstructionInput => {
                 let coeffs: Vec<i64> = trace
                     .par_iter()
                     .map(|cycle| LookupQuery::<32>::to_instruction_inputs(cycle).1)
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::I64(coeffs)
             }
             CommittedPolynomial::Product => {
                 let coeffs: Vec<u64> = trace
@@ -215,7 +290,7 @@ impl CommittedPolynomial {
                         left_input * right_input as u64
                     })
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::U64(coeffs)
             }
             CommittedPolynomial::WriteLookupOutputToRD => {
                 let coeffs: Vec<u8> = trace
@@ -226,7 +301,7 @@ impl CommittedPolynomial {
                         (cycle.rd_write().0) * (flag as u8)
                     })
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::U8(coeffs)
             }
             CommittedPolynomial::WritePCtoRD => {
                 let coeffs: Vec<u8> = trace
@@ -236,7 +311,7 @@ impl CommittedPolynomial {
                         (cycle.rd_write().0) * (flag as u8)
                     })
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::U8(coeffs)
             }
             CommittedPolynomial::ShouldBranch => {
                 let coeffs: Vec<u8> = trace
@@ -247,7 +322,7 @@ impl CommittedPolynomial {
                         (LookupQuery::<32>::to_lookup_output(cycle) as u8) * is_branch as u8
                     })
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::U8(coeffs)
             }
             CommittedPolynomial::ShouldJump => {
                 let coeffs: Vec<u8> = trace
@@ -256,16 +331,17 @@ impl CommittedPolynomial {
                         trace
                             .par_iter()
                             .skip(1)
-                            .chain(rayon::iter::once(&RV32IMCycle::NoOp)),
+                            .map(|next_cycle| {
+                                next_cycle.instruction().circuit_flags()[CircuitFlags::IsNoop]
+                            })
+                            .chain(rayon::iter::once(next_is_noop)),
                     )
-                    .map(|(cycle, next_cycle)| {
+                    .map(|(cycle, is_next_noop)| {
                         let is_jump = cycle.instruction().circuit_flags()[CircuitFlags::Jump];
-                        let is_next_noop =
-                            next_cycle.instruction().circuit_flags()[CircuitFlags::IsNoop];
                         is_jump as u8 * (1 - is_next_noop as u8)
                     })
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::U8(coeffs)
             }
             CommittedPolynomial::BytecodeRa(i) => {
                 let d = preprocessing.shared.bytecode.d;
@@ -282,7 +358,7 @@ impl CommittedPolynomial {
                         Some((pc >> (log_K_chunk * (d - 1 - i))) % K_chunk)
                     })
                     .collect();
-                MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(addresses, K_chunk))
+                WitnessCoeffs::OneHot(addresses, K_chunk)
             }
             CommittedPolynomial::RamRa(i) => {
                 let d = self.ram_d();
@@ -300,10 +376,7 @@ impl CommittedPolynomial {
                         })
                     })
                     .collect();
-                MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(
-                    addresses,
-                    DTH_ROOT_OF_K,
-                ))
+                WitnessCoeffs::OneHot(addresses, DTH_ROOT_OF_K)
             }
             CommittedPolynomial::RdInc => {
                 let coeffs: Vec<i64> = trace
@@ -313,7 +386,7 @@ impl CommittedPolynomial {
                         post_value as i64 - pre_value as i64
                     })
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::I64(coeffs)
             }
             CommittedPolynomial::RamInc => {
                 let coeffs: Vec<i64> = trace
@@ -328,7 +401,7 @@ impl CommittedPolynomial {
                         }
                     })
                     .collect();
-                coeffs.into()
+                WitnessCoeffs::I64(coeffs)
             }
             CommittedPolynomial::InstructionRa(i) => {
                 if *i > instruction_lookups::D {
@@ -341,14 +414,129 @@ impl CommittedPolynomial {
                         let k = (lookup_index
                             >> (instruction_lookups::LOG_K_CHUNK
                                 * (instruction_lookups::D - 1 - i)))
-                            % instruction_lookups::K_CHUNK as u64;
+                            % instruction_lookups::K_CHUNK as u128;
                         Some(k as usize)
                     })
                     .collect();
-                MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(
-                    addresses,
-                    instruction_lookups::K_CHUNK,
-                ))
+                WitnessCoeffs::OneHot(addresses, instruction_lookups::K_CHUNK)
+            }
+            CommittedPolynomial::PrivateInputs => {
+                panic!("PrivateInputs is not derived from the trace; use generate_witness_with_io")
+            }
+            CommittedPolynomial::RamInitialState | CommittedPolynomial::RamFinalState => {
+                panic!("{self:?} is not derived from the trace; use ram::ram_state_polynomial")
+            }
+        }
+    }
+
+    /// Whether this polynomial is derived from the trace, rather than from the program
+    /// I/O or the boundary states of a segment of a continuation.
+    fn is_derived_from_trace(&self) -> bool {
+        !matches!(
+            self,
+            CommittedPolynomial::PrivateInputs
+                | CommittedPolynomial::RamInitialState
+                | CommittedPolynomial::RamFinalState
+        )
+    }
+
+    /// Commits to all committed polynomials derived from the trace (in the order given by
+    /// [`AllCommittedPolynomials::iter`]), appending the witness for each chunk of the trace
+    /// to a [`StreamingCommitmentScheme`] commitment as soon as it is generated, so that no
+    /// witness is ever resident in memory in full. `chunks` yields consecutive chunks of the
+    /// trace, each with whether the cycle following it (if any) is a no-op. The trace is
+    /// padded with no-ops to `padded_trace_length`.
+    #[tracing::instrument(skip_all, name = "CommittedPolynomial::commit_streaming")]
+    pub fn commit_streaming<F, PCS, C>(
+        preprocessing: &JoltProverPreprocessing<F, PCS>,
+        chunks: impl Iterator<Item = (C, bool)>,
+        padded_trace_length: usize,
+    ) -> Vec<(PCS::Commitment, PCS::OpeningProofHint)>
+    where
+        F: JoltField,
+        PCS: StreamingCommitmentScheme<Field = F>,
+        C: AsRef<[RV32IMCycle]>,
+    {
+        let polynomials: Vec<_> = AllCommittedPolynomials::iter()
+            .filter(|polynomial| polynomial.is_derived_from_trace())
+            .collect();
+        let mut states: Vec<Option<PCS::State<'_>>> = polynomials.iter().map(|_| None).collect();
+
+        let mut process_chunk = |chunk: &[RV32IMCycle], next_is_noop: bool| {
+            polynomials
+                .par_iter()
+                .zip(states.par_iter_mut())
+                .for_each(|(polynomial, state)| {
+                    let coeffs = polynomial.generate_coeffs(preprocessing, chunk, next_is_noop);
+                    let current = state.take().unwrap_or_else(|| {
+                        let size = match &coeffs {
+                            WitnessCoeffs::OneHot(_, K) => K * padded_trace_length,
+                            _ => padded_trace_length,
+                        };
+                        PCS::initialize(size, &preprocessing.generators)
+                    });
+                    *state = Some(PCS::process_chunk(current, coeffs.as_chunk()));
+                });
+        };
+
+        let mut trace_length = 0;
+        for (chunk, next_is_noop) in chunks {
+            let chunk = chunk.as_ref();
+            trace_length += chunk.len();
+            process_chunk(chunk, next_is_noop);
+        }
+        assert!(
+            trace_length <= padded_trace_length,
+            "Trace length {trace_length} exceeds padded trace length {padded_trace_length}"
+        );
+
+        let mut num_padding_cycles = padded_trace_length - trace_length;
+        while num_padding_cycles > 0 {
+            let padding = vec![RV32IMCycle::NoOp; num_padding_cycles.min(PADDING_CHUNK_SIZE)];
+            num_padding_cycles -= padding.len();
+            process_chunk(&padding, true);
+        }
+
+        states
+            .into_par_iter()
+            .map(|state| PCS::finalize(state.expect("The padded trace is empty")))
+            .collect()
+    }
+}
+
+/// The maximum number of no-op cycles [`CommittedPolynomial::commit_streaming`] pads the
+/// trace with at a time.
+const PADDING_CHUNK_SIZE: usize = 1 << 16;
+
+/// The coefficients of a committed polynomial, in the compact representation
+/// produced by witness generation.
+enum WitnessCoeffs {
+    U8(Vec<u8>),
+    U64(Vec<u64>),
+    I64(Vec<i64>),
+    /// Nonzero indices and address space size K of a one-hot polynomial
+    OneHot(Vec<Option<usize>>, usize),
+}
+
+impl WitnessCoeffs {
+    fn as_chunk<F: JoltField>(&self) -> StreamingChunk<'_, F> {
+        match self {
+            WitnessCoeffs::U8(coeffs) => StreamingChunk::U8Scalars(coeffs),
+            WitnessCoeffs::U64(coeffs) => StreamingChunk::U64Scalars(coeffs),
+            WitnessCoeffs::I64(coeffs) => StreamingChunk::I64Scalars(coeffs),
+            WitnessCoeffs::OneHot(indices, K) => StreamingChunk::OneHot(indices, *K),
+        }
+    }
+}
+
+impl<F: JoltField> From<WitnessCoeffs> for MultilinearPolynomial<F> {
+    fn from(coeffs: WitnessCoeffs) -> Self {
+        match coeffs {
+            WitnessCoeffs::U8(coeffs) => coeffs.into(),
+            WitnessCoeffs::U64(coeffs) => coeffs.into(),
+            WitnessCoeffs::I64(coeffs) => coeffs.into(),
+            WitnessCoeffs::OneHot(indices, K) => {
+                MultilinearPolynomial::OneHot(OneHotPolynomial::from_indices(indices, K))
             }
         }
     }
@@ -386,6 +574,10 @@ pub enum VirtualPolynomial {
     RamValInit,
     RamValFinal,
     RamHammingWeight,
+    Fault,
+    StoreFault,
+    InlineFault,
+    FaultAddress,
     OpFlags(CircuitFlags),
     LookupTableFlag(usize),
 }
@@ -422,6 +614,10 @@ pub static ALL_VIRTUAL_POLYNOMIALS: LazyLock<Vec<VirtualPolynomial>> = LazyLock:
         VirtualPolynomial::RamValInit,
         VirtualPolynomial::RamValFinal,
         VirtualPolynomial::RamHammingWeight,
+        VirtualPolynomial::Fault,
+        VirtualPolynomial::StoreFault,
+        VirtualPolynomial::InlineFault,
+        VirtualPolynomial::FaultAddress,
     ];
     for flag in CircuitFlags::iter() {
         polynomials.push(VirtualPolynomial::OpFlags(flag));
@@ -300,6 +300,7 @@ impl Sha256SequenceBuilder {
                 imm: 0xFFFFFFF8, // SRLI by 3
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: false,
         };
         self.sequence.push(srli.into());
         self.xor(Reg(rd), Reg(ss), rd);
@@ -317,6 +318,7 @@ impl Sha256SequenceBuilder {
                 imm: 0xFFFFFC00, // SRLI by 10
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: false,
         };
         self.sequence.push(srli.into());
         self.xor(Reg(rd), Reg(ss), rd);
@@ -331,6 +333,7 @@ impl Sha256SequenceBuilder {
                 imm: offset * 4,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: false,
         };
         self.sequence.push(lw.into());
     }
@@ -344,6 +347,7 @@ impl Sha256SequenceBuilder {
                 imm: offset * 4,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: false,
         };
         self.sequence.push(sw.into());
     }
@@ -356,6 +360,7 @@ impl Sha256SequenceBuilder {
                     address: self.address,
                     operands: FormatR { rd, rs1, rs2 },
                     virtual_sequence_remaining: Some(0),
+                    is_compressed: false,
                 };
                 self.sequence.push(add.into());
                 Reg(rd)
@@ -365,6 +370,7 @@ impl Sha256SequenceBuilder {
                     address: self.address,
                     operands: FormatI { rd, rs1, imm },
                     virtual_sequence_remaining: Some(0),
+                    is_compressed: false,
                 };
                 self.sequence.push(addi.into());
                 Reg(rd)
@@ -381,6 +387,7 @@ impl Sha256SequenceBuilder {
                     address: self.address,
                     operands: FormatR { rd, rs1, rs2 },
                     virtual_sequence_remaining: Some(0),
+                    is_compressed: false,
                 };
                 self.sequence.push(add.into());
                 Reg(rd)
@@ -390,6 +397,7 @@ impl Sha256SequenceBuilder {
                     address: self.address,
                     operands: FormatI { rd, rs1, imm },
                     virtual_sequence_remaining: Some(0),
+                    is_compressed: false,
                 };
                 self.sequence.push(add.into());
                 Reg(rd)
@@ -406,6 +414,7 @@ impl Sha256SequenceBuilder {
                     address: self.address,
                     operands: FormatR { rd, rs1, rs2 },
                     virtual_sequence_remaining: Some(0),
+                    is_compressed: false,
                 };
                 self.sequence.push(xor.into());
                 Reg(rd)
@@ -415,6 +424,7 @@ impl Sha256SequenceBuilder {
                     address: self.address,
                     operands: FormatI { rd, rs1, imm },
                     virtual_sequence_remaining: Some(0),
+                    is_compressed: false,
                 };
                 self.sequence.push(xori.into());
                 Reg(rd)
@@ -435,6 +445,7 @@ impl Sha256SequenceBuilder {
                     address: self.address,
                     operands: FormatVirtualRightShiftI { rd, rs1, imm },
                     virtual_sequence_remaining: Some(0),
+                    is_compressed: false,
                 };
                 self.sequence.push(rotri.into());
                 Reg(rd)
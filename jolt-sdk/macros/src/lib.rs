@@ -3,7 +3,7 @@ extern crate proc_macro;
 use core::panic;
 
 use common::{
-    attributes::parse_attributes,
+    attributes::{parse_attributes, GuestAllocator},
     jolt_device::{MemoryConfig, MemoryLayout},
 };
 use proc_macro::TokenStream;
@@ -14,6 +14,20 @@ use syn::{parse_macro_input, AttributeArgs, Ident, ItemFn, PatType, ReturnType,
 
 static WASM_IMPORTS_INIT: Once = Once::new();
 
+/// Generates the host-side functions for building, preprocessing, proving and
+/// verifying the annotated guest function.
+///
+/// # Private inputs
+///
+/// Arguments marked `#[private]` are passed to the guest through the private
+/// input region (sized by the `max_private_input_size` attribute) instead of the
+/// public program I/O, so the verifier is never sent them.
+///
+/// **Warning: private inputs are not secret.** Neither the commitment to them
+/// nor the proof is hiding: the proof may leak information about private
+/// inputs, and low-entropy values can be recovered by brute force. Jolt proofs
+/// are not zero-knowledge, so do not use `#[private]` for keys, passwords or
+/// other secrets.
 #[proc_macro_attribute]
 pub fn provable(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attr = parse_macro_input!(attr as AttributeArgs);
@@ -41,10 +55,15 @@ struct MacroBuilder {
     func: ItemFn,
     std: bool,
     func_args: Vec<(Ident, Box<Type>)>,
+    /// Arguments marked `#[private]`, which are passed to the guest as private
+    /// inputs rather than as part of the public program I/O. They are withheld
+    /// from the verifier, but not hidden by the proof.
+    private_args: Vec<Ident>,
 }
 
 impl MacroBuilder {
-    fn new(attr: AttributeArgs, func: ItemFn) -> Self {
+    fn new(attr: AttributeArgs, mut func: ItemFn) -> Self {
+        let private_args = Self::take_private_args(&mut func);
         let func_args = Self::get_func_args(&func);
         #[cfg(feature = "guest-std")]
         let std = true;
@@ -56,6 +75,7 @@ impl MacroBuilder {
             func,
             std,
             func_args,
+            private_args,
         }
     }
 
@@ -71,6 +91,9 @@ impl MacroBuilder {
         let prove_fn = self.make_prove_func();
 
         let attributes = parse_attributes(&self.attr);
+        if !self.private_args.is_empty() && attributes.max_private_input_size == 0 {
+            panic!("#[private] arguments require the max_private_input_size attribute");
+        }
         let mut execute_fn = quote! {};
         if !attributes.guest_only {
             execute_fn = self.make_execute_function();
@@ -118,7 +141,7 @@ impl MacroBuilder {
             pub fn #build_prover_fn_name(
                 program: jolt::host::Program,
                 preprocessing: jolt::JoltProverPreprocessing<jolt::F, jolt::PCS>,
-            ) -> impl Fn(#(#input_types),*) -> #prove_output_ty + Sync + Send
+            ) -> impl Fn(#(#input_types),*) -> Result<#prove_output_ty, jolt::ProveError> + Sync + Send
             {
                 #imports
                 let program = std::sync::Arc::new(program);
@@ -140,14 +163,17 @@ impl MacroBuilder {
         let build_verifier_fn_name =
             Ident::new(&format!("build_verifier_{fn_name}"), fn_name.span());
 
-        let input_types = self.func_args.iter().map(|(_, ty)| ty);
+        // The verifier only sees the public inputs
+        let input_types = self.public_func_args().map(|(_, ty)| ty);
         let output_type: Type = match &self.func.sig.output {
             ReturnType::Default => syn::parse_quote!(()),
             ReturnType::Type(_, ty) => syn::parse_quote!((#ty)),
         };
-        let inputs = self.func.sig.inputs.iter();
+        let inputs = self
+            .public_func_args()
+            .map(|(name, ty)| quote! { #name: #ty });
         let imports = self.make_imports();
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
+        let set_program_args = self.public_func_args().map(|(name, _)| {
             quote! {
                 io_device.inputs.append(&mut jolt::postcard::to_stdvec(&#name).unwrap())
             }
@@ -166,6 +192,7 @@ impl MacroBuilder {
                     let preprocessing = (*preprocessing).clone();
                     let memory_config = MemoryConfig {
                         max_input_size: preprocessing.shared.memory_layout.max_input_size,
+                        max_private_input_size: preprocessing.shared.memory_layout.max_private_input_size,
                         max_output_size: preprocessing.shared.memory_layout.max_output_size,
                         stack_size: preprocessing.shared.memory_layout.stack_size,
                         memory_size: preprocessing.shared.memory_layout.memory_size,
@@ -209,11 +236,7 @@ impl MacroBuilder {
         let fn_name_str = fn_name.to_string();
         let analyze_fn_name = Ident::new(&format!("analyze_{fn_name}"), fn_name.span());
         let inputs = &self.func.sig.inputs;
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
-            quote! {
-                input_bytes.append(&mut jolt::postcard::to_stdvec(&#name).unwrap())
-            }
-        });
+        let set_program_args = self.make_set_program_args();
 
         quote! {
              #[cfg(not(target_arch = "wasm32"))]
@@ -227,9 +250,11 @@ impl MacroBuilder {
                 #set_mem_size
 
                 let mut input_bytes = vec![];
+                #[allow(unused_mut)]
+                let mut private_input_bytes: Vec<u8> = vec![];
                 #(#set_program_args;)*
 
-                program.trace_analyze::<jolt::F>(&input_bytes)
+                program.trace_analyze_with_private_inputs::<jolt::F>(&input_bytes, &private_input_bytes)
              }
         }
     }
@@ -244,11 +269,7 @@ impl MacroBuilder {
         let fn_name_str = fn_name.to_string();
         let trace_to_file_fn_name = Ident::new(&format!("trace_{fn_name}_to_file"), fn_name.span());
         let inputs = &self.func.sig.inputs;
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
-            quote! {
-                input_bytes.append(&mut jolt::postcard::to_stdvec(&#name).unwrap())
-            }
-        });
+        let set_program_args = self.make_set_program_args();
         quote! {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #trace_to_file_fn_name(target_dir: &str, #inputs) {
@@ -261,9 +282,11 @@ impl MacroBuilder {
                 #set_mem_size
 
                 let mut input_bytes = vec![];
+                #[allow(unused_mut)]
+                let mut private_input_bytes: Vec<u8> = vec![];
                 #(#set_program_args;)*
 
-                program.trace_to_file(&input_bytes, &path);
+                program.trace_to_file_with_private_inputs(&input_bytes, &private_input_bytes, &path);
             }
         }
     }
@@ -296,6 +319,8 @@ impl MacroBuilder {
     fn make_preprocess_prover_func(&self) -> TokenStream2 {
         let attributes = parse_attributes(&self.attr);
         let max_input_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_input_size);
+        let max_private_input_size =
+            proc_macro2::Literal::u64_unsuffixed(attributes.max_private_input_size);
         let max_output_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_output_size);
         let stack_size = proc_macro2::Literal::u64_unsuffixed(attributes.stack_size);
         let memory_size = proc_macro2::Literal::u64_unsuffixed(attributes.memory_size);
@@ -315,6 +340,7 @@ impl MacroBuilder {
                 let (bytecode, memory_init, program_size) = program.decode();
                 let memory_config = MemoryConfig {
                     max_input_size: #max_input_size,
+                    max_private_input_size: #max_private_input_size,
                     max_output_size: #max_output_size,
                     stack_size: #stack_size,
                     memory_size: #memory_size,
@@ -339,6 +365,8 @@ impl MacroBuilder {
     fn make_preprocess_verifier_func(&self) -> TokenStream2 {
         let attributes = parse_attributes(&self.attr);
         let max_input_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_input_size);
+        let max_private_input_size =
+            proc_macro2::Literal::u64_unsuffixed(attributes.max_private_input_size);
         let max_output_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_output_size);
         let stack_size = proc_macro2::Literal::u64_unsuffixed(attributes.stack_size);
         let memory_size = proc_macro2::Literal::u64_unsuffixed(attributes.memory_size);
@@ -358,6 +386,7 @@ impl MacroBuilder {
                 let (bytecode, memory_init, program_size) = program.decode();
                 let memory_config = MemoryConfig {
                     max_input_size: #max_input_size,
+                    max_private_input_size: #max_private_input_size,
                     max_output_size: #max_output_size,
                     stack_size: #stack_size,
                     memory_size: #memory_size,
@@ -407,44 +436,72 @@ impl MacroBuilder {
                 let ret_val = ();
             },
             ReturnType::Type(_, ty) => quote! {
+                if let Some(fault) = io_device.fault {
+                    return Err(jolt::JoltProverError::GuestFault(fault).into());
+                }
                 let mut outputs = io_device.outputs.clone();
                 outputs.resize(preprocessing.shared.memory_layout.max_output_size as usize, 0);
                 let ret_val = jolt::postcard::from_bytes::<#ty>(&outputs).unwrap();
             },
         };
 
-        let set_program_args = self.func_args.iter().map(|(name, _)| {
-            quote! {
-                input_bytes.append(&mut jolt::postcard::to_stdvec(&#name).unwrap())
-            }
-        });
+        let set_program_args = self.make_set_program_args();
 
         let fn_name = self.get_func_name();
         let inputs = &self.func.sig.inputs;
+        let input_names = self.func_args.iter().map(|(name, _)| name);
         let imports = self.make_imports();
 
         let prove_fn_name = syn::Ident::new(&format!("prove_{fn_name}"), fn_name.span());
+        let prove_with_advice_fn_name =
+            syn::Ident::new(&format!("prove_{fn_name}_with_advice"), fn_name.span());
         quote! {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #prove_fn_name(
+                program: jolt::host::Program,
+                preprocessing: jolt::JoltProverPreprocessing<jolt::F, jolt::PCS>,
+                #inputs
+            ) -> Result<#prove_output_ty, jolt::ProveError> {
+                let advice = jolt::host::advice::AdviceTape::new();
+                #prove_with_advice_fn_name(program, preprocessing, &advice, #(#input_names),*)
+            }
+
+            #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
+            pub fn #prove_with_advice_fn_name(
                 mut program: jolt::host::Program,
                 preprocessing: jolt::JoltProverPreprocessing<jolt::F, jolt::PCS>,
+                advice: &jolt::host::advice::AdviceTape,
                 #inputs
-            ) -> #prove_output_ty {
+            ) -> Result<#prove_output_ty, jolt::ProveError> {
                 #imports
 
                 let mut input_bytes = vec![];
+                #[allow(unused_mut)]
+                let mut private_input_bytes: Vec<u8> = vec![];
                 #(#set_program_args;)*
 
-                let (jolt_proof, io_device, _) = JoltRV32IM::prove(
+                let (jolt_proof, io_device, _) = JoltRV32IM::prove_with_witness(
                     &preprocessing,
                     &mut program,
-                    &input_bytes,
-                );
+                    jolt::GuestInputs {
+                        inputs: &input_bytes,
+                        private_inputs: &private_input_bytes,
+                        advice: advice.as_bytes(),
+                    },
+                )?;
+
+                // A panicking guest still yields a valid proof (of the panic), so hand it
+                // back rather than discarding it, whether or not the function returns a value.
+                if io_device.panic {
+                    return Err(jolt::ProveError::GuestPanicked {
+                        proof: Box::new(jolt_proof),
+                        io_device,
+                    });
+                }
 
                 #handle_return
 
-                (ret_val, jolt_proof, io_device)
+                Ok((ret_val, jolt_proof, io_device))
             }
         }
     }
@@ -453,6 +510,7 @@ impl MacroBuilder {
         let attributes = parse_attributes(&self.attr);
         let memory_layout = MemoryLayout::new(&MemoryConfig {
             max_input_size: attributes.max_input_size,
+            max_private_input_size: attributes.max_private_input_size,
             max_output_size: attributes.max_output_size,
             stack_size: attributes.stack_size,
             memory_size: attributes.memory_size,
@@ -460,8 +518,10 @@ impl MacroBuilder {
             program_size: Some(0),
         });
         let input_start = memory_layout.input_start;
+        let private_input_start = memory_layout.private_input_start;
         let output_start = memory_layout.output_start;
         let max_input_len = attributes.max_input_size as usize;
+        let max_private_input_len = memory_layout.max_private_input_size as usize;
         let max_output_len = attributes.max_output_size as usize;
         let termination_bit = memory_layout.termination as usize;
 
@@ -471,12 +531,29 @@ impl MacroBuilder {
                 core::slice::from_raw_parts(input_ptr, #max_input_len)
             };
         };
+        let get_private_input_slice = if self.private_args.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                let private_input_ptr = #private_input_start as *const u8;
+                let private_input_slice = unsafe {
+                    core::slice::from_raw_parts(private_input_ptr, #max_private_input_len)
+                };
+            }
+        };
 
         let args = &self.func_args;
         let args_fetch = args.iter().map(|(name, ty)| {
-            quote! {
-                let (#name, input_slice) =
-                    jolt::postcard::take_from_bytes::<#ty>(input_slice).unwrap();
+            if self.is_private_arg(name) {
+                quote! {
+                    let (#name, private_input_slice) =
+                        jolt::postcard::take_from_bytes::<#ty>(private_input_slice).unwrap();
+                }
+            } else {
+                quote! {
+                    let (#name, input_slice) =
+                        jolt::postcard::take_from_bytes::<#ty>(input_slice).unwrap();
+                }
             }
         });
 
@@ -522,6 +599,7 @@ impl MacroBuilder {
             pub extern "C" fn main() {
                 let mut offset = 0;
                 #get_input_slice
+                #get_private_input_slice
                 #(#args_fetch;)*
                 #check_input_len
                 #block
@@ -567,14 +645,23 @@ impl MacroBuilder {
     }
 
     fn make_allocator(&self) -> TokenStream2 {
+        let attributes = parse_attributes(&self.attr);
         if self.std {
-            quote! {}
-        } else {
-            quote! {
-                #[cfg(feature = "guest")]
-                #[global_allocator]
-                static ALLOCATOR: jolt::BumpAllocator = jolt::BumpAllocator;
+            // std guests allocate through the toolchain's own allocator
+            if attributes.allocator != GuestAllocator::Bump {
+                panic!("the allocator attribute is not supported with guest-std");
             }
+            return quote! {};
+        }
+
+        let allocator = match attributes.allocator {
+            GuestAllocator::Bump => quote! { jolt::BumpAllocator },
+            GuestAllocator::FreeList => quote! { jolt::FreeListAllocator },
+        };
+        quote! {
+            #[cfg(feature = "guest")]
+            #[global_allocator]
+            static ALLOCATOR: #allocator = #allocator;
         }
     }
 
@@ -646,6 +733,11 @@ impl MacroBuilder {
             program.set_max_input_size(#value);
         });
 
+        let value = attributes.max_private_input_size;
+        code.push(quote! {
+            program.set_max_private_input_size(#value);
+        });
+
         let value = attributes.max_output_size;
         code.push(quote! {
             program.set_max_output_size(#value);
@@ -696,6 +788,56 @@ impl MacroBuilder {
         args
     }
 
+    /// Strips the `#[private]` attribute from the function's arguments, returning
+    /// the names of the arguments it was applied to.
+    fn take_private_args(func: &mut ItemFn) -> Vec<Ident> {
+        let mut private_args = Vec::new();
+        for arg in func.sig.inputs.iter_mut() {
+            if let syn::FnArg::Typed(PatType { pat, attrs, .. }) = arg {
+                let num_attrs = attrs.len();
+                attrs.retain(|attr| !attr.path.is_ident("private"));
+                if attrs.len() != num_attrs {
+                    if let syn::Pat::Ident(pat_ident) = pat.as_ref() {
+                        private_args.push(pat_ident.ident.clone());
+                    } else {
+                        panic!("cannot parse arg");
+                    }
+                }
+            }
+        }
+
+        private_args
+    }
+
+    fn is_private_arg(&self, name: &Ident) -> bool {
+        self.private_args.contains(name)
+    }
+
+    fn public_func_args(&self) -> impl Iterator<Item = &(Ident, Box<Type>)> {
+        self.func_args
+            .iter()
+            .filter(|(name, _)| !self.is_private_arg(name))
+    }
+
+    /// Serializes each argument into `input_bytes`, or into `private_input_bytes`
+    /// if it is marked `#[private]`.
+    fn make_set_program_args(&self) -> Vec<TokenStream2> {
+        self.func_args
+            .iter()
+            .map(|(name, _)| {
+                if self.is_private_arg(name) {
+                    quote! {
+                        private_input_bytes.append(&mut jolt::postcard::to_stdvec(&#name).unwrap())
+                    }
+                } else {
+                    quote! {
+                        input_bytes.append(&mut jolt::postcard::to_stdvec(&#name).unwrap())
+                    }
+                }
+            })
+            .collect()
+    }
+
     fn get_func_name(&self) -> &Ident {
         &self.func.sig.ident
     }
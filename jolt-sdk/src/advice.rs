@@ -0,0 +1,56 @@
+//! Untrusted advice from the host.
+//! `jolt::advice::read::<T>()` deserializes the next value from the advice tape
+//! that the host passed to `prove_{fn}_with_advice` (see
+//! `jolt::host::advice::AdviceTape`). Each word is read with an ADVICE
+//! instruction, which the prover fills in at trace time.
+//!
+//! The advice tape is not part of the program I/O, so the verifier never sees
+//! it and nothing constrains the values the guest reads. Guests must check that
+//! any advice they use is correct, e.g. by verifying a claimed quotient by
+//! multiplication rather than trusting it.
+
+extern crate alloc as alloc_crate;
+
+use alloc_crate::vec::Vec;
+use serde::de::DeserializeOwned;
+
+/// Number of advice bytes returned by a single ADVICE instruction.
+const WORD_SIZE: usize = core::mem::size_of::<u32>();
+
+/// Reads the next word of the advice tape.
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+#[inline(always)]
+pub fn read_word() -> u32 {
+    let word: u32;
+    unsafe {
+        core::arch::asm!(
+            ".insn r 0x0B, 0x0, 0x7F, {rd}, x0, x0", // ADVICE (custom-0, funct7 = 0x7F)
+            rd = out(reg) word,
+            options(nomem, nostack, preserves_flags)
+        );
+    }
+    word
+}
+
+#[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+pub fn read_word() -> u32 {
+    panic!("jolt::advice can only be read inside the guest");
+}
+
+/// Reads `len` bytes from the advice tape, consuming whole words.
+pub fn read_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(len.next_multiple_of(WORD_SIZE));
+    while bytes.len() < len {
+        bytes.extend_from_slice(&read_word().to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+/// Reads the next value written to the advice tape by
+/// `AdviceTape::write`.
+pub fn read<T: DeserializeOwned>() -> T {
+    let len = read_word() as usize;
+    let bytes = read_bytes(len);
+    postcard::from_bytes(&bytes).expect("failed to deserialize advice")
+}
@@ -1,5 +1,7 @@
 use core::alloc::{GlobalAlloc, Layout};
+use core::ptr::null_mut;
 
+/// Hands out memory from the heap and never reclaims it.
 pub struct BumpAllocator;
 
 unsafe impl GlobalAlloc for BumpAllocator {
@@ -10,30 +12,137 @@ unsafe impl GlobalAlloc for BumpAllocator {
     unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
 }
 
+/// A size-class allocator that reuses freed memory.
+///
+/// Every allocation is rounded up to a power-of-two block, aligned to its own
+/// size. Freed blocks are kept on one intrusive free list per size class and
+/// handed out again before the heap is grown with `sys_alloc`. Blocks are
+/// never split or coalesced, so this suits guests that repeatedly allocate
+/// and free objects of similar sizes (e.g. collections that grow and shrink).
+pub struct FreeListAllocator;
+
+/// Heads of the free lists, indexed by log2 of the block size. Each free
+/// block stores the address of the next one in its first word.
+static mut FREE_LISTS: [usize; usize::BITS as usize] = [0; usize::BITS as usize];
+
+impl FreeListAllocator {
+    /// Returns log2 of the size of the block serving `layout`.
+    fn size_class(layout: Layout) -> Option<usize> {
+        let size = layout
+            .size()
+            .max(layout.align())
+            .max(core::mem::size_of::<usize>());
+        size.checked_next_power_of_two()
+            .map(|block_size| block_size.trailing_zeros() as usize)
+    }
+
+    unsafe fn push(class: usize, block: usize) {
+        *(block as *mut usize) = FREE_LISTS[class];
+        FREE_LISTS[class] = block;
+    }
+
+    unsafe fn pop(class: usize) -> Option<usize> {
+        let block = FREE_LISTS[class];
+        if block == 0 {
+            return None;
+        }
+        FREE_LISTS[class] = *(block as *const usize);
+        Some(block)
+    }
+}
+
+unsafe impl GlobalAlloc for FreeListAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let Some(class) = Self::size_class(layout) else {
+            return null_mut();
+        };
+        if let Some(block) = Self::pop(class) {
+            return block as *mut u8;
+        }
+
+        let block_size = 1 << class;
+        let next = heap_next();
+        let ptr = sys_alloc(block_size, block_size);
+        if !ptr.is_null() {
+            // Rather than waste the padding in front of the block, hand it
+            // out to the free lists as naturally aligned smaller blocks.
+            carve(next, ptr as usize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // `alloc` succeeded for this layout, so it has a size class.
+        let class = Self::size_class(layout).unwrap();
+        Self::push(class, ptr as usize);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        if Self::size_class(layout) == Self::size_class(new_layout) {
+            return ptr;
+        }
+
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
+/// Splits `[start, end)` into naturally aligned blocks and frees them.
+unsafe fn carve(mut start: usize, end: usize) {
+    let min_size = core::mem::size_of::<usize>();
+    start = (start + min_size - 1) & !(min_size - 1);
+    while start < end {
+        let mut block_size = 1 << start.trailing_zeros();
+        while block_size > end - start {
+            block_size >>= 1;
+        }
+        if block_size < min_size {
+            return;
+        }
+        FreeListAllocator::push(block_size.trailing_zeros() as usize, start);
+        start += block_size;
+    }
+}
+
 extern "C" {
     static _HEAP_PTR: u8;
+    static _HEAP_END: u8;
 }
 
 static mut ALLOC_NEXT: usize = 0;
 
+fn heap_next() -> usize {
+    match unsafe { ALLOC_NEXT } {
+        0 => unsafe { (&_HEAP_PTR) as *const u8 as usize },
+        next => next,
+    }
+}
+
+/// Bump-allocates `size` bytes aligned to `align` from the heap, which spans
+/// `_HEAP_PTR` to `_HEAP_END` (the end of the guest's memory). Returns null
+/// once the heap is exhausted, which the global allocators pass on so that
+/// `handle_alloc_error` panics in the guest.
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_alloc(size: usize, align: usize) -> *mut u8 {
-    let mut next = unsafe { ALLOC_NEXT };
-
-    if next == 0 {
-        next = unsafe { (&_HEAP_PTR) as *const u8 as usize };
-    }
-
-    next = align_up(next, align);
+    let heap_end = unsafe { (&_HEAP_END) as *const u8 as usize };
 
-    let ptr = next as *mut u8;
-    next += size;
+    let next =
+        align_up(heap_next(), align).and_then(|start| Some((start, start.checked_add(size)?)));
+    let (start, next) = match next {
+        Some((start, next)) if next <= heap_end => (start, next),
+        _ => return null_mut(),
+    };
 
     unsafe { ALLOC_NEXT = next };
-    ptr
+    start as *mut u8
 }
 
-fn align_up(addr: usize, align: usize) -> usize {
-    (addr + align - 1) & !(align - 1)
+fn align_up(addr: usize, align: usize) -> Option<usize> {
+    addr.checked_add(align - 1).map(|addr| addr & !(align - 1))
 }
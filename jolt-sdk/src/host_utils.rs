@@ -1,10 +1,59 @@
 pub use ark_bn254::Fr as F;
-pub use common::jolt_device::{MemoryConfig, MemoryLayout};
+pub use common::jolt_device::{GuestFault, MemoryConfig, MemoryLayout};
 pub use jolt_core::field::JoltField;
 pub use jolt_core::host;
 pub use jolt_core::poly::commitment::dory::DoryCommitmentScheme as PCS;
+pub use jolt_core::utils::errors::JoltProverError;
 pub use jolt_core::zkvm::{
     dag::proof_serialization::serialize_and_print_size, dag::proof_serialization::JoltProof, Jolt,
     JoltProverPreprocessing, JoltRV32IM, JoltVerifierPreprocessing, RV32IMJoltProof, Serializable,
 };
-pub use tracer::JoltDevice;
+pub use tracer::{GuestInputs, JoltDevice};
+
+/// Error returned by the `prove_*` functions generated by `#[jolt::provable]`.
+pub enum ProveError {
+    /// The guest could not be built, traced or proven.
+    Prover(JoltProverError),
+    /// The guest panicked. Proving still succeeded, so `proof` attests to the
+    /// panic and can be checked against `io_device` like any other proof.
+    GuestPanicked {
+        proof: Box<RV32IMJoltProof>,
+        io_device: JoltDevice,
+    },
+}
+
+impl From<JoltProverError> for ProveError {
+    fn from(err: JoltProverError) -> Self {
+        ProveError::Prover(err)
+    }
+}
+
+impl core::fmt::Debug for ProveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProveError::Prover(err) => f.debug_tuple("Prover").field(err).finish(),
+            ProveError::GuestPanicked { io_device, .. } => f
+                .debug_struct("GuestPanicked")
+                .field("io_device", io_device)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl core::fmt::Display for ProveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProveError::Prover(err) => write!(f, "{err}"),
+            ProveError::GuestPanicked { .. } => write!(f, "Guest panicked"),
+        }
+    }
+}
+
+impl std::error::Error for ProveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProveError::Prover(err) => Some(err),
+            ProveError::GuestPanicked { .. } => None,
+        }
+    }
+}
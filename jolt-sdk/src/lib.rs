@@ -10,15 +10,22 @@ pub mod host_utils;
 #[cfg(feature = "host")]
 pub use host_utils::*;
 
+pub mod advice;
+
 pub mod alloc;
 pub use alloc::*;
 
 pub mod cycle_tracking;
 pub use cycle_tracking::*;
 
-// This is a dummy _HEAP_PTR to keep the compiler happy.
+pub mod print;
+
+// These are dummy _HEAP_PTR and _HEAP_END symbols to keep the compiler happy.
 // It should never be used when compiled as a guest or with
 // our custom allocator
 #[no_mangle]
 #[cfg(feature = "host")]
 pub static mut _HEAP_PTR: u8 = 0;
+#[no_mangle]
+#[cfg(feature = "host")]
+pub static mut _HEAP_END: u8 = 0;
@@ -0,0 +1,104 @@
+//! Printing from the guest.
+//! `jolt::print!`, `jolt::println!`, `jolt::eprint!` and `jolt::eprintln!` format
+//! their arguments like their `std` counterparts. In the guest, the resulting text
+//! is passed to the emulator through an ECALL and captured by the tracer; in the
+//! proof, the ECALL is a no-op, so printing only costs the cycles spent formatting.
+//! On the host, the text is printed directly.
+
+// Constants to signal the emulator
+pub const JOLT_PRINT_ECALL_NUM: i32 = 0x9E1A7; // "P R I N T"
+pub const JOLT_PRINT_STDOUT: i32 = 1;
+pub const JOLT_PRINT_STDERR: i32 = 2;
+
+#[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+mod riscv_specific {
+    use super::JOLT_PRINT_ECALL_NUM;
+
+    pub struct GuestWriter {
+        pub stream: i32,
+    }
+
+    impl core::fmt::Write for GuestWriter {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            emit_jolt_print_ecall(s.as_ptr() as i32, s.len() as i32, self.stream);
+            Ok(())
+        }
+    }
+
+    // inserts an ECALL directly into the compiled code
+    #[inline(always)]
+    fn emit_jolt_print_ecall(ptr: i32, len: i32, stream: i32) {
+        unsafe {
+            core::arch::asm!(
+                ".word 0x00000073", // ECALL opcode
+                in("x10") JOLT_PRINT_ECALL_NUM,
+                in("x11") ptr, // address of the text to print
+                in("x12") len, // length of the text to print
+                in("x13") stream, // either stdout or stderr
+                options(nostack, readonly, preserves_flags)
+            );
+        }
+    }
+}
+
+#[doc(hidden)]
+#[allow(unused_variables)]
+pub fn _print(args: core::fmt::Arguments, stream: i32) {
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    {
+        use core::fmt::Write;
+        let _ = riscv_specific::GuestWriter { stream }.write_fmt(args);
+    }
+    #[cfg(all(
+        not(any(target_arch = "riscv32", target_arch = "riscv64")),
+        feature = "host"
+    ))]
+    match stream {
+        JOLT_PRINT_STDERR => ::std::eprint!("{args}"),
+        _ => ::std::print!("{args}"),
+    }
+}
+
+/// Prints to the guest's stdout.
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::print::_print(::core::format_args!($($arg)*), $crate::print::JOLT_PRINT_STDOUT)
+    };
+}
+
+/// Prints to the guest's stdout, with a newline.
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::print::_print(
+            ::core::format_args!("{}\n", ::core::format_args!($($arg)*)),
+            $crate::print::JOLT_PRINT_STDOUT,
+        )
+    };
+}
+
+/// Prints to the guest's stderr.
+#[macro_export]
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        $crate::print::_print(::core::format_args!($($arg)*), $crate::print::JOLT_PRINT_STDERR)
+    };
+}
+
+/// Prints to the guest's stderr, with a newline.
+#[macro_export]
+macro_rules! eprintln {
+    () => {
+        $crate::eprint!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::print::_print(
+            ::core::format_args!("{}\n", ::core::format_args!($($arg)*)),
+            $crate::print::JOLT_PRINT_STDERR,
+        )
+    };
+}
@@ -4,10 +4,12 @@ use jolt_verifier::zkvm::{
 
 #[test]
 fn verify_proof() {
-    let preprocessing = JoltVerifierPreprocessing::read_from_target_dir("tests/fixtures/").unwrap();
-    let proof = RV32IMJoltProof::from_file("tests/fixtures/fib_proof.bin").unwrap();
-    let device =
-        common::jolt_device::JoltDevice::from_file("tests/fixtures/fib_io_device.bin").unwrap();
+    const STALE: &str = "stale fixture, run ./update-test-fixtures.sh";
+    let preprocessing =
+        JoltVerifierPreprocessing::read_from_target_dir("tests/fixtures/").expect(STALE);
+    let proof = RV32IMJoltProof::from_file("tests/fixtures/fib_proof.bin").expect(STALE);
+    let device = common::jolt_device::JoltDevice::from_file("tests/fixtures/fib_io_device.bin")
+        .expect(STALE);
     let start = std::time::Instant::now();
     println!("Verifying proof...");
     let verifier = JoltRV32IM::verify(&preprocessing, proof, device, None);
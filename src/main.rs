@@ -217,7 +217,7 @@ const HOST_MAIN: &str = r#"pub fn main() {
     let prove_fib = guest::build_prover_fib(program, prover_preprocessing);
     let verify_fib = guest::build_verifier_fib(verifier_preprocessing);
 
-    let (output, proof, io_device) = prove_fib(50);
+    let (output, proof, io_device) = prove_fib(50).unwrap();
     let is_valid = verify_fib(50, output, io_device.panic, proof);
 
     println!("output: {output}");
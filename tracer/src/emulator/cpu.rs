@@ -10,8 +10,11 @@ use alloc::collections::btree_map::BTreeMap as FnvHashMap;
 use core::convert::TryInto;
 
 use crate::instruction::{RV32IMCycle, RV32IMInstruction};
+use common::constants::REGISTER_COUNT;
 
-use super::mmu::{AddressingMode, Mmu};
+use super::cycle_tracking::{CycleTrackingEvent, CycleTrackingReport};
+use super::guest_output::GuestOutput;
+use super::mmu::{AddressingMode, Mmu, DRAM_BASE};
 use super::terminal::Terminal;
 
 #[cfg(not(feature = "std"))]
@@ -71,11 +74,18 @@ const MIP_SSIP: u64 = 0x002;
 pub const JOLT_CYCLE_TRACK_ECALL_NUM: u32 = 0xC7C1E;
 pub const JOLT_CYCLE_MARKER_START: u32 = 1;
 pub const JOLT_CYCLE_MARKER_END: u32 = 2;
+pub const JOLT_PRINT_ECALL_NUM: u32 = 0x9E1A7; // "PRINT"
+pub const JOLT_PRINT_STDOUT: u32 = 1;
+pub const JOLT_PRINT_STDERR: u32 = 2;
+/// Upper bound on the buffer preallocated for a single print ECALL.
+const JOLT_PRINT_MAX_PREALLOCATION: usize = 1 << 12;
+
 #[derive(Clone)]
 struct ActiveMarker {
     label: String,
     start_instrs: u64,      // executed_instrs  at ‘start’
     start_trace_len: usize, // trace.len()      at ‘start’
+    depth: usize,           // number of active markers at ‘start’
 }
 
 /// Emulates a RISC-V CPU core
@@ -87,8 +97,7 @@ pub struct Cpu {
     wfi: bool,
     // using only lower 32bits of x, pc, and csr registers
     // for 32-bit mode
-    pub x: [i64; 64],
-    f: [f64; 32],
+    pub x: [i64; REGISTER_COUNT as usize],
     pub(crate) pc: u64,
     csr: [u64; CSR_CAPACITY],
     pub mmu: Mmu,
@@ -100,6 +109,11 @@ pub struct Cpu {
     pub trace_len: usize,
     executed_instrs: u64, // “real” RV32IM cycles
     active_markers: FnvHashMap<u32, ActiveMarker>,
+    cycle_tracking_report: CycleTrackingReport,
+    guest_output: GuestOutput,
+    advice_tape: Vec<u8>,
+    advice_cursor: usize,
+    advice_exhausted: bool,
 }
 
 #[derive(Clone)]
@@ -251,8 +265,7 @@ impl Cpu {
             xlen: Xlen::Bit64,
             privilege_mode: PrivilegeMode::Machine,
             wfi: false,
-            x: [0; 64],
-            f: [0.0; 32],
+            x: [0; REGISTER_COUNT as usize],
             pc: 0,
             csr: [0; CSR_CAPACITY],
             mmu: Mmu::new(Xlen::Bit64, terminal),
@@ -264,6 +277,11 @@ impl Cpu {
             trace_len: 0,
             executed_instrs: 0,
             active_markers: FnvHashMap::default(),
+            cycle_tracking_report: CycleTrackingReport::default(),
+            guest_output: GuestOutput::default(),
+            advice_tape: Vec::new(),
+            advice_cursor: 0,
+            advice_exhausted: false,
         };
         // cpu.x[0xb] = 0x1020; // I don't know why but Linux boot seems to require this initialization
         cpu.write_csr_raw(CSR_MISA_ADDRESS, 0x800000008014312f);
@@ -362,25 +380,30 @@ impl Cpu {
 
         let original_word = self.fetch()?;
         let instruction_address = normalize_u64(self.pc, &self.xlen);
-        let word = match (original_word & 0x3) == 0x3 {
-            true => {
+        let is_compressed = (original_word & 0x3) != 0x3;
+        let word = match is_compressed {
+            false => {
                 self.pc = self.pc.wrapping_add(4); // 32-bit length non-compressed instruction
                 original_word
             }
-            false => {
+            true => {
                 self.pc = self.pc.wrapping_add(2); // 16-bit length compressed instruction
-                self.uncompress(original_word & 0xffff)
+                Self::uncompress(original_word & 0xffff, &self.xlen)
             }
         };
 
-        let instr = RV32IMInstruction::decode(word, instruction_address)
+        let mut instr = RV32IMInstruction::decode(word, instruction_address)
             .ok()
             .unwrap();
-
-        if trace.is_none() {
-            instr.execute(self);
-        } else {
-            instr.trace(self, trace);
+        instr.set_is_compressed(is_compressed);
+
+        match trace {
+            None => instr.execute(self),
+            Some(trace) => {
+                let prev_len = trace.len();
+                instr.trace(self, Some(&mut *trace));
+                self.trace_len += trace.len() - prev_len;
+            }
         }
 
         // check if current instruction is real or not for cycle profiling
@@ -527,6 +550,16 @@ impl Cpu {
                 // usual access-fault on the *next* instruction fetch.
                 let _ = self.handle_jolt_cycle_marker(marker_ptr, marker_len, event_type);
 
+                return false; // we don't take the trap
+            }
+            if call_id == JOLT_PRINT_ECALL_NUM {
+                let ptr = self.x[11] as u32; // a1
+                let len = self.x[12] as u32; // a2
+                let stream = self.x[13] as u32; // a3
+
+                // As above, faults while reading the guest's buffer are swallowed.
+                let _ = self.handle_jolt_print(ptr, len, stream);
+
                 return false; // we don't take the trap
             }
         }
@@ -907,8 +940,11 @@ impl Cpu {
         }
     }
 
+    /// Expands a 16-bit compressed ("C" extension) instruction into the
+    /// equivalent 32-bit instruction word. Returns `0xffffffff` if `halfword`
+    /// is not a valid compressed instruction.
     // @TODO: Optimize
-    fn uncompress(&self, halfword: u32) -> u32 {
+    pub fn uncompress(halfword: u32, xlen: &Xlen) -> u32 {
         let op = halfword & 0x3; // [1:0]
         let funct3 = (halfword >> 13) & 0x7; // [15:13]
 
@@ -1043,7 +1079,7 @@ impl Cpu {
                         }
                     }
                     1 => {
-                        match self.xlen {
+                        match xlen {
                             Xlen::Bit32 => {
                                 // C.JAL (RV32C only)
                                 // jal x1, offset
@@ -1498,7 +1534,7 @@ impl Cpu {
             true => original_word,
             false => {
                 original_word &= 0xffff;
-                self.uncompress(original_word)
+                Self::uncompress(original_word, &self.xlen)
             }
         };
 
@@ -1545,18 +1581,21 @@ impl Cpu {
                         label,
                         start_instrs: self.executed_instrs,
                         start_trace_len: self.trace_len,
+                        depth: self.active_markers.len(),
                     },
                 );
             }
 
             JOLT_CYCLE_MARKER_END => {
                 if let Some(mark) = self.active_markers.remove(&ptr) {
-                    let real = self.executed_instrs - mark.start_instrs;
-                    let virt = self.trace_len - mark.start_trace_len;
-                    println!(
-                        "\"{}\": {} RV32IM cycles, {} virtual cycles",
-                        mark.label, real, virt
-                    );
+                    self.cycle_tracking_report.events.push(CycleTrackingEvent {
+                        label: mark.label,
+                        start_cycle: mark.start_instrs,
+                        end_cycle: self.executed_instrs,
+                        start_virtual_cycle: mark.start_trace_len as u64,
+                        end_virtual_cycle: self.trace_len as u64,
+                        depth: mark.depth,
+                    });
                 } else {
                     println!(
                         "Warning: Attempt to end a marker (ptr: 0x{ptr:x}) that was never started"
@@ -1570,6 +1609,78 @@ impl Cpu {
         Ok(())
     }
 
+    /// The cycle-tracking spans completed so far.
+    pub fn cycle_tracking_report(&self) -> &CycleTrackingReport {
+        &self.cycle_tracking_report
+    }
+
+    /// The text written by the guest so far.
+    pub fn guest_output(&self) -> &GuestOutput {
+        &self.guest_output
+    }
+
+    /// Sets the untrusted advice tape that the guest reads from with `ADVICE`.
+    pub fn set_advice_tape(&mut self, advice_tape: Vec<u8>) {
+        self.advice_tape = advice_tape;
+        self.advice_cursor = 0;
+        self.advice_exhausted = false;
+    }
+
+    /// Whether the guest tried to read past the end of the advice tape.
+    pub fn advice_exhausted(&self) -> bool {
+        self.advice_exhausted
+    }
+
+    /// Consumes the next 32-bit little-endian word of the advice tape. The word
+    /// size is fixed (rather than XLEN) so that the tape layout does not depend
+    /// on the guest's target.
+    ///
+    /// If the tape is exhausted, this returns 0 and records it (see
+    /// [`Cpu::advice_exhausted`]); the guest is then stopped before the reading
+    /// instruction (see `step_emulator`).
+    pub fn read_advice_word(&mut self) -> u64 {
+        let end = self.advice_cursor + 4;
+        if end > self.advice_tape.len() {
+            self.advice_exhausted = true;
+            return 0;
+        }
+        let bytes: [u8; 4] = self.advice_tape[self.advice_cursor..end]
+            .try_into()
+            .unwrap();
+        self.advice_cursor = end;
+        u32::from_le_bytes(bytes) as u64
+    }
+
+    fn handle_jolt_print(&mut self, ptr: u32, len: u32, stream: u32) -> Result<(), Trap> {
+        let output = match stream {
+            JOLT_PRINT_STDOUT => &mut self.guest_output.stdout,
+            JOLT_PRINT_STDERR => &mut self.guest_output.stderr,
+            // An unknown stream is the guest's problem; ignore the print
+            _ => return Ok(()),
+        };
+        // Likewise ignore buffers outside of RAM, rather than panicking on them or
+        // reporting them as a heap overflow
+        let (start, end) = (ptr as u64, ptr as u64 + len as u64);
+        let in_ram = start >= DRAM_BASE
+            && (len == 0 || self.mmu.memory.validate_address(end - 1))
+            && self
+                .mmu
+                .jolt_device
+                .as_ref()
+                .is_none_or(|device| end <= device.memory_layout.memory_end);
+        if !in_ram {
+            return Ok(());
+        }
+        // `len` is guest-controlled, so don't trust it for the allocation
+        let mut bytes = Vec::with_capacity((len as usize).min(JOLT_PRINT_MAX_PREALLOCATION));
+        for i in 0..len {
+            let (b, _) = self.mmu.load(ptr.wrapping_add(i).into())?;
+            bytes.push(b);
+        }
+        output.extend(bytes);
+        Ok(())
+    }
+
     /// Read a NUL-terminated guest string from memory.
     fn read_c_string(&mut self, mut addr: u32, len: u32) -> Result<String, Trap> {
         let mut bytes = Vec::with_capacity(len as usize);
@@ -1826,17 +1937,39 @@ mod test_cpu {
     //     // @TODO: Should I test all instructions?
     // }
 
-    // #[test]
-    // fn uncompress() {
-    //     let mut cpu = create_cpu();
-    //     // .uncompress() doesn't directly return an instruction but
-    //     // it returns uncompressed word. Then you need to call .decode().
-    //     match cpu.decode(cpu.uncompress(0x20)) {
-    //         Ok(inst) => assert_eq!(inst.name, "ADDI"),
-    //         Err(_e) => panic!("Failed to decode"),
-    //     };
-    //     // @TODO: Should I test all compressed instructions?
-    // }
+    #[test]
+    fn uncompress() {
+        // .uncompress() doesn't directly return an instruction but
+        // it returns uncompressed word. Then you need to call .decode().
+        let word = Cpu::uncompress(0x20, &Xlen::Bit32);
+        assert!(matches!(
+            RV32IMInstruction::decode(word, DRAM_BASE),
+            Ok(RV32IMInstruction::ADDI(_))
+        ));
+        // An all-zero halfword is a reserved instruction
+        assert_eq!(0xffffffff, Cpu::uncompress(0x0, &Xlen::Bit32));
+        // @TODO: Should I test all compressed instructions?
+    }
+
+    #[test]
+    fn trace_compressed_instruction() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(4);
+        cpu.update_pc(DRAM_BASE);
+        // Write compressed "addi x8, x0, 8" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x20) {
+            Ok(_) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        let mut trace = vec![];
+        cpu.tick(Some(&mut trace));
+
+        assert_eq!(DRAM_BASE + 2, cpu.read_pc());
+        assert_eq!(1, trace.len());
+        let instr = trace[0].instruction().normalize();
+        assert_eq!(DRAM_BASE as usize, instr.address);
+        assert!(instr.is_compressed);
+    }
 
     // #[test]
     // fn wfi() {
@@ -1940,6 +2073,124 @@ mod test_cpu {
         // @TODO: Test vector type handlers
     }
 
+    #[test]
+    fn jolt_print_ecall() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        // Write ECALL instruction, followed by the text to print
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00000073) {
+            Ok(_) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        for (i, b) in b"hi!\n".iter().enumerate() {
+            match cpu.get_mut_mmu().store(DRAM_BASE + 4 + i as u64, *b) {
+                Ok(_) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        cpu.x[10] = JOLT_PRINT_ECALL_NUM as i64;
+        cpu.x[11] = (DRAM_BASE + 4) as i64;
+        cpu.x[12] = 4;
+        cpu.x[13] = JOLT_PRINT_STDOUT as i64;
+        cpu.update_pc(DRAM_BASE);
+
+        cpu.tick(None);
+
+        // The ECALL is handled by the emulator rather than trapping
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+        assert_eq!(b"hi!\n".to_vec(), cpu.guest_output().stdout);
+        assert!(cpu.guest_output().stderr.is_empty());
+    }
+
+    #[test]
+    fn jolt_print_ecall_ignores_unknown_stream() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00000073) {
+            Ok(_) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        cpu.x[10] = JOLT_PRINT_ECALL_NUM as i64;
+        cpu.x[11] = (DRAM_BASE + 4) as i64;
+        cpu.x[12] = 4;
+        cpu.x[13] = 3;
+        cpu.update_pc(DRAM_BASE);
+
+        cpu.tick(None);
+
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+        assert!(cpu.guest_output().stdout.is_empty());
+        assert!(cpu.guest_output().stderr.is_empty());
+    }
+
+    #[test]
+    fn jolt_print_ecall_with_bad_length() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00000073) {
+            Ok(_) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        cpu.x[10] = JOLT_PRINT_ECALL_NUM as i64;
+        cpu.x[11] = (DRAM_BASE + 4) as i64;
+        // The buffer runs off the end of memory
+        cpu.x[12] = u32::MAX as i64;
+        cpu.x[13] = JOLT_PRINT_STDOUT as i64;
+        cpu.update_pc(DRAM_BASE);
+
+        cpu.tick(None);
+
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+        assert!(cpu.guest_output().stdout.is_empty());
+    }
+
+    #[test]
+    fn advice_instruction() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        // Write two ADVICE instructions (custom-0, funct7 = 0x7F) with rd = x5 and rd = x6
+        for (i, word) in [0xfe00028b, 0xfe00030b].into_iter().enumerate() {
+            match cpu.get_mut_mmu().store_word(DRAM_BASE + 4 * i as u64, word) {
+                Ok(_) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        let mut advice_tape = 0xdeadbeefu32.to_le_bytes().to_vec();
+        advice_tape.extend(7u32.to_le_bytes());
+        cpu.set_advice_tape(advice_tape);
+        cpu.update_pc(DRAM_BASE);
+
+        let mut trace = vec![];
+        cpu.tick(Some(&mut trace));
+        cpu.tick(Some(&mut trace));
+
+        assert_eq!(0xdeadbeef, cpu.read_register(5) as u32);
+        assert_eq!(7, cpu.read_register(6));
+        assert_eq!(2, trace.len());
+        assert!(matches!(
+            &trace[0],
+            RV32IMCycle::VirtualAdvice(cycle) if cycle.instruction.advice == 0xdeadbeef
+        ));
+    }
+
+    #[test]
+    fn advice_instruction_past_end_of_tape() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(16);
+        // ADVICE with rd = x5
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0xfe00028b) {
+            Ok(_) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        cpu.set_advice_tape(vec![1, 2]);
+        cpu.update_pc(DRAM_BASE);
+
+        cpu.tick(None);
+
+        assert!(cpu.advice_exhausted());
+        assert_eq!(0, cpu.read_register(5));
+    }
+
     #[test]
     fn hardocded_zero() {
         let mut cpu = create_cpu();
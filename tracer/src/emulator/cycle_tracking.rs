@@ -0,0 +1,136 @@
+//! Structured cycle-tracking events, recorded from the markers emitted by
+//! `jolt_sdk::start_cycle_tracking` / `jolt_sdk::end_cycle_tracking`.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+/// A single completed cycle-tracking span.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleTrackingEvent {
+    pub label: String,
+    /// Number of RV32IM instructions executed before the span started.
+    pub start_cycle: u64,
+    /// Number of RV32IM instructions executed before the span ended.
+    pub end_cycle: u64,
+    /// Number of trace rows (including virtual instructions) before the span started.
+    pub start_virtual_cycle: u64,
+    /// Number of trace rows (including virtual instructions) before the span ended.
+    pub end_virtual_cycle: u64,
+    /// Number of spans that were still open when this one started.
+    pub depth: usize,
+}
+
+impl CycleTrackingEvent {
+    pub fn rv32im_cycles(&self) -> u64 {
+        self.end_cycle - self.start_cycle
+    }
+
+    pub fn virtual_cycles(&self) -> u64 {
+        self.end_virtual_cycle - self.start_virtual_cycle
+    }
+}
+
+/// Aggregate over all spans that share a label.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelSummary {
+    pub label: String,
+    pub calls: usize,
+    pub rv32im_cycles: u64,
+    pub virtual_cycles: u64,
+}
+
+/// All cycle-tracking spans completed during an execution, in the order in
+/// which they ended.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleTrackingReport {
+    pub events: Vec<CycleTrackingEvent>,
+}
+
+impl CycleTrackingReport {
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Per-label totals and call counts, in order of each label's first appearance.
+    pub fn summary(&self) -> Vec<LabelSummary> {
+        let mut summaries: Vec<LabelSummary> = Vec::new();
+        for event in self.events.iter() {
+            match summaries.iter_mut().find(|s| s.label == event.label) {
+                Some(summary) => {
+                    summary.calls += 1;
+                    summary.rv32im_cycles += event.rv32im_cycles();
+                    summary.virtual_cycles += event.virtual_cycles();
+                }
+                None => summaries.push(LabelSummary {
+                    label: event.label.clone(),
+                    calls: 1,
+                    rv32im_cycles: event.rv32im_cycles(),
+                    virtual_cycles: event.virtual_cycles(),
+                }),
+            }
+        }
+        summaries
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        #[derive(Serialize)]
+        struct Json<'a> {
+            events: &'a [CycleTrackingEvent],
+            summary: Vec<LabelSummary>,
+        }
+        serde_json::to_string_pretty(&Json {
+            events: &self.events,
+            summary: self.summary(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(label: &str, start: u64, end: u64, depth: usize) -> CycleTrackingEvent {
+        CycleTrackingEvent {
+            label: label.into(),
+            start_cycle: start,
+            end_cycle: end,
+            start_virtual_cycle: 2 * start,
+            end_virtual_cycle: 2 * end,
+            depth,
+        }
+    }
+
+    #[test]
+    fn summary_aggregates_by_label() {
+        let report = CycleTrackingReport {
+            events: vec![
+                event("inner", 10, 15, 1),
+                event("inner", 20, 30, 1),
+                event("outer", 0, 40, 0),
+            ],
+        };
+        assert_eq!(
+            report.summary(),
+            vec![
+                LabelSummary {
+                    label: "inner".into(),
+                    calls: 2,
+                    rv32im_cycles: 15,
+                    virtual_cycles: 30,
+                },
+                LabelSummary {
+                    label: "outer".into(),
+                    calls: 1,
+                    rv32im_cycles: 40,
+                    virtual_cycles: 80,
+                },
+            ]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["events"].as_array().unwrap().len(), 3);
+        assert_eq!(json["summary"][0]["calls"], 2);
+    }
+}
@@ -6,6 +6,9 @@ use alloc::{vec, vec::Vec};
 pub struct Memory {
     /// Memory content
     pub data: Vec<u64>,
+    /// The LR/SC reservation word at `RESERVATION_ADDRESS`. It lies below main
+    /// memory and is only accessible from the LR.W/SC.W virtual sequences.
+    pub reservation: u32,
 }
 
 impl Memory {
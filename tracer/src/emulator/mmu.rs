@@ -3,7 +3,7 @@
 pub const DRAM_BASE: u64 = RAM_START_ADDRESS;
 
 use crate::instruction::{RAMRead, RAMWrite};
-use common::constants::{RAM_START_ADDRESS, STACK_CANARY_SIZE};
+use common::constants::{RAM_START_ADDRESS, RESERVATION_ADDRESS};
 use common::jolt_device::JoltDevice;
 
 use super::cpu::{get_privilege_mode, PrivilegeMode, Trap, TrapType, Xlen};
@@ -26,6 +26,10 @@ pub struct Mmu {
 
     pub jolt_device: Option<JoltDevice>,
 
+    /// Whether the LR/SC reservation word may be accessed, i.e. whether an LR.W/SC.W
+    /// virtual sequence is being executed. Guest code cannot access it directly.
+    reservation_access: bool,
+
     /// Address translation can be affected `mstatus` (MPRV, MPP in machine mode)
     /// then `Mmu` has copy of it.
     mstatus: u64,
@@ -70,10 +74,17 @@ impl Mmu {
             privilege_mode: PrivilegeMode::Machine,
             memory: MemoryWrapper::new(),
             jolt_device: None,
+            reservation_access: false,
             mstatus: 0,
         }
     }
 
+    /// Allows or forbids accesses to the LR/SC reservation word. Only the LR.W/SC.W
+    /// virtual sequences may access it.
+    pub fn set_reservation_access(&mut self, enabled: bool) {
+        self.reservation_access = enabled;
+    }
+
     /// Updates XLEN, 32-bit or 64-bit
     ///
     /// # Arguments
@@ -136,27 +147,30 @@ impl Mmu {
     }
 
     #[inline]
-    fn assert_effective_store_address(&self, effective_address: u64) {
-        self.assert_effective_address(effective_address, true)
+    fn check_effective_store_address(&mut self, effective_address: u64) -> bool {
+        self.check_effective_address(effective_address, true)
     }
 
     #[inline]
-    fn assert_effective_load_address(&self, effective_address: u64) {
-        self.assert_effective_address(effective_address, false)
+    fn check_effective_load_address(&mut self, effective_address: u64) -> bool {
+        self.check_effective_address(effective_address, false)
     }
 
-    /// Asserts the validity of an effective memory address.
-    /// Panics if the address is invalid.
+    /// Checks the validity of an effective memory address, returning whether the
+    /// access should be performed.
+    /// Panics if the address is invalid I/O. If the guest overflows its stack (writes
+    /// to the stack canary) or heap (accesses memory past `memory_end`), the fault is
+    /// recorded in the `JoltDevice` and `false` is returned; the guest is then stopped
+    /// at the faulting access (see `step_emulator`).
     ///
     /// # Arguments
     /// * `effective_address` Effective memory address to validate
     #[inline]
-    fn assert_effective_address(&self, ea: u64, is_write: bool) {
-        if self.jolt_device.is_none() {
-            return;
-        }
+    fn check_effective_address(&mut self, ea: u64, is_write: bool) -> bool {
+        let Some(jolt_device) = self.jolt_device.as_mut() else {
+            return true;
+        };
 
-        let jolt_device = self.jolt_device.as_ref().unwrap();
         let layout = &jolt_device.memory_layout;
         // helper strings
         let (action, verb) = if is_write {
@@ -167,9 +181,17 @@ impl Mmu {
 
         if ea < DRAM_BASE {
             // below DRAM_BASE => I/O
+            if jolt_device.is_reservation(ea) {
+                assert!(
+                    self.reservation_access,
+                    "Illegal {}: 0x{ea:X} holds the LR/SC reservation, which is only accessible from LR.W/SC.W",
+                    action.to_lowercase(),
+                );
+                return true;
+            }
             // bounds‐check against the termination (top) of the I/O region
             assert!(
-                ea <= layout.io_end,
+                ea <= layout.io_end || jolt_device.is_private_input(ea),
                 "I/O overflow: Attempted to {verb} 0x{ea:X}. Out of bounds.\n{layout:#?}",
             );
             assert!(
@@ -184,8 +206,9 @@ impl Mmu {
                     || jolt_device.is_panic(ea)
                     || jolt_device.is_termination(ea)
             } else {
-                // loads also from input
+                // loads also from (public and private) input
                 jolt_device.is_input(ea)
+                    || jolt_device.is_private_input(ea)
                     || jolt_device.is_output(ea)
                     || jolt_device.is_panic(ea)
                     || jolt_device.is_termination(ea)
@@ -195,25 +218,17 @@ impl Mmu {
                 "Illegal device {}: Unknown memory mapping: 0x{ea:X}\n{layout:#?}",
                 action.to_lowercase(),
             );
+            true
         } else {
-            // check within RAM
-            if is_write {
-                // These errors aren't necessarily correct as there's no way to distinguish between an
-                // attempt to write to the stack vs heap, but they're trying their best
-                assert!(
-                    ea <= layout.stack_end || ea > layout.stack_end + STACK_CANARY_SIZE,
-                    "Stack overflow: Triggered Stack Canary. Attempted to {verb} 0x{ea:X}.\n{layout:#?}",
-                );
-                assert!(
-                    ea < layout.memory_end,
-                    "Heap overflow: Attempted to {verb} 0x{ea:X}. Heap too small.\n{layout:#?}",
-                );
-            } else {
-                // allow reads across the whole designated memory region as long as the address is valid
-                assert!(
-                    ea < layout.memory_end,
-                    "Illegal Memory Access: Attempted to {verb} 0x{ea:X}.\n{layout:#?}",
-                );
+            // check within RAM. These faults aren't necessarily correct as there's no way to
+            // distinguish between an attempt to write to the stack vs heap, but they're trying
+            // their best. Reads are allowed across the whole designated memory region.
+            match layout.fault(ea, is_write) {
+                Some(fault) => {
+                    jolt_device.fault.get_or_insert(fault);
+                    false
+                }
+                None => true,
             }
         }
     }
@@ -486,7 +501,9 @@ impl Mmu {
     /// * `p_address` Physical address
     pub fn load_raw(&mut self, p_address: u64) -> u8 {
         let effective_address = self.get_effective_address(p_address);
-        self.assert_effective_load_address(effective_address);
+        if !self.check_effective_load_address(effective_address) {
+            return 0;
+        }
         // @TODO: Mapping should be configurable with dtb
         match effective_address >= DRAM_BASE {
             true => self.memory.read_byte(effective_address),
@@ -502,11 +519,13 @@ impl Mmu {
                 _ => {
                     if let Some(jolt_device) = self.jolt_device.as_ref() {
                         if jolt_device.is_input(effective_address)
+                            || jolt_device.is_private_input(effective_address)
                             || jolt_device.is_output(effective_address)
                             || jolt_device.is_panic(effective_address)
                             || jolt_device.is_termination(effective_address)
+                            || jolt_device.is_reservation(effective_address)
                         {
-                            return jolt_device.load(effective_address);
+                            return self.load_io_byte(effective_address);
                         }
                     }
                     panic!("Load Failed: Unknown memory mapping {effective_address:X}.");
@@ -515,9 +534,24 @@ impl Mmu {
         }
     }
 
+    /// Loads a byte from the I/O region, i.e. from the `JoltDevice` or, for the
+    /// LR/SC reservation word, from the emulator's memory.
+    fn load_io_byte(&self, address: u64) -> u8 {
+        let jolt_device = self.jolt_device.as_ref().expect("JoltDevice not set");
+        if jolt_device.is_reservation(address) {
+            let offset = (address - RESERVATION_ADDRESS) as usize;
+            self.memory.memory.reservation.to_le_bytes()[offset]
+        } else {
+            jolt_device.load(address)
+        }
+    }
+
     /// Records the memory word being accessed by a load instruction. The memory
     /// state is used in Jolt to construct the witnesses in `read_write_memory.rs`.
-    fn trace_load(&self, effective_address: u64) -> RAMRead {
+    fn trace_load(&mut self, effective_address: u64) -> RAMRead {
+        if !self.check_effective_load_address(effective_address) {
+            return RAMRead::default();
+        }
         let word_address = (effective_address >> 2) << 2;
         let bytes = match self.xlen {
             Xlen::Bit32 => 4,
@@ -526,11 +560,7 @@ impl Mmu {
         if word_address < DRAM_BASE {
             let mut value_bytes = [0u8; 8];
             for i in 0..bytes {
-                value_bytes[i as usize] = self
-                    .jolt_device
-                    .as_ref()
-                    .expect("JoltDevice not set")
-                    .load(word_address + i);
+                value_bytes[i as usize] = self.load_io_byte(word_address + i);
             }
             RAMRead {
                 address: word_address,
@@ -552,7 +582,9 @@ impl Mmu {
     /// before and after the store instruction. The memory state is used in Jolt to
     /// construct the witnesses in `read_write_memory.rs`.
     fn trace_store_byte(&mut self, effective_address: u64, value: u64) -> RAMWrite {
-        self.assert_effective_store_address(effective_address);
+        if !self.check_effective_store_address(effective_address) {
+            return RAMWrite::default();
+        }
         let bytes = match self.xlen {
             Xlen::Bit32 => 4,
             Xlen::Bit64 => 8,
@@ -562,11 +594,7 @@ impl Mmu {
         let pre_value = if effective_address < DRAM_BASE {
             let mut pre_value_bytes = [0u8; 8];
             for i in 0..bytes {
-                pre_value_bytes[i as usize] = self
-                    .jolt_device
-                    .as_ref()
-                    .expect("JoltDevice not set")
-                    .load(word_address + i);
+                pre_value_bytes[i as usize] = self.load_io_byte(word_address + i);
             }
             u64::from_le_bytes(pre_value_bytes)
         } else {
@@ -597,7 +625,9 @@ impl Mmu {
     /// before and after the store instruction. The memory state is used in Jolt to
     /// construct the witnesses in `read_write_memory.rs`.
     fn trace_store_halfword(&mut self, effective_address: u64, value: u64) -> RAMWrite {
-        self.assert_effective_store_address(effective_address);
+        if !self.check_effective_store_address(effective_address) {
+            return RAMWrite::default();
+        }
         let bytes = match self.xlen {
             Xlen::Bit32 => 4,
             Xlen::Bit64 => 8,
@@ -607,11 +637,7 @@ impl Mmu {
         let pre_value = if effective_address < DRAM_BASE {
             let mut pre_value_bytes = [0u8; 8];
             for i in 0..bytes {
-                pre_value_bytes[i as usize] = self
-                    .jolt_device
-                    .as_ref()
-                    .expect("JoltDevice not set")
-                    .load(word_address + i);
+                pre_value_bytes[i as usize] = self.load_io_byte(word_address + i);
             }
             u64::from_le_bytes(pre_value_bytes)
         } else {
@@ -642,7 +668,9 @@ impl Mmu {
     /// instruction. The memory state is used in Jolt to construct the witnesses
     /// in `read_write_memory.rs`.
     fn trace_store(&mut self, effective_address: u64, value: u64) -> RAMWrite {
-        self.assert_effective_store_address(effective_address);
+        if !self.check_effective_store_address(effective_address) {
+            return RAMWrite::default();
+        }
         let bytes = match self.xlen {
             Xlen::Bit32 => 4,
             Xlen::Bit64 => 8,
@@ -651,11 +679,7 @@ impl Mmu {
         if effective_address < DRAM_BASE {
             let mut pre_value_bytes = [0u8; 8];
             for i in 0..bytes {
-                pre_value_bytes[i as usize] = self
-                    .jolt_device
-                    .as_ref()
-                    .expect("JoltDevice not set")
-                    .load(effective_address + i);
+                pre_value_bytes[i as usize] = self.load_io_byte(effective_address + i);
             }
             let pre_value = u64::from_le_bytes(pre_value_bytes);
             RAMWrite {
@@ -689,7 +713,9 @@ impl Mmu {
         {
             // Fast path. Directly load main memory at a time.
             true => {
-                self.assert_effective_load_address(effective_address);
+                if !self.check_effective_load_address(effective_address) {
+                    return 0;
+                }
                 self.memory.read_halfword(effective_address)
             }
             false => {
@@ -714,7 +740,9 @@ impl Mmu {
         {
             // Fast path. Directly load main memory at a time.
             true => {
-                self.assert_effective_load_address(effective_address);
+                if !self.check_effective_load_address(effective_address) {
+                    return 0;
+                }
                 self.memory.read_word(effective_address)
             }
             false => {
@@ -739,7 +767,9 @@ impl Mmu {
         {
             // Fast path. Directly load main memory at a time.
             true => {
-                self.assert_effective_load_address(effective_address);
+                if !self.check_effective_load_address(effective_address) {
+                    return 0;
+                }
                 self.memory.read_doubleword(effective_address)
             }
             false => {
@@ -763,8 +793,9 @@ impl Mmu {
         // @TODO: Mapping should be configurable with dtb
         match effective_address >= DRAM_BASE {
             true => {
-                self.assert_effective_store_address(effective_address);
-                self.memory.write_byte(effective_address, value)
+                if self.check_effective_store_address(effective_address) {
+                    self.memory.write_byte(effective_address, value)
+                }
             }
             false => match effective_address {
                 0x02000000..=0x0200ffff => panic!("store_raw:clint is unsupported."),
@@ -772,8 +803,17 @@ impl Mmu {
                 0x10000000..=0x100000ff => panic!("store_raw:UART is unsupported."),
                 0x10001000..=0x10001FFF => panic!("store_raw:disk is unsupported."),
                 _ => {
-                    self.assert_effective_store_address(effective_address);
+                    if !self.check_effective_store_address(effective_address) {
+                        return;
+                    }
                     if let Some(jolt_device) = self.jolt_device.as_mut() {
+                        if jolt_device.is_reservation(effective_address) {
+                            let reservation = &mut self.memory.memory.reservation;
+                            let mut bytes = reservation.to_le_bytes();
+                            bytes[(effective_address - RESERVATION_ADDRESS) as usize] = value;
+                            *reservation = u32::from_le_bytes(bytes);
+                            return;
+                        }
                         return jolt_device.store(effective_address, value);
                     };
 
@@ -814,8 +854,9 @@ impl Mmu {
         {
             // Fast path. Directly store to main memory at a time.
             true => {
-                self.assert_effective_store_address(effective_address);
-                self.memory.write_halfword(effective_address, value)
+                if self.check_effective_store_address(effective_address) {
+                    self.memory.write_halfword(effective_address, value)
+                }
             }
             false => {
                 for i in 0..2 {
@@ -841,8 +882,9 @@ impl Mmu {
         {
             // Fast path. Directly store to main memory at a time.
             true => {
-                self.assert_effective_store_address(effective_address);
-                self.memory.write_word(effective_address, value)
+                if self.check_effective_store_address(effective_address) {
+                    self.memory.write_word(effective_address, value)
+                }
             }
             false => {
                 for i in 0..4 {
@@ -868,8 +910,9 @@ impl Mmu {
         {
             // Fast path. Directly store to main memory at a time.
             true => {
-                self.assert_effective_store_address(effective_address);
-                self.memory.write_doubleword(effective_address, value)
+                if self.check_effective_store_address(effective_address) {
+                    self.memory.write_doubleword(effective_address, value)
+                }
             }
             false => {
                 for i in 0..8 {
@@ -1186,12 +1229,13 @@ mod test_mmu {
     use super::*;
     use crate::emulator::terminal::DummyTerminal;
     use common::constants::DEFAULT_MEMORY_SIZE;
-    use common::jolt_device::MemoryConfig;
+    use common::jolt_device::{GuestFault, MemoryConfig};
 
     fn setup_mmu() -> Mmu {
         let terminal = Box::new(DummyTerminal::default());
         let mut mmu = Mmu::new(Xlen::Bit64, terminal);
         let memory_config = MemoryConfig {
+            max_private_input_size: 64,
             program_size: Some(1024),
             ..Default::default()
         };
@@ -1202,22 +1246,57 @@ mod test_mmu {
     }
 
     #[test]
-    #[should_panic(expected = "Heap overflow")]
     fn test_heap_overflow() {
         let mut mmu = setup_mmu();
 
         // Try to write beyond the allocated memory
         let overflow_address = mmu.jolt_device.as_ref().unwrap().memory_layout.memory_end + 1;
         mmu.trace_store(overflow_address, 0xc50513);
+        assert_eq!(
+            mmu.jolt_device.as_ref().unwrap().fault,
+            Some(GuestFault::HeapOverflow)
+        );
+    }
+
+    #[test]
+    fn test_heap_overflow_load() {
+        let mut mmu = setup_mmu();
+
+        let overflow_address = mmu.jolt_device.as_ref().unwrap().memory_layout.memory_end;
+        assert_eq!(mmu.load_word(overflow_address).unwrap().0, 0);
+        assert_eq!(
+            mmu.jolt_device.as_ref().unwrap().fault,
+            Some(GuestFault::HeapOverflow)
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Stack Canary")]
     fn test_stack_overflow() {
         let mut mmu = setup_mmu();
 
         let invalid_address = mmu.jolt_device.as_ref().unwrap().memory_layout.stack_end + 1;
         mmu.trace_store(invalid_address, 0xc50513);
+        assert_eq!(
+            mmu.jolt_device.as_ref().unwrap().fault,
+            Some(GuestFault::StackOverflow)
+        );
+
+        // The first fault is the one reported
+        let overflow_address = mmu.jolt_device.as_ref().unwrap().memory_layout.memory_end;
+        mmu.trace_store(overflow_address, 0xc50513);
+        assert_eq!(
+            mmu.jolt_device.as_ref().unwrap().fault,
+            Some(GuestFault::StackOverflow)
+        );
+    }
+
+    #[test]
+    fn test_stack_canary_write_is_not_performed() {
+        let mut mmu = setup_mmu();
+
+        let canary_address = mmu.jolt_device.as_ref().unwrap().memory_layout.stack_end + 8;
+        mmu.store_word(canary_address, 0xdeadbeef).unwrap();
+        assert_eq!(mmu.load_word_raw(canary_address), 0);
     }
 
     #[test]
@@ -1237,4 +1316,53 @@ mod test_mmu {
         // illegal write to inputs
         mmu.store_bytes(invalid_addr, 0xc50513, 2).unwrap();
     }
+
+    #[test]
+    fn test_private_input_load() {
+        let mut mmu = setup_mmu();
+        let jolt_device = mmu.jolt_device.as_mut().unwrap();
+        jolt_device.private_inputs = vec![0xab, 0xcd];
+        let private_input_start = jolt_device.memory_layout.private_input_start;
+        assert_eq!(mmu.load(private_input_start).unwrap().0, 0xab);
+        assert_eq!(mmu.load(private_input_start + 1).unwrap().0, 0xcd);
+    }
+
+    #[test]
+    #[should_panic(expected = "Illegal device store")]
+    fn test_private_input_store() {
+        let mut mmu = setup_mmu();
+        let private_input_start = mmu
+            .jolt_device
+            .as_ref()
+            .unwrap()
+            .memory_layout
+            .private_input_start;
+        // illegal write to private inputs
+        mmu.store_bytes(private_input_start, 0xc50513, 2).unwrap();
+    }
+
+    #[test]
+    fn test_reservation_access() {
+        let mut mmu = setup_mmu();
+        mmu.update_xlen(Xlen::Bit32);
+        mmu.set_reservation_access(true);
+        mmu.store_word(RESERVATION_ADDRESS, 0x8000_1000).unwrap();
+        assert_eq!(mmu.load_word(RESERVATION_ADDRESS).unwrap().0, 0x8000_1000);
+        assert_eq!(mmu.memory.memory.reservation, 0x8000_1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "holds the LR/SC reservation")]
+    fn test_reservation_store_outside_lr_sc() {
+        let mut mmu = setup_mmu();
+        // guests cannot forge a reservation
+        mmu.store_word(RESERVATION_ADDRESS, 0x8000_1000).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "holds the LR/SC reservation")]
+    fn test_reservation_load_outside_lr_sc() {
+        let mut mmu = setup_mmu();
+        mmu.load_word(RESERVATION_ADDRESS).unwrap();
+    }
 }
@@ -23,8 +23,10 @@ use alloc::{
 };
 
 pub mod cpu;
+pub mod cycle_tracking;
 pub mod default_terminal;
 pub mod elf_analyzer;
+pub mod guest_output;
 pub mod memory;
 pub mod mmu;
 pub mod terminal;
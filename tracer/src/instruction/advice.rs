@@ -0,0 +1,62 @@
+//! ADVICE (custom-0, funct3 = 0, funct7 = 0x7F) — reads the next 32-bit word of the
+//! host-provided advice tape into `rd`.
+//!
+//! The advice tape is untrusted: it is not part of the `JoltDevice`, and the
+//! value written to `rd` is unconstrained (modulo a range check), so guests
+//! must verify anything they read from it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::{
+    format::{format_j::FormatJ, format_r::FormatR, InstructionFormat},
+    virtual_advice::VirtualAdvice,
+    RISCVInstruction, RISCVTrace, RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence,
+};
+
+declare_riscv_instr!(
+    name   = ADVICE,
+    mask   = 0xfe00707f,
+    match  = 0xfe00000b,
+    format = FormatR,
+    ram    = ()
+);
+
+impl ADVICE {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <ADVICE as RISCVInstruction>::RAMAccess) {
+        cpu.x[self.operands.rd as usize] = cpu.read_advice_word() as i64;
+    }
+}
+
+impl RISCVTrace for ADVICE {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let mut virtual_sequence = self.virtual_sequence();
+        if let RV32IMInstruction::VirtualAdvice(instr) = &mut virtual_sequence[0] {
+            instr.advice = cpu.read_advice_word();
+        } else {
+            panic!("Expected Advice instruction");
+        }
+
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for ADVICE {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        let advice = VirtualAdvice {
+            address: self.address,
+            operands: FormatJ {
+                rd: self.operands.rd,
+                imm: 0,
+            },
+            virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
+            advice: 0,
+        };
+        vec![advice.into()]
+    }
+}
@@ -0,0 +1,384 @@
+//! Virtual sequences for the RV32A extension.
+//!
+//! An AMO*.W loads the word at `rs1`, stores the updated word back, and writes
+//! the original word to `rd`. LR.W/SC.W keep their reservation in the word at
+//! `RESERVATION_ADDRESS`, so the reservation is checked by RAM Twist along with
+//! the memory accesses themselves.
+
+use common::constants::{virtual_register_index, RESERVATION_ADDRESS};
+
+use super::{
+    add::ADD,
+    and::AND,
+    format::{
+        format_i::FormatI, format_load::FormatLoad, format_r::FormatR, format_s::FormatS,
+        format_u::FormatU,
+    },
+    lui::LUI,
+    lw::LW,
+    or::OR,
+    slt::SLT,
+    sltu::SLTU,
+    sub::SUB,
+    sw::SW,
+    virtual_move::VirtualMove,
+    xor::XOR,
+    RV32IMInstruction,
+};
+
+/// The read-modify-write operation performed by an AMO*.W instruction.
+#[derive(Clone, Copy, Debug)]
+pub enum AMOOp {
+    Swap,
+    Add,
+    And,
+    Or,
+    Xor,
+    Min,
+    Max,
+    MinU,
+    MaxU,
+}
+
+/// Builds a virtual sequence one instruction at a time, filling in
+/// `virtual_sequence_remaining` once the length of the sequence is known.
+struct SequenceBuilder {
+    address: u64,
+    sequence: Vec<RV32IMInstruction>,
+}
+
+impl SequenceBuilder {
+    fn new(address: u64) -> Self {
+        Self {
+            address,
+            sequence: vec![],
+        }
+    }
+
+    fn r<I: Into<RV32IMInstruction>>(
+        &mut self,
+        constructor: fn(u64, FormatR) -> I,
+        rd: u8,
+        rs1: u8,
+        rs2: u8,
+    ) {
+        let instr = constructor(self.address, FormatR { rd, rs1, rs2 });
+        self.sequence.push(instr.into());
+    }
+
+    fn lui(&mut self, rd: u8, imm: u64) {
+        self.sequence.push(
+            LUI {
+                address: self.address,
+                operands: FormatU { rd, imm },
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            }
+            .into(),
+        );
+    }
+
+    fn lw(&mut self, rd: u8, rs1: u8) {
+        self.sequence.push(
+            LW {
+                address: self.address,
+                operands: FormatLoad { rd, rs1, imm: 0 },
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            }
+            .into(),
+        );
+    }
+
+    fn sw(&mut self, rs1: u8, rs2: u8) {
+        self.sequence.push(
+            SW {
+                address: self.address,
+                operands: FormatS { rs1, rs2, imm: 0 },
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            }
+            .into(),
+        );
+    }
+
+    fn mov(&mut self, rd: u8, rs1: u8) {
+        self.sequence.push(
+            VirtualMove {
+                address: self.address,
+                operands: FormatI { rd, rs1, imm: 0 },
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            }
+            .into(),
+        );
+    }
+
+    /// rd = if lhs < rhs { if_true } else { if_false }, without branching:
+    /// rd = if_false ^ ((if_true ^ if_false) & -(lhs < rhs))
+    #[allow(clippy::too_many_arguments)]
+    fn select_less_than(
+        &mut self,
+        signed: bool,
+        lhs: u8,
+        rhs: u8,
+        if_true: u8,
+        if_false: u8,
+        rd: u8,
+        v_mask: u8,
+    ) {
+        if signed {
+            self.r(slt, v_mask, lhs, rhs);
+        } else {
+            self.r(sltu, v_mask, lhs, rhs);
+        }
+        self.r(sub, v_mask, 0, v_mask);
+        self.r(xor, rd, if_true, if_false);
+        self.r(and, rd, rd, v_mask);
+        self.r(xor, rd, rd, if_false);
+    }
+
+    fn build(mut self) -> Vec<RV32IMInstruction> {
+        let len = self.sequence.len();
+        for (i, instr) in self.sequence.iter_mut().enumerate() {
+            instr.set_virtual_sequence_remaining(Some((len - i - 1) as u16));
+        }
+        self.sequence
+    }
+}
+
+macro_rules! format_r_constructor {
+    ($name:ident, $instr:ident) => {
+        fn $name(address: u64, operands: FormatR) -> $instr {
+            $instr {
+                address,
+                operands,
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            }
+        }
+    };
+}
+
+format_r_constructor!(add, ADD);
+format_r_constructor!(and, AND);
+format_r_constructor!(or, OR);
+format_r_constructor!(xor, XOR);
+format_r_constructor!(slt, SLT);
+format_r_constructor!(sltu, SLTU);
+format_r_constructor!(sub, SUB);
+
+/// Virtual sequence for an AMO*.W instruction with the given operands.
+pub fn amo_w_virtual_sequence(
+    address: u64,
+    operands: &FormatR,
+    op: AMOOp,
+) -> Vec<RV32IMInstruction> {
+    let v_old = virtual_register_index(0);
+    let v_new = virtual_register_index(1);
+    let v_mask = virtual_register_index(2);
+    let FormatR { rd, rs1, rs2 } = *operands;
+
+    let mut builder = SequenceBuilder::new(address);
+    builder.lw(v_old, rs1);
+    let new_value = match op {
+        AMOOp::Swap => rs2,
+        AMOOp::Add => {
+            builder.r(add, v_new, v_old, rs2);
+            v_new
+        }
+        AMOOp::And => {
+            builder.r(and, v_new, v_old, rs2);
+            v_new
+        }
+        AMOOp::Or => {
+            builder.r(or, v_new, v_old, rs2);
+            v_new
+        }
+        AMOOp::Xor => {
+            builder.r(xor, v_new, v_old, rs2);
+            v_new
+        }
+        AMOOp::Min | AMOOp::MinU => {
+            let signed = matches!(op, AMOOp::Min);
+            builder.select_less_than(signed, v_old, rs2, v_old, rs2, v_new, v_mask);
+            v_new
+        }
+        AMOOp::Max | AMOOp::MaxU => {
+            let signed = matches!(op, AMOOp::Max);
+            builder.select_less_than(signed, rs2, v_old, v_old, rs2, v_new, v_mask);
+            v_new
+        }
+    };
+    builder.sw(rs1, new_value);
+    builder.mov(rd, v_old);
+    builder.build()
+}
+
+/// Virtual sequence for LR.W: records `rs1` as the reserved address, then loads
+/// the word at `rs1` into `rd`.
+pub fn lr_w_virtual_sequence(address: u64, operands: &FormatR) -> Vec<RV32IMInstruction> {
+    let v_reservation_address = virtual_register_index(0);
+    let FormatR { rd, rs1, .. } = *operands;
+
+    let mut builder = SequenceBuilder::new(address);
+    builder.lui(v_reservation_address, RESERVATION_ADDRESS);
+    builder.sw(v_reservation_address, rs1);
+    builder.lw(rd, rs1);
+    builder.build()
+}
+
+/// Virtual sequence for SC.W. The reservation is always cleared. If `rs1` is
+/// the reserved address, `rs2` is stored at `rs1` and `rd` is set to 0;
+/// otherwise the word at `rs1` is stored back unchanged and `rd` is set to 1.
+pub fn sc_w_virtual_sequence(address: u64, operands: &FormatR) -> Vec<RV32IMInstruction> {
+    let v_reservation_address = virtual_register_index(0);
+    let v_failed = virtual_register_index(1);
+    let v_old = virtual_register_index(2);
+    let v_new = virtual_register_index(3);
+    let v_mask = virtual_register_index(4);
+    let FormatR { rd, rs1, rs2 } = *operands;
+
+    let mut builder = SequenceBuilder::new(address);
+    builder.lui(v_reservation_address, RESERVATION_ADDRESS);
+    builder.lw(v_failed, v_reservation_address);
+    builder.sw(v_reservation_address, 0);
+    // v_failed = (reservation != rs1)
+    builder.r(xor, v_failed, v_failed, rs1);
+    builder.r(sltu, v_failed, 0, v_failed);
+    builder.lw(v_old, rs1);
+    // v_new = if v_failed { v_old } else { rs2 }
+    builder.r(sub, v_mask, 0, v_failed);
+    builder.r(xor, v_new, v_old, rs2);
+    builder.r(and, v_new, v_new, v_mask);
+    builder.r(xor, v_new, v_new, rs2);
+    builder.sw(rs1, v_new);
+    builder.mov(rd, v_failed);
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::{
+        cpu::{Cpu, Xlen},
+        terminal::DummyTerminal,
+    };
+    use crate::instruction::{lrw::LRW, scw::SCW, RISCVTrace};
+    use common::constants::DEFAULT_MEMORY_SIZE;
+    use common::jolt_device::{JoltDevice, MemoryConfig};
+
+    const RD: u8 = 10;
+    const RS1: u8 = 11;
+    const RS2: u8 = 12;
+
+    fn setup_cpu() -> (Cpu, u64) {
+        let mut cpu = Cpu::new(Box::new(DummyTerminal::default()));
+        cpu.update_xlen(Xlen::Bit32);
+        let memory_config = MemoryConfig {
+            program_size: Some(1024),
+            ..Default::default()
+        };
+        let jolt_device = JoltDevice::new(&memory_config);
+        let address = jolt_device.memory_layout.stack_end - 4;
+        cpu.mmu.jolt_device = Some(jolt_device);
+        cpu.mmu.init_memory(DEFAULT_MEMORY_SIZE);
+        (cpu, address)
+    }
+
+    fn run(cpu: &mut Cpu, sequence: Vec<RV32IMInstruction>) {
+        let mut trace = vec![];
+        for instr in sequence {
+            instr.trace(cpu, Some(&mut trace));
+        }
+    }
+
+    #[test]
+    fn amo_w_sequences() {
+        let values: [i32; 4] = [5, -7, i32::MIN, i32::MAX];
+        let ops = [
+            AMOOp::Swap,
+            AMOOp::Add,
+            AMOOp::And,
+            AMOOp::Or,
+            AMOOp::Xor,
+            AMOOp::Min,
+            AMOOp::Max,
+            AMOOp::MinU,
+            AMOOp::MaxU,
+        ];
+        for op in ops {
+            for &old in values.iter() {
+                for &operand in values.iter() {
+                    let (mut cpu, address) = setup_cpu();
+                    cpu.mmu.store_word(address, old as u32).unwrap();
+                    cpu.x[RS1 as usize] = address as u32 as i32 as i64;
+                    cpu.x[RS2 as usize] = operand as i64;
+                    let operands = FormatR {
+                        rd: RD,
+                        rs1: RS1,
+                        rs2: RS2,
+                    };
+                    run(&mut cpu, amo_w_virtual_sequence(0, &operands, op));
+
+                    let expected = match op {
+                        AMOOp::Swap => operand,
+                        AMOOp::Add => old.wrapping_add(operand),
+                        AMOOp::And => old & operand,
+                        AMOOp::Or => old | operand,
+                        AMOOp::Xor => old ^ operand,
+                        AMOOp::Min => old.min(operand),
+                        AMOOp::Max => old.max(operand),
+                        AMOOp::MinU => (old as u32).min(operand as u32) as i32,
+                        AMOOp::MaxU => (old as u32).max(operand as u32) as i32,
+                    };
+                    assert_eq!(cpu.x[RD as usize], old as i64, "{op:?}");
+                    assert_eq!(
+                        cpu.mmu.load_word(address).unwrap().0,
+                        expected as u32,
+                        "{op:?}({old}, {operand})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lr_sc_w_sequences() {
+        let (mut cpu, address) = setup_cpu();
+        cpu.mmu.store_word(address, 1).unwrap();
+        cpu.x[RS1 as usize] = address as u32 as i32 as i64;
+        cpu.x[RS2 as usize] = 2;
+        let operands = FormatR {
+            rd: RD,
+            rs1: RS1,
+            rs2: RS2,
+        };
+
+        let lr = LRW {
+            operands,
+            ..Default::default()
+        };
+        let sc = SCW {
+            operands,
+            ..Default::default()
+        };
+
+        lr.trace(&mut cpu, Some(&mut vec![]));
+        assert_eq!(cpu.x[RD as usize], 1);
+        // The reservation is kept in the emulator's memory, not in the JoltDevice
+        assert_eq!(cpu.mmu.memory.memory.reservation, address as u32);
+
+        // The reservation is held, so the store succeeds
+        sc.trace(&mut cpu, Some(&mut vec![]));
+        assert_eq!(cpu.x[RD as usize], 0);
+        assert_eq!(cpu.mmu.load_word(address).unwrap().0, 2);
+        assert_eq!(cpu.mmu.memory.memory.reservation, 0);
+
+        // The first SC cleared the reservation, so this one fails
+        cpu.x[RS2 as usize] = 3;
+        sc.trace(&mut cpu, Some(&mut vec![]));
+        assert_eq!(cpu.x[RD as usize], 1);
+        assert_eq!(cpu.mmu.load_word(address).unwrap().0, 2);
+    }
+}
@@ -2,10 +2,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::{declare_riscv_instr, emulator::cpu::Cpu};
 
+use super::amo::{amo_w_virtual_sequence, AMOOp};
 use super::{
     format::{format_r::FormatR, InstructionFormat},
     RISCVInstruction, RISCVTrace,
 };
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
 
 declare_riscv_instr!(
     name   = AMOADDW,
@@ -38,4 +40,19 @@ impl AMOADDW {
     }
 }
 
-impl RISCVTrace for AMOADDW {}
+impl RISCVTrace for AMOADDW {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for AMOADDW {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        amo_w_virtual_sequence(self.address, &self.operands, AMOOp::Add)
+    }
+}
@@ -109,6 +109,7 @@ impl VirtualInstructionSequence for DIV {
             address: self.address,
             operands: FormatJ { rd: v_q, imm: 0 },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -117,6 +118,7 @@ impl VirtualInstructionSequence for DIV {
             address: self.address,
             operands: FormatJ { rd: v_r, imm: 0 },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -129,6 +131,7 @@ impl VirtualInstructionSequence for DIV {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.push(is_valid.into());
 
@@ -140,6 +143,7 @@ impl VirtualInstructionSequence for DIV {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(is_valid.into());
 
@@ -151,6 +155,7 @@ impl VirtualInstructionSequence for DIV {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mul.into());
 
@@ -162,6 +167,7 @@ impl VirtualInstructionSequence for DIV {
                 rs2: v_r,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -173,6 +179,7 @@ impl VirtualInstructionSequence for DIV {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(assert_eq.into());
 
@@ -184,6 +191,7 @@ impl VirtualInstructionSequence for DIV {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(virtual_move.into());
 
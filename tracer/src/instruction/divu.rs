@@ -95,6 +95,7 @@ impl VirtualInstructionSequence for DIVU {
             address: self.address,
             operands: FormatJ { rd: v_q, imm: 0 },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -103,6 +104,7 @@ impl VirtualInstructionSequence for DIVU {
             address: self.address,
             operands: FormatJ { rd: v_r, imm: 0 },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -115,6 +117,7 @@ impl VirtualInstructionSequence for DIVU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.push(is_valid.into());
 
@@ -126,6 +129,7 @@ impl VirtualInstructionSequence for DIVU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(is_valid.into());
 
@@ -137,6 +141,7 @@ impl VirtualInstructionSequence for DIVU {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mul.into());
 
@@ -148,6 +153,7 @@ impl VirtualInstructionSequence for DIVU {
                 rs2: v_r,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -159,6 +165,7 @@ impl VirtualInstructionSequence for DIVU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(assert_eq.into());
 
@@ -170,6 +177,7 @@ impl VirtualInstructionSequence for DIVU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(virtual_move.into());
 
@@ -1,4 +1,4 @@
-//! ECALL (SYSTEM 0x0000_0073) — currently only serves for Jolt cycle-tracking.
+//! ECALL (SYSTEM 0x0000_0073) — currently only serves for Jolt cycle-tracking and guest printing.
 //! Although, this will be used for "pseudo-precompiles"
 
 use serde::{Deserialize, Serialize};
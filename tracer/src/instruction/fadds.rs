@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::float::fadd_s_virtual_sequence;
+use super::{
+    format::{format_float_r::FormatFloatR, InstructionFormat},
+    RISCVInstruction, RISCVTrace,
+};
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
+
+declare_riscv_instr!(
+    name   = FADDS,
+    mask   = 0xfe00007f,
+    match  = 0x00000053,
+    format = FormatFloatR,
+    ram    = ()
+);
+
+impl FADDS {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <FADDS as RISCVInstruction>::RAMAccess) {
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
+        }
+    }
+}
+
+impl RISCVTrace for FADDS {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for FADDS {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        fadd_s_virtual_sequence(self.address, self.is_compressed, &self.operands, false)
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::float::fcvt_s_w_virtual_sequence;
+use super::{
+    format::{format_float_r::FormatFloatR, InstructionFormat},
+    RISCVInstruction, RISCVTrace,
+};
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
+
+declare_riscv_instr!(
+    name   = FCVTSW,
+    mask   = 0xfff0007f,
+    match  = 0xd0000053,
+    format = FormatFloatR,
+    ram    = ()
+);
+
+impl FCVTSW {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <FCVTSW as RISCVInstruction>::RAMAccess) {
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
+        }
+    }
+}
+
+impl RISCVTrace for FCVTSW {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for FCVTSW {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        fcvt_s_w_virtual_sequence(self.address, self.is_compressed, &self.operands, false)
+    }
+}
@@ -0,0 +1,1651 @@
+//! Virtual sequences for the RV32F extension.
+//!
+//! Single-precision values live in the floating-point registers `f0`-`f31`,
+//! which are ordinary (virtual) registers of the register file (see
+//! `float_register_index`), so each F instruction can be expanded into a
+//! sequence of integer and virtual instructions that operate on raw IEEE 754
+//! bit patterns. The sequences are branch-free: every special case is computed
+//! and then selected with a mask.
+//!
+//! FDIV.S and FSQRT.S use untrusted advice for the quotient and square root of
+//! the significands, which the sequence checks by multiplication.
+//!
+//! The dynamic rounding mode and the exception flags in `fcsr` are not
+//! modeled; `rm = DYN` rounds to nearest, ties to even.
+
+use common::constants::{float_register_index, virtual_register_index};
+
+use super::{
+    add::ADD,
+    addi::ADDI,
+    and::AND,
+    andi::ANDI,
+    format::{
+        format_b::FormatB, format_float_r::FormatFloatR, format_i::FormatI, format_j::FormatJ,
+        format_load::FormatLoad, format_r::FormatR, format_s::FormatS,
+        format_virtual_right_shift_i::FormatVirtualRightShiftI,
+        format_virtual_right_shift_r::FormatVirtualRightShiftR,
+    },
+    lw::LW,
+    mul::MUL,
+    mulhu::MULHU,
+    or::OR,
+    ori::ORI,
+    slt::SLT,
+    slti::SLTI,
+    sltiu::SLTIU,
+    sltu::SLTU,
+    sub::SUB,
+    sw::SW,
+    virtual_advice::VirtualAdvice,
+    virtual_assert_eq::VirtualAssertEQ,
+    virtual_assert_lte::VirtualAssertLTE,
+    virtual_assert_valid_unsigned_remainder::VirtualAssertValidUnsignedRemainder,
+    virtual_move::VirtualMove,
+    virtual_movsign::VirtualMovsign,
+    virtual_muli::VirtualMULI,
+    virtual_pow2::VirtualPow2,
+    virtual_shift_right_bitmask::VirtualShiftRightBitmask,
+    virtual_srai::VirtualSRAI,
+    virtual_srl::VirtualSRL,
+    virtual_srli::VirtualSRLI,
+    xor::XOR,
+    xori::XORI,
+    RV32IMInstruction,
+};
+
+const SIGN_MASK: i64 = 0x8000_0000;
+const ABS_MASK: i64 = 0x7fff_ffff;
+const EXPONENT_MASK: i64 = 0xff;
+const FRACTION_MASK: i64 = 0x7f_ffff;
+const INFINITY: i64 = 0x7f80_0000;
+const MAX_FINITE: i64 = 0x7f7f_ffff;
+const CANONICAL_NAN: i64 = 0x7fc0_0000;
+const EXPONENT_BIAS: i64 = 127;
+
+/// Rounding mode encoded in the `rm` field of an F instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    NearestEven,
+    TowardZero,
+    Down,
+    Up,
+    NearestMaxMagnitude,
+}
+
+impl RoundingMode {
+    /// `rm = DYN` (7) selects the rounding mode in `fcsr`, which is not
+    /// modeled, so it falls back to round to nearest, ties to even.
+    pub fn from_rm(rm: u8) -> Self {
+        match rm {
+            0b001 => RoundingMode::TowardZero,
+            0b010 => RoundingMode::Down,
+            0b011 => RoundingMode::Up,
+            0b100 => RoundingMode::NearestMaxMagnitude,
+            _ => RoundingMode::NearestEven,
+        }
+    }
+}
+
+/// Whether `rm` is a valid rounding mode (5 and 6 are reserved).
+pub fn is_valid_rounding_mode(rm: u8) -> bool {
+    !matches!(rm, 0b101 | 0b110)
+}
+
+/// The unpacked fields of a single-precision value. `exponent` is the biased
+/// exponent, except that subnormals use 1 (the exponent they share with the
+/// smallest normals); `significand` includes the hidden bit.
+struct Unpacked {
+    sign: u8,
+    exponent: u8,
+    significand: u8,
+}
+
+/// Flags describing the class of a single-precision value.
+struct Class {
+    nan: u8,
+    infinity: u8,
+    zero: u8,
+}
+
+/// Builds a virtual sequence one instruction at a time, handing out virtual
+/// registers as temporaries and filling in `virtual_sequence_remaining` once
+/// the length of the sequence is known.
+struct SequenceBuilder {
+    address: u64,
+    is_compressed: bool,
+    sequence: Vec<RV32IMInstruction>,
+    free_registers: Vec<u8>,
+}
+
+impl SequenceBuilder {
+    fn new(address: u64, is_compressed: bool) -> Self {
+        Self {
+            address,
+            is_compressed,
+            sequence: vec![],
+            free_registers: (0..32).rev().map(virtual_register_index).collect(),
+        }
+    }
+
+    fn alloc(&mut self) -> u8 {
+        self.free_registers
+            .pop()
+            .expect("F virtual sequence ran out of virtual registers")
+    }
+
+    fn free(&mut self, registers: &[u8]) {
+        for &register in registers {
+            debug_assert!(!self.free_registers.contains(&register));
+            self.free_registers.push(register);
+        }
+    }
+
+    fn push<I: Into<RV32IMInstruction>>(&mut self, instr: I) {
+        self.sequence.push(instr.into());
+    }
+
+    fn r<I: Into<RV32IMInstruction>>(
+        &mut self,
+        constructor: fn(u64, FormatR) -> I,
+        rd: u8,
+        rs1: u8,
+        rs2: u8,
+    ) {
+        let instr = constructor(self.address, FormatR { rd, rs1, rs2 });
+        self.push(instr);
+    }
+
+    /// Immediates are 32-bit patterns; larger-than-12-bit immediates are fine
+    /// in virtual sequences.
+    fn i<I: Into<RV32IMInstruction>>(
+        &mut self,
+        constructor: fn(u64, FormatI) -> I,
+        rd: u8,
+        rs1: u8,
+        imm: i64,
+    ) {
+        let imm = imm as u64;
+        let instr = constructor(self.address, FormatI { rd, rs1, imm });
+        self.push(instr);
+    }
+
+    fn b<I: Into<RV32IMInstruction>>(
+        &mut self,
+        constructor: fn(u64, FormatB) -> I,
+        rs1: u8,
+        rs2: u8,
+    ) {
+        let instr = constructor(self.address, FormatB { rs1, rs2, imm: 0 });
+        self.push(instr);
+    }
+
+    fn li(&mut self, rd: u8, imm: i64) {
+        self.i(addi, rd, 0, imm);
+    }
+
+    fn mov(&mut self, rd: u8, rs1: u8) {
+        self.i(virtual_move, rd, rs1, 0);
+    }
+
+    fn advice(&mut self, rd: u8) {
+        self.push(VirtualAdvice {
+            address: self.address,
+            operands: FormatJ { rd, imm: 0 },
+            virtual_sequence_remaining: None,
+            is_compressed: self.is_compressed,
+            advice: 0,
+        });
+    }
+
+    fn lw(&mut self, rd: u8, rs1: u8, imm: i64) {
+        self.push(LW {
+            address: self.address,
+            operands: FormatLoad { rd, rs1, imm },
+            virtual_sequence_remaining: None,
+            is_compressed: self.is_compressed,
+        });
+    }
+
+    fn sw(&mut self, rs1: u8, rs2: u8, imm: i64) {
+        self.push(SW {
+            address: self.address,
+            operands: FormatS { rs1, rs2, imm },
+            virtual_sequence_remaining: None,
+            is_compressed: self.is_compressed,
+        });
+    }
+
+    /// rd = rs1 << shift
+    fn slli(&mut self, rd: u8, rs1: u8, shift: u32) {
+        self.i(virtual_muli, rd, rs1, 1 << shift);
+    }
+
+    fn right_shift_bitmask(shift: u32) -> u64 {
+        let ones = (1u64 << (32 - shift)) - 1;
+        ones << shift
+    }
+
+    /// rd = rs1 >> shift (logical)
+    fn srli(&mut self, rd: u8, rs1: u8, shift: u32) {
+        let imm = Self::right_shift_bitmask(shift);
+        self.push(VirtualSRLI {
+            address: self.address,
+            operands: FormatVirtualRightShiftI { rd, rs1, imm },
+            virtual_sequence_remaining: None,
+            is_compressed: self.is_compressed,
+        });
+    }
+
+    /// rd = rs1 >> shift (arithmetic)
+    fn srai(&mut self, rd: u8, rs1: u8, shift: u32) {
+        let imm = Self::right_shift_bitmask(shift);
+        self.push(VirtualSRAI {
+            address: self.address,
+            operands: FormatVirtualRightShiftI { rd, rs1, imm },
+            virtual_sequence_remaining: None,
+            is_compressed: self.is_compressed,
+        });
+    }
+
+    /// rd = rs1 << (amount % 32)
+    fn sll(&mut self, rd: u8, rs1: u8, amount: u8) {
+        let v_pow2 = self.alloc();
+        self.i(virtual_pow2, v_pow2, amount, 0);
+        self.r(mul, rd, rs1, v_pow2);
+        self.free(&[v_pow2]);
+    }
+
+    /// rd = rs1 >> (amount % 32) (logical)
+    fn srl(&mut self, rd: u8, rs1: u8, amount: u8) {
+        let v_bitmask = self.alloc();
+        self.i(virtual_shift_right_bitmask, v_bitmask, amount, 0);
+        self.push(VirtualSRL {
+            address: self.address,
+            operands: FormatVirtualRightShiftR {
+                rd,
+                rs1,
+                rs2: v_bitmask,
+            },
+            virtual_sequence_remaining: None,
+            is_compressed: self.is_compressed,
+        });
+        self.free(&[v_bitmask]);
+    }
+
+    /// rd = (rs1 >> amount) | sticky, where the sticky bit is set if any of the
+    /// bits shifted out were set. `amount` must be less than 32.
+    fn srl_sticky(&mut self, rd: u8, rs1: u8, amount: u8) {
+        let v_shifted = self.alloc();
+        let v_lost = self.alloc();
+        self.srl(v_shifted, rs1, amount);
+        self.sll(v_lost, v_shifted, amount);
+        self.r(sltu, v_lost, v_lost, rs1);
+        self.r(or, rd, v_shifted, v_lost);
+        self.free(&[v_shifted, v_lost]);
+    }
+
+    /// rd = if condition { if_true } else { if_false }, for a condition that
+    /// is 0 or 1: rd = if_false ^ ((if_true ^ if_false) & -condition)
+    fn select(&mut self, rd: u8, condition: u8, if_true: u8, if_false: u8) {
+        let v_mask = self.alloc();
+        let v_diff = self.alloc();
+        self.r(sub, v_mask, 0, condition);
+        self.r(xor, v_diff, if_true, if_false);
+        self.r(and, v_diff, v_diff, v_mask);
+        self.r(xor, rd, v_diff, if_false);
+        self.free(&[v_mask, v_diff]);
+    }
+
+    fn select_imm(&mut self, rd: u8, condition: u8, if_true: i64, if_false: u8) {
+        let v_imm = self.alloc();
+        self.li(v_imm, if_true);
+        self.select(rd, condition, v_imm, if_false);
+        self.free(&[v_imm]);
+    }
+
+    /// rd = min(rs1, 31), for a non-negative rs1
+    fn clamp_shift(&mut self, rd: u8, rs1: u8) {
+        let v_out_of_range = self.alloc();
+        self.i(sltiu, v_out_of_range, rs1, 32);
+        self.i(xori, v_out_of_range, v_out_of_range, 1);
+        self.select_imm(rd, v_out_of_range, 31, rs1);
+        self.free(&[v_out_of_range]);
+    }
+
+    /// rd = -rs1 if negate else rs1, for a `negate` flag that is 0 or 1
+    fn conditional_negate(&mut self, rd: u8, rs1: u8, negate: u8) {
+        let v_mask = self.alloc();
+        self.r(sub, v_mask, 0, negate);
+        self.r(xor, rd, rs1, v_mask);
+        self.r(sub, rd, rd, v_mask);
+        self.free(&[v_mask]);
+    }
+
+    /// Shifts `significand` left until its leading one is at bit `top` (or it
+    /// has been shifted by 31 bits, if it is zero), adding the shift amount to
+    /// `count`. `significand` must be below `2^(top + 1)`.
+    fn normalize(&mut self, significand: u8, count: u8, top: u32) {
+        let v_short = self.alloc();
+        let v_amount = self.alloc();
+        for k in [16, 8, 4, 2, 1] {
+            // Shift by k if the top k bits of the window are all zero
+            self.i(sltiu, v_short, significand, 1 << (top + 1 - k));
+            self.i(virtual_muli, v_amount, v_short, k as i64);
+            self.r(add, count, count, v_amount);
+            self.i(virtual_muli, v_short, v_short, (1 << k) - 1);
+            self.i(addi, v_short, v_short, 1);
+            self.r(mul, significand, significand, v_short);
+        }
+        self.free(&[v_short, v_amount]);
+    }
+
+    /// Unpacks the single-precision value in `rs1`.
+    fn unpack(&mut self, rs1: u8) -> Unpacked {
+        let unpacked = Unpacked {
+            sign: self.alloc(),
+            exponent: self.alloc(),
+            significand: self.alloc(),
+        };
+        let v_normal = self.alloc();
+        self.srli(unpacked.sign, rs1, 31);
+        self.srli(unpacked.exponent, rs1, 23);
+        self.i(andi, unpacked.exponent, unpacked.exponent, EXPONENT_MASK);
+        self.i(andi, unpacked.significand, rs1, FRACTION_MASK);
+        self.r(sltu, v_normal, 0, unpacked.exponent);
+        self.slli(v_normal, v_normal, 23);
+        self.r(or, unpacked.significand, unpacked.significand, v_normal);
+        self.i(sltiu, v_normal, unpacked.exponent, 1);
+        self.r(add, unpacked.exponent, unpacked.exponent, v_normal);
+        self.free(&[v_normal]);
+        unpacked
+    }
+
+    /// Unpacks the single-precision value in `rs1`, normalizing subnormal
+    /// significands so that the leading one is at bit 23. The exponent of a
+    /// normalized subnormal may be zero or negative.
+    fn unpack_normalized(&mut self, rs1: u8) -> Unpacked {
+        let unpacked = self.unpack(rs1);
+        let v_shift = self.alloc();
+        self.li(v_shift, 0);
+        self.normalize(unpacked.significand, v_shift, 23);
+        self.r(sub, unpacked.exponent, unpacked.exponent, v_shift);
+        self.free(&[v_shift]);
+        unpacked
+    }
+
+    fn free_unpacked(&mut self, unpacked: Unpacked) {
+        self.free(&[unpacked.sign, unpacked.exponent, unpacked.significand]);
+    }
+
+    fn classify(&mut self, rs1: u8) -> Class {
+        let class = Class {
+            nan: self.alloc(),
+            infinity: self.alloc(),
+            zero: self.alloc(),
+        };
+        let v_abs = self.alloc();
+        self.i(andi, v_abs, rs1, ABS_MASK);
+        self.i(sltiu, class.zero, v_abs, 1);
+        self.li(class.nan, INFINITY);
+        self.r(xor, class.infinity, v_abs, class.nan);
+        self.i(sltiu, class.infinity, class.infinity, 1);
+        self.r(sltu, class.nan, class.nan, v_abs);
+        self.free(&[v_abs]);
+        class
+    }
+
+    fn free_class(&mut self, class: Class) {
+        self.free(&[class.nan, class.infinity, class.zero]);
+    }
+
+    /// Rounds a significand with three extra low-order bits (guard, round and
+    /// sticky) to an integer, in place.
+    fn round(&mut self, significand: u8, sign: u8, rounding_mode: RoundingMode) {
+        let v_guard = self.alloc();
+        let v_sticky = self.alloc();
+        self.srli(v_guard, significand, 2);
+        self.i(andi, v_guard, v_guard, 1);
+        self.i(andi, v_sticky, significand, 3);
+        self.r(sltu, v_sticky, 0, v_sticky);
+        match rounding_mode {
+            RoundingMode::NearestEven => {
+                // Round up if above the halfway point, or at it and odd
+                let v_lsb = self.alloc();
+                self.srli(v_lsb, significand, 3);
+                self.i(andi, v_lsb, v_lsb, 1);
+                self.r(or, v_sticky, v_sticky, v_lsb);
+                self.r(and, v_guard, v_guard, v_sticky);
+                self.free(&[v_lsb]);
+            }
+            RoundingMode::TowardZero => self.li(v_guard, 0),
+            RoundingMode::Down => {
+                self.r(or, v_guard, v_guard, v_sticky);
+                self.r(and, v_guard, v_guard, sign);
+            }
+            RoundingMode::Up => {
+                self.r(or, v_guard, v_guard, v_sticky);
+                self.i(xori, v_sticky, sign, 1);
+                self.r(and, v_guard, v_guard, v_sticky);
+            }
+            RoundingMode::NearestMaxMagnitude => {}
+        }
+        self.srli(significand, significand, 3);
+        self.r(add, significand, significand, v_guard);
+        self.free(&[v_guard, v_sticky]);
+    }
+
+    /// rd = the single-precision value (-1)^sign * significand * 2^(exponent -
+    /// 153), rounded. The leading one of `significand` is at bit 26, unless
+    /// `exponent` is 1 (subnormal) or the significand is zero; the exponent
+    /// may be out of range in either direction. Clobbers `exponent` and
+    /// `significand`.
+    fn round_pack(
+        &mut self,
+        rd: u8,
+        sign: u8,
+        exponent: u8,
+        significand: u8,
+        rounding_mode: RoundingMode,
+    ) {
+        // Denormalize: shift right by 1 - exponent if the exponent is below 1
+        let v_shift = self.alloc();
+        let v_tiny = self.alloc();
+        self.i(slti, v_tiny, exponent, 1);
+        self.r(sub, v_shift, 0, exponent);
+        self.i(addi, v_shift, v_shift, 1);
+        self.r(mul, v_shift, v_shift, v_tiny);
+        self.r(add, exponent, exponent, v_shift);
+        self.clamp_shift(v_shift, v_shift);
+        self.srl_sticky(significand, significand, v_shift);
+        self.free(&[v_shift, v_tiny]);
+
+        self.round(significand, sign, rounding_mode);
+
+        // A carry out of the significand increments the exponent field
+        self.i(addi, exponent, exponent, -1);
+        self.slli(exponent, exponent, 23);
+        self.r(add, significand, significand, exponent);
+
+        // Overflow rounds to infinity or to the largest finite value
+        let v_overflow = self.alloc();
+        let v_limit = self.alloc();
+        self.i(sltiu, v_overflow, significand, INFINITY);
+        self.i(xori, v_overflow, v_overflow, 1);
+        match rounding_mode {
+            RoundingMode::NearestEven | RoundingMode::NearestMaxMagnitude => {
+                self.li(v_limit, INFINITY)
+            }
+            RoundingMode::TowardZero => self.li(v_limit, MAX_FINITE),
+            RoundingMode::Down => self.i(addi, v_limit, sign, MAX_FINITE),
+            RoundingMode::Up => {
+                self.li(v_limit, INFINITY);
+                self.r(sub, v_limit, v_limit, sign);
+            }
+        }
+        self.select(significand, v_overflow, v_limit, significand);
+        self.slli(v_limit, sign, 31);
+        self.r(or, rd, significand, v_limit);
+        self.free(&[v_overflow, v_limit]);
+    }
+
+    fn build(mut self) -> Vec<RV32IMInstruction> {
+        debug_assert_eq!(self.free_registers.len(), 32, "leaked virtual registers");
+        let len = self.sequence.len();
+        for (i, instr) in self.sequence.iter_mut().enumerate() {
+            instr.set_virtual_sequence_remaining(Some((len - i - 1) as u16));
+            instr.set_is_compressed(self.is_compressed);
+        }
+        self.sequence
+    }
+}
+
+macro_rules! constructor {
+    ($name:ident, $instr:ident, $format:ident) => {
+        fn $name(address: u64, operands: $format) -> $instr {
+            $instr {
+                address,
+                operands,
+                virtual_sequence_remaining: None,
+                is_compressed: false,
+            }
+        }
+    };
+}
+
+constructor!(add, ADD, FormatR);
+constructor!(and, AND, FormatR);
+constructor!(mul, MUL, FormatR);
+constructor!(mulhu, MULHU, FormatR);
+constructor!(or, OR, FormatR);
+constructor!(slt, SLT, FormatR);
+constructor!(sltu, SLTU, FormatR);
+constructor!(sub, SUB, FormatR);
+constructor!(xor, XOR, FormatR);
+constructor!(addi, ADDI, FormatI);
+constructor!(andi, ANDI, FormatI);
+constructor!(ori, ORI, FormatI);
+constructor!(slti, SLTI, FormatI);
+constructor!(sltiu, SLTIU, FormatI);
+constructor!(xori, XORI, FormatI);
+constructor!(virtual_move, VirtualMove, FormatI);
+constructor!(virtual_movsign, VirtualMovsign, FormatI);
+constructor!(virtual_muli, VirtualMULI, FormatI);
+constructor!(virtual_pow2, VirtualPow2, FormatI);
+constructor!(
+    virtual_shift_right_bitmask,
+    VirtualShiftRightBitmask,
+    FormatI
+);
+constructor!(assert_eq, VirtualAssertEQ, FormatB);
+constructor!(assert_lte, VirtualAssertLTE, FormatB);
+constructor!(
+    assert_valid_unsigned_remainder,
+    VirtualAssertValidUnsignedRemainder,
+    FormatB
+);
+
+/// Fills in the `VirtualAdvice` instructions of `sequence`, in order.
+pub fn set_advice(sequence: &mut [RV32IMInstruction], advice: &[u64]) {
+    let mut advice = advice.iter();
+    for instr in sequence.iter_mut() {
+        if let RV32IMInstruction::VirtualAdvice(instr) = instr {
+            instr.advice = *advice.next().expect("Expected advice");
+        }
+    }
+    assert!(advice.next().is_none(), "Unused advice");
+}
+
+/// The exponent and significand of `bits`, as computed by
+/// `SequenceBuilder::unpack_normalized`.
+fn unpack_normalized(bits: u32) -> (i64, u64) {
+    let exponent = (bits >> 23) & 0xff;
+    let mut significand = bits & 0x7f_ffff;
+    if exponent != 0 {
+        significand |= 1 << 23;
+    }
+    let shift = match significand {
+        0 => 31,
+        _ => significand.leading_zeros() - 8,
+    };
+    (
+        exponent.max(1) as i64 - shift as i64,
+        (significand << (shift % 32)) as u64,
+    )
+}
+
+/// Virtual sequence for FLW: loads a word into `f[rd]`.
+pub fn flw_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatLoad,
+) -> Vec<RV32IMInstruction> {
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    builder.lw(
+        float_register_index(operands.rd),
+        operands.rs1,
+        operands.imm,
+    );
+    builder.build()
+}
+
+/// Virtual sequence for FSW: stores `f[rs2]`.
+pub fn fsw_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatS,
+) -> Vec<RV32IMInstruction> {
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    builder.sw(
+        operands.rs1,
+        float_register_index(operands.rs2),
+        operands.imm,
+    );
+    builder.build()
+}
+
+/// Virtual sequence for FADD.S, or FSUB.S if `subtract` is set.
+pub fn fadd_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatFloatR,
+    subtract: bool,
+) -> Vec<RV32IMInstruction> {
+    let rounding_mode = RoundingMode::from_rm(operands.rm);
+    let rs1 = float_register_index(operands.rs1);
+    let rs2 = float_register_index(operands.rs2);
+    let rd = float_register_index(operands.rd);
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let v_rhs = b.alloc();
+    if subtract {
+        b.i(xori, v_rhs, rs2, SIGN_MASK);
+    } else {
+        b.mov(v_rhs, rs2);
+    }
+
+    // Order the operands so that |x| >= |y|
+    let v_x = b.alloc();
+    let v_y = b.alloc();
+    let v_swap = b.alloc();
+    b.i(andi, v_x, rs1, ABS_MASK);
+    b.i(andi, v_y, v_rhs, ABS_MASK);
+    b.r(sltu, v_swap, v_x, v_y);
+    b.select(v_x, v_swap, v_rhs, rs1);
+    b.select(v_y, v_swap, rs1, v_rhs);
+    b.free(&[v_rhs, v_swap]);
+
+    let x = b.unpack(v_x);
+    let y = b.unpack(v_y);
+
+    // Align y with x, keeping guard, round and sticky bits
+    let v_diff = b.alloc();
+    b.r(sub, v_diff, x.exponent, y.exponent);
+    b.clamp_shift(v_diff, v_diff);
+    b.slli(x.significand, x.significand, 3);
+    b.slli(y.significand, y.significand, 3);
+    b.srl_sticky(y.significand, y.significand, v_diff);
+
+    // Effective subtraction if the signs differ
+    let v_subtract = v_diff;
+    b.r(xor, v_subtract, x.sign, y.sign);
+    b.conditional_negate(y.significand, y.significand, v_subtract);
+    let sum = x.significand;
+    b.r(add, sum, sum, y.significand);
+
+    // A carry out of bit 26 shifts the sum right by one
+    let v_carry = b.alloc();
+    let v_shifted = b.alloc();
+    let v_lost = b.alloc();
+    b.srli(v_carry, sum, 27);
+    b.srli(v_shifted, sum, 1);
+    b.i(andi, v_lost, sum, 1);
+    b.r(or, v_shifted, v_shifted, v_lost);
+    b.select(sum, v_carry, v_shifted, sum);
+    b.r(add, x.exponent, x.exponent, v_carry);
+    b.free(&[v_carry, v_shifted, v_lost]);
+
+    // Cancellation shifts the sum left, as far as the exponent allows
+    let v_shift = b.alloc();
+    let v_copy = b.alloc();
+    let v_limit = b.alloc();
+    b.li(v_shift, 0);
+    b.mov(v_copy, sum);
+    b.normalize(v_copy, v_shift, 26);
+    b.i(addi, v_limit, x.exponent, -1);
+    b.r(sltu, v_copy, v_shift, v_limit);
+    b.select(v_shift, v_copy, v_shift, v_limit);
+    b.sll(sum, sum, v_shift);
+    b.r(sub, x.exponent, x.exponent, v_shift);
+    b.free(&[v_copy, v_limit]);
+
+    // An exact zero is negative only if both operands are (or if either is,
+    // when rounding down)
+    let v_zero = v_shift;
+    let v_zero_sign = b.alloc();
+    b.i(sltiu, v_zero, sum, 1);
+    if rounding_mode == RoundingMode::Down {
+        b.r(or, v_zero_sign, x.sign, y.sign);
+    } else {
+        b.r(and, v_zero_sign, x.sign, y.sign);
+    }
+    b.slli(v_zero_sign, v_zero_sign, 31);
+
+    let v_result = b.alloc();
+    b.round_pack(v_result, x.sign, x.exponent, sum, rounding_mode);
+    b.select(v_result, v_zero, v_zero_sign, v_result);
+    b.free(&[v_subtract, v_zero, v_zero_sign]);
+    b.free_unpacked(x);
+    b.free_unpacked(y);
+
+    // Infinities: x is infinite if either operand is, and inf - inf is NaN
+    let x_class = b.classify(v_x);
+    let y_class = b.classify(v_y);
+    let v_invalid = b.alloc();
+    b.r(xor, v_invalid, v_x, v_y);
+    b.srli(v_invalid, v_invalid, 31);
+    b.r(and, v_invalid, v_invalid, y_class.infinity);
+    b.r(or, v_invalid, v_invalid, x_class.nan);
+    b.select(v_result, x_class.infinity, v_x, v_result);
+    b.select_imm(rd, v_invalid, CANONICAL_NAN, v_result);
+    b.free(&[v_x, v_y, v_invalid, v_result]);
+    b.free_class(x_class);
+    b.free_class(y_class);
+    builder.build()
+}
+
+/// Virtual sequence for FMUL.S.
+pub fn fmul_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatFloatR,
+) -> Vec<RV32IMInstruction> {
+    let rounding_mode = RoundingMode::from_rm(operands.rm);
+    let rs1 = float_register_index(operands.rs1);
+    let rs2 = float_register_index(operands.rs2);
+    let rd = float_register_index(operands.rd);
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let x = b.unpack_normalized(rs1);
+    let y = b.unpack_normalized(rs2);
+    let v_sign = b.alloc();
+    b.r(xor, v_sign, x.sign, y.sign);
+
+    // The 48-bit product has its leading one at bit 46 or 47
+    let v_lo = b.alloc();
+    let v_hi = b.alloc();
+    let v_top = b.alloc();
+    b.r(mul, v_lo, x.significand, y.significand);
+    b.r(mulhu, v_hi, x.significand, y.significand);
+    b.srli(v_top, v_hi, 15);
+
+    // significand = product >> (20 + top), with a sticky bit
+    let v_shift = b.alloc();
+    let v_significand = b.alloc();
+    let v_lost = b.alloc();
+    b.i(addi, v_shift, v_top, 20);
+    b.srl(v_significand, v_lo, v_shift);
+    b.sll(v_lost, v_significand, v_shift);
+    b.r(sltu, v_lost, v_lost, v_lo);
+    b.li(v_shift, 12);
+    b.r(sub, v_shift, v_shift, v_top);
+    b.sll(v_hi, v_hi, v_shift);
+    b.r(or, v_significand, v_significand, v_hi);
+    b.r(or, v_significand, v_significand, v_lost);
+    b.free(&[v_lo, v_hi, v_shift, v_lost]);
+
+    let v_exponent = x.exponent;
+    b.r(add, v_exponent, v_exponent, y.exponent);
+    b.i(addi, v_exponent, v_exponent, -EXPONENT_BIAS);
+    b.r(add, v_exponent, v_exponent, v_top);
+    b.free(&[v_top]);
+
+    let v_result = b.alloc();
+    b.round_pack(v_result, v_sign, v_exponent, v_significand, rounding_mode);
+    b.free(&[v_significand]);
+    b.free_unpacked(x);
+    b.free_unpacked(y);
+
+    let x_class = b.classify(rs1);
+    let y_class = b.classify(rs2);
+    let v_zero = b.alloc();
+    let v_infinity = b.alloc();
+    b.r(or, v_zero, x_class.zero, y_class.zero);
+    b.r(or, v_infinity, x_class.infinity, y_class.infinity);
+    b.slli(v_sign, v_sign, 31);
+    b.select(v_result, v_zero, v_sign, v_result);
+    b.i(ori, v_sign, v_sign, INFINITY);
+    b.select(v_result, v_infinity, v_sign, v_result);
+    // 0 * inf is NaN
+    b.r(and, v_zero, v_zero, v_infinity);
+    b.r(or, v_zero, v_zero, x_class.nan);
+    b.r(or, v_zero, v_zero, y_class.nan);
+    b.select_imm(rd, v_zero, CANONICAL_NAN, v_result);
+    b.free(&[v_sign, v_zero, v_infinity, v_result]);
+    b.free_class(x_class);
+    b.free_class(y_class);
+    builder.build()
+}
+
+/// Virtual sequence for FDIV.S. The significand of the quotient comes from
+/// two advice values, the quotient `q` and remainder `r` of `N / d`, where `d`
+/// is the normalized significand of rs2 and `N` is the normalized significand
+/// of rs1 shifted so that `q` has its leading one at bit 26 (see
+/// `fdiv_s_advice`).
+pub fn fdiv_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatFloatR,
+) -> Vec<RV32IMInstruction> {
+    let rounding_mode = RoundingMode::from_rm(operands.rm);
+    let rs1 = float_register_index(operands.rs1);
+    let rs2 = float_register_index(operands.rs2);
+    let rd = float_register_index(operands.rd);
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let x = b.unpack_normalized(rs1);
+    let y = b.unpack_normalized(rs2);
+    let y_class = b.classify(rs2);
+    let v_sign = b.alloc();
+    b.r(xor, v_sign, x.sign, y.sign);
+
+    // Divide by 2^23 instead of 0, so that the advice is well-defined
+    let v_tmp = b.alloc();
+    b.slli(v_tmp, y_class.zero, 23);
+    b.r(or, y.significand, y.significand, v_tmp);
+
+    // N = x << (26 + (x < y)), as a 64-bit value
+    let v_small = b.alloc();
+    let v_n_lo = b.alloc();
+    let v_n_hi = b.alloc();
+    b.r(sltu, v_small, x.significand, y.significand);
+    b.i(addi, v_tmp, v_small, 26);
+    b.i(virtual_pow2, v_tmp, v_tmp, 0);
+    b.r(mul, v_n_lo, x.significand, v_tmp);
+    b.r(mulhu, v_n_hi, x.significand, v_tmp);
+
+    // Check q * y + r == N and r < y
+    let v_quotient = b.alloc();
+    let v_remainder = b.alloc();
+    let v_lo = b.alloc();
+    let v_carry = b.alloc();
+    b.advice(v_quotient);
+    b.advice(v_remainder);
+    b.b(assert_valid_unsigned_remainder, v_remainder, y.significand);
+    b.r(mul, v_lo, v_quotient, y.significand);
+    b.r(mulhu, v_tmp, v_quotient, y.significand);
+    b.r(add, v_lo, v_lo, v_remainder);
+    b.r(sltu, v_carry, v_lo, v_remainder);
+    b.r(add, v_tmp, v_tmp, v_carry);
+    b.b(assert_eq, v_lo, v_n_lo);
+    b.b(assert_eq, v_tmp, v_n_hi);
+
+    // A nonzero remainder sets the sticky bit
+    b.r(sltu, v_carry, 0, v_remainder);
+    b.r(or, v_quotient, v_quotient, v_carry);
+    b.free(&[v_n_lo, v_n_hi, v_remainder, v_lo, v_carry, v_tmp]);
+
+    let v_exponent = x.exponent;
+    b.r(sub, v_exponent, v_exponent, y.exponent);
+    b.i(addi, v_exponent, v_exponent, EXPONENT_BIAS);
+    b.r(sub, v_exponent, v_exponent, v_small);
+    b.free(&[v_small]);
+
+    let v_result = b.alloc();
+    b.round_pack(v_result, v_sign, v_exponent, v_quotient, rounding_mode);
+    b.free(&[v_quotient]);
+    b.free_unpacked(x);
+    b.free_unpacked(y);
+
+    let x_class = b.classify(rs1);
+    let v_zero = b.alloc();
+    let v_infinity = b.alloc();
+    let v_invalid = b.alloc();
+    b.r(or, v_zero, x_class.zero, y_class.infinity);
+    b.r(or, v_infinity, x_class.infinity, y_class.zero);
+    // 0 / 0 and inf / inf are NaN
+    b.r(and, v_invalid, x_class.zero, y_class.zero);
+    b.r(or, v_invalid, v_invalid, x_class.nan);
+    b.r(or, v_invalid, v_invalid, y_class.nan);
+    b.r(and, x_class.nan, x_class.infinity, y_class.infinity);
+    b.r(or, v_invalid, v_invalid, x_class.nan);
+    b.slli(v_sign, v_sign, 31);
+    b.select(v_result, v_zero, v_sign, v_result);
+    b.i(ori, v_sign, v_sign, INFINITY);
+    b.select(v_result, v_infinity, v_sign, v_result);
+    b.select_imm(rd, v_invalid, CANONICAL_NAN, v_result);
+    b.free(&[v_sign, v_zero, v_infinity, v_invalid, v_result]);
+    b.free_class(x_class);
+    b.free_class(y_class);
+    builder.build()
+}
+
+/// Advice for FDIV.S: the quotient and remainder checked by
+/// `fdiv_s_virtual_sequence`.
+pub fn fdiv_s_advice(rs1: u32, rs2: u32) -> [u64; 2] {
+    let (_, dividend) = unpack_normalized(rs1);
+    let divisor = match rs2 & ABS_MASK as u32 {
+        0 => 1 << 23,
+        _ => unpack_normalized(rs2).1,
+    };
+    let numerator = dividend << (26 + (dividend < divisor) as u64);
+    [numerator / divisor, numerator % divisor]
+}
+
+/// Virtual sequence for FSQRT.S. The significand of the result comes from an
+/// advice value, the integer square root `r` of `N`, the normalized
+/// significand of rs1 (doubled if its exponent is odd) shifted left by 29
+/// (see `fsqrt_s_advice`).
+pub fn fsqrt_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatFloatR,
+) -> Vec<RV32IMInstruction> {
+    let rounding_mode = RoundingMode::from_rm(operands.rm);
+    let rs1 = float_register_index(operands.rs1);
+    let rd = float_register_index(operands.rd);
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let x = b.unpack_normalized(rs1);
+
+    // Make the unbiased exponent even, and halve it
+    let v_odd = b.alloc();
+    let v_exponent = x.exponent;
+    b.i(addi, v_exponent, v_exponent, -EXPONENT_BIAS);
+    b.i(andi, v_odd, v_exponent, 1);
+    b.r(sub, v_exponent, v_exponent, v_odd);
+    b.srai(v_exponent, v_exponent, 1);
+    b.i(addi, v_exponent, v_exponent, EXPONENT_BIAS);
+    b.i(addi, v_odd, v_odd, 1);
+    b.r(mul, x.significand, x.significand, v_odd);
+
+    // N = significand << 29, as a 64-bit value
+    let v_n_lo = v_odd;
+    let v_n_hi = b.alloc();
+    b.slli(v_n_lo, x.significand, 29);
+    b.srli(v_n_hi, x.significand, 3);
+
+    // Check 0 <= N - r^2 <= 2r
+    let v_root = b.alloc();
+    let v_lo = b.alloc();
+    let v_hi = b.alloc();
+    b.advice(v_root);
+    b.r(mul, v_lo, v_root, v_root);
+    b.r(mulhu, v_hi, v_root, v_root);
+    b.r(sub, v_n_hi, v_n_hi, v_hi);
+    b.r(sltu, v_hi, v_n_lo, v_lo);
+    b.r(sub, v_n_hi, v_n_hi, v_hi);
+    b.r(sub, v_n_lo, v_n_lo, v_lo);
+    b.b(assert_eq, v_n_hi, 0);
+    b.r(add, v_lo, v_root, v_root);
+    b.b(assert_lte, v_n_lo, v_lo);
+    b.r(sltu, v_n_lo, 0, v_n_lo);
+    b.r(or, v_root, v_root, v_n_lo);
+    b.free(&[v_n_lo, v_n_hi, v_lo, v_hi]);
+
+    let v_result = b.alloc();
+    b.round_pack(v_result, 0, v_exponent, v_root, rounding_mode);
+    b.free(&[v_root]);
+
+    // sqrt(+-0) = +-0, sqrt(inf) = inf, and the square root of a negative
+    // number is NaN
+    let x_class = b.classify(rs1);
+    b.select(v_result, x_class.zero, rs1, v_result);
+    b.select_imm(v_result, x_class.infinity, INFINITY, v_result);
+    b.i(xori, x_class.zero, x_class.zero, 1);
+    b.r(and, x_class.zero, x_class.zero, x.sign);
+    b.r(or, x_class.nan, x_class.nan, x_class.zero);
+    b.select_imm(rd, x_class.nan, CANONICAL_NAN, v_result);
+    b.free(&[v_result]);
+    b.free_unpacked(x);
+    b.free_class(x_class);
+    builder.build()
+}
+
+/// Advice for FSQRT.S: the integer square root checked by
+/// `fsqrt_s_virtual_sequence`.
+pub fn fsqrt_s_advice(rs1: u32) -> [u64; 1] {
+    let (exponent, significand) = unpack_normalized(rs1);
+    let numerator = (significand << ((exponent - EXPONENT_BIAS) & 1)) << 29;
+    let mut root = (numerator as f64).sqrt() as u64;
+    while root * root > numerator {
+        root -= 1;
+    }
+    while (root + 1) * (root + 1) <= numerator {
+        root += 1;
+    }
+    [root]
+}
+
+/// Virtual sequence for FCVT.W.S, or FCVT.WU.S if `unsigned` is set. Out of
+/// range values saturate, and NaN converts to the largest integer.
+pub fn fcvt_w_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatFloatR,
+    unsigned: bool,
+) -> Vec<RV32IMInstruction> {
+    let rounding_mode = RoundingMode::from_rm(operands.rm);
+    let rs1 = float_register_index(operands.rs1);
+    let rd = operands.rd;
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let x = b.unpack(rs1);
+
+    // Exact if the exponent is at least 150 (no fractional bits)
+    let v_large = b.alloc();
+    let v_shift = b.alloc();
+    let v_shifted = b.alloc();
+    b.i(addi, v_shift, x.exponent, -150);
+    b.sll(v_shifted, x.significand, v_shift);
+    b.i(sltiu, v_large, x.exponent, 150);
+    b.i(xori, v_large, v_large, 1);
+
+    // Otherwise shift right by 150 - exponent and round
+    b.li(v_shift, 150);
+    b.r(sub, v_shift, v_shift, x.exponent);
+    b.clamp_shift(v_shift, v_shift);
+    b.slli(x.significand, x.significand, 3);
+    b.srl_sticky(x.significand, x.significand, v_shift);
+    b.round(x.significand, x.sign, rounding_mode);
+    b.select(x.significand, v_large, v_shifted, x.significand);
+    b.free(&[v_large, v_shift, v_shifted]);
+
+    let v_result = x.significand;
+    let v_overflow = b.alloc();
+    let x_class = b.classify(rs1);
+    if unsigned {
+        b.i(sltiu, v_overflow, x.exponent, 159);
+        b.i(xori, v_overflow, v_overflow, 1);
+        b.select_imm(v_result, v_overflow, u32::MAX as i64, v_result);
+        b.select(v_result, x.sign, 0, v_result);
+        b.select_imm(rd, x_class.nan, u32::MAX as i64, v_result);
+    } else {
+        b.conditional_negate(v_result, v_result, x.sign);
+        b.i(sltiu, v_overflow, x.exponent, 158);
+        b.i(xori, v_overflow, v_overflow, 1);
+        b.i(addi, x.exponent, x.sign, i32::MAX as i64);
+        b.select(v_result, v_overflow, x.exponent, v_result);
+        b.select_imm(rd, x_class.nan, i32::MAX as i64, v_result);
+    }
+    b.free(&[v_overflow]);
+    b.free_unpacked(x);
+    b.free_class(x_class);
+    builder.build()
+}
+
+/// Virtual sequence for FCVT.S.W, or FCVT.S.WU if `unsigned` is set.
+pub fn fcvt_s_w_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatFloatR,
+    unsigned: bool,
+) -> Vec<RV32IMInstruction> {
+    let rounding_mode = RoundingMode::from_rm(operands.rm);
+    let rs1 = operands.rs1;
+    let rd = float_register_index(operands.rd);
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let v_sign = b.alloc();
+    let v_significand = b.alloc();
+    if unsigned {
+        b.li(v_sign, 0);
+        b.mov(v_significand, rs1);
+    } else {
+        b.srli(v_sign, rs1, 31);
+        b.conditional_negate(v_significand, rs1, v_sign);
+    }
+
+    // Shift the leading one to bit 31, then keep 27 bits and a sticky bit
+    let v_exponent = b.alloc();
+    let v_shift = b.alloc();
+    b.li(v_shift, 0);
+    b.normalize(v_significand, v_shift, 31);
+    b.li(v_exponent, EXPONENT_BIAS + 31);
+    b.r(sub, v_exponent, v_exponent, v_shift);
+    b.i(andi, v_shift, v_significand, 0x1f);
+    b.r(sltu, v_shift, 0, v_shift);
+    b.srli(v_significand, v_significand, 5);
+    b.r(or, v_significand, v_significand, v_shift);
+
+    let v_result = b.alloc();
+    b.round_pack(v_result, v_sign, v_exponent, v_significand, rounding_mode);
+    b.i(sltiu, v_shift, rs1, 1);
+    b.select(rd, v_shift, 0, v_result);
+    b.free(&[v_sign, v_significand, v_exponent, v_shift, v_result]);
+    builder.build()
+}
+
+/// The sign-injection instructions.
+#[derive(Clone, Copy, Debug)]
+pub enum SignInjection {
+    /// FSGNJ.S: the sign of rs2
+    Copy,
+    /// FSGNJN.S: the opposite of the sign of rs2
+    Negate,
+    /// FSGNJX.S: the xor of the signs of rs1 and rs2
+    Xor,
+}
+
+/// Virtual sequence for FSGNJ.S, FSGNJN.S or FSGNJX.S.
+pub fn fsgnj_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatR,
+    injection: SignInjection,
+) -> Vec<RV32IMInstruction> {
+    let rs1 = float_register_index(operands.rs1);
+    let rs2 = float_register_index(operands.rs2);
+    let rd = float_register_index(operands.rd);
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let v_sign = b.alloc();
+    let v_abs = b.alloc();
+    match injection {
+        SignInjection::Copy => b.i(andi, v_sign, rs2, SIGN_MASK),
+        SignInjection::Negate => {
+            b.i(xori, v_sign, rs2, SIGN_MASK);
+            b.i(andi, v_sign, v_sign, SIGN_MASK);
+        }
+        SignInjection::Xor => b.i(andi, v_sign, rs2, SIGN_MASK),
+    }
+    if let SignInjection::Xor = injection {
+        b.r(xor, rd, rs1, v_sign);
+    } else {
+        b.i(andi, v_abs, rs1, ABS_MASK);
+        b.r(or, rd, v_abs, v_sign);
+    }
+    b.free(&[v_sign, v_abs]);
+    builder.build()
+}
+
+/// Virtual sequence for FMIN.S, or FMAX.S if `max` is set. -0 is less than +0,
+/// and if exactly one operand is NaN the result is the other operand.
+pub fn fmin_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatR,
+    max: bool,
+) -> Vec<RV32IMInstruction> {
+    let rs1 = float_register_index(operands.rs1);
+    let rs2 = float_register_index(operands.rs2);
+    let rd = float_register_index(operands.rd);
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let x_class = b.classify(rs1);
+    let y_class = b.classify(rs2);
+
+    // Comparing bit patterns with the magnitude bits of negative values
+    // inverted orders them as numbers (with -0 < +0)
+    let v_x = b.alloc();
+    let v_y = b.alloc();
+    let v_mask = b.alloc();
+    b.i(virtual_movsign, v_mask, rs1, 0);
+    b.i(andi, v_x, rs1, ABS_MASK);
+    b.r(xor, v_x, v_x, v_mask);
+    b.i(virtual_movsign, v_mask, rs2, 0);
+    b.i(andi, v_y, rs2, ABS_MASK);
+    b.r(xor, v_y, v_y, v_mask);
+    b.r(slt, v_mask, v_x, v_y);
+    if max {
+        b.select(v_x, v_mask, rs2, rs1);
+    } else {
+        b.select(v_x, v_mask, rs1, rs2);
+    }
+
+    b.select(v_x, y_class.nan, rs1, v_x);
+    b.select(v_x, x_class.nan, rs2, v_x);
+    b.r(and, v_mask, x_class.nan, y_class.nan);
+    b.select_imm(rd, v_mask, CANONICAL_NAN, v_x);
+    b.free(&[v_x, v_y, v_mask]);
+    b.free_class(x_class);
+    b.free_class(y_class);
+    builder.build()
+}
+
+/// The comparison instructions.
+#[derive(Clone, Copy, Debug)]
+pub enum Comparison {
+    Equal,
+    LessThan,
+    LessThanOrEqual,
+}
+
+/// Virtual sequence for FEQ.S, FLT.S or FLE.S. Comparisons with NaN are false.
+pub fn fcmp_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatR,
+    comparison: Comparison,
+) -> Vec<RV32IMInstruction> {
+    let rs1 = float_register_index(operands.rs1);
+    let rs2 = float_register_index(operands.rs2);
+    let rd = operands.rd;
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let x_class = b.classify(rs1);
+    let y_class = b.classify(rs2);
+
+    // Sign-magnitude to two's complement, so that -0 == +0
+    let v_x = b.alloc();
+    let v_y = b.alloc();
+    let v_sign = b.alloc();
+    b.srli(v_sign, rs1, 31);
+    b.i(andi, v_x, rs1, ABS_MASK);
+    b.conditional_negate(v_x, v_x, v_sign);
+    b.srli(v_sign, rs2, 31);
+    b.i(andi, v_y, rs2, ABS_MASK);
+    b.conditional_negate(v_y, v_y, v_sign);
+    match comparison {
+        Comparison::Equal => {
+            b.r(xor, v_x, v_x, v_y);
+            b.i(sltiu, v_x, v_x, 1);
+        }
+        Comparison::LessThan => b.r(slt, v_x, v_x, v_y),
+        Comparison::LessThanOrEqual => {
+            b.r(slt, v_x, v_y, v_x);
+            b.i(xori, v_x, v_x, 1);
+        }
+    }
+    b.r(or, v_sign, x_class.nan, y_class.nan);
+    b.i(xori, v_sign, v_sign, 1);
+    b.r(and, rd, v_x, v_sign);
+    b.free(&[v_x, v_y, v_sign]);
+    b.free_class(x_class);
+    b.free_class(y_class);
+    builder.build()
+}
+
+/// Virtual sequence for FCLASS.S, which sets exactly one of the ten bits of
+/// `rd` according to the class of `f[rs1]`.
+pub fn fclass_s_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatR,
+) -> Vec<RV32IMInstruction> {
+    let rs1 = float_register_index(operands.rs1);
+    let rd = operands.rd;
+
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    let b = &mut builder;
+    let v_sign = b.alloc();
+    let v_exponent = b.alloc();
+    let v_fraction = b.alloc();
+    b.srli(v_sign, rs1, 31);
+    b.srli(v_exponent, rs1, 23);
+    b.i(andi, v_exponent, v_exponent, EXPONENT_MASK);
+    b.i(andi, v_fraction, rs1, FRACTION_MASK);
+
+    let v_subnormal = b.alloc();
+    let v_special = b.alloc();
+    let v_zero = b.alloc();
+    let v_infinity = b.alloc();
+    let v_normal = b.alloc();
+    let v_quiet = b.alloc();
+    b.i(sltiu, v_subnormal, v_exponent, 1);
+    b.i(xori, v_special, v_exponent, EXPONENT_MASK);
+    b.i(sltiu, v_special, v_special, 1);
+    b.i(sltiu, v_fraction, v_fraction, 1);
+    b.r(and, v_zero, v_subnormal, v_fraction);
+    b.r(xor, v_subnormal, v_subnormal, v_zero);
+    b.r(and, v_infinity, v_special, v_fraction);
+    b.r(or, v_normal, v_subnormal, v_zero);
+    b.r(or, v_normal, v_normal, v_special);
+    b.i(xori, v_normal, v_normal, 1);
+    // What remains of the special exponent is NaN
+    b.r(xor, v_special, v_special, v_infinity);
+    b.srli(v_quiet, rs1, 22);
+    b.i(andi, v_quiet, v_quiet, 1);
+    b.r(and, v_quiet, v_quiet, v_special);
+    b.r(xor, v_special, v_special, v_quiet);
+
+    // Negative classes are bits 0-3 and positive classes bits 7-4
+    let v_negative = v_exponent;
+    let v_positive = v_fraction;
+    let v_tmp = b.alloc();
+    b.mov(v_negative, v_infinity);
+    b.slli(v_tmp, v_normal, 1);
+    b.r(or, v_negative, v_negative, v_tmp);
+    b.slli(v_tmp, v_subnormal, 2);
+    b.r(or, v_negative, v_negative, v_tmp);
+    b.slli(v_tmp, v_zero, 3);
+    b.r(or, v_negative, v_negative, v_tmp);
+    b.slli(v_positive, v_zero, 4);
+    b.slli(v_tmp, v_subnormal, 5);
+    b.r(or, v_positive, v_positive, v_tmp);
+    b.slli(v_tmp, v_normal, 6);
+    b.r(or, v_positive, v_positive, v_tmp);
+    b.slli(v_tmp, v_infinity, 7);
+    b.r(or, v_positive, v_positive, v_tmp);
+    b.select(v_negative, v_sign, v_negative, v_positive);
+    b.slli(v_tmp, v_special, 8);
+    b.r(or, v_negative, v_negative, v_tmp);
+    b.slli(v_tmp, v_quiet, 9);
+    b.r(or, rd, v_negative, v_tmp);
+    b.free(&[
+        v_sign,
+        v_exponent,
+        v_fraction,
+        v_subnormal,
+        v_special,
+        v_zero,
+        v_infinity,
+        v_normal,
+        v_quiet,
+        v_tmp,
+    ]);
+    builder.build()
+}
+
+/// Virtual sequence for FMV.X.W (`to_float` unset), which moves the bits of
+/// `f[rs1]` to `x[rd]`, or FMV.W.X (`to_float` set), which moves the bits of
+/// `x[rs1]` to `f[rd]`.
+pub fn fmv_virtual_sequence(
+    address: u64,
+    is_compressed: bool,
+    operands: &FormatR,
+    to_float: bool,
+) -> Vec<RV32IMInstruction> {
+    let mut builder = SequenceBuilder::new(address, is_compressed);
+    if to_float {
+        builder.mov(float_register_index(operands.rd), operands.rs1);
+    } else {
+        builder.mov(operands.rd, float_register_index(operands.rs1));
+    }
+    builder.build()
+}
+
+#[cfg(all(test, feature = "float"))]
+mod tests {
+    use super::*;
+    use crate::emulator::{
+        cpu::{Cpu, Xlen},
+        terminal::DummyTerminal,
+    };
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+    const OP_FP: u32 = 0b1010011;
+    const RD: u32 = 1;
+    const RS1: u32 = 2;
+    const RS2: u32 = 3;
+
+    const SPECIAL_VALUES: [u32; 18] = [
+        0x0000_0000, // +0
+        0x8000_0000, // -0
+        0x0000_0001, // smallest subnormal
+        0x007f_ffff, // largest subnormal
+        0x0080_0000, // smallest normal
+        0x3f80_0000, // 1
+        0xbf80_0000, // -1
+        0x3f80_0001, // 1 + ulp
+        0x3fc0_0000, // 1.5
+        0x4b80_0001, // 2^24 + 2
+        0x3dcc_cccd, // 0.1
+        0x7f7f_ffff, // largest finite
+        0xff7f_ffff, // smallest finite
+        0x7f80_0000, // +inf
+        0xff80_0000, // -inf
+        0x7fc0_0000, // quiet NaN
+        0x7f80_0001, // signaling NaN
+        0xffc0_0001, // negative NaN
+    ];
+
+    const ROUNDING_MODES: [RoundingMode; 5] = [
+        RoundingMode::NearestEven,
+        RoundingMode::TowardZero,
+        RoundingMode::Down,
+        RoundingMode::Up,
+        RoundingMode::NearestMaxMagnitude,
+    ];
+
+    fn rm(rounding_mode: RoundingMode) -> u32 {
+        match rounding_mode {
+            RoundingMode::NearestEven => 0,
+            RoundingMode::TowardZero => 1,
+            RoundingMode::Down => 2,
+            RoundingMode::Up => 3,
+            RoundingMode::NearestMaxMagnitude => 4,
+        }
+    }
+
+    fn op_fp(funct7: u32, rs2: u32, funct3: u32) -> u32 {
+        (funct7 << 25) | (rs2 << 20) | (RS1 << 15) | (funct3 << 12) | (RD << 7) | OP_FP
+    }
+
+    /// Decodes and traces `word`, with the given bit patterns in `x`/`f` rs1
+    /// and rs2, and returns the bit pattern of `x`/`f` rd.
+    fn run(word: u32, rs1: u32, rs2: u32, float_rs1: bool, float_rd: bool) -> u32 {
+        let mut cpu = Cpu::new(Box::new(DummyTerminal::default()));
+        cpu.update_xlen(Xlen::Bit32);
+        let register = |index: u32, float: bool| match float {
+            true => float_register_index(index as u8) as usize,
+            false => index as usize,
+        };
+        cpu.x[register(RS1, float_rs1)] = rs1 as i32 as i64;
+        cpu.x[register(RS2, float_rs1)] = rs2 as i32 as i64;
+
+        let instr = RV32IMInstruction::decode(word, 0).unwrap();
+        let mut trace = vec![];
+        instr.trace(&mut cpu, Some(&mut trace));
+        let sequence_length = trace.len();
+        for (i, cycle) in trace.iter().enumerate() {
+            let remaining = cycle.instruction().normalize().virtual_sequence_remaining;
+            assert_eq!(remaining, Some((sequence_length - i - 1) as u16));
+        }
+        cpu.x[register(RD, float_rd)] as u32
+    }
+
+    fn assert_float_eq(result: u32, expected: f32, context: &str) {
+        if expected.is_nan() {
+            assert_eq!(result, CANONICAL_NAN as u32, "{context}: expected NaN");
+        } else {
+            assert_eq!(
+                result,
+                expected.to_bits(),
+                "{context}: got {}, expected {expected}",
+                f32::from_bits(result)
+            );
+        }
+    }
+
+    /// Rounds `x` to single precision with the given rounding mode.
+    fn round_to_f32(x: f64, rounding_mode: RoundingMode) -> f32 {
+        let nearest = x as f32;
+        if x.is_nan() || nearest as f64 == x {
+            return nearest;
+        }
+        let (below, above) = if (nearest as f64) < x {
+            (nearest, nearest.next_up())
+        } else {
+            (nearest.next_down(), nearest)
+        };
+        match rounding_mode {
+            RoundingMode::NearestEven => nearest,
+            RoundingMode::TowardZero if x > 0.0 => below,
+            RoundingMode::TowardZero => above,
+            RoundingMode::Down => below,
+            RoundingMode::Up => above,
+            RoundingMode::NearestMaxMagnitude => {
+                if x == (below as f64 + above as f64) / 2.0 {
+                    if x > 0.0 {
+                        above
+                    } else {
+                        below
+                    }
+                } else {
+                    nearest
+                }
+            }
+        }
+    }
+
+    /// Special values, random bit patterns, and pairs of nearby values (to
+    /// exercise cancellation).
+    fn operand_pairs() -> Vec<(u32, u32)> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pairs = vec![];
+        for &a in SPECIAL_VALUES.iter() {
+            for &b in SPECIAL_VALUES.iter() {
+                pairs.push((a, b));
+            }
+        }
+        for _ in 0..300 {
+            let a = rng.next_u32();
+            pairs.push((a, rng.next_u32()));
+            pairs.push((a, (a ^ 0x8000_0000).wrapping_add(rng.next_u32() % 64)));
+            pairs.push((a, a & 0x807f_ffff | (rng.next_u32() & 0x0780_0000)));
+        }
+        pairs
+    }
+
+    #[test]
+    fn decode_float_instructions() {
+        // fadd.s f1, f2, f3
+        let instr = RV32IMInstruction::decode(0x003100d3, 0).unwrap();
+        assert!(matches!(instr, RV32IMInstruction::FADDS(_)));
+        // flw f1, 8(x2); fsw f1, 8(x2)
+        let instr = RV32IMInstruction::decode(0x00812087, 0).unwrap();
+        assert!(matches!(instr, RV32IMInstruction::FLW(_)));
+        let instr = RV32IMInstruction::decode(0x00112427, 0).unwrap();
+        assert!(matches!(instr, RV32IMInstruction::FSW(_)));
+        // fadd.s with a reserved rounding mode
+        assert!(RV32IMInstruction::decode(0x003150d3, 0).is_err());
+    }
+
+    #[test]
+    fn arithmetic_matches_native() {
+        for (a, b) in operand_pairs() {
+            let (x, y) = (f32::from_bits(a), f32::from_bits(b));
+            let context = format!("{x:e} ({a:#x}), {y:e} ({b:#x})");
+            let add = run(op_fp(0b0000000, RS2, 0), a, b, true, true);
+            assert_float_eq(add, x + y, &format!("fadd {context}"));
+            let sub = run(op_fp(0b0000100, RS2, 0), a, b, true, true);
+            assert_float_eq(sub, x - y, &format!("fsub {context}"));
+            let mul = run(op_fp(0b0001000, RS2, 0), a, b, true, true);
+            assert_float_eq(mul, x * y, &format!("fmul {context}"));
+            let div = run(op_fp(0b0001100, RS2, 0), a, b, true, true);
+            assert_float_eq(div, x / y, &format!("fdiv {context}"));
+            let sqrt = run(op_fp(0b0101100, 0, 0), a, b, true, true);
+            assert_float_eq(sqrt, x.sqrt(), &format!("fsqrt {context}"));
+        }
+    }
+
+    #[test]
+    fn directed_rounding() {
+        for rounding_mode in ROUNDING_MODES {
+            for (a, b) in operand_pairs() {
+                let (x, y) = (f32::from_bits(a), f32::from_bits(b));
+                let context = format!("{rounding_mode:?} {x:e} ({a:#x}), {y:e} ({b:#x})");
+
+                // Products of single-precision values are exact in double precision
+                let mul = run(op_fp(0b0001000, RS2, rm(rounding_mode)), a, b, true, true);
+                let expected = round_to_f32(x as f64 * y as f64, rounding_mode);
+                assert_float_eq(mul, expected, &format!("fmul {context}"));
+
+                // Only check sums that are exact in double precision
+                let sum = x as f64 + y as f64;
+                if sum.is_finite() && sum - x as f64 == y as f64 && sum - y as f64 == x as f64 {
+                    let add = run(op_fp(0b0000000, RS2, rm(rounding_mode)), a, b, true, true);
+                    let expected = if sum == 0.0 {
+                        let negative = match rounding_mode {
+                            RoundingMode::Down => x.is_sign_negative() || y.is_sign_negative(),
+                            _ => x.is_sign_negative() && y.is_sign_negative(),
+                        };
+                        if negative {
+                            -0.0
+                        } else {
+                            0.0
+                        }
+                    } else {
+                        round_to_f32(sum, rounding_mode)
+                    };
+                    assert_float_eq(add, expected, &format!("fadd {context}"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for rounding_mode in ROUNDING_MODES {
+            let rm = rm(rounding_mode);
+            let mut values: Vec<u32> = SPECIAL_VALUES.to_vec();
+            values.extend([0x4effffff, 0x4f000000, 0xcf000000, 0xcf000001, 0x4f800000]);
+            values.extend([0x3f000000, 0xbf000000, 0x3fc00000, 0xc0200000, 0x40200000]);
+            values.extend((0..200).map(|_| rng.next_u32()));
+            values.extend((0..200).map(|_| 0x3f00_0000 + rng.next_u32() % 0x0f80_0000));
+            for &a in values.iter() {
+                let x = f32::from_bits(a) as f64;
+                let rounded = match rounding_mode {
+                    RoundingMode::NearestEven => x.round_ties_even(),
+                    RoundingMode::TowardZero => x.trunc(),
+                    RoundingMode::Down => x.floor(),
+                    RoundingMode::Up => x.ceil(),
+                    RoundingMode::NearestMaxMagnitude => x.round(),
+                };
+                let context = format!("{rounding_mode:?} {x:e} ({a:#x})");
+
+                let w = run(op_fp(0b1100000, 0, rm), a, 0, true, false);
+                let expected = if x.is_nan() { i32::MAX } else { rounded as i32 };
+                assert_eq!(w as i32, expected, "fcvt.w.s {context}");
+
+                let wu = run(op_fp(0b1100000, 1, rm), a, 0, true, false);
+                let expected = if x.is_nan() { u32::MAX } else { rounded as u32 };
+                assert_eq!(wu, expected, "fcvt.wu.s {context}");
+            }
+
+            let mut integers: Vec<u32> = vec![0, 1, u32::MAX, 0x8000_0000, 0x7fff_ffff];
+            integers.extend([0x0100_0001, 0x0100_0003, 0xfeff_ffff, 0xffff_fffd]);
+            integers.extend((0..200).map(|_| rng.next_u32()));
+            integers.extend((0..200).map(|_| rng.next_u32() >> (rng.next_u32() % 32)));
+            for &a in integers.iter() {
+                let context = format!("{rounding_mode:?} {a:#x}");
+                let s = run(op_fp(0b1101000, 0, rm), a, 0, false, true);
+                let expected = round_to_f32(a as i32 as f64, rounding_mode);
+                assert_float_eq(s, expected, &format!("fcvt.s.w {context}"));
+                let su = run(op_fp(0b1101000, 1, rm), a, 0, false, true);
+                let expected = round_to_f32(a as f64, rounding_mode);
+                assert_float_eq(su, expected, &format!("fcvt.s.wu {context}"));
+            }
+        }
+    }
+
+    #[test]
+    fn comparisons_and_sign_injection() {
+        for (a, b) in operand_pairs() {
+            let (x, y) = (f32::from_bits(a), f32::from_bits(b));
+            let context = format!("{x:e} ({a:#x}), {y:e} ({b:#x})");
+            assert_eq!(
+                run(op_fp(0b1010000, RS2, 0b010), a, b, true, false),
+                (x == y) as u32
+            );
+            assert_eq!(
+                run(op_fp(0b1010000, RS2, 0b001), a, b, true, false),
+                (x < y) as u32
+            );
+            assert_eq!(
+                run(op_fp(0b1010000, RS2, 0b000), a, b, true, false),
+                (x <= y) as u32
+            );
+
+            let (min, max) = match (x.is_nan(), y.is_nan()) {
+                (true, true) => (f32::NAN, f32::NAN),
+                (true, false) => (y, y),
+                (false, true) => (x, x),
+                (false, false) if x.total_cmp(&y).is_lt() => (x, y),
+                (false, false) => (y, x),
+            };
+            let result = run(op_fp(0b0010100, RS2, 0b000), a, b, true, true);
+            assert_float_eq(result, min, &format!("fmin {context}"));
+            let result = run(op_fp(0b0010100, RS2, 0b001), a, b, true, true);
+            assert_float_eq(result, max, &format!("fmax {context}"));
+
+            let sign = 0x8000_0000;
+            let result = run(op_fp(0b0010000, RS2, 0b000), a, b, true, true);
+            assert_eq!(result, (a & !sign) | (b & sign), "fsgnj {context}");
+            let result = run(op_fp(0b0010000, RS2, 0b001), a, b, true, true);
+            assert_eq!(result, (a & !sign) | (!b & sign), "fsgnjn {context}");
+            let result = run(op_fp(0b0010000, RS2, 0b010), a, b, true, true);
+            assert_eq!(result, a ^ (b & sign), "fsgnjx {context}");
+        }
+    }
+
+    #[test]
+    fn classify_and_move() {
+        let expected_classes = [
+            1 << 4,
+            1 << 3,
+            1 << 5,
+            1 << 5,
+            1 << 6,
+            1 << 6,
+            1 << 1,
+            1 << 6,
+            1 << 6,
+            1 << 6,
+            1 << 6,
+            1 << 6,
+            1 << 1,
+            1 << 7,
+            1 << 0,
+            1 << 9,
+            1 << 8,
+            1 << 9,
+        ];
+        for (&a, expected) in SPECIAL_VALUES.iter().zip(expected_classes) {
+            assert_eq!(run(op_fp(0b1110000, 0, 0b001), a, 0, true, false), expected);
+            assert_eq!(run(op_fp(0b1110000, 0, 0b000), a, 0, true, false), a);
+            assert_eq!(run(op_fp(0b1111000, 0, 0b000), a, 0, false, true), a);
+        }
+    }
+}
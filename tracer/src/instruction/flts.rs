@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::float::{fcmp_s_virtual_sequence, Comparison};
+use super::{
+    format::{format_r::FormatR, InstructionFormat},
+    RISCVInstruction, RISCVTrace,
+};
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
+
+declare_riscv_instr!(
+    name   = FLTS,
+    mask   = 0xfe00707f,
+    match  = 0xa0001053,
+    format = FormatR,
+    ram    = ()
+);
+
+impl FLTS {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <FLTS as RISCVInstruction>::RAMAccess) {
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
+        }
+    }
+}
+
+impl RISCVTrace for FLTS {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for FLTS {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        fcmp_s_virtual_sequence(
+            self.address,
+            self.is_compressed,
+            &self.operands,
+            Comparison::LessThan,
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::float::flw_virtual_sequence;
+use super::{
+    format::{format_load::FormatLoad, InstructionFormat},
+    RISCVInstruction, RISCVTrace,
+};
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
+
+declare_riscv_instr!(
+    name   = FLW,
+    mask   = 0x0000707f,
+    match  = 0x00002007,
+    format = FormatLoad,
+    ram    = ()
+);
+
+impl FLW {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <FLW as RISCVInstruction>::RAMAccess) {
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
+        }
+    }
+}
+
+impl RISCVTrace for FLW {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for FLW {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        flw_virtual_sequence(self.address, self.is_compressed, &self.operands)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::emulator::cpu::Cpu;
+use common::constants::REGISTER_COUNT;
+use rand::rngs::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+use super::{
+    format_r::RegisterStateFormatR, normalize_register_value, InstructionFormat, NormalizedOperands,
+};
+
+/// R-type format of F-extension instructions that take a rounding mode
+/// (`rm`, in the funct3 field). The register operands are indices into the
+/// integer or floating-point register file, depending on the instruction.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FormatFloatR {
+    pub rd: u8,
+    pub rs1: u8,
+    pub rs2: u8,
+    pub rm: u8,
+}
+
+impl InstructionFormat for FormatFloatR {
+    type RegisterState = RegisterStateFormatR;
+
+    fn parse(word: u32) -> Self {
+        FormatFloatR {
+            rd: ((word >> 7) & 0x1f) as u8,   // [11:7]
+            rm: ((word >> 12) & 0x7) as u8,   // [14:12]
+            rs1: ((word >> 15) & 0x1f) as u8, // [19:15]
+            rs2: ((word >> 20) & 0x1f) as u8, // [24:20]
+        }
+    }
+
+    fn capture_pre_execution_state(&self, state: &mut Self::RegisterState, cpu: &mut Cpu) {
+        state.rs1 = normalize_register_value(cpu.x[self.rs1 as usize], &cpu.xlen);
+        state.rs2 = normalize_register_value(cpu.x[self.rs2 as usize], &cpu.xlen);
+        state.rd.0 = normalize_register_value(cpu.x[self.rd as usize], &cpu.xlen);
+    }
+
+    fn capture_post_execution_state(&self, state: &mut Self::RegisterState, cpu: &mut Cpu) {
+        state.rd.1 = normalize_register_value(cpu.x[self.rd as usize], &cpu.xlen);
+    }
+
+    fn random(rng: &mut StdRng) -> Self {
+        Self {
+            rd: (rng.next_u64() as u8 % REGISTER_COUNT),
+            rs1: (rng.next_u64() as u8 % REGISTER_COUNT),
+            rs2: (rng.next_u64() as u8 % REGISTER_COUNT),
+            rm: (rng.next_u64() % 5) as u8,
+        }
+    }
+
+    fn normalize(&self) -> NormalizedOperands {
+        NormalizedOperands {
+            rs1: self.rs1,
+            rs2: self.rs2,
+            rd: self.rd,
+            imm: 0,
+        }
+    }
+}
@@ -4,6 +4,7 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
 pub mod format_b;
+pub mod format_float_r;
 pub mod format_i;
 pub mod format_j;
 pub mod format_load;
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::float::{fsgnj_s_virtual_sequence, SignInjection};
+use super::{
+    format::{format_r::FormatR, InstructionFormat},
+    RISCVInstruction, RISCVTrace,
+};
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
+
+declare_riscv_instr!(
+    name   = FSGNJXS,
+    mask   = 0xfe00707f,
+    match  = 0x20002053,
+    format = FormatR,
+    ram    = ()
+);
+
+impl FSGNJXS {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <FSGNJXS as RISCVInstruction>::RAMAccess) {
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
+        }
+    }
+}
+
+impl RISCVTrace for FSGNJXS {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for FSGNJXS {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        fsgnj_s_virtual_sequence(
+            self.address,
+            self.is_compressed,
+            &self.operands,
+            SignInjection::Xor,
+        )
+    }
+}
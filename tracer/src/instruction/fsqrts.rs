@@ -0,0 +1,52 @@
+use common::constants::float_register_index;
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::float::{fsqrt_s_advice, fsqrt_s_virtual_sequence, set_advice};
+use super::{
+    format::{format_float_r::FormatFloatR, InstructionFormat},
+    RISCVInstruction, RISCVTrace,
+};
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
+
+declare_riscv_instr!(
+    name   = FSQRTS,
+    mask   = 0xfff0007f,
+    match  = 0x58000053,
+    format = FormatFloatR,
+    ram    = ()
+);
+
+impl FSQRTS {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <FSQRTS as RISCVInstruction>::RAMAccess) {
+        for instr in self.advised_sequence(cpu) {
+            instr.execute(cpu);
+        }
+    }
+
+    /// The virtual sequence, with its advice computed from the operands.
+    fn advised_sequence(&self, cpu: &Cpu) -> Vec<RV32IMInstruction> {
+        let rs1 = cpu.x[float_register_index(self.operands.rs1) as usize] as u32;
+        let mut virtual_sequence = self.virtual_sequence();
+        set_advice(&mut virtual_sequence, &fsqrt_s_advice(rs1));
+        virtual_sequence
+    }
+}
+
+impl RISCVTrace for FSQRTS {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.advised_sequence(cpu);
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for FSQRTS {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        fsqrt_s_virtual_sequence(self.address, self.is_compressed, &self.operands)
+    }
+}
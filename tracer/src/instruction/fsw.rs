@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{declare_riscv_instr, emulator::cpu::Cpu};
+
+use super::float::fsw_virtual_sequence;
+use super::{
+    format::{format_s::FormatS, InstructionFormat},
+    RISCVInstruction, RISCVTrace,
+};
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
+
+declare_riscv_instr!(
+    name   = FSW,
+    mask   = 0x0000707f,
+    match  = 0x00002027,
+    format = FormatS,
+    ram    = ()
+);
+
+impl FSW {
+    fn exec(&self, cpu: &mut Cpu, _: &mut <FSW as RISCVInstruction>::RAMAccess) {
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
+        }
+    }
+}
+
+impl RISCVTrace for FSW {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+    }
+}
+
+impl VirtualInstructionSequence for FSW {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        fsw_virtual_sequence(self.address, self.is_compressed, &self.operands)
+    }
+}
@@ -132,6 +132,8 @@ pub struct INLINE {
     pub operands: FormatR,
     /// Tracks remaining virtual instructions (used by tracer)
     pub virtual_sequence_remaining: Option<u16>,
+    /// Always false: inlines have no compressed encoding
+    pub is_compressed: bool,
 }
 
 impl RISCVInstruction for INLINE {
@@ -153,6 +155,7 @@ impl RISCVInstruction for INLINE {
             address,
             operands: FormatR::parse(word),
             virtual_sequence_remaining: None,
+            is_compressed: false,
         }
     }
 
@@ -164,6 +167,7 @@ impl RISCVInstruction for INLINE {
             address: rng.next_u64(),
             operands: FormatR::random(rng),
             virtual_sequence_remaining: None,
+            is_compressed: false,
         }
     }
 
@@ -14,6 +14,9 @@ macro_rules! declare_riscv_instr {
             pub address: u64,
             pub operands: $format,
             pub virtual_sequence_remaining: Option<u16>,
+            /// Whether this instruction was decoded from a 16-bit compressed
+            /// ("C" extension) encoding, and therefore advances the PC by 2.
+            pub is_compressed: bool,
         }
 
         impl $crate::instruction::RISCVInstruction for $name {
@@ -41,6 +44,7 @@ macro_rules! declare_riscv_instr {
                     address,
                     operands: <$format>::parse(word),
                     virtual_sequence_remaining: None,
+                    is_compressed: false,
                 }
             }
 
@@ -49,6 +53,7 @@ macro_rules! declare_riscv_instr {
                     address: rand::RngCore::next_u64(rng),
                     operands: <$format>::random(rng),
                     virtual_sequence_remaining: None,
+                    is_compressed: false,
                 }
             }
 
@@ -71,6 +71,7 @@ impl VirtualInstructionSequence for LBU {
                 imm: self.operands.imm as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -82,6 +83,7 @@ impl VirtualInstructionSequence for LBU {
                 imm: -4i64 as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
         };
         sequence.push(andi.into());
 
@@ -93,6 +95,7 @@ impl VirtualInstructionSequence for LBU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.push(lw.into());
 
@@ -104,6 +107,7 @@ impl VirtualInstructionSequence for LBU {
                 imm: 3,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(xori.into());
 
@@ -115,6 +119,7 @@ impl VirtualInstructionSequence for LBU {
                 imm: 3,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(slli.virtual_sequence());
 
@@ -126,6 +131,7 @@ impl VirtualInstructionSequence for LBU {
                 rs2: v_shift,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(sll.virtual_sequence());
 
@@ -137,6 +143,7 @@ impl VirtualInstructionSequence for LBU {
                 imm: 24,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(srli.virtual_sequence());
 
@@ -72,6 +72,7 @@ impl VirtualInstructionSequence for LH {
                 imm: self.operands.imm,
             },
             virtual_sequence_remaining: Some(8),
+            is_compressed: self.is_compressed,
         };
         sequence.push(alignment_check.into());
 
@@ -83,6 +84,7 @@ impl VirtualInstructionSequence for LH {
                 imm: self.operands.imm as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -94,6 +96,7 @@ impl VirtualInstructionSequence for LH {
                 imm: -4i64 as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
         };
         sequence.push(andi.into());
 
@@ -105,6 +108,7 @@ impl VirtualInstructionSequence for LH {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.push(lw.into());
 
@@ -116,6 +120,7 @@ impl VirtualInstructionSequence for LH {
                 imm: 2,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(xori.into());
 
@@ -127,6 +132,7 @@ impl VirtualInstructionSequence for LH {
                 imm: 3,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(slli.virtual_sequence());
 
@@ -138,6 +144,7 @@ impl VirtualInstructionSequence for LH {
                 rs2: v_shift,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(sll.virtual_sequence());
 
@@ -149,6 +156,7 @@ impl VirtualInstructionSequence for LH {
                 imm: 16,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(srai.virtual_sequence());
 
@@ -73,6 +73,7 @@ impl VirtualInstructionSequence for LHU {
                 imm: self.operands.imm,
             },
             virtual_sequence_remaining: Some(8),
+            is_compressed: self.is_compressed,
         };
         sequence.push(assert_alignment.into());
 
@@ -84,6 +85,7 @@ impl VirtualInstructionSequence for LHU {
                 imm: self.operands.imm as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -95,6 +97,7 @@ impl VirtualInstructionSequence for LHU {
                 imm: -4i64 as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
         };
         sequence.push(andi.into());
 
@@ -106,6 +109,7 @@ impl VirtualInstructionSequence for LHU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.push(lw.into());
 
@@ -117,6 +121,7 @@ impl VirtualInstructionSequence for LHU {
                 imm: 2,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(xori.into());
 
@@ -128,6 +133,7 @@ impl VirtualInstructionSequence for LHU {
                 imm: 3,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(slli.virtual_sequence());
 
@@ -139,6 +145,7 @@ impl VirtualInstructionSequence for LHU {
                 rs2: v_shift,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(sll.virtual_sequence());
 
@@ -150,6 +157,7 @@ impl VirtualInstructionSequence for LHU {
                 imm: 16,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(srli.virtual_sequence());
 
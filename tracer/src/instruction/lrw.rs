@@ -2,43 +2,49 @@ use serde::{Deserialize, Serialize};
 
 use crate::{declare_riscv_instr, emulator::cpu::Cpu};
 
+use super::amo::lr_w_virtual_sequence;
 use super::{
     format::{format_r::FormatR, InstructionFormat},
-    RAMRead, RISCVInstruction, RISCVTrace,
+    RISCVInstruction, RISCVTrace,
 };
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
 
 declare_riscv_instr!(
     name   = LRW,
     mask   = 0xf9f0707f,
     match  = 0x1000202f,
     format = FormatR,
-    ram    = RAMRead
+    ram    = ()
 );
 
 impl LRW {
-    fn exec(&self, cpu: &mut Cpu, ram_access: &mut <LRW as RISCVInstruction>::RAMAccess) {
-        if cpu.is_reservation_set() {
-            println!("LRW: Reservation is already set");
+    fn exec(&self, cpu: &mut Cpu, _: &mut <LRW as RISCVInstruction>::RAMAccess) {
+        // The reservation lives in RAM (see `RESERVATION_ADDRESS`), so executing
+        // the virtual sequence keeps untraced execution consistent with tracing.
+        cpu.mmu.set_reservation_access(true);
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
         }
+        cpu.mmu.set_reservation_access(false);
+    }
+}
 
-        let address = cpu.x[self.operands.rs1 as usize] as u64;
-
-        // Load the word from memory
-        let value = cpu.mmu.load_word(address);
-
-        cpu.x[self.operands.rd as usize] = match value {
-            Ok((word, memory_read)) => {
-                *ram_access = memory_read;
-
-                // Set reservation for this address
-                cpu.set_reservation(address);
-
-                // Sign extend the 32-bit value
-                word as i32 as i64
-            }
-            Err(_) => panic!("MMU load error"),
-        };
+impl RISCVTrace for LRW {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        // Only this sequence may access the reservation word
+        cpu.mmu.set_reservation_access(true);
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
+        }
+        cpu.mmu.set_reservation_access(false);
     }
 }
 
-impl RISCVTrace for LRW {}
+impl VirtualInstructionSequence for LRW {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        lr_w_virtual_sequence(self.address, &self.operands)
+    }
+}
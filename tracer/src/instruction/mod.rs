@@ -4,6 +4,7 @@ use add::ADD;
 use addi::ADDI;
 use addiw::ADDIW;
 use addw::ADDW;
+use advice::ADVICE;
 use amoaddd::AMOADDD;
 use amoaddw::AMOADDW;
 use amoandd::AMOANDD;
@@ -39,7 +40,29 @@ use divu::DIVU;
 use divuw::DIVUW;
 use divw::DIVW;
 use ecall::ECALL;
+use fadds::FADDS;
+use fclasss::FCLASSS;
+use fcvtsw::FCVTSW;
+use fcvtswu::FCVTSWU;
+use fcvtws::FCVTWS;
+use fcvtwus::FCVTWUS;
+use fdivs::FDIVS;
 use fence::FENCE;
+use feqs::FEQS;
+use fles::FLES;
+use flts::FLTS;
+use flw::FLW;
+use fmaxs::FMAXS;
+use fmins::FMINS;
+use fmuls::FMULS;
+use fmvwx::FMVWX;
+use fmvxw::FMVXW;
+use fsgnjns::FSGNJNS;
+use fsgnjs::FSGNJS;
+use fsgnjxs::FSGNJXS;
+use fsqrts::FSQRTS;
+use fsubs::FSUBS;
+use fsw::FSW;
 use jal::JAL;
 use jalr::JALR;
 use lb::LB;
@@ -127,6 +150,8 @@ pub mod add;
 pub mod addi;
 pub mod addiw;
 pub mod addw;
+pub mod advice;
+pub mod amo;
 pub mod amoaddd;
 pub mod amoaddw;
 pub mod amoandd;
@@ -159,7 +184,30 @@ pub mod divu;
 pub mod divuw;
 pub mod divw;
 pub mod ecall;
+pub mod fadds;
+pub mod fclasss;
+pub mod fcvtsw;
+pub mod fcvtswu;
+pub mod fcvtws;
+pub mod fcvtwus;
+pub mod fdivs;
 pub mod fence;
+pub mod feqs;
+pub mod fles;
+pub mod float;
+pub mod flts;
+pub mod flw;
+pub mod fmaxs;
+pub mod fmins;
+pub mod fmuls;
+pub mod fmvwx;
+pub mod fmvxw;
+pub mod fsgnjns;
+pub mod fsgnjs;
+pub mod fsgnjxs;
+pub mod fsqrts;
+pub mod fsubs;
+pub mod fsw;
 pub mod inline;
 pub mod jal;
 pub mod jalr;
@@ -285,6 +333,7 @@ pub struct NormalizedInstruction {
     pub address: usize,
     pub operands: NormalizedOperands,
     pub virtual_sequence_remaining: Option<u16>,
+    pub is_compressed: bool,
 }
 
 pub trait RISCVInstruction: std::fmt::Debug + Sized + Copy + Into<RV32IMInstruction> {
@@ -474,12 +523,14 @@ macro_rules! define_rv32im_enums {
                             address: instr.address as usize,
                             operands: instr.operands.normalize(),
                             virtual_sequence_remaining: instr.virtual_sequence_remaining,
+                            is_compressed: instr.is_compressed,
                         },
                     )*
                     RV32IMInstruction::INLINE(instr) => NormalizedInstruction {
                         address: instr.address as usize,
                         operands: instr.operands.normalize(),
                         virtual_sequence_remaining: instr.virtual_sequence_remaining,
+                        is_compressed: instr.is_compressed,
                     },
                 }
             }
@@ -494,13 +545,24 @@ macro_rules! define_rv32im_enums {
                     RV32IMInstruction::INLINE(instr) => {instr.virtual_sequence_remaining = remaining;}
                 }
             }
+
+            pub fn set_is_compressed(&mut self, is_compressed: bool) {
+                match self {
+                    RV32IMInstruction::NoOp => (),
+                    RV32IMInstruction::UNIMPL => (),
+                    $(
+                        RV32IMInstruction::$instr(instr) => {instr.is_compressed = is_compressed;}
+                    )*
+                    RV32IMInstruction::INLINE(instr) => {instr.is_compressed = is_compressed;}
+                }
+            }
         }
     };
 }
 
 define_rv32im_enums! {
     instructions: [
-        ADD, ADDI, AND, ANDI, AUIPC, BEQ, BGE, BGEU, BLT, BLTU, BNE, DIV, DIVU,
+        ADD, ADDI, ADVICE, AND, ANDI, AUIPC, BEQ, BGE, BGEU, BLT, BLTU, BNE, DIV, DIVU,
         ECALL, FENCE, JAL, JALR, LB, LBU, LD, LH, LHU, LUI, LW, MUL, MULH, MULHSU,
         MULHU, OR, ORI, REM, REMU, SB, SD, SH, SLL, SLLI, SLT, SLTI, SLTIU, SLTU,
         SRA, SRAI, SRL, SRLI, SUB, SW, XOR, XORI,
@@ -512,6 +574,9 @@ define_rv32im_enums! {
         LRW, SCW, AMOSWAPW, AMOADDW, AMOANDW, AMOORW, AMOXORW, AMOMINW, AMOMAXW, AMOMINUW, AMOMAXUW,
         // RV64A (Atomic Memory Operations)
         LRD, SCD, AMOSWAPD, AMOADDD, AMOANDD, AMOORD, AMOXORD, AMOMIND, AMOMAXD, AMOMINUD, AMOMAXUD,
+        // RV32F (single-precision floating point)
+        FLW, FSW, FADDS, FSUBS, FMULS, FDIVS, FSQRTS, FSGNJS, FSGNJNS, FSGNJXS, FMINS, FMAXS,
+        FEQS, FLTS, FLES, FCLASSS, FCVTWS, FCVTWUS, FCVTSW, FCVTSWU, FMVXW, FMVWX,
         // Virtual
         VirtualAdvice, VirtualAssertEQ, VirtualAssertHalfwordAlignment, VirtualAssertLTE,
         VirtualAssertValidDiv0, VirtualAssertValidSignedRemainder, VirtualAssertValidUnsignedRemainder,
@@ -789,6 +854,67 @@ impl RV32IMInstruction {
                     }
                 }
             }
+            0b0000111 | 0b0100111 | 0b1010011 if !cfg!(feature = "float") => {
+                Err("Floating-point instructions require the float feature")
+            }
+            0b0000111 => {
+                // Floating-point load (F-extension)
+                match (instr >> 12) & 0x7 {
+                    0b010 => Ok(FLW::new(instr, address, true).into()),
+                    _ => Err("Unsupported floating-point load width"),
+                }
+            }
+            0b0100111 => {
+                // Floating-point store (F-extension)
+                match (instr >> 12) & 0x7 {
+                    0b010 => Ok(FSW::new(instr, address, true).into()),
+                    _ => Err("Unsupported floating-point store width"),
+                }
+            }
+            0b1010011 => {
+                // Floating-point operations (F-extension). funct7 selects the
+                // operation; funct3 holds either the rounding mode or a sub-operation.
+                let funct3 = ((instr >> 12) & 0x7) as u8;
+                let funct7 = (instr >> 25) & 0x7f;
+                let rs2 = (instr >> 20) & 0x1f;
+                let rounded = matches!(
+                    funct7,
+                    0b0000000
+                        | 0b0000100
+                        | 0b0001000
+                        | 0b0001100
+                        | 0b0101100
+                        | 0b1100000
+                        | 0b1101000
+                );
+                if rounded && !float::is_valid_rounding_mode(funct3) {
+                    return Err("Invalid floating-point rounding mode");
+                }
+
+                match (funct7, funct3, rs2) {
+                    (0b0000000, _, _) => Ok(FADDS::new(instr, address, true).into()),
+                    (0b0000100, _, _) => Ok(FSUBS::new(instr, address, true).into()),
+                    (0b0001000, _, _) => Ok(FMULS::new(instr, address, true).into()),
+                    (0b0001100, _, _) => Ok(FDIVS::new(instr, address, true).into()),
+                    (0b0101100, _, 0) => Ok(FSQRTS::new(instr, address, true).into()),
+                    (0b0010000, 0b000, _) => Ok(FSGNJS::new(instr, address, true).into()),
+                    (0b0010000, 0b001, _) => Ok(FSGNJNS::new(instr, address, true).into()),
+                    (0b0010000, 0b010, _) => Ok(FSGNJXS::new(instr, address, true).into()),
+                    (0b0010100, 0b000, _) => Ok(FMINS::new(instr, address, true).into()),
+                    (0b0010100, 0b001, _) => Ok(FMAXS::new(instr, address, true).into()),
+                    (0b1010000, 0b000, _) => Ok(FLES::new(instr, address, true).into()),
+                    (0b1010000, 0b001, _) => Ok(FLTS::new(instr, address, true).into()),
+                    (0b1010000, 0b010, _) => Ok(FEQS::new(instr, address, true).into()),
+                    (0b1100000, _, 0) => Ok(FCVTWS::new(instr, address, true).into()),
+                    (0b1100000, _, 1) => Ok(FCVTWUS::new(instr, address, true).into()),
+                    (0b1101000, _, 0) => Ok(FCVTSW::new(instr, address, true).into()),
+                    (0b1101000, _, 1) => Ok(FCVTSWU::new(instr, address, true).into()),
+                    (0b1110000, 0b000, 0) => Ok(FMVXW::new(instr, address, true).into()),
+                    (0b1110000, 0b001, 0) => Ok(FCLASSS::new(instr, address, true).into()),
+                    (0b1111000, 0b000, 0) => Ok(FMVWX::new(instr, address, true).into()),
+                    _ => Err("Unsupported floating-point instruction"),
+                }
+            }
             0b1110011 => {
                 // For now this only (potentially) maps to ECALL.
                 if instr == ECALL::MATCH {
@@ -803,7 +929,14 @@ impl RV32IMInstruction {
             // while funct3 should hold all necessary instructions for that operation.
             // funct7:
             // - 0x00: SHA256
-            0b0001011 => Ok(INLINE::new(instr, address, false).into()),
+            // - 0x7F: reserved for ADVICE (funct3 = 0)
+            0b0001011 => {
+                if instr & ADVICE::MASK == ADVICE::MATCH {
+                    Ok(ADVICE::new(instr, address, true).into())
+                } else {
+                    Ok(INLINE::new(instr, address, false).into())
+                }
+            }
             // 0x2B is reserved for external inlines
             0b0101011 => Ok(INLINE::new(instr, address, false).into()),
             _ => Err("Unknown opcode"),
@@ -847,4 +980,12 @@ mod tests {
             "RV32IMCycle size should be {expected} bytes, but is {size} bytes"
         );
     }
+    #[test]
+    #[cfg(not(feature = "float"))]
+    fn float_instructions_require_float_feature() {
+        // fadd.s f1, f2, f3 / flw f1, 8(x2) / fsw f1, 8(x2)
+        for word in [0x003100d3, 0x00812087, 0x00112427] {
+            assert!(RV32IMInstruction::decode(word, 0).is_err());
+        }
+    }
 }
@@ -69,6 +69,7 @@ impl VirtualInstructionSequence for MULH {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
         };
         sequence.push(movsign_x.into());
 
@@ -80,6 +81,7 @@ impl VirtualInstructionSequence for MULH {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.push(movsign_y.into());
 
@@ -91,6 +93,7 @@ impl VirtualInstructionSequence for MULH {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mulhu.into());
 
@@ -102,6 +105,7 @@ impl VirtualInstructionSequence for MULH {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mulu_sx_y.into());
 
@@ -113,6 +117,7 @@ impl VirtualInstructionSequence for MULH {
                 rs2: self.operands.rs1,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mulu_sy_x.into());
 
@@ -124,6 +129,7 @@ impl VirtualInstructionSequence for MULH {
                 rs2: v_1,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add_1.into());
 
@@ -135,6 +141,7 @@ impl VirtualInstructionSequence for MULH {
                 rs2: v_2,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add_2.into());
 
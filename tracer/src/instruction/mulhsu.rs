@@ -81,6 +81,7 @@ impl VirtualInstructionSequence for MULHSU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(movsign.into());
 
@@ -92,6 +93,7 @@ impl VirtualInstructionSequence for MULHSU {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mulhu.into());
 
@@ -103,6 +105,7 @@ impl VirtualInstructionSequence for MULHSU {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mulu.into());
 
@@ -114,6 +117,7 @@ impl VirtualInstructionSequence for MULHSU {
                 rs2: v_2,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
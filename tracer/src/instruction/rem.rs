@@ -110,6 +110,7 @@ impl VirtualInstructionSequence for REM {
             address: self.address,
             operands: FormatJ { rd: v_q, imm: 0 },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -118,6 +119,7 @@ impl VirtualInstructionSequence for REM {
             address: self.address,
             operands: FormatJ { rd: v_r, imm: 0 },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -130,6 +132,7 @@ impl VirtualInstructionSequence for REM {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(is_valid.into());
 
@@ -141,6 +144,7 @@ impl VirtualInstructionSequence for REM {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mul.into());
 
@@ -152,6 +156,7 @@ impl VirtualInstructionSequence for REM {
                 rs2: v_r,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -163,6 +168,7 @@ impl VirtualInstructionSequence for REM {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(assert_eq.into());
 
@@ -174,6 +180,7 @@ impl VirtualInstructionSequence for REM {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(virtual_move.into());
 
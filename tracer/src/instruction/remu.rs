@@ -91,6 +91,7 @@ impl VirtualInstructionSequence for REMU {
             address: self.address,
             operands: FormatJ { rd: v_q, imm: 0 },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -99,6 +100,7 @@ impl VirtualInstructionSequence for REMU {
             address: self.address,
             operands: FormatJ { rd: v_r, imm: 0 },
             virtual_sequence_remaining: Some(6),
+            is_compressed: self.is_compressed,
             advice: 0,
         };
         sequence.push(advice.into());
@@ -111,6 +113,7 @@ impl VirtualInstructionSequence for REMU {
                 rs2: self.operands.rs2,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.push(mul.into());
 
@@ -122,6 +125,7 @@ impl VirtualInstructionSequence for REMU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(4),
+            is_compressed: self.is_compressed,
         };
         sequence.push(assert_remainder.into());
 
@@ -133,6 +137,7 @@ impl VirtualInstructionSequence for REMU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(assert_lte.into());
 
@@ -144,6 +149,7 @@ impl VirtualInstructionSequence for REMU {
                 rs2: v_r,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -155,6 +161,7 @@ impl VirtualInstructionSequence for REMU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(assert_eq.into());
 
@@ -166,6 +173,7 @@ impl VirtualInstructionSequence for REMU {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(virtual_move.into());
 
@@ -73,6 +73,7 @@ impl VirtualInstructionSequence for SB {
                 imm: self.operands.imm as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(12),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -84,6 +85,7 @@ impl VirtualInstructionSequence for SB {
                 imm: -4i64 as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(11),
+            is_compressed: self.is_compressed,
         };
         sequence.push(andi.into());
 
@@ -95,6 +97,7 @@ impl VirtualInstructionSequence for SB {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(10),
+            is_compressed: self.is_compressed,
         };
         sequence.push(lw.into());
 
@@ -106,6 +109,7 @@ impl VirtualInstructionSequence for SB {
                 imm: 3,
             },
             virtual_sequence_remaining: Some(9),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(slli.virtual_sequence());
 
@@ -116,6 +120,7 @@ impl VirtualInstructionSequence for SB {
                 imm: 0xff,
             },
             virtual_sequence_remaining: Some(8),
+            is_compressed: self.is_compressed,
         };
         sequence.push(lui.into());
 
@@ -127,6 +132,7 @@ impl VirtualInstructionSequence for SB {
                 rs2: v_shift,
             },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(sll.virtual_sequence());
 
@@ -138,6 +144,7 @@ impl VirtualInstructionSequence for SB {
                 rs2: v_shift,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(sll.virtual_sequence());
 
@@ -149,6 +156,7 @@ impl VirtualInstructionSequence for SB {
                 rs2: v_byte,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(xor.into());
 
@@ -160,6 +168,7 @@ impl VirtualInstructionSequence for SB {
                 rs2: v_mask,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(and.into());
 
@@ -171,6 +180,7 @@ impl VirtualInstructionSequence for SB {
                 rs2: v_byte,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(xor.into());
 
@@ -182,6 +192,7 @@ impl VirtualInstructionSequence for SB {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(sw.into());
 
@@ -2,44 +2,49 @@ use serde::{Deserialize, Serialize};
 
 use crate::{declare_riscv_instr, emulator::cpu::Cpu};
 
+use super::amo::sc_w_virtual_sequence;
 use super::{
     format::{format_r::FormatR, InstructionFormat},
-    RAMWrite, RISCVInstruction, RISCVTrace,
+    RISCVInstruction, RISCVTrace,
 };
+use super::{RV32IMCycle, RV32IMInstruction, VirtualInstructionSequence};
 
 declare_riscv_instr!(
     name   = SCW,
     mask   = 0xf800707f,
     match  = 0x1800202f,
     format = FormatR,
-    ram    = RAMWrite
+    ram    = ()
 );
 
 impl SCW {
-    fn exec(&self, cpu: &mut Cpu, ram_access: &mut <SCW as RISCVInstruction>::RAMAccess) {
-        let address = cpu.x[self.operands.rs1 as usize] as u64;
-        let value = cpu.x[self.operands.rs2 as usize] as u32;
-
-        // Check if reservation is set and matches the address
-        if cpu.has_reservation(address) {
-            // Store the word to memory
-            let result = cpu.mmu.store_word(address, value);
+    fn exec(&self, cpu: &mut Cpu, _: &mut <SCW as RISCVInstruction>::RAMAccess) {
+        // The reservation lives in RAM (see `RESERVATION_ADDRESS`), so executing
+        // the virtual sequence keeps untraced execution consistent with tracing.
+        cpu.mmu.set_reservation_access(true);
+        for instr in self.virtual_sequence() {
+            instr.execute(cpu);
+        }
+        cpu.mmu.set_reservation_access(false);
+    }
+}
 
-            match result {
-                Ok(memory_write) => {
-                    *ram_access = memory_write;
-                    // Clear the reservation
-                    cpu.clear_reservation();
-                    // Return 0 to indicate success
-                    cpu.x[self.operands.rd as usize] = 0;
-                }
-                Err(_) => panic!("MMU store error"),
-            }
-        } else {
-            // Reservation failed, return 1 to indicate failure
-            cpu.x[self.operands.rd as usize] = 1;
+impl RISCVTrace for SCW {
+    fn trace(&self, cpu: &mut Cpu, trace: Option<&mut Vec<RV32IMCycle>>) {
+        let virtual_sequence = self.virtual_sequence();
+        let mut trace = trace;
+        // Only this sequence may access the reservation word
+        cpu.mmu.set_reservation_access(true);
+        for instr in virtual_sequence {
+            // In each iteration, create a new Option containing a re-borrowed reference
+            instr.trace(cpu, trace.as_deref_mut());
         }
+        cpu.mmu.set_reservation_access(false);
     }
 }
 
-impl RISCVTrace for SCW {}
+impl VirtualInstructionSequence for SCW {
+    fn virtual_sequence(&self) -> Vec<RV32IMInstruction> {
+        sc_w_virtual_sequence(self.address, &self.operands)
+    }
+}
@@ -78,6 +78,7 @@ impl VirtualInstructionSequence for SH {
                 imm: self.operands.imm,
             },
             virtual_sequence_remaining: Some(13),
+            is_compressed: self.is_compressed,
         };
         sequence.push(align_check.into());
 
@@ -89,6 +90,7 @@ impl VirtualInstructionSequence for SH {
                 imm: self.operands.imm as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(12),
+            is_compressed: self.is_compressed,
         };
         sequence.push(add.into());
 
@@ -100,6 +102,7 @@ impl VirtualInstructionSequence for SH {
                 imm: -4i64 as u32 as u64, // TODO(moodlezoup): this only works for Xlen = 32
             },
             virtual_sequence_remaining: Some(11),
+            is_compressed: self.is_compressed,
         };
         sequence.push(andi.into());
 
@@ -111,6 +114,7 @@ impl VirtualInstructionSequence for SH {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(10),
+            is_compressed: self.is_compressed,
         };
         sequence.push(lw.into());
 
@@ -122,6 +126,7 @@ impl VirtualInstructionSequence for SH {
                 imm: 3,
             },
             virtual_sequence_remaining: Some(9),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(slli.virtual_sequence());
 
@@ -132,6 +137,7 @@ impl VirtualInstructionSequence for SH {
                 imm: 0xffff,
             },
             virtual_sequence_remaining: Some(8),
+            is_compressed: self.is_compressed,
         };
         sequence.push(lui.into());
 
@@ -143,6 +149,7 @@ impl VirtualInstructionSequence for SH {
                 rs2: v_shift,
             },
             virtual_sequence_remaining: Some(7),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(sll_mask.virtual_sequence());
 
@@ -154,6 +161,7 @@ impl VirtualInstructionSequence for SH {
                 rs2: v_shift,
             },
             virtual_sequence_remaining: Some(5),
+            is_compressed: self.is_compressed,
         };
         sequence.extend(sll_value.virtual_sequence());
 
@@ -165,6 +173,7 @@ impl VirtualInstructionSequence for SH {
                 rs2: v_halfword,
             },
             virtual_sequence_remaining: Some(3),
+            is_compressed: self.is_compressed,
         };
         sequence.push(xor.into());
 
@@ -176,6 +185,7 @@ impl VirtualInstructionSequence for SH {
                 rs2: v_mask,
             },
             virtual_sequence_remaining: Some(2),
+            is_compressed: self.is_compressed,
         };
         sequence.push(and.into());
 
@@ -187,6 +197,7 @@ impl VirtualInstructionSequence for SH {
                 rs2: v_halfword,
             },
             virtual_sequence_remaining: Some(1),
+            is_compressed: self.is_compressed,
         };
         sequence.push(xor_final.into());
 
@@ -198,6 +209,7 @@ impl VirtualInstructionSequence for SH {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(0),
+            is_compressed: self.is_compressed,
         };
         sequence.push(sw.into());
 
@@ -56,6 +56,7 @@ impl VirtualInstructionSequence for SLLI {
                 imm: (1 << (self.operands.imm % 32)),
             },
             virtual_sequence_remaining: Some(virtual_sequence_remaining),
+            is_compressed: self.is_compressed,
         });
         sequence.push(mul);
 
@@ -63,6 +63,7 @@ impl VirtualInstructionSequence for SRA {
                 imm: 0,
             },
             virtual_sequence_remaining: Some(virtual_sequence_remaining),
+            is_compressed: self.is_compressed,
         };
         sequence.push(bitmask.into());
         virtual_sequence_remaining -= 1;
@@ -75,6 +76,7 @@ impl VirtualInstructionSequence for SRA {
                 rs2: v_bitmask,
             },
             virtual_sequence_remaining: Some(virtual_sequence_remaining),
+            is_compressed: self.is_compressed,
         };
         sequence.push(sra.into());
 
@@ -62,6 +62,7 @@ impl VirtualInstructionSequence for SRAI {
                 imm: bitmask,
             },
             virtual_sequence_remaining: Some(virtual_sequence_remaining),
+            is_compressed: self.is_compressed,
         };
         sequence.push(sra.into());
 
@@ -63,6 +63,7 @@ impl VirtualInstructionSequence for SRLI {
                 imm: bitmask,
             },
             virtual_sequence_remaining: Some(virtual_sequence_remaining),
+            is_compressed: self.is_compressed,
         };
         sequence.push(srl.into());
 
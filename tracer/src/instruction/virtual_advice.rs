@@ -19,6 +19,8 @@ pub struct VirtualAdvice {
     /// `virtual_sequence_remaining` will be Some(0); if this is the penultimate instruction
     /// in the sequence, `virtual_sequence_remaining` will be Some(1); etc.
     pub virtual_sequence_remaining: Option<u16>,
+    /// Whether the instruction this sequence expands was compressed.
+    pub is_compressed: bool,
     pub advice: u64,
 }
 
@@ -43,6 +45,7 @@ impl RISCVInstruction for VirtualAdvice {
             operands: FormatJ::random(rng),
             advice: rng.next_u64(),
             virtual_sequence_remaining: None,
+            is_compressed: false,
         }
     }
 
@@ -11,7 +11,11 @@ use std::vec;
 #[cfg(not(feature = "std"))]
 use alloc::{boxed::Box, vec::Vec};
 
-use common::{self, constants::RAM_START_ADDRESS, jolt_device::MemoryConfig};
+use common::{
+    self,
+    constants::RAM_START_ADDRESS,
+    jolt_device::{GuestFault, MemoryConfig},
+};
 use emulator::{
     cpu::{self, Xlen},
     default_terminal::DefaultTerminal,
@@ -26,6 +30,8 @@ pub mod instruction;
 pub mod utils;
 
 pub use common::jolt_device::JoltDevice;
+pub use emulator::cycle_tracking::{CycleTrackingEvent, CycleTrackingReport, LabelSummary};
+pub use emulator::guest_output::GuestOutput;
 pub use instruction::inline::{list_registered_inlines, register_inline};
 
 use crate::emulator::memory::Memory;
@@ -42,18 +48,15 @@ use crate::emulator::memory::Memory;
 /// # Arguments
 ///
 /// * `elf_contents`
-/// * `inputs`
+/// * `guest_inputs` - Public inputs, private inputs and advice tape
 /// * `memory_config`
 /// * `checkpoint_interval` - Number of RV32IMCycle at which to save emulator checkpoints
 ///                          If None, no checkpoints will be saved
 ///
 /// # Returns
 ///
-/// Returns a tuple containing:
-/// * `Vec<RV32IMCycle>` - Complete execution trace
-/// * `JoltDevice`
-/// * `Option<Vec<LazyTraceIterator>>` - If checkpoint_interval is not None, contains emulator
-///                                      checkpoints every n RV32IMCycle. Otherwise None.
+/// A [`TraceOutput`] with the complete execution trace, the final memory state, the
+/// `JoltDevice`, and what the guest reported through ECALLs along the way.
 ///
 /// # Example Usage
 ///
@@ -71,20 +74,65 @@ use crate::emulator::memory::Memory;
 #[tracing::instrument(skip_all)]
 pub fn trace(
     elf_contents: Vec<u8>,
-    inputs: &[u8],
+    guest_inputs: GuestInputs,
     memory_config: &MemoryConfig,
-) -> (Vec<RV32IMCycle>, Memory, JoltDevice) {
+) -> TraceOutput {
     let mut lazy_trace_iter =
-        LazyTraceIterator::new(setup_emulator(elf_contents, inputs, memory_config));
+        LazyTraceIterator::new(setup_emulator(elf_contents, guest_inputs, memory_config));
     let trace: Vec<RV32IMCycle> = lazy_trace_iter.by_ref().collect();
     let final_memory_state = std::mem::take(lazy_trace_iter.final_memory_state.as_mut().unwrap());
-    (trace, final_memory_state, lazy_trace_iter.get_jolt_device())
+    let cycle_tracking = lazy_trace_iter.cycle_tracking_report().clone();
+    let guest_output = lazy_trace_iter.guest_output().clone();
+    let advice_exhausted = lazy_trace_iter.advice_exhausted();
+    TraceOutput {
+        trace,
+        final_memory_state,
+        io_device: lazy_trace_iter.get_jolt_device(),
+        cycle_tracking,
+        guest_output,
+        advice_exhausted,
+    }
+}
+
+/// What the host provides to an execution of the guest, besides the program itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GuestInputs<'a> {
+    /// Public inputs, which are part of the `JoltDevice`
+    pub inputs: &'a [u8],
+    /// Inputs that are loaded into the private input region, known only to the prover
+    pub private_inputs: &'a [u8],
+    /// Untrusted advice tape, read by the guest with `ADVICE` instructions
+    pub advice: &'a [u8],
+}
+
+impl<'a> GuestInputs<'a> {
+    /// Public inputs only, without private inputs or advice.
+    pub fn new(inputs: &'a [u8]) -> Self {
+        Self {
+            inputs,
+            ..Default::default()
+        }
+    }
+}
+
+/// The result of [`trace`]: a complete execution of the guest.
+pub struct TraceOutput {
+    pub trace: Vec<RV32IMCycle>,
+    pub final_memory_state: Memory,
+    pub io_device: JoltDevice,
+    /// Spans recorded by the guest's cycle-tracking markers
+    pub cycle_tracking: CycleTrackingReport,
+    /// Text written by the guest to stdout/stderr
+    pub guest_output: GuestOutput,
+    /// Whether the guest read past the end of the advice tape. Execution stopped
+    /// before that read, so the trace is incomplete.
+    pub advice_exhausted: bool,
 }
 use crate::utils::trace_writer::{TraceBatchCollector, TraceWriter, TraceWriterConfig};
 
 pub fn trace_to_file(
     elf_contents: Vec<u8>,
-    inputs: &[u8],
+    guest_inputs: GuestInputs,
     memory_config: &MemoryConfig,
     out_path: &std::path::PathBuf,
 ) -> (Memory, JoltDevice) {
@@ -93,7 +141,8 @@ pub fn trace_to_file(
     let writer =
         TraceWriter::<RV32IMCycle>::new(out_path, config).expect("Failed to create trace writer");
     let mut collector = TraceBatchCollector::new(writer);
-    let mut lazy = LazyTraceIterator::new(setup_emulator(elf_contents, inputs, memory_config));
+    let mut lazy =
+        LazyTraceIterator::new(setup_emulator(elf_contents, guest_inputs, memory_config));
 
     for cycle in &mut lazy {
         collector.push(cycle);
@@ -115,7 +164,11 @@ pub fn trace_lazy(
     inputs: &[u8],
     memory_config: &MemoryConfig,
 ) -> LazyTraceIterator {
-    LazyTraceIterator::new(setup_emulator(elf_contents, inputs, memory_config))
+    LazyTraceIterator::new(setup_emulator(
+        elf_contents,
+        GuestInputs::new(inputs),
+        memory_config,
+    ))
 }
 
 #[tracing::instrument(skip_all)]
@@ -125,8 +178,11 @@ pub fn trace_checkpoints(
     memory_config: &MemoryConfig,
     checkpoint_interval: usize,
 ) -> (Vec<std::iter::Take<LazyTraceIterator>>, JoltDevice) {
-    let mut emulator_trace_iter =
-        LazyTraceIterator::new(setup_emulator(elf_contents, inputs, memory_config));
+    let mut emulator_trace_iter = LazyTraceIterator::new(setup_emulator(
+        elf_contents,
+        GuestInputs::new(inputs),
+        memory_config,
+    ));
     let mut checkpoints = Vec::new();
 
     loop {
@@ -140,27 +196,134 @@ pub fn trace_checkpoints(
     (checkpoints, emulator_trace_iter.get_jolt_device())
 }
 
-fn step_emulator(emulator: &mut Emulator, prev_pc: &mut u64, trace: Option<&mut Vec<RV32IMCycle>>) {
+/// Splits execution into segments of (at least) `segment_length` cycles, for use in
+/// continuations. Unlike [`trace_checkpoints`], segments always start and end on an
+/// instruction boundary, i.e. never in the middle of a virtual sequence, so the
+/// architectural state at each boundary is well-defined.
+///
+/// Returns the segments and an iterator holding the emulator state after execution
+/// has completed.
+pub fn trace_segments(
+    elf_contents: Vec<u8>,
+    inputs: &[u8],
+    memory_config: &MemoryConfig,
+    segment_length: usize,
+) -> (Vec<Segment>, LazyTraceIterator) {
+    assert!(segment_length > 0, "Segment length must be positive");
+    let mut emulator_trace_iter = LazyTraceIterator::new(setup_emulator(
+        elf_contents,
+        GuestInputs::new(inputs),
+        memory_config,
+    ));
+    let mut segments = Vec::new();
+    let mut start_cycle = 0;
+
+    loop {
+        let checkpoint = emulator_trace_iter.clone();
+        let mut len = 0;
+        while len < segment_length || !emulator_trace_iter.at_tick_boundary() {
+            if emulator_trace_iter.next().is_none() {
+                break;
+            }
+            len += 1;
+        }
+        if len == 0 {
+            break;
+        }
+        segments.push(Segment {
+            checkpoint,
+            start_cycle,
+            len,
+        });
+        start_cycle += len;
+        if emulator_trace_iter.is_empty() {
+            break;
+        }
+    }
+    (segments, emulator_trace_iter)
+}
+
+/// A contiguous chunk of execution, starting on an instruction boundary.
+#[derive(Clone)]
+pub struct Segment {
+    /// Emulator checkpoint at the start of the segment
+    pub checkpoint: LazyTraceIterator,
+    /// Index of the segment's first cycle in the full trace
+    pub start_cycle: usize,
+    /// Number of cycles in the segment
+    pub len: usize,
+}
+
+impl Segment {
+    /// Lazily re-executes this segment's chunk of the trace.
+    pub fn trace(&self) -> std::iter::Take<LazyTraceIterator> {
+        self.checkpoint.clone().take(self.len)
+    }
+}
+
+fn step_emulator(
+    emulator: &mut Emulator,
+    prev_pc: &mut u64,
+    mut trace: Option<&mut Vec<RV32IMCycle>>,
+) {
     let pc = emulator.get_cpu().read_pc();
     // This is a trick to see if the program has terminated by throwing itself
     // into an infinite loop. It seems to be a good heuristic for now but we
     // should eventually migrate to an explicit shutdown signal.
-    if *prev_pc == pc {
+    if *prev_pc == pc || guest_stopped(emulator) {
+        return;
+    }
+    let trace_len = trace.as_ref().map_or(0, |trace| trace.len());
+    emulator.tick(trace.as_deref_mut());
+    if guest_stopped(emulator) {
+        // The guest overflowed its stack or heap, or read past the end of the advice
+        // tape. After a stack or heap overflow, the trace ends with the faulting load
+        // or store, which was not performed (its RAM access is recorded at address 0).
+        // The rest of the instruction is dropped from the trace.
+        if let Some(trace) = trace {
+            let faulting_cycle = guest_fault(emulator).and_then(|_| {
+                trace[trace_len..].iter().position(|cycle| {
+                    matches!(cycle, RV32IMCycle::LW(_) | RV32IMCycle::SW(_))
+                        && cycle.ram_access().address() == 0
+                })
+            });
+            trace.truncate(faulting_cycle.map_or(trace_len, |i| trace_len + i + 1));
+        }
         return;
     }
-    emulator.tick(trace);
     *prev_pc = pc;
 }
 
+fn guest_fault(emulator: &Emulator) -> Option<GuestFault> {
+    emulator
+        .get_cpu()
+        .mmu
+        .jolt_device
+        .as_ref()
+        .and_then(|jolt_device| jolt_device.fault)
+}
+
+fn guest_stopped(emulator: &Emulator) -> bool {
+    emulator.get_cpu().advice_exhausted() || guest_fault(emulator).is_some()
+}
+
 #[tracing::instrument(skip_all)]
-fn setup_emulator(elf_contents: Vec<u8>, inputs: &[u8], memory_config: &MemoryConfig) -> Emulator {
+fn setup_emulator(
+    elf_contents: Vec<u8>,
+    guest_inputs: GuestInputs,
+    memory_config: &MemoryConfig,
+) -> Emulator {
     let term = DefaultTerminal::default();
     let mut emulator = Emulator::new(Box::new(term));
     emulator.update_xlen(get_xlen());
 
     let mut jolt_device = JoltDevice::new(memory_config);
-    jolt_device.inputs = inputs.to_vec();
+    jolt_device.inputs = guest_inputs.inputs.to_vec();
+    jolt_device.private_inputs = guest_inputs.private_inputs.to_vec();
     emulator.get_mut_cpu().get_mut_mmu().jolt_device = Some(jolt_device);
+    emulator
+        .get_mut_cpu()
+        .set_advice_tape(guest_inputs.advice.to_vec());
 
     emulator.setup_program(elf_contents);
     emulator
@@ -226,6 +389,48 @@ impl LazyTraceIterator {
     pub fn is_empty(&self) -> bool {
         self.finished
     }
+
+    /// The program counter of the next instruction to be executed.
+    pub fn pc(&self) -> u64 {
+        self.emulator_state.get_cpu().read_pc()
+    }
+
+    /// The current values of all registers, including virtual registers.
+    pub fn registers(&self) -> Vec<u64> {
+        let cpu = self.emulator_state.get_cpu();
+        cpu.x.iter().map(|&x| cpu.unsigned_data(x)).collect()
+    }
+
+    /// The current contents of the emulator's memory.
+    pub fn memory(&self) -> &Memory {
+        &self.emulator_state.get_cpu().mmu.memory.memory
+    }
+
+    /// The current state of the I/O device, i.e. the inputs, outputs and panic bit.
+    pub fn jolt_device(&self) -> &JoltDevice {
+        self.emulator_state
+            .get_cpu()
+            .mmu
+            .jolt_device
+            .as_ref()
+            .expect("JoltDevice was not initialized")
+    }
+
+    /// The cycle-tracking spans completed so far.
+    pub fn cycle_tracking_report(&self) -> &CycleTrackingReport {
+        self.emulator_state.get_cpu().cycle_tracking_report()
+    }
+
+    /// The text written by the guest so far.
+    pub fn guest_output(&self) -> &GuestOutput {
+        self.emulator_state.get_cpu().guest_output()
+    }
+
+    /// Whether the guest tried to read past the end of the advice tape, which stops
+    /// execution.
+    pub fn advice_exhausted(&self) -> bool {
+        self.emulator_state.get_cpu().advice_exhausted()
+    }
 }
 
 impl Iterator for LazyTraceIterator {
@@ -295,11 +500,27 @@ pub fn decode(elf: &[u8]) -> (Vec<RV32IMInstruction>, Vec<(u64, u8)>, u64) {
         let raw_data = section.data().unwrap();
 
         if let SectionKind::Text = section.kind() {
-            for (chunk, word) in raw_data.chunks(4).enumerate() {
-                let word = u32::from_le_bytes(word.try_into().unwrap());
-                let address = chunk as u64 * 4 + section.address();
+            // Instructions are 4 bytes, or 2 bytes if compressed ("C" extension).
+            // The two lowest bits of a compressed instruction are not both set.
+            let xlen = get_xlen();
+            let mut offset = 0;
+            while offset + 1 < raw_data.len() {
+                let address = start + offset as u64;
+                let halfword = u16::from_le_bytes([raw_data[offset], raw_data[offset + 1]]);
+                let is_compressed = halfword & 0x3 != 0x3;
+                let word = if is_compressed {
+                    offset += 2;
+                    cpu::Cpu::uncompress(halfword as u32, &xlen)
+                } else if offset + 3 < raw_data.len() {
+                    let word = u32::from_le_bytes(raw_data[offset..offset + 4].try_into().unwrap());
+                    offset += 4;
+                    word
+                } else {
+                    break;
+                };
 
-                if let Ok(inst) = RV32IMInstruction::decode(word, address) {
+                if let Ok(mut inst) = RV32IMInstruction::decode(word, address) {
+                    inst.set_is_compressed(is_compressed);
                     instructions.push(inst);
                     continue;
                 }
@@ -327,6 +548,8 @@ fn get_xlen() -> Xlen {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::instruction::RAMWrite;
+    use common::jolt_device::GuestFault;
     const ELF_CONTENTS: [u8; 6404] = [
         0x7f, 0x45, 0x4c, 0x46, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
         0x00, 0x02, 0x00, 0xf3, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x34, 0x00,
@@ -772,7 +995,7 @@ mod test {
             program_size: Some(elf.len() as u64),
             ..Default::default()
         };
-        let (execution_trace, _, _) = trace(elf.clone(), &INPUTS, &memory_config);
+        let execution_trace = trace(elf.clone(), GuestInputs::new(&INPUTS), &memory_config).trace;
         let (checkpoints, _) = trace_checkpoints(elf, &INPUTS, &memory_config, n);
         assert_eq!(execution_trace.len(), expected_trace_length);
         assert_eq!(checkpoints.len(), 10);
@@ -795,8 +1018,17 @@ mod test {
             ..Default::default()
         };
 
-        let (execution_trace, _, _) = trace(ELF_CONTENTS.to_vec(), &INPUTS, &memory_config);
-        let mut emulator = setup_emulator(ELF_CONTENTS.to_vec(), &INPUTS, &memory_config);
+        let execution_trace = trace(
+            ELF_CONTENTS.to_vec(),
+            GuestInputs::new(&INPUTS),
+            &memory_config,
+        )
+        .trace;
+        let mut emulator = setup_emulator(
+            ELF_CONTENTS.to_vec(),
+            GuestInputs::new(&INPUTS),
+            &memory_config,
+        );
         let mut prev_pc: u64 = 0;
         let mut trace = vec![];
         let mut prev_trace_len = 0;
@@ -809,4 +1041,68 @@ mod test {
         }
         assert_eq!(execution_trace, trace);
     }
+
+    #[test]
+    fn test_guest_fault_ends_trace() {
+        let memory_config = MemoryConfig {
+            program_size: Some(ELF_CONTENTS.len() as u64),
+            ..Default::default()
+        };
+        let TraceOutput {
+            trace: execution_trace,
+            io_device,
+            ..
+        } = trace(
+            ELF_CONTENTS.to_vec(),
+            GuestInputs::new(&INPUTS),
+            &memory_config,
+        );
+        assert_eq!(io_device.fault, None);
+
+        // The guest's stack lies at 0x80001458..0x80001470. Moving `stack_end` just below
+        // it turns its first push into a write to the stack canary, and moving `memory_end`
+        // below it turns that write into a heap overflow.
+        let stack_overflow_config = MemoryConfig {
+            program_size: Some(0x1440),
+            stack_size: 0,
+            ..memory_config
+        };
+        let heap_overflow_config = MemoryConfig {
+            program_size: Some(0x1300),
+            stack_size: 0,
+            memory_size: 0x80,
+            ..memory_config
+        };
+        for (memory_config, fault) in [
+            (stack_overflow_config, GuestFault::StackOverflow),
+            (heap_overflow_config, GuestFault::HeapOverflow),
+        ] {
+            let TraceOutput {
+                trace: faulted_trace,
+                io_device,
+                ..
+            } = trace(
+                ELF_CONTENTS.to_vec(),
+                GuestInputs::new(&INPUTS),
+                &memory_config,
+            );
+            assert_eq!(io_device.fault, Some(fault));
+            assert!(!io_device.panic);
+
+            // The trace ends with the faulting store, which matches the unfaulted
+            // execution in everything but its RAM access
+            let (faulting_cycle, guest_trace) = faulted_trace.split_last().unwrap();
+            assert!(guest_trace.len() < execution_trace.len());
+            assert_eq!(guest_trace, &execution_trace[..guest_trace.len()]);
+            let RV32IMCycle::SW(store) = faulting_cycle else {
+                panic!("Expected the trace to end with the faulting store");
+            };
+            assert_eq!(store.ram_access, RAMWrite::default());
+            let address = store
+                .register_state
+                .rs1
+                .wrapping_add(store.instruction.operands.imm as u64);
+            assert_eq!(io_device.memory_layout.fault(address, true), Some(fault));
+        }
+    }
 }